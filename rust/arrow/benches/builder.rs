@@ -50,6 +50,25 @@ fn bench_primitive(c: &mut Criterion) {
     );
 }
 
+fn bench_primitive_reserved(c: &mut Criterion) {
+    c.bench(
+        "bench_primitive_reserved",
+        Benchmark::new("bench_primitive_reserved", move |b| {
+            b.iter(|| {
+                let mut builder = Int64Builder::new(64);
+                let _ = black_box(builder.reserve(BATCH_SIZE * NUM_BATCHES));
+                for i in 0..BATCH_SIZE * NUM_BATCHES {
+                    let _ = black_box(builder.append_value(i as i64));
+                }
+                black_box(builder.finish());
+            })
+        })
+        .throughput(Throughput::Bytes(
+            (BATCH_SIZE * NUM_BATCHES * size_of::<i64>()) as u32,
+        )),
+    );
+}
+
 fn bench_bool(c: &mut Criterion) {
     let data: Vec<bool> = thread_rng()
         .sample_iter(&Standard)
@@ -73,5 +92,10 @@ fn bench_bool(c: &mut Criterion) {
     );
 }
 
-criterion_group!(benches, bench_primitive, bench_bool);
+criterion_group!(
+    benches,
+    bench_primitive,
+    bench_primitive_reserved,
+    bench_bool
+);
 criterion_main!(benches);
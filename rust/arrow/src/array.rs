@@ -55,9 +55,9 @@
 //! ```
 
 use std::any::Any;
-use std::convert::From;
+use std::convert::{From, TryFrom};
 use std::fmt;
-use std::io::Write;
+use std::iter::FromIterator;
 use std::mem;
 use std::sync::Arc;
 
@@ -67,17 +67,18 @@ use crate::array_data::{ArrayData, ArrayDataRef};
 use crate::buffer::{Buffer, MutableBuffer};
 use crate::builder::*;
 use crate::datatypes::*;
+use crate::error::{ArrowError, Result};
 use crate::memory;
 use crate::util::bit_util;
 
 /// Number of seconds in a day
-const SECONDS_IN_DAY: i64 = 86_400;
+pub(crate) const SECONDS_IN_DAY: i64 = 86_400;
 /// Number of milliseconds in a second
-const MILLISECONDS: i64 = 1_000;
+pub(crate) const MILLISECONDS: i64 = 1_000;
 /// Number of microseconds in a second
-const MICROSECONDS: i64 = 1_000_000;
+pub(crate) const MICROSECONDS: i64 = 1_000_000;
 /// Number of nanoseconds in a second
-const NANOSECONDS: i64 = 1_000_000_000;
+pub(crate) const NANOSECONDS: i64 = 1_000_000_000;
 
 /// Trait for dealing with different types of array at runtime when the type of the
 /// array is not known in advance
@@ -120,16 +121,94 @@ pub trait Array: Send + Sync {
     fn null_count(&self) -> usize {
         self.data().null_count()
     }
+
+    /// Returns whether this array has no null values, according to its null count
+    fn all_valid(&self) -> bool {
+        self.null_count() == 0
+    }
+
+    /// Returns a copy of this array, sharing its buffers, whose null count is recomputed
+    /// from the null bitmap over the range of the array.
+    ///
+    /// This repairs arrays built from untrusted `ArrayData` whose null count disagrees
+    /// with their null bitmap.
+    fn with_correct_null_count(&self) -> ArrayRef {
+        let data = self.data_ref();
+        let mut builder = ArrayData::builder(data.data_type().clone())
+            .len(data.len())
+            .offset(data.offset())
+            .buffers(data.buffers().to_vec())
+            .child_data(data.child_data().to_vec());
+        match data.null_bitmap() {
+            Some(bitmap) => builder = builder.null_bit_buffer(bitmap.bits.clone()),
+            // all the elements of a `NullArray` are null, without a null bitmap
+            None if data.data_type() == &DataType::Null => {
+                builder = builder.null_count(data.len())
+            }
+            None => builder = builder.null_count(0),
+        }
+        make_array(builder.build())
+    }
+
+    /// Returns the total number of bytes of memory allocated for the buffers and the null
+    /// bitmap of this array, not including the memory of its children.
+    ///
+    /// Buffers shared with other arrays, e.g. after slicing, are fully counted.
+    fn get_buffer_memory_size(&self) -> usize {
+        self.data_ref().get_buffer_memory_size()
+    }
+
+    /// Returns the total number of bytes of memory occupied by this array, including the
+    /// array struct, its `ArrayData` and, recursively, the memory of its children.
+    fn get_array_memory_size(&self) -> usize {
+        mem::size_of_val(self) + self.data_ref().get_array_memory_size()
+    }
+
+    /// Returns a human-readable representation of the element at index `i`, or `"null"`
+    /// if the element is null.
+    ///
+    /// Temporal values are formatted as dates, times or date-times, `Utf8` values as
+    /// strings, and lists and structs by recursively formatting their values.
+    fn value_to_string(&self, i: usize) -> String {
+        if self.is_null(i) {
+            return "null".to_string();
+        }
+        any_value_to_string(self.as_any(), self.data_type(), i)
+    }
+
+    /// Returns whether this array is logically equal to `other`, i.e. whether both
+    /// arrays have the same data type and length, and the same values and nulls.
+    ///
+    /// This allows comparing arrays whose concrete types are not known, e.g.
+    /// `ArrayRef`s. Only the logical values are compared: arrays with different offsets
+    /// or with different values in null slots can be equal.
+    fn equals(&self, other: &dyn Array) -> bool {
+        self.data_type() == other.data_type()
+            && self.len() == other.len()
+            && self.null_count() == other.null_count()
+            && (0..self.len()).all(|i| {
+                self.is_null(i) == other.is_null(i)
+                    && (self.is_null(i)
+                        || any_value_equals(
+                            self.as_any(),
+                            other.as_any(),
+                            self.data_type(),
+                            i,
+                            i,
+                        ))
+            })
+    }
 }
 
 pub type ArrayRef = Arc<Array>;
 
 /// Constructs an array using the input `data`. Returns a reference-counted `Array`
 /// instance.
-fn make_array(data: ArrayDataRef) -> ArrayRef {
+pub fn make_array(data: ArrayDataRef) -> ArrayRef {
     // TODO: here data_type() needs to clone the type - maybe add a type tag enum to
     // avoid the cloning.
     match data.data_type().clone() {
+        DataType::Null => Arc::new(NullArray::from(data)) as ArrayRef,
         DataType::Boolean => Arc::new(BooleanArray::from(data)) as ArrayRef,
         DataType::Int8 => Arc::new(Int8Array::from(data)) as ArrayRef,
         DataType::Int16 => Arc::new(Int16Array::from(data)) as ArrayRef,
@@ -141,13 +220,473 @@ fn make_array(data: ArrayDataRef) -> ArrayRef {
         DataType::UInt64 => Arc::new(UInt64Array::from(data)) as ArrayRef,
         DataType::Float32 => Arc::new(Float32Array::from(data)) as ArrayRef,
         DataType::Float64 => Arc::new(Float64Array::from(data)) as ArrayRef,
+        DataType::Decimal(_, _) => Arc::new(DecimalArray::from(data)) as ArrayRef,
+        DataType::Timestamp(TimeUnit::Second, _) => {
+            Arc::new(TimestampSecondArray::from(data)) as ArrayRef
+        }
+        DataType::Timestamp(TimeUnit::Millisecond, _) => {
+            Arc::new(TimestampMillisecondArray::from(data)) as ArrayRef
+        }
+        DataType::Timestamp(TimeUnit::Microsecond, _) => {
+            Arc::new(TimestampMicrosecondArray::from(data)) as ArrayRef
+        }
+        DataType::Timestamp(TimeUnit::Nanosecond, _) => {
+            Arc::new(TimestampNanosecondArray::from(data)) as ArrayRef
+        }
+        DataType::Date32(DateUnit::Day) => Arc::new(Date32Array::from(data)) as ArrayRef,
+        DataType::Date64(DateUnit::Millisecond) => {
+            Arc::new(Date64Array::from(data)) as ArrayRef
+        }
+        DataType::Time32(TimeUnit::Second) => {
+            Arc::new(Time32SecondArray::from(data)) as ArrayRef
+        }
+        DataType::Time32(TimeUnit::Millisecond) => {
+            Arc::new(Time32MillisecondArray::from(data)) as ArrayRef
+        }
+        DataType::Time64(TimeUnit::Microsecond) => {
+            Arc::new(Time64MicrosecondArray::from(data)) as ArrayRef
+        }
+        DataType::Time64(TimeUnit::Nanosecond) => {
+            Arc::new(Time64NanosecondArray::from(data)) as ArrayRef
+        }
         DataType::Utf8 => Arc::new(BinaryArray::from(data)) as ArrayRef,
+        DataType::LargeUtf8 => Arc::new(LargeBinaryArray::from(data)) as ArrayRef,
+        DataType::FixedSizeBinary(_) => {
+            Arc::new(FixedSizeBinaryArray::from(data)) as ArrayRef
+        }
         DataType::List(_) => Arc::new(ListArray::from(data)) as ArrayRef,
+        DataType::LargeList(_) => Arc::new(LargeListArray::from(data)) as ArrayRef,
+        DataType::FixedSizeList(_, _) => {
+            Arc::new(FixedSizeListArray::from(data)) as ArrayRef
+        }
         DataType::Struct(_) => Arc::new(StructArray::from(data)) as ArrayRef,
+        DataType::Union(_, _) => Arc::new(UnionArray::from(data)) as ArrayRef,
+        DataType::Dictionary(key_type, _) => match *key_type {
+            DataType::Int8 => Arc::new(Int8DictionaryArray::from(data)) as ArrayRef,
+            DataType::Int16 => Arc::new(Int16DictionaryArray::from(data)) as ArrayRef,
+            DataType::Int32 => Arc::new(Int32DictionaryArray::from(data)) as ArrayRef,
+            DataType::Int64 => Arc::new(Int64DictionaryArray::from(data)) as ArrayRef,
+            DataType::UInt8 => Arc::new(UInt8DictionaryArray::from(data)) as ArrayRef,
+            DataType::UInt16 => Arc::new(UInt16DictionaryArray::from(data)) as ArrayRef,
+            DataType::UInt32 => Arc::new(UInt32DictionaryArray::from(data)) as ArrayRef,
+            DataType::UInt64 => Arc::new(UInt64DictionaryArray::from(data)) as ArrayRef,
+            dt => panic!("Unexpected dictionary key type {:?}", dt),
+        },
         dt => panic!("Unexpected data type {:?}", dt),
     }
 }
 
+/// Downcasts `arr` to the concrete array type `A`, panicking with the data type of `arr`
+/// if it is of another type.
+fn downcast_array<'a, A: Any>(arr: &'a ArrayRef, name: &str) -> &'a A {
+    arr.as_any().downcast_ref::<A>().unwrap_or_else(|| {
+        panic!(
+            "Expected a {} but got an array of type {:?}",
+            name,
+            arr.data_type()
+        )
+    })
+}
+
+/// Downcasts `arr` to a `PrimitiveArray<T>`, e.g. an `Int32Array`.
+///
+/// # Panics
+///
+/// Panics if `arr` is not a `PrimitiveArray<T>`.
+pub fn as_primitive_array<T: ArrowPrimitiveType>(arr: &ArrayRef) -> &PrimitiveArray<T> {
+    downcast_array(arr, &format!("PrimitiveArray<{:?}>", T::get_data_type()))
+}
+
+/// Downcasts `arr` to a `BooleanArray`.
+///
+/// # Panics
+///
+/// Panics if `arr` is not a `BooleanArray`.
+pub fn as_boolean_array(arr: &ArrayRef) -> &BooleanArray {
+    downcast_array(arr, "BooleanArray")
+}
+
+/// Downcasts a `Utf8` array `arr` to a `BinaryArray`.
+///
+/// # Panics
+///
+/// Panics if `arr` is not a `BinaryArray`.
+pub fn as_string_array(arr: &ArrayRef) -> &BinaryArray {
+    downcast_array(arr, "BinaryArray")
+}
+
+/// Downcasts `arr` to a `ListArray`.
+///
+/// # Panics
+///
+/// Panics if `arr` is not a `ListArray`.
+pub fn as_list_array(arr: &ArrayRef) -> &ListArray {
+    downcast_array(arr, "ListArray")
+}
+
+/// Downcasts `arr` to a `StructArray`.
+///
+/// # Panics
+///
+/// Panics if `arr` is not a `StructArray`.
+pub fn as_struct_array(arr: &ArrayRef) -> &StructArray {
+    downcast_array(arr, "StructArray")
+}
+
+macro_rules! primitive_value_to_string {
+    ($array:expr, $i:expr, $array_type:ty) => {{
+        let a = $array.downcast_ref::<$array_type>().unwrap();
+        format!("{:?}", a.value($i))
+    }};
+}
+
+macro_rules! temporal_value_to_string {
+    ($array:expr, $i:expr, $array_type:ident, $as_value:ident) => {{
+        let a = $array.downcast_ref::<$array_type>().unwrap();
+        match a.$as_value($i) {
+            Some(v) => format!("{:?}", v),
+            None => "null".to_string(),
+        }
+    }};
+}
+
+/// Formats a timestamp as a zoned date-time if it has a timezone, and as a naive
+/// date-time otherwise
+macro_rules! timestamp_value_to_string {
+    ($array:expr, $i:expr, $tz:expr, $array_type:ident) => {{
+        match $tz {
+            Some(_) => {
+                temporal_value_to_string!($array, $i, $array_type, value_as_datetime_tz)
+            }
+            None => temporal_value_to_string!($array, $i, $array_type, value_as_datetime),
+        }
+    }};
+}
+
+macro_rules! list_value_to_string {
+    ($array:expr, $i:expr, $array_type:ident) => {{
+        let a = $array.downcast_ref::<$array_type>().unwrap();
+        let values = a.values();
+        let start = a.value_offset($i) as usize;
+        let end = start + a.value_length($i) as usize;
+        let values: Vec<String> =
+            (start..end).map(|j| values.value_to_string(j)).collect();
+        format!("[{}]", values.join(", "))
+    }};
+}
+
+macro_rules! dictionary_value_to_string {
+    ($array:expr, $i:expr, $array_type:ident) => {{
+        let a = $array.downcast_ref::<$array_type>().unwrap();
+        a.values().value_to_string(a.keys().value($i) as usize)
+    }};
+}
+
+/// Formats the non-null element at index `i` of `array`, whose data type is `data_type`.
+fn any_value_to_string(array: &dyn Any, data_type: &DataType, i: usize) -> String {
+    match data_type {
+        DataType::Boolean => primitive_value_to_string!(array, i, BooleanArray),
+        DataType::Int8 => primitive_value_to_string!(array, i, Int8Array),
+        DataType::Int16 => primitive_value_to_string!(array, i, Int16Array),
+        DataType::Int32 => primitive_value_to_string!(array, i, Int32Array),
+        DataType::Int64 => primitive_value_to_string!(array, i, Int64Array),
+        DataType::UInt8 => primitive_value_to_string!(array, i, UInt8Array),
+        DataType::UInt16 => primitive_value_to_string!(array, i, UInt16Array),
+        DataType::UInt32 => primitive_value_to_string!(array, i, UInt32Array),
+        DataType::UInt64 => primitive_value_to_string!(array, i, UInt64Array),
+        DataType::Float32 => primitive_value_to_string!(array, i, Float32Array),
+        DataType::Float64 => primitive_value_to_string!(array, i, Float64Array),
+        DataType::Decimal(_, _) => array
+            .downcast_ref::<DecimalArray>()
+            .unwrap()
+            .value_as_string(i),
+        DataType::Timestamp(TimeUnit::Second, tz) => {
+            timestamp_value_to_string!(array, i, tz, TimestampSecondArray)
+        }
+        DataType::Timestamp(TimeUnit::Millisecond, tz) => {
+            timestamp_value_to_string!(array, i, tz, TimestampMillisecondArray)
+        }
+        DataType::Timestamp(TimeUnit::Microsecond, tz) => {
+            timestamp_value_to_string!(array, i, tz, TimestampMicrosecondArray)
+        }
+        DataType::Timestamp(TimeUnit::Nanosecond, tz) => {
+            timestamp_value_to_string!(array, i, tz, TimestampNanosecondArray)
+        }
+        DataType::Date32(DateUnit::Day) => {
+            temporal_value_to_string!(array, i, Date32Array, value_as_date)
+        }
+        DataType::Date64(DateUnit::Millisecond) => {
+            temporal_value_to_string!(array, i, Date64Array, value_as_date)
+        }
+        DataType::Time32(TimeUnit::Second) => {
+            temporal_value_to_string!(array, i, Time32SecondArray, value_as_time)
+        }
+        DataType::Time32(TimeUnit::Millisecond) => {
+            temporal_value_to_string!(array, i, Time32MillisecondArray, value_as_time)
+        }
+        DataType::Time64(TimeUnit::Microsecond) => {
+            temporal_value_to_string!(array, i, Time64MicrosecondArray, value_as_time)
+        }
+        DataType::Time64(TimeUnit::Nanosecond) => {
+            temporal_value_to_string!(array, i, Time64NanosecondArray, value_as_time)
+        }
+        DataType::Interval(IntervalUnit::YearMonth) => {
+            primitive_value_to_string!(array, i, PrimitiveArray<IntervalYearMonthType>)
+        }
+        DataType::Interval(IntervalUnit::DayTime) => {
+            primitive_value_to_string!(array, i, PrimitiveArray<IntervalDayTimeType>)
+        }
+        DataType::Utf8 => array.downcast_ref::<BinaryArray>().unwrap().get_string(i),
+        DataType::LargeUtf8 => array
+            .downcast_ref::<LargeBinaryArray>()
+            .unwrap()
+            .get_string(i),
+        DataType::FixedSizeBinary(_) => {
+            let a = array.downcast_ref::<FixedSizeBinaryArray>().unwrap();
+            a.value(i).iter().map(|b| format!("{:02x}", b)).collect()
+        }
+        DataType::List(_) => list_value_to_string!(array, i, ListArray),
+        DataType::LargeList(_) => list_value_to_string!(array, i, LargeListArray),
+        DataType::FixedSizeList(_, _) => {
+            let a = array.downcast_ref::<FixedSizeListArray>().unwrap();
+            let values = a.value(i);
+            let values: Vec<String> = (0..values.len())
+                .map(|j| values.value_to_string(j))
+                .collect();
+            format!("[{}]", values.join(", "))
+        }
+        DataType::Struct(fields) => {
+            let a = array.downcast_ref::<StructArray>().unwrap();
+            let values: Vec<String> = fields
+                .iter()
+                .enumerate()
+                .map(|(j, field)| {
+                    format!("{}: {}", field.name(), a.column(j).value_to_string(i))
+                })
+                .collect();
+            format!("{{{}}}", values.join(", "))
+        }
+        DataType::Union(_, _) => {
+            let a = array.downcast_ref::<UnionArray>().unwrap();
+            a.child(a.type_id(i))
+                .value_to_string(a.value_offset(i) as usize)
+        }
+        DataType::Dictionary(key_type, _) => match **key_type {
+            DataType::Int8 => dictionary_value_to_string!(array, i, Int8DictionaryArray),
+            DataType::Int16 => {
+                dictionary_value_to_string!(array, i, Int16DictionaryArray)
+            }
+            DataType::Int32 => {
+                dictionary_value_to_string!(array, i, Int32DictionaryArray)
+            }
+            DataType::Int64 => {
+                dictionary_value_to_string!(array, i, Int64DictionaryArray)
+            }
+            DataType::UInt8 => {
+                dictionary_value_to_string!(array, i, UInt8DictionaryArray)
+            }
+            DataType::UInt16 => {
+                dictionary_value_to_string!(array, i, UInt16DictionaryArray)
+            }
+            DataType::UInt32 => {
+                dictionary_value_to_string!(array, i, UInt32DictionaryArray)
+            }
+            DataType::UInt64 => {
+                dictionary_value_to_string!(array, i, UInt64DictionaryArray)
+            }
+            ref dt => panic!("Unexpected dictionary key type {:?}", dt),
+        },
+        dt => panic!("Cannot format values of data type {:?}", dt),
+    }
+}
+
+macro_rules! primitive_value_equals {
+    ($left:expr, $right:expr, $i:expr, $j:expr, $array_type:ty) => {{
+        let l = $left.downcast_ref::<$array_type>().unwrap();
+        let r = $right.downcast_ref::<$array_type>().unwrap();
+        l.value($i) == r.value($j)
+    }};
+}
+
+macro_rules! list_value_equals {
+    ($left:expr, $right:expr, $i:expr, $j:expr, $array_type:ident) => {{
+        let l = $left.downcast_ref::<$array_type>().unwrap();
+        let r = $right.downcast_ref::<$array_type>().unwrap();
+        let (l_values, r_values) = (l.values(), r.values());
+        let (l_start, r_start) = (l.value_offset($i), r.value_offset($j));
+        l.value_length($i) == r.value_length($j)
+            && (0..l.value_length($i)).all(|k| {
+                array_value_equals(
+                    &*l_values,
+                    (l_start + k).to_usize(),
+                    &*r_values,
+                    (r_start + k).to_usize(),
+                )
+            })
+    }};
+}
+
+macro_rules! dictionary_value_equals {
+    ($left:expr, $right:expr, $i:expr, $j:expr, $array_type:ident) => {{
+        let l = $left.downcast_ref::<$array_type>().unwrap();
+        let r = $right.downcast_ref::<$array_type>().unwrap();
+        array_value_equals(
+            &*l.values(),
+            l.keys().value($i) as usize,
+            &*r.values(),
+            r.keys().value($j) as usize,
+        )
+    }};
+}
+
+/// Returns whether the element at index `i` of `left` equals the element at index `j`
+/// of `right`, where both arrays have the same data type.
+pub(crate) fn array_value_equals(
+    left: &dyn Array,
+    i: usize,
+    right: &dyn Array,
+    j: usize,
+) -> bool {
+    match (left.is_null(i), right.is_null(j)) {
+        (true, true) => true,
+        (false, false) => {
+            any_value_equals(left.as_any(), right.as_any(), left.data_type(), i, j)
+        }
+        _ => false,
+    }
+}
+
+/// Compares the non-null element at index `i` of `left` with the non-null element at
+/// index `j` of `right`, where both arrays have the data type `data_type`.
+fn any_value_equals(
+    left: &dyn Any,
+    right: &dyn Any,
+    data_type: &DataType,
+    i: usize,
+    j: usize,
+) -> bool {
+    match data_type {
+        DataType::Null => true,
+        DataType::Boolean => primitive_value_equals!(left, right, i, j, BooleanArray),
+        DataType::Int8 => primitive_value_equals!(left, right, i, j, Int8Array),
+        DataType::Int16 => primitive_value_equals!(left, right, i, j, Int16Array),
+        DataType::Int32 => primitive_value_equals!(left, right, i, j, Int32Array),
+        DataType::Int64 => primitive_value_equals!(left, right, i, j, Int64Array),
+        DataType::UInt8 => primitive_value_equals!(left, right, i, j, UInt8Array),
+        DataType::UInt16 => primitive_value_equals!(left, right, i, j, UInt16Array),
+        DataType::UInt32 => primitive_value_equals!(left, right, i, j, UInt32Array),
+        DataType::UInt64 => primitive_value_equals!(left, right, i, j, UInt64Array),
+        DataType::Float32 => primitive_value_equals!(left, right, i, j, Float32Array),
+        DataType::Float64 => primitive_value_equals!(left, right, i, j, Float64Array),
+        DataType::Decimal(_, _) => {
+            primitive_value_equals!(left, right, i, j, DecimalArray)
+        }
+        DataType::Timestamp(TimeUnit::Second, _) => {
+            primitive_value_equals!(left, right, i, j, TimestampSecondArray)
+        }
+        DataType::Timestamp(TimeUnit::Millisecond, _) => {
+            primitive_value_equals!(left, right, i, j, TimestampMillisecondArray)
+        }
+        DataType::Timestamp(TimeUnit::Microsecond, _) => {
+            primitive_value_equals!(left, right, i, j, TimestampMicrosecondArray)
+        }
+        DataType::Timestamp(TimeUnit::Nanosecond, _) => {
+            primitive_value_equals!(left, right, i, j, TimestampNanosecondArray)
+        }
+        DataType::Date32(DateUnit::Day) => {
+            primitive_value_equals!(left, right, i, j, Date32Array)
+        }
+        DataType::Date64(DateUnit::Millisecond) => {
+            primitive_value_equals!(left, right, i, j, Date64Array)
+        }
+        DataType::Time32(TimeUnit::Second) => {
+            primitive_value_equals!(left, right, i, j, Time32SecondArray)
+        }
+        DataType::Time32(TimeUnit::Millisecond) => {
+            primitive_value_equals!(left, right, i, j, Time32MillisecondArray)
+        }
+        DataType::Time64(TimeUnit::Microsecond) => {
+            primitive_value_equals!(left, right, i, j, Time64MicrosecondArray)
+        }
+        DataType::Time64(TimeUnit::Nanosecond) => {
+            primitive_value_equals!(left, right, i, j, Time64NanosecondArray)
+        }
+        DataType::Interval(IntervalUnit::YearMonth) => primitive_value_equals!(
+            left,
+            right,
+            i,
+            j,
+            PrimitiveArray<IntervalYearMonthType>
+        ),
+        DataType::Interval(IntervalUnit::DayTime) => primitive_value_equals!(
+            left,
+            right,
+            i,
+            j,
+            PrimitiveArray<IntervalDayTimeType>
+        ),
+        DataType::Utf8 => primitive_value_equals!(left, right, i, j, BinaryArray),
+        DataType::LargeUtf8 => {
+            primitive_value_equals!(left, right, i, j, LargeBinaryArray)
+        }
+        DataType::FixedSizeBinary(_) => {
+            primitive_value_equals!(left, right, i, j, FixedSizeBinaryArray)
+        }
+        DataType::List(_) => list_value_equals!(left, right, i, j, ListArray),
+        DataType::LargeList(_) => list_value_equals!(left, right, i, j, LargeListArray),
+        DataType::FixedSizeList(_, _) => {
+            let l = left.downcast_ref::<FixedSizeListArray>().unwrap();
+            let r = right.downcast_ref::<FixedSizeListArray>().unwrap();
+            l.value(i).equals(&*r.value(j))
+        }
+        DataType::Struct(fields) => {
+            let l = left.downcast_ref::<StructArray>().unwrap();
+            let r = right.downcast_ref::<StructArray>().unwrap();
+            (0..fields.len())
+                .all(|k| array_value_equals(&**l.column(k), i, &**r.column(k), j))
+        }
+        DataType::Union(_, _) => {
+            let l = left.downcast_ref::<UnionArray>().unwrap();
+            let r = right.downcast_ref::<UnionArray>().unwrap();
+            l.type_id(i) == r.type_id(j)
+                && array_value_equals(
+                    &**l.child(l.type_id(i)),
+                    l.value_offset(i) as usize,
+                    &**r.child(r.type_id(j)),
+                    r.value_offset(j) as usize,
+                )
+        }
+        DataType::Dictionary(key_type, _) => match **key_type {
+            DataType::Int8 => {
+                dictionary_value_equals!(left, right, i, j, Int8DictionaryArray)
+            }
+            DataType::Int16 => {
+                dictionary_value_equals!(left, right, i, j, Int16DictionaryArray)
+            }
+            DataType::Int32 => {
+                dictionary_value_equals!(left, right, i, j, Int32DictionaryArray)
+            }
+            DataType::Int64 => {
+                dictionary_value_equals!(left, right, i, j, Int64DictionaryArray)
+            }
+            DataType::UInt8 => {
+                dictionary_value_equals!(left, right, i, j, UInt8DictionaryArray)
+            }
+            DataType::UInt16 => {
+                dictionary_value_equals!(left, right, i, j, UInt16DictionaryArray)
+            }
+            DataType::UInt32 => {
+                dictionary_value_equals!(left, right, i, j, UInt32DictionaryArray)
+            }
+            DataType::UInt64 => {
+                dictionary_value_equals!(left, right, i, j, UInt64DictionaryArray)
+            }
+            ref dt => panic!("Unexpected dictionary key type {:?}", dt),
+        },
+        dt => panic!("Cannot compare values of data type {:?}", dt),
+    }
+}
+
 /// ----------------------------------------------------------------------------
 /// Implementations of different array types
 
@@ -168,6 +707,68 @@ impl<T> RawPtrBox<T> {
 unsafe impl<T> Send for RawPtrBox<T> {}
 unsafe impl<T> Sync for RawPtrBox<T> {}
 
+/// An array whose elements are all null, of type `DataType::Null`.
+///
+/// The array has no buffers at all, not even a null bitmap: it only stores its length.
+pub struct NullArray {
+    data: ArrayDataRef,
+}
+
+impl NullArray {
+    /// Creates a new array of `length` null elements.
+    pub fn new(length: usize) -> Self {
+        let array_data = ArrayData::builder(DataType::Null)
+            .len(length)
+            .null_count(length)
+            .build();
+        NullArray::from(array_data)
+    }
+}
+
+impl From<ArrayDataRef> for NullArray {
+    fn from(data: ArrayDataRef) -> Self {
+        assert_eq!(
+            data.data_type(),
+            &DataType::Null,
+            "NullArray data should be of the Null data type"
+        );
+        assert!(
+            data.buffers().is_empty(),
+            "NullArray data should not contain any buffer"
+        );
+        Self { data }
+    }
+}
+
+impl Array for NullArray {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn data(&self) -> ArrayDataRef {
+        self.data.clone()
+    }
+
+    fn data_ref(&self) -> &ArrayDataRef {
+        &self.data
+    }
+
+    /// Returns `true`, all elements of a `NullArray` are null
+    fn is_null(&self, _i: usize) -> bool {
+        true
+    }
+
+    /// Returns `false`, all elements of a `NullArray` are null
+    fn is_valid(&self, _i: usize) -> bool {
+        false
+    }
+
+    /// Returns the length of this array, as all its elements are null
+    fn null_count(&self) -> usize {
+        self.data.len()
+    }
+}
+
 /// Array whose elements are of primitive types.
 pub struct PrimitiveArray<T: ArrowPrimitiveType> {
     data: ArrayDataRef,
@@ -295,7 +896,7 @@ where
                 (v % MILLISECONDS * MICROSECONDS) as u32,
             )),
             DataType::Time32(_) | DataType::Time64(_) => None,
-            DataType::Timestamp(unit) => match unit {
+            DataType::Timestamp(unit, _) => match unit {
                 TimeUnit::Second => Some(NaiveDateTime::from_timestamp(v, 0)),
                 TimeUnit::Millisecond => Some(NaiveDateTime::from_timestamp(
                     // extract seconds from milliseconds
@@ -322,6 +923,23 @@ where
         }
     }
 
+    /// Returns value as a chrono `DateTime` in the timezone of the array.
+    ///
+    /// Timezones are fixed offsets from UTC such as `"+05:30"`, `"-0800"` or `"UTC"`.
+    /// Values of arrays without timezone, or with a timezone that isn't a fixed offset,
+    /// are returned in UTC.
+    pub fn value_as_datetime_tz(&self, i: usize) -> Option<DateTime<FixedOffset>> {
+        let offset = match self.data_type() {
+            DataType::Timestamp(_, Some(tz)) => timezone_offset_seconds(tz)
+                .ok()
+                .and_then(|seconds| FixedOffset::east_opt(seconds as i32)),
+            _ => None,
+        };
+        let offset = offset.unwrap_or_else(|| Utc.fix());
+        self.value_as_datetime(i)
+            .map(|datetime| offset.from_utc_datetime(&datetime))
+    }
+
     /// Returns value as a chrono `NaiveDate` by using `Self::datetime()`
     ///
     /// If a data type cannot be converted to `NaiveDate`, a `None` is returned
@@ -379,7 +997,7 @@ where
                     _ => None,
                 }
             }
-            DataType::Timestamp(_) => match self.value_as_datetime(i) {
+            DataType::Timestamp(_, _) => match self.value_as_datetime(i) {
                 Some(datetime) => Some(datetime.time()),
                 None => None,
             },
@@ -392,50 +1010,37 @@ where
     }
 }
 
-impl<T: ArrowNumericType> fmt::Debug for PrimitiveArray<T> {
-    default fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "PrimitiveArray<{:?}>\n[\n", T::get_data_type())?;
-        for i in 0..self.len() {
-            if self.is_null(i) {
-                write!(f, "  null,\n")?;
-            } else {
-                write!(f, "  {:?},\n", self.value(i))?;
-            }
+#[cfg(feature = "ndarray")]
+impl<T: ArrowNumericType> PrimitiveArray<T> {
+    /// Creates a null-free array by copying the values of the 1-dimensional `array`
+    pub fn from_ndarray(array: ndarray::ArrayView1<T::Native>) -> Self {
+        let values: Vec<T::Native> = array.iter().cloned().collect();
+        let data = ArrayData::builder(T::get_data_type())
+            .len(values.len())
+            .add_buffer(Buffer::from(values.to_byte_slice()))
+            .build();
+        PrimitiveArray::from(data)
+    }
+
+    /// Copies the values of this array into a 1-dimensional `ndarray::Array1`, which
+    /// fails if the array contains nulls
+    pub fn to_ndarray(&self) -> Result<ndarray::Array1<T::Native>> {
+        if self.null_count() > 0 {
+            return Err(ArrowError::InvalidArgumentError(
+                "Cannot convert an array with nulls to an ndarray".to_string(),
+            ));
         }
-        write!(f, "]")
+        Ok(ndarray::Array1::from(
+            self.value_slice(0, self.len()).to_vec(),
+        ))
     }
 }
 
-impl<T: ArrowNumericType + ArrowTemporalType> fmt::Debug for PrimitiveArray<T>
-where
-    i64: std::convert::From<T::Native>,
-{
+impl<T: ArrowPrimitiveType> fmt::Debug for PrimitiveArray<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "PrimitiveArray<{:?}>\n[\n", T::get_data_type())?;
+        write!(f, "PrimitiveArray<{:?}>\n[\n", self.data_type())?;
         for i in 0..self.len() {
-            if self.is_null(i) {
-                write!(f, "  null,\n")?;
-            } else {
-                match T::get_data_type() {
-                    DataType::Date32(_) | DataType::Date64(_) => {
-                        match self.value_as_date(i) {
-                            Some(date) => write!(f, "  {:?},\n", date)?,
-                            None => write!(f, "  null,\n")?,
-                        }
-                    }
-                    DataType::Time32(_) | DataType::Time64(_) => {
-                        match self.value_as_time(i) {
-                            Some(time) => write!(f, "  {:?},\n", time)?,
-                            None => write!(f, "  null,\n")?,
-                        }
-                    }
-                    DataType::Timestamp(_) => match self.value_as_datetime(i) {
-                        Some(datetime) => write!(f, "  {:?},\n", datetime)?,
-                        None => write!(f, "  null,\n")?,
-                    },
-                    _ => write!(f, "  {:?},\n", "null,\n")?,
-                }
-            }
+            write!(f, "  {},\n", self.value_to_string(i))?;
         }
         write!(f, "]")
     }
@@ -460,10 +1065,20 @@ impl PrimitiveArray<BooleanType> {
         self.data.buffers()[0].clone()
     }
 
+    /// Returns the values of this array as bit-packed bytes, together with the offset of
+    /// the bit of the first value in the first byte, which is less than 8.
+    ///
+    /// Unlike `values`, the returned buffer starts at the byte holding the first value of
+    /// this array, so its bits can be processed a byte or a word at a time.
+    pub fn bit_values(&self) -> (Buffer, usize) {
+        let offset = self.offset();
+        (self.data.buffers()[0].slice(offset / 8), offset % 8)
+    }
+
     /// Returns the boolean value at index `i`.
     pub fn value(&self, i: usize) -> bool {
+        assert!(i < self.data.len());
         let offset = i + self.offset();
-        assert!(offset < self.data.len());
         unsafe { bit_util::get_bit_raw(self.raw_values.get() as *const u8, offset) }
     }
 
@@ -473,20 +1088,6 @@ impl PrimitiveArray<BooleanType> {
     }
 }
 
-impl fmt::Debug for PrimitiveArray<BooleanType> {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "PrimitiveArray<{:?}>\n[\n", BooleanType::get_data_type())?;
-        for i in 0..self.len() {
-            if self.is_null(i) {
-                write!(f, "  null,\n")?
-            } else {
-                write!(f, "  {:?},\n", self.value(i))?
-            }
-        }
-        write!(f, "]")
-    }
-}
-
 // TODO: the macro is needed here because we'd get "conflicting implementations" error
 // otherwise with both `From<Vec<T::Native>>` and `From<Vec<Option<T::Native>>>`.
 // We should revisit this in future.
@@ -518,11 +1119,9 @@ macro_rules! def_numeric_from_vec {
                     for (i, v) in data.iter().enumerate() {
                         if let Some(n) = v {
                             bit_util::set_bit(null_slice, i);
-                            // unwrap() in the following should be safe here since we've
-                            // made sure enough space is allocated for the values.
-                            val_buf.write(&n.to_byte_slice()).unwrap();
+                            val_buf.extend_from_slice(n.to_byte_slice());
                         } else {
-                            val_buf.write(&null).unwrap();
+                            val_buf.extend_from_slice(&null);
                         }
                     }
                 }
@@ -553,22 +1152,22 @@ def_numeric_from_vec!(Float64Type, f64, DataType::Float64);
 def_numeric_from_vec!(
     TimestampSecondType,
     i64,
-    DataType::Timestamp(TimeUnit::Second)
+    DataType::Timestamp(TimeUnit::Second, None)
 );
 def_numeric_from_vec!(
     TimestampMillisecondType,
     i64,
-    DataType::Timestamp(TimeUnit::Millisecond)
+    DataType::Timestamp(TimeUnit::Millisecond, None)
 );
 def_numeric_from_vec!(
     TimestampMicrosecondType,
     i64,
-    DataType::Timestamp(TimeUnit::Microsecond)
+    DataType::Timestamp(TimeUnit::Microsecond, None)
 );
 def_numeric_from_vec!(
     TimestampNanosecondType,
     i64,
-    DataType::Timestamp(TimeUnit::Nanosecond)
+    DataType::Timestamp(TimeUnit::Nanosecond, None)
 );
 def_numeric_from_vec!(Date32Type, i32, DataType::Date32(DateUnit::Day));
 def_numeric_from_vec!(Date64Type, i64, DataType::Date64(DateUnit::Millisecond));
@@ -640,6 +1239,110 @@ impl From<Vec<Option<bool>>> for BooleanArray {
     }
 }
 
+/// Compares boolean arrays logically like `Array::equals`. Arrays without an offset or
+/// nulls are compared by their bit-packed values, ignoring the padding bits past the
+/// end of the arrays.
+impl PartialEq for BooleanArray {
+    fn eq(&self, other: &Self) -> bool {
+        if self.offset() == 0
+            && other.offset() == 0
+            && self.null_count() == 0
+            && other.null_count() == 0
+        {
+            return self.len() == other.len()
+                && self.data.buffers()[0]
+                    .bit_equals(&other.data.buffers()[0], self.len());
+        }
+        self.equals(other)
+    }
+}
+
+/// A boolean array without nulls stored as the sorted positions of its `true` values.
+///
+/// This is much smaller than a `BooleanArray` for masks that are overwhelmingly `false`:
+/// each `true` value takes 4 bytes instead of 1 bit for every value, so the sparse form
+/// is smaller as long as fewer than 1 in 32 values are `true`. The trade-off is that
+/// `value` does a binary search, taking `O(log(n))` time instead of `O(1)`, and that the
+/// array must be converted back with `to_dense` to be used with other kernels.
+#[derive(Debug)]
+pub struct SparseBooleanArray {
+    true_indices: UInt32Array,
+    len: usize,
+}
+
+impl SparseBooleanArray {
+    /// Creates a sparse boolean array of length `len` whose `true` values are at the
+    /// positions in `true_indices`, which must be strictly increasing, non-null and
+    /// lower than `len`.
+    pub fn new(true_indices: UInt32Array, len: usize) -> Self {
+        assert_eq!(
+            0,
+            true_indices.null_count(),
+            "SparseBooleanArray true indices must not be null"
+        );
+        let indices = true_indices.value_slice(0, true_indices.len());
+        assert!(
+            indices.windows(2).all(|w| w[0] < w[1]),
+            "SparseBooleanArray true indices must be strictly increasing"
+        );
+        if let Some(last) = indices.last() {
+            assert!(
+                (*last as usize) < len,
+                "SparseBooleanArray true index {} is out of bounds for length {}",
+                last,
+                len
+            );
+        }
+        Self { true_indices, len }
+    }
+
+    /// Returns the length of this array.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether this array is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the sorted positions of the `true` values of this array.
+    pub fn true_indices(&self) -> &UInt32Array {
+        &self.true_indices
+    }
+
+    /// Returns the number of `true` values of this array.
+    pub fn true_count(&self) -> usize {
+        self.true_indices.len()
+    }
+
+    /// Returns the boolean value at index `i`.
+    pub fn value(&self, i: usize) -> bool {
+        assert!(i < self.len);
+        self.true_indices
+            .value_slice(0, self.true_indices.len())
+            .binary_search(&(i as u32))
+            .is_ok()
+    }
+
+    /// Converts this array to a `BooleanArray` without nulls.
+    pub fn to_dense(&self) -> BooleanArray {
+        let num_bytes = bit_util::ceil(self.len, 8);
+        let mut values = MutableBuffer::new(num_bytes).with_bitset(num_bytes, false);
+        {
+            let values = values.data_mut();
+            for i in self.true_indices.value_slice(0, self.true_indices.len()) {
+                bit_util::set_bit(values, *i as usize);
+            }
+        }
+        let array_data = ArrayData::builder(DataType::Boolean)
+            .len(self.len)
+            .add_buffer(values.freeze())
+            .build();
+        BooleanArray::from(array_data)
+    }
+}
+
 /// Constructs a `PrimitiveArray` from an array data reference.
 impl<T: ArrowPrimitiveType> From<ArrayDataRef> for PrimitiveArray<T> {
     default fn from(data: ArrayDataRef) -> Self {
@@ -660,75 +1363,315 @@ impl<T: ArrowPrimitiveType> From<ArrayDataRef> for PrimitiveArray<T> {
     }
 }
 
-/// A list array where each element is a variable-sized sequence of values with the same
-/// type.
-pub struct ListArray {
-    data: ArrayDataRef,
-    values: ArrayRef,
-    value_offsets: RawPtrBox<i32>,
+/// Constructs a `PrimitiveArray` from an iterator of optional values in a single pass,
+/// where `None` values are null elements, e.g. with `collect::<Int32Array>()`.
+impl<T: ArrowPrimitiveType> FromIterator<Option<T::Native>> for PrimitiveArray<T> {
+    fn from_iter<I: IntoIterator<Item = Option<T::Native>>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut builder = PrimitiveBuilder::<T>::new(iter.size_hint().0);
+        for v in iter {
+            builder
+                .append_option(v)
+                .expect("Failed to append a value to the array");
+        }
+        builder.finish()
+    }
 }
 
-impl ListArray {
-    /// Returns an reference to the values of this list.
-    pub fn values(&self) -> ArrayRef {
-        self.values.clone()
+impl<T: ArrowPrimitiveType> PrimitiveArray<T> {
+    /// Constructs an array without null elements from an iterator of values, in a single
+    /// pass.
+    pub fn from_iter_values<I: IntoIterator<Item = T::Native>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut builder = PrimitiveBuilder::<T>::new(iter.size_hint().0);
+        for v in iter {
+            builder
+                .append_value(v)
+                .expect("Failed to append a value to the array");
+        }
+        builder.finish()
     }
+}
 
-    /// Returns a clone of the value type of this list.
-    pub fn value_type(&self) -> DataType {
-        self.values.data().data_type().clone()
+/// Trait for the native types of the value offsets of variable-sized arrays: `i32` for
+/// `ListArray` and `BinaryArray`, and `i64` for `LargeListArray` and `LargeBinaryArray`.
+pub trait OffsetSizeTrait:
+    ArrowNativeType + std::ops::Sub<Output = Self> + ToByteSlice
+{
+    /// Returns whether this is the offset type of the large array variants.
+    fn is_large() -> bool;
+
+    /// Converts this offset to a `usize`, panicking if it is negative.
+    fn to_usize(self) -> usize;
+
+    /// Converts `v` to an offset, or returns `None` if it doesn't fit.
+    fn from_usize(v: usize) -> Option<Self>;
+
+    /// Returns the prefix of the names of the arrays using this offset type, i.e.
+    /// `"Large"` for the large variants.
+    fn prefix() -> &'static str {
+        if Self::is_large() {
+            "Large"
+        } else {
+            ""
+        }
     }
+}
 
-    /// Returns the offset for value at index `i`.
-    ///
-    /// Note this doesn't do any bound checking, for performance reason.
-    #[inline]
-    pub fn value_offset(&self, i: usize) -> i32 {
-        self.value_offset_at(self.data.offset() + i)
+impl OffsetSizeTrait for i32 {
+    fn is_large() -> bool {
+        false
     }
 
-    /// Returns the length for value at index `i`.
+    fn to_usize(self) -> usize {
+        assert!(self >= 0, "negative offset {}", self);
+        self as usize
+    }
+
+    fn from_usize(v: usize) -> Option<Self> {
+        if v <= i32::max_value() as usize {
+            Some(v as i32)
+        } else {
+            None
+        }
+    }
+}
+
+impl OffsetSizeTrait for i64 {
+    fn is_large() -> bool {
+        true
+    }
+
+    fn to_usize(self) -> usize {
+        assert!(self >= 0, "negative offset {}", self);
+        self as usize
+    }
+
+    fn from_usize(v: usize) -> Option<Self> {
+        if v <= i64::max_value() as usize {
+            Some(v as i64)
+        } else {
+            None
+        }
+    }
+}
+
+/// A list array where each element is a variable-sized sequence of values with the same
+/// type, with value offsets of type `OffsetSize`.
+///
+/// Use the `ListArray` and `LargeListArray` aliases rather than this type directly.
+pub struct GenericListArray<OffsetSize> {
+    data: ArrayDataRef,
+    values: ArrayRef,
+    value_offsets: RawPtrBox<OffsetSize>,
+}
+
+/// A list array with 32-bit value offsets.
+pub type ListArray = GenericListArray<i32>;
+/// A list array with 64-bit value offsets, so that the values of all its lists together
+/// can hold more than `i32::max_value()` elements.
+pub type LargeListArray = GenericListArray<i64>;
+
+impl<OffsetSize: OffsetSizeTrait> GenericListArray<OffsetSize> {
+    /// Creates a list array from the array of the values of all its lists and the
+    /// offsets of each list into it, so that the list at index `i` holds the values
+    /// between `offsets[i]` and `offsets[i + 1]`.
+    ///
+    /// Returns an error if the offsets don't start at zero, decrease, or don't end at
+    /// the length of `values`, or if `null_bitmap` is too short for the lists.
+    pub fn try_new(
+        values: ArrayRef,
+        offsets: &[OffsetSize],
+        null_bitmap: Option<Buffer>,
+    ) -> Result<Self> {
+        let (first, last) = match (offsets.first(), offsets.last()) {
+            (Some(first), Some(last)) => (*first, *last),
+            _ => {
+                return Err(ArrowError::InvalidArgumentError(format!(
+                    "{}ListArray offsets must contain at least one element",
+                    OffsetSize::prefix()
+                )))
+            }
+        };
+        if Some(first) != OffsetSize::from_usize(0) {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "{}ListArray offsets must start at 0, but start at {:?}",
+                OffsetSize::prefix(),
+                first
+            )));
+        }
+        if let Some(i) = offsets.windows(2).position(|w| w[0] > w[1]) {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "{}ListArray offsets must be monotonic, but offset {} is {:?} and \
+                 offset {} is {:?}",
+                OffsetSize::prefix(),
+                i,
+                offsets[i],
+                i + 1,
+                offsets[i + 1]
+            )));
+        }
+        if last.to_usize() != values.len() {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "{}ListArray offsets must end at the length of the values ({}), but \
+                 end at {:?}",
+                OffsetSize::prefix(),
+                values.len(),
+                last
+            )));
+        }
+        let len = offsets.len() - 1;
+        if let Some(ref bitmap) = null_bitmap {
+            if bitmap.len() < bit_util::ceil(len, 8) {
+                return Err(ArrowError::InvalidArgumentError(format!(
+                    "{}ListArray null bitmap of {} bytes is too short for {} lists",
+                    OffsetSize::prefix(),
+                    bitmap.len(),
+                    len
+                )));
+            }
+        }
+
+        let value_type = Box::new(values.data_type().clone());
+        let data_type = if OffsetSize::is_large() {
+            DataType::LargeList(value_type)
+        } else {
+            DataType::List(value_type)
+        };
+        let mut builder = ArrayData::builder(data_type)
+            .len(len)
+            .add_buffer(Buffer::from(offsets.to_byte_slice()))
+            .add_child_data(values.data());
+        if let Some(bitmap) = null_bitmap {
+            builder = builder.null_bit_buffer(bitmap);
+        }
+        Ok(Self::from(builder.build()))
+    }
+
+    /// Returns an reference to the values of this list.
+    pub fn values(&self) -> ArrayRef {
+        self.values.clone()
+    }
+
+    /// Returns a clone of the value type of this list.
+    pub fn value_type(&self) -> DataType {
+        self.values.data().data_type().clone()
+    }
+
+    /// Returns the offset for value at index `i`.
+    ///
+    /// Note this doesn't do any bound checking, for performance reason.
+    #[inline]
+    pub fn value_offset(&self, i: usize) -> OffsetSize {
+        self.value_offset_at(self.data.offset() + i)
+    }
+
+    /// Returns the length for value at index `i`.
     ///
     /// Note this doesn't do any bound checking, for performance reason.
     #[inline]
-    pub fn value_length(&self, mut i: usize) -> i32 {
+    pub fn value_length(&self, mut i: usize) -> OffsetSize {
         i += self.data.offset();
         self.value_offset_at(i + 1) - self.value_offset_at(i)
     }
 
+    /// Returns the number of values of the child array, i.e. of all the lists of the
+    /// underlying data.
+    pub fn values_len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns the range of the values of the lists at indices `start..end` in the child
+    /// array, as a pair of start and end offsets.
+    ///
+    /// Panics if `start > end` or `end > len()`.
+    pub fn value_range(&self, start: usize, end: usize) -> (usize, usize) {
+        assert!(
+            start <= end && end <= self.len(),
+            "{}ListArray range {}..{} is out of bounds",
+            OffsetSize::prefix(),
+            start,
+            end
+        );
+        (
+            self.value_offset(start).to_usize(),
+            self.value_offset(end).to_usize(),
+        )
+    }
+
+    /// Returns the offsets of the values of all the lists of this array, accounting for
+    /// its offset, so that the values of the list at index `i` are between
+    /// `value_offsets()[i]` and `value_offsets()[i + 1]`.
+    pub fn value_offsets(&self) -> &[OffsetSize] {
+        unsafe {
+            ::std::slice::from_raw_parts(
+                self.value_offsets.get().offset(self.data.offset() as isize),
+                self.data.len() + 1,
+            )
+        }
+    }
+
     #[inline]
-    fn value_offset_at(&self, i: usize) -> i32 {
+    fn value_offset_at(&self, i: usize) -> OffsetSize {
         unsafe { *self.value_offsets.get().offset(i as isize) }
     }
 }
 
-/// Constructs a `ListArray` from an array data reference.
-impl From<ArrayDataRef> for ListArray {
+/// Constructs a `ListArray` or a `LargeListArray` from an array data reference.
+impl<OffsetSize: OffsetSizeTrait> From<ArrayDataRef> for GenericListArray<OffsetSize> {
     fn from(data: ArrayDataRef) -> Self {
         assert_eq!(
             data.buffers().len(),
             1,
-            "ListArray data should contain a single buffer only (value offsets)"
+            "{}ListArray data should contain a single buffer only (value offsets)",
+            OffsetSize::prefix()
         );
         assert_eq!(
             data.child_data().len(),
             1,
-            "ListArray should contain a single child array (values array)"
+            "{}ListArray should contain a single child array (values array)",
+            OffsetSize::prefix()
         );
         let values = make_array(data.child_data()[0].clone());
         let raw_value_offsets = data.buffers()[0].raw_data();
         assert!(
-            memory::is_aligned(raw_value_offsets, mem::align_of::<i32>()),
+            memory::is_aligned(raw_value_offsets, mem::align_of::<OffsetSize>()),
             "memory is not aligned"
         );
-        let value_offsets = raw_value_offsets as *const i32;
+        let value_offsets = raw_value_offsets as *const OffsetSize;
+        let num_offsets = data.buffers()[0].len() / mem::size_of::<OffsetSize>();
+        let end = data.offset() + data.len();
+        assert!(
+            end < num_offsets,
+            "{}ListArray data of length {} at offset {} needs {} offsets, the offsets \
+             buffer holds {}",
+            OffsetSize::prefix(),
+            data.len(),
+            data.offset(),
+            end + 1,
+            num_offsets
+        );
         unsafe {
-            assert_eq!(*value_offsets.offset(0), 0, "offsets do not start at zero");
             assert_eq!(
-                *value_offsets.offset(data.len() as isize),
-                values.data().len() as i32,
-                "inconsistent offsets buffer and values array"
+                (*value_offsets.offset(0)).to_usize(),
+                0,
+                "offsets do not start at zero"
             );
+            let end_offset = (*value_offsets.offset(end as isize)).to_usize();
+            // the offsets of sliced list data may end before the end of the values, but
+            // the last offset of the buffer must be the number of values
+            if end + 1 == num_offsets {
+                assert_eq!(
+                    end_offset,
+                    values.data().len(),
+                    "inconsistent offsets buffer and values array"
+                );
+            } else {
+                assert!(
+                    end_offset <= values.data().len(),
+                    "inconsistent offsets buffer and values array"
+                );
+            }
         }
         Self {
             data: data.clone(),
@@ -738,7 +1681,170 @@ impl From<ArrayDataRef> for ListArray {
     }
 }
 
-impl Array for ListArray {
+impl<OffsetSize: OffsetSizeTrait> Array for GenericListArray<OffsetSize> {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn data(&self) -> ArrayDataRef {
+        self.data.clone()
+    }
+
+    fn data_ref(&self) -> &ArrayDataRef {
+        &self.data
+    }
+}
+
+/// A list array where each element is a sequence of the same fixed number of values.
+///
+/// Unlike `ListArray`, there is no offsets buffer: the values of the element at index `i`
+/// are the values `i * value_length()..(i + 1) * value_length()` of the child array.
+pub struct FixedSizeListArray {
+    data: ArrayDataRef,
+    values: ArrayRef,
+    length: i32,
+}
+
+impl FixedSizeListArray {
+    /// Returns an reference to the values of this list.
+    pub fn values(&self) -> ArrayRef {
+        self.values.clone()
+    }
+
+    /// Returns a clone of the value type of this list.
+    pub fn value_type(&self) -> DataType {
+        self.values.data().data_type().clone()
+    }
+
+    /// Returns the values of the element at index `i` as an array sharing the buffers of
+    /// the child array.
+    pub fn value(&self, i: usize) -> ArrayRef {
+        assert!(
+            i < self.data.len(),
+            "FixedSizeListArray out of bounds access"
+        );
+        let values = self.values.data_ref();
+        let mut builder = ArrayData::builder(values.data_type().clone())
+            .len(self.length as usize)
+            .offset(values.offset() + self.value_offset(i) as usize)
+            .buffers(values.buffers().to_vec())
+            .child_data(values.child_data().to_vec());
+        if let Some(bitmap) = values.null_bitmap() {
+            builder = builder.null_bit_buffer(bitmap.bits.clone());
+        }
+        make_array(builder.build())
+    }
+
+    /// Returns the offset in the child array of the values of the element at index `i`.
+    ///
+    /// Panics if the offset doesn't fit in an `i32`.
+    #[inline]
+    pub fn value_offset(&self, i: usize) -> i32 {
+        i32::try_from((self.data.offset() + i) * self.length as usize)
+            .expect("FixedSizeListArray value offset doesn't fit in an i32")
+    }
+
+    /// Returns the number of values of each element of this list.
+    #[inline]
+    pub fn value_length(&self) -> i32 {
+        self.length
+    }
+}
+
+#[cfg(feature = "ndarray")]
+impl FixedSizeListArray {
+    /// Creates a null-free list array with one list per row of the 2-dimensional
+    /// `array`, by copying its values
+    pub fn from_ndarray2<T: ArrowNumericType>(
+        array: ndarray::ArrayView2<T::Native>,
+    ) -> Self {
+        let (rows, columns) = array.dim();
+        let values: Vec<T::Native> = array.iter().cloned().collect();
+        let values = ArrayData::builder(T::get_data_type())
+            .len(values.len())
+            .add_buffer(Buffer::from(values.to_byte_slice()))
+            .build();
+        let data = ArrayData::builder(DataType::FixedSizeList(
+            Box::new(T::get_data_type()),
+            columns as i32,
+        ))
+        .len(rows)
+        .add_child_data(values)
+        .build();
+        FixedSizeListArray::from(data)
+    }
+
+    /// Copies the values of this array into a 2-dimensional `ndarray::Array2`, with one
+    /// row per list.
+    ///
+    /// Fails if the list or its values contain nulls, or if the values aren't of type
+    /// `T`.
+    pub fn to_ndarray2<T: ArrowNumericType>(&self) -> Result<ndarray::Array2<T::Native>> {
+        let values = self
+            .values
+            .as_any()
+            .downcast_ref::<PrimitiveArray<T>>()
+            .ok_or_else(|| {
+                ArrowError::InvalidArgumentError(format!(
+                    "Cannot convert a list of {:?} to an ndarray of {:?}",
+                    self.value_type(),
+                    T::get_data_type()
+                ))
+            })?;
+        let start = self.value_offset(0) as usize;
+        let len = self.len() * self.length as usize;
+        if self.null_count() > 0 || (start..start + len).any(|i| values.is_null(i)) {
+            return Err(ArrowError::InvalidArgumentError(
+                "Cannot convert an array with nulls to an ndarray".to_string(),
+            ));
+        }
+        let shape = (self.len(), self.length as usize);
+        ndarray::Array2::from_shape_vec(shape, values.value_slice(start, len).to_vec())
+            .map_err(|e| ArrowError::ComputeError(e.to_string()))
+    }
+}
+
+/// Constructs a `FixedSizeListArray` from an array data reference.
+impl From<ArrayDataRef> for FixedSizeListArray {
+    fn from(data: ArrayDataRef) -> Self {
+        assert_eq!(
+            data.buffers().len(),
+            0,
+            "FixedSizeListArray data should not contain a buffer for value offsets"
+        );
+        assert_eq!(
+            data.child_data().len(),
+            1,
+            "FixedSizeListArray should contain a single child array (values array)"
+        );
+        let length = match data.data_type() {
+            DataType::FixedSizeList(_, length) => *length,
+            _ => {
+                panic!("FixedSizeListArray data should contain a FixedSizeList data type")
+            }
+        };
+        assert!(length >= 0, "FixedSizeListArray size must not be negative");
+        let values = make_array(data.child_data()[0].clone());
+        if length > 0 {
+            assert_eq!(
+                values.len() % length as usize,
+                0,
+                "FixedSizeListArray child array length must be a multiple of the list size"
+            );
+        }
+        assert!(
+            values.len() >= (data.offset() + data.len()) * length as usize,
+            "FixedSizeListArray child array is too short for the list length"
+        );
+        Self {
+            data,
+            values,
+            length,
+        }
+    }
+}
+
+impl Array for FixedSizeListArray {
     fn as_any(&self) -> &Any {
         self
     }
@@ -752,27 +1858,83 @@ impl Array for ListArray {
     }
 }
 
-/// A special type of `ListArray` whose elements are binaries.
-pub struct BinaryArray {
+/// A special type of `ListArray` whose elements are binaries, with value offsets of type
+/// `OffsetSize`.
+///
+/// Use the `BinaryArray` and `LargeBinaryArray` aliases rather than this type directly.
+pub struct GenericBinaryArray<OffsetSize> {
     data: ArrayDataRef,
-    value_offsets: RawPtrBox<i32>,
+    value_offsets: RawPtrBox<OffsetSize>,
     value_data: RawPtrBox<u8>,
 }
 
-impl BinaryArray {
+/// A binary array with 32-bit value offsets, of type `Utf8`.
+pub type BinaryArray = GenericBinaryArray<i32>;
+/// A binary array with 64-bit value offsets, of type `LargeUtf8`, for arrays holding more
+/// than `i32::max_value()` bytes.
+pub type LargeBinaryArray = GenericBinaryArray<i64>;
+
+impl<OffsetSize: OffsetSizeTrait> GenericBinaryArray<OffsetSize> {
+    /// Returns the data type of binary arrays with offsets of type `OffsetSize`.
+    fn get_data_type() -> DataType {
+        if OffsetSize::is_large() {
+            DataType::LargeUtf8
+        } else {
+            DataType::Utf8
+        }
+    }
+
     /// Returns the element at index `i` as a byte slice.
+    ///
+    /// Null elements are empty slices, use `is_null` to tell them apart from empty
+    /// values.
     pub fn value(&self, i: usize) -> &[u8] {
-        assert!(i < self.data.len(), "BinaryArray out of bounds access");
+        assert!(
+            i < self.data.len(),
+            "{}BinaryArray out of bounds access",
+            OffsetSize::prefix()
+        );
         let offset = i.checked_add(self.data.offset()).unwrap();
         unsafe {
             let pos = self.value_offset_at(offset);
             ::std::slice::from_raw_parts(
-                self.value_data.get().offset(pos as isize),
-                (self.value_offset_at(offset + 1) - pos) as usize,
+                self.value_data.get().offset(pos.to_usize() as isize),
+                (self.value_offset_at(offset + 1) - pos).to_usize(),
             )
         }
     }
 
+    /// Returns an iterator over the elements of this array as byte slices, yielding
+    /// `None` for the null elements.
+    pub fn iter(&self) -> impl Iterator<Item = Option<&[u8]>> + '_ {
+        (0..self.len()).map(move |i| {
+            if self.is_null(i) {
+                None
+            } else {
+                Some(self.value(i))
+            }
+        })
+    }
+
+    /// Returns an iterator over the elements of this array as strings, yielding `None`
+    /// for the null elements.
+    ///
+    /// The iterator panics when it reaches an element that is not valid UTF-8.
+    pub fn string_iter(&self) -> impl Iterator<Item = Option<&str>> + '_ {
+        self.iter().enumerate().map(|(i, v)| {
+            v.map(|bytes| {
+                ::std::str::from_utf8(bytes).unwrap_or_else(|e| {
+                    panic!(
+                        "{}BinaryArray element {} is not valid UTF-8: {}",
+                        OffsetSize::prefix(),
+                        i,
+                        e
+                    )
+                })
+            })
+        })
+    }
+
     /// Returns the element at index `i` as a string.
     ///
     /// Note this doesn't do any bound checking, for performance reason.
@@ -785,7 +1947,7 @@ impl BinaryArray {
     ///
     /// Note this doesn't do any bound checking, for performance reason.
     #[inline]
-    pub fn value_offset(&self, i: usize) -> i32 {
+    pub fn value_offset(&self, i: usize) -> OffsetSize {
         self.value_offset_at(self.data.offset() + i)
     }
 
@@ -793,109 +1955,751 @@ impl BinaryArray {
     ///
     /// Note this doesn't do any bound checking, for performance reason.
     #[inline]
-    pub fn value_length(&self, mut i: usize) -> i32 {
+    pub fn value_length(&self, mut i: usize) -> OffsetSize {
         i += self.data.offset();
         self.value_offset_at(i + 1) - self.value_offset_at(i)
     }
 
+    /// Returns a clone of the buffer of the bytes of all the elements, i.e. buffer 1.
+    ///
+    /// The bytes of the element at index `i` are between `value_offsets()[i]` and
+    /// `value_offsets()[i + 1]`. The buffer is not sliced to the offset of this array,
+    /// and may contain bytes that don't belong to any element of it.
+    pub fn value_data(&self) -> Buffer {
+        self.data.buffers()[1].clone()
+    }
+
+    /// Returns the offsets of the bytes of all the elements of this array, accounting for
+    /// its offset, so that the element at index `i` is made of the bytes between
+    /// `value_offsets()[i]` and `value_offsets()[i + 1]`.
+    pub fn value_offsets(&self) -> &[OffsetSize] {
+        unsafe {
+            ::std::slice::from_raw_parts(
+                self.value_offsets.get().offset(self.data.offset() as isize),
+                self.data.len() + 1,
+            )
+        }
+    }
+
     #[inline]
-    fn value_offset_at(&self, i: usize) -> i32 {
+    fn value_offset_at(&self, i: usize) -> OffsetSize {
         unsafe { *self.value_offsets.get().offset(i as isize) }
     }
+
+    /// Builds an array from the given values, where `None` values are null elements. The
+    /// null bitmap is omitted if `nullable` is false.
+    fn from_values<'a, I>(len: usize, values_iter: I, nullable: bool) -> Self
+    where
+        I: Iterator<Item = Option<&'a [u8]>>,
+    {
+        let overflow = || {
+            panic!(
+                "{}BinaryArray values are too large for its offsets",
+                OffsetSize::prefix()
+            )
+        };
+        let mut offsets = Vec::with_capacity(len + 1);
+        let mut values = Vec::new();
+        let num_bytes = bit_util::ceil(len, 8);
+        let mut null_buf = MutableBuffer::new(num_bytes).with_bitset(num_bytes, false);
+        offsets.push(OffsetSize::from_usize(0).unwrap());
+        {
+            let null_slice = null_buf.data_mut();
+            for (i, s) in values_iter.enumerate() {
+                if let Some(s) = s {
+                    bit_util::set_bit(null_slice, i);
+                    values.extend_from_slice(s);
+                }
+                offsets
+                    .push(OffsetSize::from_usize(values.len()).unwrap_or_else(overflow));
+            }
+        }
+        let mut builder = ArrayData::builder(Self::get_data_type())
+            .len(len)
+            .add_buffer(Buffer::from(offsets.to_byte_slice()))
+            .add_buffer(Buffer::from(&values[..]));
+        if nullable {
+            builder = builder.null_bit_buffer(null_buf.freeze());
+        }
+        Self::from(builder.build())
+    }
 }
 
-impl From<ArrayDataRef> for BinaryArray {
+impl<OffsetSize: OffsetSizeTrait> From<ArrayDataRef> for GenericBinaryArray<OffsetSize> {
     fn from(data: ArrayDataRef) -> Self {
         assert_eq!(
             data.buffers().len(),
             2,
-            "BinaryArray data should contain 2 buffers only (offsets and values)"
+            "{}BinaryArray data should contain 2 buffers only (offsets and values)",
+            OffsetSize::prefix()
         );
         let raw_value_offsets = data.buffers()[0].raw_data();
         assert!(
-            memory::is_aligned(raw_value_offsets, mem::align_of::<i32>()),
+            memory::is_aligned(raw_value_offsets, mem::align_of::<OffsetSize>()),
             "memory is not aligned"
         );
         let value_data = data.buffers()[1].raw_data();
         Self {
             data: data.clone(),
-            value_offsets: RawPtrBox::new(raw_value_offsets as *const i32),
+            value_offsets: RawPtrBox::new(raw_value_offsets as *const OffsetSize),
             value_data: RawPtrBox::new(value_data),
         }
     }
 }
 
-impl<'a> From<Vec<&'a str>> for BinaryArray {
+impl<'a, OffsetSize: OffsetSizeTrait> From<Vec<&'a str>>
+    for GenericBinaryArray<OffsetSize>
+{
     fn from(v: Vec<&'a str>) -> Self {
-        let mut offsets = Vec::with_capacity(v.len() + 1);
-        let mut values = Vec::new();
-        let mut length_so_far = 0;
-        offsets.push(length_so_far);
-        for s in &v {
-            length_so_far += s.len() as i32;
-            offsets.push(length_so_far as i32);
-            values.extend_from_slice(s.as_bytes());
-        }
-        let array_data = ArrayData::builder(DataType::Utf8)
+        Self::from_values(v.len(), v.iter().map(|s| Some(s.as_bytes())), false)
+    }
+}
+
+impl<'a, OffsetSize: OffsetSizeTrait> From<Vec<Option<&'a str>>>
+    for GenericBinaryArray<OffsetSize>
+{
+    fn from(v: Vec<Option<&'a str>>) -> Self {
+        Self::from_values(v.len(), v.iter().map(|s| s.map(str::as_bytes)), true)
+    }
+}
+
+impl<'a, OffsetSize: OffsetSizeTrait> From<Vec<&[u8]>>
+    for GenericBinaryArray<OffsetSize>
+{
+    fn from(v: Vec<&[u8]>) -> Self {
+        Self::from_values(v.len(), v.iter().map(|s| Some(*s)), false)
+    }
+}
+
+/// Creates a `BinaryArray` from `List<u8>` array, or a `LargeBinaryArray` from a
+/// `LargeList<u8>` array
+impl<OffsetSize: OffsetSizeTrait> From<GenericListArray<OffsetSize>>
+    for GenericBinaryArray<OffsetSize>
+{
+    fn from(v: GenericListArray<OffsetSize>) -> Self {
+        assert_eq!(
+            v.data().child_data()[0].child_data().len(),
+            0,
+            "BinaryArray can only be created from list array of u8 values \
+             (i.e. List<PrimitiveArray<u8>>)."
+        );
+        assert_eq!(
+            v.data().child_data()[0].data_type(),
+            &DataType::UInt8,
+            "BinaryArray can only be created from List<u8> arrays, mismatched data types."
+        );
+
+        // the offsets are relative to the first value of the child array, which is at
+        // its offset in the values buffer
+        let child_data = &v.data().child_data()[0];
+        let mut builder = ArrayData::builder(Self::get_data_type())
             .len(v.len())
-            .add_buffer(Buffer::from(offsets.to_byte_slice()))
-            .add_buffer(Buffer::from(&values[..]))
+            .offset(v.offset())
+            .add_buffer(v.data().buffers()[0].clone())
+            .add_buffer(child_data.buffers()[0].slice(child_data.offset()));
+        if let Some(bitmap) = v.data().null_bitmap() {
+            builder = builder
+                .null_count(v.data().null_count())
+                .null_bit_buffer(bitmap.bits.clone())
+        }
+
+        let data = builder.build();
+        Self::from(data)
+    }
+}
+
+impl<OffsetSize: OffsetSizeTrait> Array for GenericBinaryArray<OffsetSize> {
+    fn as_any(&self) -> &Any {
+        self
+    }
+
+    fn data(&self) -> ArrayDataRef {
+        self.data.clone()
+    }
+
+    fn data_ref(&self) -> &ArrayDataRef {
+        &self.data
+    }
+}
+
+/// An array of binary values that all have the same number of bytes, e.g. UUIDs or
+/// hashes.
+///
+/// Unlike `BinaryArray`, there is no offsets buffer: the element at index `i` is made of
+/// the bytes `i * value_length()..(i + 1) * value_length()` of the values buffer.
+pub struct FixedSizeBinaryArray {
+    data: ArrayDataRef,
+    length: i32,
+}
+
+impl FixedSizeBinaryArray {
+    /// Constructs an array of values of `byte_width` bytes, where `None` values are null
+    /// elements.
+    ///
+    /// Returns an error if a value doesn't have `byte_width` bytes.
+    pub fn from_values(values: Vec<Option<&[u8]>>, byte_width: i32) -> Result<Self> {
+        if byte_width < 0 {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "FixedSizeBinaryArray width must not be negative, got {}",
+                byte_width
+            )));
+        }
+        let width = byte_width as usize;
+        let mut value_buf = MutableBuffer::new(values.len() * width);
+        let mut null_buf = BooleanBufferBuilder::new(values.len());
+        let zeros = vec![0u8; width];
+        for v in &values {
+            match v {
+                Some(v) if v.len() != width => {
+                    return Err(ArrowError::InvalidArgumentError(format!(
+                        "FixedSizeBinaryArray value has {} bytes instead of {}",
+                        v.len(),
+                        width
+                    )));
+                }
+                Some(v) => value_buf.extend_from_slice(v),
+                None => value_buf.extend_from_slice(&zeros),
+            }
+            null_buf.append(v.is_some())?;
+        }
+        let data = ArrayData::builder(DataType::FixedSizeBinary(byte_width))
+            .len(values.len())
+            .add_buffer(value_buf.freeze())
+            .null_bit_buffer(null_buf.finish())
             .build();
-        BinaryArray::from(array_data)
+        Ok(FixedSizeBinaryArray::from(data))
+    }
+
+    /// Returns the bytes of the element at index `i`.
+    pub fn value(&self, i: usize) -> &[u8] {
+        assert!(
+            i < self.data.len(),
+            "FixedSizeBinaryArray out of bounds access"
+        );
+        let start = self.value_offset(i) as usize;
+        &self.data.buffers()[0].data()[start..start + self.length as usize]
+    }
+
+    /// Returns the offset in the values buffer of the element at index `i`.
+    ///
+    /// Panics if the offset doesn't fit in an `i32`.
+    #[inline]
+    pub fn value_offset(&self, i: usize) -> i32 {
+        i32::try_from((self.data.offset() + i) * self.length as usize)
+            .expect("FixedSizeBinaryArray value offset doesn't fit in an i32")
+    }
+
+    /// Returns the number of bytes of each element of this array.
+    #[inline]
+    pub fn value_length(&self) -> i32 {
+        self.length
     }
 }
 
-impl<'a> From<Vec<&[u8]>> for BinaryArray {
-    fn from(v: Vec<&[u8]>) -> Self {
-        let mut offsets = Vec::with_capacity(v.len() + 1);
-        let mut values = Vec::new();
-        let mut length_so_far = 0;
-        offsets.push(length_so_far);
-        for s in &v {
-            length_so_far += s.len() as i32;
-            offsets.push(length_so_far as i32);
-            values.extend_from_slice(s);
+/// Constructs a `FixedSizeBinaryArray` from an array data reference.
+impl From<ArrayDataRef> for FixedSizeBinaryArray {
+    fn from(data: ArrayDataRef) -> Self {
+        assert_eq!(
+            data.buffers().len(),
+            1,
+            "FixedSizeBinaryArray data should contain a single buffer only (values buffer)"
+        );
+        let length = match data.data_type() {
+            DataType::FixedSizeBinary(length) => *length,
+            _ => panic!(
+                "FixedSizeBinaryArray data should contain a FixedSizeBinary data type"
+            ),
+        };
+        assert!(
+            length >= 0,
+            "FixedSizeBinaryArray width must not be negative"
+        );
+        let values_len = data.buffers()[0].len();
+        if length > 0 {
+            assert_eq!(
+                values_len % length as usize,
+                0,
+                "FixedSizeBinaryArray values buffer length must be a multiple of the width"
+            );
         }
-        let array_data = ArrayData::builder(DataType::Utf8)
-            .len(v.len())
-            .add_buffer(Buffer::from(offsets.to_byte_slice()))
-            .add_buffer(Buffer::from(&values[..]))
+        assert!(
+            values_len >= (data.offset() + data.len()) * length as usize,
+            "FixedSizeBinaryArray values buffer is too short for the array length"
+        );
+        Self { data, length }
+    }
+}
+
+impl Array for FixedSizeBinaryArray {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn data(&self) -> ArrayDataRef {
+        self.data.clone()
+    }
+
+    fn data_ref(&self) -> &ArrayDataRef {
+        &self.data
+    }
+}
+
+/// An array of exact decimal numbers of type `Decimal(precision, scale)`.
+///
+/// The values are stored as 16-byte little-endian integers scaled by `10^scale`, e.g.
+/// `12345` is `123.45` with a scale of 2.
+pub struct DecimalArray {
+    data: ArrayDataRef,
+    precision: usize,
+    scale: usize,
+}
+
+impl DecimalArray {
+    /// Constructs a decimal array with the `values` already scaled by `10^scale`, where
+    /// `None` values are null elements.
+    ///
+    /// Returns an error if the precision or the scale is out of range, see
+    /// `DataType::try_decimal`.
+    pub fn from_values(
+        values: Vec<Option<i128>>,
+        precision: usize,
+        scale: usize,
+    ) -> Result<Self> {
+        let data_type = DataType::try_decimal(precision, scale)?;
+        let mut value_buf = MutableBuffer::new(values.len() * 16);
+        let mut null_buf = BooleanBufferBuilder::new(values.len());
+        for v in &values {
+            value_buf.extend_from_slice(&v.unwrap_or(0).to_le_bytes());
+            null_buf.append(v.is_some())?;
+        }
+        let data = ArrayData::builder(data_type)
+            .len(values.len())
+            .add_buffer(value_buf.freeze())
+            .null_bit_buffer(null_buf.finish())
+            .build();
+        Ok(DecimalArray::from(data))
+    }
+
+    /// Returns the value at index `i`, scaled by `10^scale`.
+    pub fn value(&self, i: usize) -> i128 {
+        assert!(i < self.data.len(), "DecimalArray out of bounds access");
+        let start = (self.data.offset() + i) * 16;
+        let mut bytes = [0u8; 16];
+        bytes.copy_from_slice(&self.data.buffers()[0].data()[start..start + 16]);
+        i128::from_le_bytes(bytes)
+    }
+
+    /// Returns the value at index `i` as a string with the decimal point placed according
+    /// to the scale, e.g. `"-0.05"` for the value `-5` with a scale of 2.
+    pub fn value_as_string(&self, i: usize) -> String {
+        let value = self.value(i).to_string();
+        if self.scale == 0 {
+            return value;
+        }
+        let (sign, digits) = if value.starts_with('-') {
+            ("-", &value[1..])
+        } else {
+            ("", &value[..])
+        };
+        let digits = format!("{:0>width$}", digits, width = self.scale + 1);
+        let (integer, fraction) = digits.split_at(digits.len() - self.scale);
+        format!("{}{}.{}", sign, integer, fraction)
+    }
+
+    /// Returns the maximum number of digits of the values of this array.
+    pub fn precision(&self) -> usize {
+        self.precision
+    }
+
+    /// Returns the number of digits after the decimal point of the values of this array.
+    pub fn scale(&self) -> usize {
+        self.scale
+    }
+}
+
+/// Constructs a `DecimalArray` from an array data reference.
+impl From<ArrayDataRef> for DecimalArray {
+    fn from(data: ArrayDataRef) -> Self {
+        assert_eq!(
+            data.buffers().len(),
+            1,
+            "DecimalArray data should contain a single buffer only (values buffer)"
+        );
+        let (precision, scale) = match data.data_type() {
+            DataType::Decimal(precision, scale) => (*precision, *scale),
+            _ => panic!("DecimalArray data should contain a Decimal data type"),
+        };
+        match DataType::try_decimal(precision, scale) {
+            Ok(_) => {}
+            Err(ArrowError::InvalidArgumentError(message)) => panic!("{}", message),
+            Err(e) => panic!("Invalid DecimalArray data type: {:?}", e),
+        }
+        assert!(
+            data.buffers()[0].len() >= (data.offset() + data.len()) * 16,
+            "DecimalArray values buffer is too short for the array length"
+        );
+        Self {
+            data,
+            precision,
+            scale,
+        }
+    }
+}
+
+impl Array for DecimalArray {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn data(&self) -> ArrayDataRef {
+        self.data.clone()
+    }
+
+    fn data_ref(&self) -> &ArrayDataRef {
+        &self.data
+    }
+}
+
+/// A nested array type where each child (called *field*) is represented by a separate
+/// array.
+pub struct StructArray {
+    data: ArrayDataRef,
+    boxed_fields: Vec<ArrayRef>,
+}
+
+impl StructArray {
+    /// Creates a struct array from `fields` and the corresponding child `arrays`, reusing
+    /// the arrays as they are.
+    ///
+    /// Unlike `From<Vec<(Field, ArrayRef)>>`, this doesn't check that the arrays all have
+    /// the same length, nor that they match their fields: it is meant for hot paths where
+    /// the callers already know it to be the case. Passing inconsistent arrays results in
+    /// an invalid array.
+    pub fn from_arrays_unchecked(fields: Vec<Field>, arrays: Vec<ArrayRef>) -> Self {
+        let len = arrays.first().map_or(0, |a| a.len());
+        let data = ArrayData::builder(DataType::Struct(fields))
+            .len(len)
+            .child_data(arrays.iter().map(|a| a.data()).collect())
+            .build();
+        Self {
+            data,
+            boxed_fields: arrays,
+        }
+    }
+
+    /// Creates a struct array of `length` rows from pairs of fields and their child
+    /// arrays.
+    ///
+    /// Unlike `From<Vec<(Field, ArrayRef)>>`, this accepts an empty list of fields, in
+    /// which case the struct still has `length` rows. Returns an error if a child array
+    /// doesn't have `length` elements.
+    pub fn try_new(fields: Vec<(Field, ArrayRef)>, length: usize) -> Result<Self> {
+        if let Some((field, array)) = fields.iter().find(|(_, a)| a.len() != length) {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "StructArray of length {} has child array '{}' of length {}",
+                length,
+                field.name(),
+                array.len()
+            )));
+        }
+        let (field_types, field_values): (Vec<_>, Vec<_>) = fields.into_iter().unzip();
+        let data = ArrayData::builder(DataType::Struct(field_types))
+            .len(length)
+            .child_data(field_values.iter().map(|a| a.data()).collect())
+            .build();
+        Ok(Self {
+            data,
+            boxed_fields: field_values,
+        })
+    }
+
+    /// Returns the field at `pos`.
+    pub fn column(&self, pos: usize) -> &ArrayRef {
+        &self.boxed_fields[pos]
+    }
+
+    /// Returns a new struct array with the fields renamed according to `mapping`, a list
+    /// of `(from, to)` name pairs matched against the current field names. Fields not
+    /// named in `mapping` keep their name.
+    ///
+    /// Only the data type changes: the returned array shares its buffers and child
+    /// arrays with this one. Returns an error if a source name is not a field of this
+    /// array, or if renaming would produce duplicate field names.
+    pub fn rename_fields(&self, mapping: &[(&str, &str)]) -> Result<StructArray> {
+        let original = match self.data.data_type() {
+            DataType::Struct(fields) => fields,
+            _ => unreachable!("StructArray must have a Struct data type"),
+        };
+        let mut fields = original.clone();
+        for (from, to) in mapping {
+            match original.iter().position(|f| f.name() == from) {
+                Some(i) => {
+                    fields[i] = Field::new(
+                        to,
+                        original[i].data_type().clone(),
+                        original[i].is_nullable(),
+                    )
+                }
+                None => {
+                    return Err(ArrowError::InvalidArgumentError(format!(
+                        "Unable to rename field '{}': no such field in struct",
+                        from
+                    )));
+                }
+            }
+        }
+        for (i, field) in fields.iter().enumerate() {
+            if fields[..i].iter().any(|f| f.name() == field.name()) {
+                return Err(ArrowError::InvalidArgumentError(format!(
+                    "Duplicate field name '{}' after renaming struct fields",
+                    field.name()
+                )));
+            }
+        }
+
+        let mut builder = ArrayData::builder(DataType::Struct(fields))
+            .len(self.data.len())
+            .null_count(self.data.null_count())
+            .offset(self.data.offset())
+            .buffers(self.data.buffers().to_vec())
+            .child_data(self.data.child_data().to_vec());
+        if let Some(bitmap) = self.data.null_bitmap() {
+            builder = builder.null_bit_buffer(bitmap.bits.clone());
+        }
+        Ok(Self {
+            data: builder.build(),
+            boxed_fields: self.boxed_fields.clone(),
+        })
+    }
+
+    /// Returns a new struct array with the rows sorted by the values of the field named
+    /// `field_name`, in ascending or descending order.
+    ///
+    /// See `compute::sort_struct_by_field`, which this delegates to.
+    pub fn sort_by_field(
+        &self,
+        field_name: &str,
+        descending: bool,
+    ) -> Result<StructArray> {
+        crate::compute::sort_struct_by_field(self, field_name, descending)
+    }
+}
+
+/// Constructs a `StructArray` from an array data reference.
+///
+/// The offset of `data` applies to its children, so the fields of the array are the
+/// slices of the child data starting at that offset.
+impl From<ArrayDataRef> for StructArray {
+    fn from(data: ArrayDataRef) -> Self {
+        let mut boxed_fields = vec![];
+        for cd in data.child_data() {
+            assert!(
+                cd.len() >= data.offset() + data.len(),
+                "StructArray child of length {} is too short for an offset of {} and a length of {}",
+                cd.len(),
+                data.offset(),
+                data.len()
+            );
+            if data.offset() == 0 {
+                boxed_fields.push(make_array(cd.clone()));
+            } else {
+                let len = cd.len() - data.offset();
+                boxed_fields.push(make_array(cd.slice(data.offset(), len)));
+            }
+        }
+        Self { data, boxed_fields }
+    }
+}
+
+impl Array for StructArray {
+    fn as_any(&self) -> &Any {
+        self
+    }
+
+    fn data(&self) -> ArrayDataRef {
+        self.data.clone()
+    }
+
+    fn data_ref(&self) -> &ArrayDataRef {
+        &self.data
+    }
+}
+
+impl From<Vec<(Field, ArrayRef)>> for StructArray {
+    fn from(v: Vec<(Field, ArrayRef)>) -> Self {
+        let (field_types, field_values): (Vec<_>, Vec<_>) = v.into_iter().unzip();
+
+        // Check the length of the child arrays
+        let length = field_values[0].len();
+        for i in 1..field_values.len() {
+            assert_eq!(
+                length,
+                field_values[i].len(),
+                "all child arrays of a StructArray must have the same length"
+            );
+        }
+
+        let data = ArrayData::builder(DataType::Struct(field_types))
+            .len(length)
+            .child_data(field_values.into_iter().map(|a| a.data()).collect())
             .build();
-        BinaryArray::from(array_data)
+        Self::from(data)
+    }
+}
+
+/// An array whose elements each have the type of one of its children, selected by the
+/// type id of the element.
+///
+/// The type ids are stored in the first buffer of this array's data, one `i8` per
+/// element, and a type id is the index of the selected child. In the `Dense` mode, the
+/// second buffer holds the `i32` offset of each element in its child, while in the
+/// `Sparse` mode the children have the same length as the union and each element is at
+/// the same index in its child, so a sliced sparse union only requires its children to
+/// hold the elements up to its end.
+pub struct UnionArray {
+    data: ArrayDataRef,
+    boxed_fields: Vec<ArrayRef>,
+    mode: UnionMode,
+}
+
+impl UnionArray {
+    /// Returns the type id of the element at index `i`, i.e. the index of its child.
+    pub fn type_id(&self, i: usize) -> i8 {
+        assert!(i < self.data.len(), "UnionArray out of bounds access");
+        self.type_ids()[self.data.offset() + i]
+    }
+
+    /// Returns the offset in its child of the element at index `i`.
+    pub fn value_offset(&self, i: usize) -> i32 {
+        assert!(i < self.data.len(), "UnionArray out of bounds access");
+        match self.mode {
+            UnionMode::Dense => self.value_offsets()[self.data.offset() + i],
+            UnionMode::Sparse => i32::try_from(self.data.offset() + i)
+                .expect("UnionArray value offset doesn't fit in an i32"),
+        }
+    }
+
+    /// Returns the element at index `i` as an array of length 1 of the type of its
+    /// child, sharing the buffers of the child.
+    pub fn value(&self, i: usize) -> ArrayRef {
+        let child = self.child(self.type_id(i));
+        let child_data = child.data_ref();
+        let mut builder = ArrayData::builder(child_data.data_type().clone())
+            .len(1)
+            .offset(child_data.offset() + self.value_offset(i) as usize)
+            .buffers(child_data.buffers().to_vec())
+            .child_data(child_data.child_data().to_vec());
+        if let Some(bitmap) = child_data.null_bitmap() {
+            builder = builder.null_bit_buffer(bitmap.bits.clone());
+        }
+        make_array(builder.build())
+    }
+
+    /// Returns the child with the type id `type_id`.
+    pub fn child(&self, type_id: i8) -> &ArrayRef {
+        &self.boxed_fields[type_id as usize]
+    }
+
+    /// Returns the layout of the children of this array.
+    pub fn mode(&self) -> &UnionMode {
+        &self.mode
+    }
+
+    /// Returns the type ids of all the elements of the buffer, ignoring the offset of
+    /// this array.
+    fn type_ids(&self) -> &[i8] {
+        self.data.buffers()[0].typed_data::<i8>()
+    }
+
+    /// Returns the value offsets of all the elements of the buffer, ignoring the offset
+    /// of this array.
+    fn value_offsets(&self) -> &[i32] {
+        self.data.buffers()[1].typed_data::<i32>()
     }
 }
 
-/// Creates a `BinaryArray` from `List<u8>` array
-impl From<ListArray> for BinaryArray {
-    fn from(v: ListArray) -> Self {
+/// Constructs a `UnionArray` from an array data reference, validating the type ids and
+/// the value offsets against the children.
+impl From<ArrayDataRef> for UnionArray {
+    fn from(data: ArrayDataRef) -> Self {
+        let (fields, mode) = match data.data_type() {
+            DataType::Union(fields, mode) => (fields, mode.clone()),
+            _ => panic!("UnionArray data should contain a Union data type"),
+        };
         assert_eq!(
-            v.data().child_data()[0].child_data().len(),
-            0,
-            "BinaryArray can only be created from list array of u8 values \
-             (i.e. List<PrimitiveArray<u8>>)."
+            data.child_data().len(),
+            fields.len(),
+            "UnionArray data should contain a child array per field"
         );
+        let num_buffers = match mode {
+            UnionMode::Dense => 2,
+            UnionMode::Sparse => 1,
+        };
         assert_eq!(
-            v.data().child_data()[0].data_type(),
-            &DataType::UInt8,
-            "BinaryArray can only be created from List<u8> arrays, mismatched data types."
+            data.buffers().len(),
+            num_buffers,
+            "UnionArray data should contain a type ids buffer, and a value offsets buffer \
+             in the dense mode"
         );
-
-        let mut builder = ArrayData::builder(DataType::Utf8)
-            .len(v.len())
-            .add_buffer(v.data().buffers()[0].clone())
-            .add_buffer(v.data().child_data()[0].buffers()[0].clone());
-        if let Some(bitmap) = v.data().null_bitmap() {
-            builder = builder
-                .null_count(v.data().null_count())
-                .null_bit_buffer(bitmap.bits.clone())
+        let boxed_fields: Vec<ArrayRef> = data
+            .child_data()
+            .iter()
+            .map(|cd| make_array(cd.clone()))
+            .collect();
+        let array = Self {
+            data,
+            boxed_fields,
+            mode,
+        };
+        let end = array.data.offset() + array.data.len();
+        assert!(
+            array.data.buffers()[0].len() >= end,
+            "UnionArray type ids buffer is too short"
+        );
+        if array.mode == UnionMode::Sparse {
+            for (type_id, child) in array.boxed_fields.iter().enumerate() {
+                assert!(
+                    child.len() >= end,
+                    "UnionArray child {} has {} elements, but the sparse union has {}",
+                    type_id,
+                    child.len(),
+                    end
+                );
+            }
         }
-
-        let data = builder.build();
-        Self::from(data)
+        if array.mode == UnionMode::Dense {
+            assert!(
+                memory::is_aligned(
+                    array.data.buffers()[1].raw_data(),
+                    mem::align_of::<i32>()
+                ),
+                "memory is not aligned"
+            );
+            assert!(
+                array.data.buffers()[1].len() >= end * mem::size_of::<i32>(),
+                "UnionArray value offsets buffer is too short"
+            );
+        }
+        for i in 0..array.len() {
+            let type_id = array.type_id(i);
+            assert!(
+                type_id >= 0 && (type_id as usize) < array.boxed_fields.len(),
+                "UnionArray type id {} is not the index of a child",
+                type_id
+            );
+            let offset = array.value_offset(i);
+            assert!(
+                offset >= 0 && (offset as usize) < array.child(type_id).len(),
+                "UnionArray value offset {} is out of the bounds of child {}",
+                offset,
+                type_id
+            );
+        }
+        array
     }
 }
 
-impl Array for BinaryArray {
+impl Array for UnionArray {
     fn as_any(&self) -> &Any {
         self
     }
@@ -909,31 +2713,79 @@ impl Array for BinaryArray {
     }
 }
 
-/// A nested array type where each child (called *field*) is represented by a separate
-/// array.
-pub struct StructArray {
+/// A dictionary-encoded array, where each element is stored as an integer key into an
+/// array of (typically distinct) values.
+///
+/// The keys are stored in the single buffer of this array's data, together with the
+/// array's null bitmap, and the dictionary values are stored as its only child data.
+pub struct DictionaryArray<K: ArrowDictionaryKeyType> {
     data: ArrayDataRef,
-    boxed_fields: Vec<ArrayRef>,
+    keys: PrimitiveArray<K>,
+    values: ArrayRef,
 }
 
-impl StructArray {
-    /// Returns the field at `pos`.
-    pub fn column(&self, pos: usize) -> &ArrayRef {
-        &self.boxed_fields[pos]
+pub type Int8DictionaryArray = DictionaryArray<Int8Type>;
+pub type Int16DictionaryArray = DictionaryArray<Int16Type>;
+pub type Int32DictionaryArray = DictionaryArray<Int32Type>;
+pub type Int64DictionaryArray = DictionaryArray<Int64Type>;
+pub type UInt8DictionaryArray = DictionaryArray<UInt8Type>;
+pub type UInt16DictionaryArray = DictionaryArray<UInt16Type>;
+pub type UInt32DictionaryArray = DictionaryArray<UInt32Type>;
+pub type UInt64DictionaryArray = DictionaryArray<UInt64Type>;
+
+impl<K: ArrowDictionaryKeyType> DictionaryArray<K> {
+    /// Returns the keys of this array, each of which is an index into `values()`.
+    pub fn keys(&self) -> &PrimitiveArray<K> {
+        &self.keys
+    }
+
+    /// Returns an reference to the dictionary values of this array.
+    pub fn values(&self) -> ArrayRef {
+        self.values.clone()
+    }
+
+    /// Returns a clone of the value type of this dictionary.
+    pub fn value_type(&self) -> DataType {
+        self.values.data().data_type().clone()
     }
 }
 
-impl From<ArrayDataRef> for StructArray {
+/// Constructs a `DictionaryArray` from an array data reference.
+impl<K: ArrowDictionaryKeyType> From<ArrayDataRef> for DictionaryArray<K> {
     fn from(data: ArrayDataRef) -> Self {
-        let mut boxed_fields = vec![];
-        for cd in data.child_data() {
-            boxed_fields.push(make_array(cd.clone()));
+        assert_eq!(
+            data.buffers().len(),
+            1,
+            "DictionaryArray data should contain a single buffer only (keys)"
+        );
+        assert_eq!(
+            data.child_data().len(),
+            1,
+            "DictionaryArray should contain a single child array (values array)"
+        );
+        let values = make_array(data.child_data()[0].clone());
+        let null_bit_buffer = match data.null_bitmap() {
+            Some(bitmap) => Some(bitmap.bits.clone()),
+            None => None,
+        };
+        let keys_data = ArrayData::new(
+            K::get_data_type(),
+            data.len(),
+            Some(data.null_count()),
+            null_bit_buffer,
+            data.offset(),
+            data.buffers().to_vec(),
+            vec![],
+        );
+        Self {
+            data,
+            keys: PrimitiveArray::<K>::from(Arc::new(keys_data)),
+            values,
         }
-        Self { data, boxed_fields }
     }
 }
 
-impl Array for StructArray {
+impl<K: ArrowDictionaryKeyType> Array for DictionaryArray<K> {
     fn as_any(&self) -> &Any {
         self
     }
@@ -945,32 +2797,6 @@ impl Array for StructArray {
     fn data_ref(&self) -> &ArrayDataRef {
         &self.data
     }
-
-    /// Returns the length (i.e., number of elements) of this array
-    fn len(&self) -> usize {
-        self.boxed_fields[0].len()
-    }
-}
-
-impl From<Vec<(Field, ArrayRef)>> for StructArray {
-    fn from(v: Vec<(Field, ArrayRef)>) -> Self {
-        let (field_types, field_values): (Vec<_>, Vec<_>) = v.into_iter().unzip();
-
-        // Check the length of the child arrays
-        let length = field_values[0].len();
-        for i in 1..field_values.len() {
-            assert_eq!(
-                length,
-                field_values[i].len(),
-                "all child arrays of a StructArray must have the same length"
-            );
-        }
-
-        let data = ArrayData::builder(DataType::Struct(field_types))
-            .child_data(field_values.into_iter().map(|a| a.data()).collect())
-            .build();
-        Self::from(data)
-    }
 }
 
 #[cfg(test)]
@@ -985,6 +2811,76 @@ mod tests {
     use crate::datatypes::{DataType, Field};
     use crate::memory;
 
+    #[test]
+    fn test_null_array() {
+        let array = NullArray::new(4);
+        assert_eq!(&DataType::Null, array.data_type());
+        assert_eq!(4, array.len());
+        assert_eq!(4, array.null_count());
+        assert!(array.is_null(3));
+        assert!(!array.is_valid(0));
+        assert!(array.data().buffers().is_empty());
+        assert!(array.data().null_bitmap().is_none());
+        assert_eq!("null", array.value_to_string(1));
+
+        let array = make_array(array.data());
+        assert!(array.as_any().downcast_ref::<NullArray>().is_some());
+        assert_eq!(4, array.null_count());
+    }
+
+    #[test]
+    fn test_with_correct_null_count() {
+        // the null count claims that all values are valid, but two of them are null
+        let data = ArrayData::builder(DataType::Int32)
+            .len(4)
+            .null_count(0)
+            .null_bit_buffer(Buffer::from([0b0000_0101]))
+            .add_buffer(Buffer::from(&[1, 2, 3, 4].to_byte_slice()))
+            .build();
+        let array = Int32Array::from(data);
+        assert!(array.all_valid());
+
+        let repaired = array.with_correct_null_count();
+        assert_eq!(2, repaired.null_count());
+        assert!(!repaired.all_valid());
+        assert!(repaired.is_null(1));
+        assert_eq!(array.values(), repaired.data().buffers()[0]);
+
+        // only the bits over the range of the array are counted
+        let data = ArrayData::builder(DataType::Int32)
+            .len(2)
+            .offset(2)
+            .null_count(2)
+            .null_bit_buffer(Buffer::from([0b0000_0101]))
+            .add_buffer(Buffer::from(&[1, 2, 3, 4].to_byte_slice()))
+            .build();
+        let repaired = Int32Array::from(data).with_correct_null_count();
+        assert_eq!(1, repaired.null_count());
+        assert_eq!(2, repaired.offset());
+
+        let repaired = Int32Array::from(vec![1, 2]).with_correct_null_count();
+        assert!(repaired.all_valid());
+        assert_eq!(3, NullArray::new(3).with_correct_null_count().null_count());
+    }
+
+    #[test]
+    #[cfg(feature = "ndarray")]
+    fn test_primitive_array_ndarray_round_trip() {
+        let values = ndarray::arr1(&[1, -2, 3, 40]);
+        let array = Int32Array::from_ndarray(values.view());
+        assert_eq!(4, array.len());
+        assert_eq!(0, array.null_count());
+        assert_eq!(&[1, -2, 3, 40], array.value_slice(0, 4));
+        assert_eq!(values, array.to_ndarray().unwrap());
+
+        // strided views are copied element by element
+        let array = Int32Array::from_ndarray(values.slice(ndarray::s![..;2]));
+        assert_eq!(&[1, 3], array.value_slice(0, 2));
+
+        let array = Int32Array::from(vec![Some(1), None]);
+        assert!(array.to_ndarray().is_err());
+    }
+
     #[test]
     fn test_primitive_array_from_vec() {
         let buf = Buffer::from(&[0, 1, 2, 3, 4].to_byte_slice());
@@ -1003,6 +2899,25 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_primitive_array_from_iter() {
+        let arr: Int32Array = (0..6)
+            .map(|i| if i % 3 == 1 { None } else { Some(i) })
+            .collect();
+        assert_eq!(6, arr.len());
+        assert_eq!(2, arr.null_count());
+        assert!(arr.is_null(1) && arr.is_null(4));
+        assert_eq!(5, arr.value(5));
+
+        let arr = Float64Array::from_iter_values((0..3).map(|i| i as f64 * 0.5));
+        assert_eq!(0, arr.null_count());
+        assert!(arr.data().null_bitmap().is_none());
+        assert_eq!(&[0.0, 0.5, 1.0], arr.value_slice(0, 3));
+
+        let arr: BooleanArray = vec![Some(true), None, Some(false)].into_iter().collect();
+        assert!(arr.value(0) && arr.is_null(1) && !arr.value(2));
+    }
+
     #[test]
     fn test_primitive_array_from_vec_option() {
         // Test building a primitive array with null values
@@ -1151,9 +3066,54 @@ mod tests {
         let arr: PrimitiveArray<TimestampMillisecondType> =
             vec![1546214400000, 1546214400000].into();
         assert_eq!(
-            "PrimitiveArray<Timestamp(Millisecond)>\n[\n  2018-12-31T00:00:00,\n  2018-12-31T00:00:00,\n]",
+            "PrimitiveArray<Timestamp(Millisecond, None)>\n[\n  2018-12-31T00:00:00,\n  2018-12-31T00:00:00,\n]",
+            format!("{:?}", arr)
+        );
+    }
+
+    fn timestamp_with_timezone(values: Vec<i64>, tz: &str) -> TimestampMillisecondArray {
+        let data = ArrayData::builder(DataType::Timestamp(
+            TimeUnit::Millisecond,
+            Some(Arc::new(tz.to_string())),
+        ))
+        .len(values.len())
+        .add_buffer(Buffer::from(values.to_byte_slice()))
+        .build();
+        TimestampMillisecondArray::from(data)
+    }
+
+    #[test]
+    fn test_timestamp_value_as_datetime_tz() {
+        let arr = timestamp_with_timezone(vec![1546214400000], "+05:30");
+        let datetime = arr.value_as_datetime_tz(0).unwrap();
+        assert_eq!("2018-12-31T05:30:00+05:30", format!("{:?}", datetime));
+        assert_eq!(1546214400000, datetime.timestamp_millis());
+
+        let arr = timestamp_with_timezone(vec![1546214400000], "-0800");
+        let datetime = arr.value_as_datetime_tz(0).unwrap();
+        assert_eq!("2018-12-30T16:00:00-08:00", format!("{:?}", datetime));
+
+        // named timezones and arrays without timezone fall back to UTC
+        for tz in &["UTC", "Europe/Paris", "+25:00"] {
+            let arr = timestamp_with_timezone(vec![1546214400000], tz);
+            let datetime = arr.value_as_datetime_tz(0).unwrap();
+            assert_eq!("2018-12-31T00:00:00+00:00", format!("{:?}", datetime));
+        }
+        let arr: TimestampMillisecondArray = vec![1546214400000].into();
+        assert_eq!(
+            "2018-12-31T00:00:00+00:00",
+            format!("{:?}", arr.value_as_datetime_tz(0).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_timestamp_with_timezone_fmt_debug() {
+        let arr = timestamp_with_timezone(vec![1546214400000], "+01:00");
+        assert_eq!(
+            "PrimitiveArray<Timestamp(Millisecond, Some(\"+01:00\"))>\n[\n  2018-12-31T01:00:00+01:00,\n]",
             format!("{:?}", arr)
         );
+        assert_eq!("2018-12-31T01:00:00+01:00", arr.value_to_string(0));
     }
 
     #[test]
@@ -1174,6 +3134,168 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_value_to_string_primitive() {
+        let a: ArrayRef = Arc::new(Float64Array::from(vec![Some(1.5), None, Some(-2.0)]));
+        assert_eq!("1.5", a.value_to_string(0));
+        assert_eq!("null", a.value_to_string(1));
+        assert_eq!("-2.0", a.value_to_string(2));
+
+        let a: ArrayRef = Arc::new(BooleanArray::from(vec![Some(false), None]));
+        assert_eq!("false", a.value_to_string(0));
+        assert_eq!("null", a.value_to_string(1));
+    }
+
+    #[test]
+    fn test_value_to_string_temporal() {
+        let a: ArrayRef = Arc::new(TimestampSecondArray::from(vec![1546214400]));
+        assert_eq!("2018-12-31T00:00:00", a.value_to_string(0));
+        let a: ArrayRef = Arc::new(Date64Array::from(vec![1546214400000]));
+        assert_eq!("2018-12-31", a.value_to_string(0));
+        let a: ArrayRef = Arc::new(Time64MicrosecondArray::from(vec![3_723_000_001]));
+        assert_eq!("01:02:03.000001", a.value_to_string(0));
+    }
+
+    #[test]
+    fn test_value_to_string_nested() {
+        let strings = BinaryArray::from(vec!["a", "bc"]);
+        assert_eq!("bc", strings.value_to_string(1));
+
+        let values_builder = Int32Builder::new(5);
+        let mut builder = ListBuilder::new(values_builder);
+        builder.values().append_slice(&[1, 2]).unwrap();
+        builder.append(true).unwrap();
+        builder.append(false).unwrap();
+        builder.values().append_null().unwrap();
+        builder.append(true).unwrap();
+        let list: ArrayRef = Arc::new(builder.finish());
+        assert_eq!("[1, 2]", list.value_to_string(0));
+        assert_eq!("null", list.value_to_string(1));
+        assert_eq!("[null]", list.value_to_string(2));
+
+        let s = StructArray::from(vec![
+            (
+                Field::new("a", DataType::Utf8, false),
+                Arc::new(strings) as ArrayRef,
+            ),
+            (
+                Field::new("b", DataType::Int32, true),
+                Arc::new(Int32Array::from(vec![Some(1), None])) as ArrayRef,
+            ),
+        ]);
+        assert_eq!("{a: a, b: 1}", s.value_to_string(0));
+        assert_eq!("{a: bc, b: null}", s.value_to_string(1));
+    }
+
+    #[test]
+    fn test_value_to_string_dictionary() {
+        let a = BinaryArray::from(vec!["x", "y", "x"]);
+        let dict = crate::compute::dictionary_encode(&a).unwrap();
+        assert_eq!("x", dict.value_to_string(0));
+        assert_eq!("y", dict.value_to_string(1));
+        assert_eq!("x", dict.value_to_string(2));
+    }
+
+    #[test]
+    fn test_equals_primitive() {
+        let a: ArrayRef = Arc::new(Int32Array::from(vec![Some(1), None, Some(3)]));
+        let b: ArrayRef = Arc::new(Int32Array::from(vec![Some(1), None, Some(3)]));
+        assert!(a.equals(&*b));
+        assert!(b.equals(&*a));
+
+        let c: ArrayRef = Arc::new(Int32Array::from(vec![Some(1), Some(2), Some(3)]));
+        assert!(!a.equals(&*c));
+        let d: ArrayRef = Arc::new(Int64Array::from(vec![Some(1), None, Some(3)]));
+        assert!(!a.equals(&*d));
+        let e: ArrayRef = Arc::new(Int32Array::from(vec![Some(1), None]));
+        assert!(!a.equals(&*e));
+
+        // the same values at a different offset
+        let data = ArrayData::builder(DataType::Int32)
+            .len(3)
+            .offset(1)
+            .null_bit_buffer(Buffer::from([0b1010]))
+            .add_buffer(Buffer::from([0, 1, 0, 3].to_byte_slice()))
+            .build();
+        let f: ArrayRef = Arc::new(Int32Array::from(data));
+        assert!(a.equals(&*f));
+    }
+
+    #[test]
+    fn test_equals_nested() {
+        let mut builder = ListBuilder::new(Int32Builder::new(5));
+        builder.values().append_slice(&[1, 2]).unwrap();
+        builder.append(true).unwrap();
+        builder.append(false).unwrap();
+        builder.values().append_null().unwrap();
+        builder.append(true).unwrap();
+        let a: ArrayRef = Arc::new(builder.finish());
+
+        // a null list with values that must be ignored
+        let mut builder = ListBuilder::new(Int32Builder::new(5));
+        builder.values().append_slice(&[1, 2]).unwrap();
+        builder.append(true).unwrap();
+        builder.values().append_value(7).unwrap();
+        builder.append(false).unwrap();
+        builder.values().append_null().unwrap();
+        builder.append(true).unwrap();
+        let b: ArrayRef = Arc::new(builder.finish());
+        assert!(a.equals(&*b));
+
+        let mut builder = ListBuilder::new(Int32Builder::new(5));
+        builder.values().append_slice(&[1, 2]).unwrap();
+        builder.append(true).unwrap();
+        builder.append(false).unwrap();
+        builder.values().append_value(0).unwrap();
+        builder.append(true).unwrap();
+        let c: ArrayRef = Arc::new(builder.finish());
+        assert!(!a.equals(&*c));
+
+        let s = |values: Vec<Option<&str>>| -> ArrayRef {
+            Arc::new(StructArray::from(vec![(
+                Field::new("a", DataType::Utf8, true),
+                Arc::new(BinaryArray::from(values)) as ArrayRef,
+            )]))
+        };
+        assert!(s(vec![Some("x"), None]).equals(&*s(vec![Some("x"), None])));
+        assert!(!s(vec![Some("x"), None]).equals(&*s(vec![Some("x"), Some("")])));
+    }
+
+    #[test]
+    fn test_equals_dictionary() {
+        let a = crate::compute::dictionary_encode(&BinaryArray::from(vec!["x", "y"]))
+            .unwrap();
+        let b = crate::compute::dictionary_encode(&BinaryArray::from(vec!["x", "y"]))
+            .unwrap();
+        let c = crate::compute::dictionary_encode(&BinaryArray::from(vec!["y", "x"]))
+            .unwrap();
+        assert!(a.equals(&b));
+        assert!(!a.equals(&c));
+    }
+
+    #[test]
+    fn test_memory_size() {
+        let a = Int32Array::from(vec![1, 2, 3]);
+        assert_eq!(64, a.get_buffer_memory_size());
+        let b = Int32Array::from(vec![Some(1), None, Some(3)]);
+        assert_eq!(128, b.get_buffer_memory_size());
+        assert!(b.get_array_memory_size() > 128);
+
+        let s = StructArray::from(vec![(
+            Field::new("b", DataType::Int32, true),
+            Arc::new(b) as ArrayRef,
+        )]);
+        assert_eq!(0, s.get_buffer_memory_size());
+        assert!(s.get_array_memory_size() > s.column(0).get_array_memory_size());
+
+        let mut builder = ListBuilder::new(Int32Builder::new(5));
+        builder.values().append_slice(&[1, 2]).unwrap();
+        builder.append(true).unwrap();
+        let list = builder.finish();
+        assert!(list.get_array_memory_size() > list.get_buffer_memory_size());
+        assert!(list.get_array_memory_size() > list.values().get_buffer_memory_size());
+    }
+
     #[test]
     fn test_primitive_array_builder() {
         // Test building an primitive array with ArrayData builder and offset
@@ -1254,32 +3376,248 @@ mod tests {
     }
 
     #[test]
-    fn test_boolean_array_builder() {
-        // Test building a boolean array with ArrayData builder and offset
-        // 000011011
-        let buf = Buffer::from([27_u8]);
-        let buf2 = buf.clone();
-        let data = ArrayData::builder(DataType::Boolean)
-            .len(5)
-            .offset(2)
-            .add_buffer(buf)
+    fn test_boolean_array_builder() {
+        // Test building a boolean array with ArrayData builder and offset
+        // 000011011
+        let buf = Buffer::from([27_u8]);
+        let buf2 = buf.clone();
+        let data = ArrayData::builder(DataType::Boolean)
+            .len(5)
+            .offset(2)
+            .add_buffer(buf)
+            .build();
+        let arr = BooleanArray::from(data);
+        assert_eq!(buf2, arr.values());
+        assert_eq!(5, arr.len());
+        assert_eq!(2, arr.offset());
+        assert_eq!(0, arr.null_count());
+        for i in 0..3 {
+            assert_eq!(i != 0, arr.value(i), "failed at {}", i);
+        }
+    }
+
+    #[test]
+    fn test_boolean_array_bit_values() {
+        // 00011011 00000101
+        let buf = Buffer::from([27_u8, 5_u8]);
+        let arr = BooleanArray::new(5, buf.clone(), 0, 2);
+        let (values, bit_offset) = arr.bit_values();
+        assert_eq!(buf, values);
+        assert_eq!(2, bit_offset);
+        for i in 0..5 {
+            assert_eq!(
+                arr.value(i),
+                bit_util::get_bit(values.data(), bit_offset + i),
+                "failed at {}",
+                i
+            );
+        }
+
+        let arr = BooleanArray::new(5, buf, 0, 10);
+        let (values, bit_offset) = arr.bit_values();
+        assert_eq!(&[5], values.data());
+        assert_eq!(2, bit_offset);
+        assert!(arr.value(0));
+        assert!(!arr.value(1));
+    }
+
+    #[test]
+    fn test_boolean_array_eq() {
+        let arr = BooleanArray::from(vec![true, false, true, true]);
+        // the same values, with the padding bits set
+        let other = BooleanArray::new(4, Buffer::from([0b1111_1101]), 0, 0);
+        assert_ne!(arr.values(), other.values());
+        assert_eq!(arr, other);
+        assert_ne!(arr, BooleanArray::from(vec![true, false, true, false]));
+        assert_ne!(arr, BooleanArray::from(vec![true, false, true]));
+
+        // arrays with offsets or nulls are compared logically
+        let other = BooleanArray::new(4, Buffer::from([0b0011_0100]), 0, 2);
+        assert_eq!(arr, other);
+        let with_nulls =
+            BooleanArray::from(vec![Some(true), None, Some(true), Some(true)]);
+        assert_ne!(arr, with_nulls);
+        assert_eq!(
+            with_nulls,
+            BooleanArray::from(vec![Some(true), None, Some(true), Some(true)])
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "PrimitiveArray data should contain a single buffer only \
+                               (values buffer)")]
+    fn test_boolean_array_invalid_buffer_len() {
+        let data = ArrayData::builder(DataType::Boolean).len(5).build();
+        BooleanArray::from(data);
+    }
+
+    #[test]
+    fn test_downcast_helpers() {
+        let arr: ArrayRef = Arc::new(Int32Array::from(vec![Some(1), None]));
+        assert_eq!(1, as_primitive_array::<Int32Type>(&arr).value(0));
+
+        let arr: ArrayRef = Arc::new(BooleanArray::from(vec![false, true]));
+        assert!(as_boolean_array(&arr).value(1));
+
+        let arr: ArrayRef = Arc::new(BinaryArray::from(vec!["a", "bc"]));
+        assert_eq!("bc", as_string_array(&arr).get_string(1));
+
+        let arr: ArrayRef = Arc::new(StructArray::from(vec![(
+            Field::new("a", DataType::Int32, false),
+            Arc::new(Int32Array::from(vec![7])) as ArrayRef,
+        )]));
+        assert_eq!(
+            7,
+            as_primitive_array::<Int32Type>(as_struct_array(&arr).column(0)).value(0)
+        );
+
+        let values = Int32Array::from(vec![1, 2, 3]).data();
+        let list = ArrayData::builder(DataType::List(Box::new(DataType::Int32)))
+            .len(2)
+            .add_buffer(Buffer::from(&[0, 1, 3].to_byte_slice()))
+            .add_child_data(values)
+            .build();
+        let arr = make_array(list);
+        assert_eq!(2, as_list_array(&arr).value_length(1));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Expected a PrimitiveArray<Int64> but got an array of type Int32"
+    )]
+    fn test_downcast_helpers_mismatch() {
+        let arr: ArrayRef = Arc::new(Int32Array::from(vec![1]));
+        as_primitive_array::<Int64Type>(&arr);
+    }
+
+    #[test]
+    fn test_decimal_array() {
+        let values = vec![
+            Some(12345),
+            None,
+            Some(-5),
+            Some(0),
+            Some(-123_456_789_012_345_678_901_234_567_890_123),
+        ];
+        let arr = DecimalArray::from_values(values, 38, 2).unwrap();
+        assert_eq!(&DataType::Decimal(38, 2), arr.data_type());
+        assert_eq!(38, arr.precision());
+        assert_eq!(2, arr.scale());
+        assert_eq!(5, arr.len());
+        assert_eq!(1, arr.null_count());
+        assert_eq!(12345, arr.value(0));
+        assert!(arr.is_null(1));
+        assert_eq!("123.45", arr.value_as_string(0));
+        assert_eq!("-0.05", arr.value_as_string(2));
+        assert_eq!("0.00", arr.value_as_string(3));
+        assert_eq!(
+            "-1234567890123456789012345678901.23",
+            arr.value_as_string(4)
+        );
+
+        let arr = DecimalArray::from_values(vec![Some(-7)], 5, 0).unwrap();
+        assert_eq!("-7", arr.value_as_string(0));
+
+        assert!(DecimalArray::from_values(vec![Some(1)], 39, 0).is_err());
+        assert!(DecimalArray::from_values(vec![Some(1)], 4, 5).is_err());
+    }
+
+    #[test]
+    fn test_decimal_array_make_array() {
+        let values: Vec<u8> = [100i128, 250, i128::max_value()]
+            .iter()
+            .flat_map(|v| v.to_le_bytes().to_vec())
+            .collect();
+        let data = ArrayData::builder(DataType::Decimal(10, 1))
+            .len(2)
+            .offset(1)
+            .add_buffer(Buffer::from(values))
+            .build();
+        let arr = make_array(data);
+        assert_eq!("25.0", arr.value_to_string(0));
+        let arr = arr.as_any().downcast_ref::<DecimalArray>().unwrap();
+        assert_eq!(250, arr.value(0));
+        assert_eq!(i128::max_value(), arr.value(1));
+        assert!(arr.equals(
+            &DecimalArray::from_values(vec![Some(250), Some(i128::max_value())], 10, 1)
+                .unwrap()
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "Decimal scale 3 is greater than its precision 2")]
+    fn test_decimal_array_invalid_scale() {
+        let data = ArrayData::builder(DataType::Decimal(2, 3))
+            .add_buffer(Buffer::from(&[0u8; 16][..]))
+            .len(1)
+            .build();
+        DecimalArray::from(data);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "DecimalArray values buffer is too short for the array length"
+    )]
+    fn test_decimal_array_short_buffer() {
+        let data = ArrayData::builder(DataType::Decimal(10, 2))
+            .add_buffer(Buffer::from(&[0u8; 16][..]))
+            .len(2)
+            .build();
+        DecimalArray::from(data);
+    }
+
+    #[test]
+    fn test_fixed_size_binary_array() {
+        let values: Vec<Option<&[u8]>> = vec![Some(b"abc"), None, Some(b"xyz")];
+        let arr = FixedSizeBinaryArray::from_values(values, 3).unwrap();
+        assert_eq!(&DataType::FixedSizeBinary(3), arr.data_type());
+        assert_eq!(3, arr.len());
+        assert_eq!(1, arr.null_count());
+        assert_eq!(3, arr.value_length());
+        assert_eq!(b"abc", arr.value(0));
+        assert!(arr.is_null(1));
+        assert_eq!(b"xyz", arr.value(2));
+        assert_eq!(6, arr.value_offset(2));
+        assert_eq!(9, arr.data().buffers()[0].len());
+
+        let values: Vec<Option<&[u8]>> = vec![Some(b"abc"), Some(b"de")];
+        assert!(FixedSizeBinaryArray::from_values(values, 3).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "FixedSizeBinaryArray value offset doesn't fit in an i32")]
+    fn test_fixed_size_binary_array_value_offset_overflow() {
+        let values: Vec<Option<&[u8]>> = vec![Some(b"abc")];
+        let arr = FixedSizeBinaryArray::from_values(values, 3).unwrap();
+        arr.value_offset(i32::max_value() as usize);
+    }
+
+    #[test]
+    fn test_fixed_size_binary_array_make_array() {
+        let data = ArrayData::builder(DataType::FixedSizeBinary(2))
+            .len(2)
+            .offset(1)
+            .add_buffer(Buffer::from(&[1u8, 2, 3, 4, 0xab, 0xcd][..]))
             .build();
-        let arr = BooleanArray::from(data);
-        assert_eq!(buf2, arr.values());
-        assert_eq!(5, arr.len());
-        assert_eq!(2, arr.offset());
-        assert_eq!(0, arr.null_count());
-        for i in 0..3 {
-            assert_eq!(i != 0, arr.value(i), "failed at {}", i);
-        }
+        let arr = make_array(data);
+        assert_eq!("abcd", arr.value_to_string(1));
+        let arr = arr.as_any().downcast_ref::<FixedSizeBinaryArray>().unwrap();
+        assert_eq!(&[3, 4], arr.value(0));
+        assert_eq!(4, arr.value_offset(1));
+        let other: Vec<Option<&[u8]>> = vec![Some(&[3, 4]), Some(&[0xab, 0xcd])];
+        assert!(arr.equals(&FixedSizeBinaryArray::from_values(other, 2).unwrap()));
     }
 
     #[test]
-    #[should_panic(expected = "PrimitiveArray data should contain a single buffer only \
-                               (values buffer)")]
-    fn test_boolean_array_invalid_buffer_len() {
-        let data = ArrayData::builder(DataType::Boolean).len(5).build();
-        BooleanArray::from(data);
+    #[should_panic(
+        expected = "FixedSizeBinaryArray values buffer length must be a multiple of the width"
+    )]
+    fn test_fixed_size_binary_array_invalid_buffer_len() {
+        let data = ArrayData::builder(DataType::FixedSizeBinary(4))
+            .len(1)
+            .add_buffer(Buffer::from(&[0u8; 6][..]))
+            .build();
+        FixedSizeBinaryArray::from(data);
     }
 
     #[test]
@@ -1315,7 +3653,9 @@ mod tests {
             assert!(!list_array.is_null(i));
         }
 
-        // Now test with a non-zero offset
+        // Now test with a non-zero offset, over the offsets of
+        //  [[0, 1, 2], [3, 4, 5], [6, 7], []]
+        let value_offsets = Buffer::from(&[0, 3, 6, 8, 8].to_byte_slice());
         let list_data = ArrayData::builder(list_data_type)
             .len(3)
             .offset(1)
@@ -1333,6 +3673,98 @@ mod tests {
         assert_eq!(2, list_array.value_length(1));
     }
 
+    #[test]
+    fn test_list_array_try_new() {
+        let values: ArrayRef = Arc::new(Int32Array::from(vec![0, 1, 2, 3, 4, 5]));
+        // [[0, 1, 2], null, [3, 4, 5]]
+        let list_array = ListArray::try_new(
+            values.clone(),
+            &[0, 3, 3, 6],
+            Some(Buffer::from([0b00000101])),
+        )
+        .unwrap();
+        assert_eq!(
+            &DataType::List(Box::new(DataType::Int32)),
+            list_array.data_type()
+        );
+        assert_eq!(3, list_array.len());
+        assert_eq!(1, list_array.null_count());
+        assert!(list_array.is_null(1));
+        assert_eq!(&[0, 3, 3, 6], list_array.value_offsets());
+        assert_eq!(values.data(), list_array.values().data());
+
+        let large_list_array =
+            LargeListArray::try_new(values.clone(), &[0, 2, 6], None).unwrap();
+        assert_eq!(
+            &DataType::LargeList(Box::new(DataType::Int32)),
+            large_list_array.data_type()
+        );
+        assert_eq!(0, large_list_array.null_count());
+        assert_eq!(4, large_list_array.value_length(1));
+    }
+
+    #[test]
+    fn test_list_array_try_new_invalid_offsets() {
+        let values: ArrayRef = Arc::new(Int32Array::from(vec![0, 1, 2, 3, 4, 5]));
+        assert!(ListArray::try_new(values.clone(), &[], None).is_err());
+        assert!(ListArray::try_new(values.clone(), &[1, 3, 6], None).is_err());
+        assert!(ListArray::try_new(values.clone(), &[0, 4, 3, 6], None).is_err());
+        assert!(ListArray::try_new(values.clone(), &[0, 3, 5], None).is_err());
+        assert!(ListArray::try_new(values.clone(), &[0, 3, 7], None).is_err());
+        match ListArray::try_new(values, &[0, 4, 3, 6], None) {
+            Err(ArrowError::InvalidArgumentError(message)) => assert_eq!(
+                "ListArray offsets must be monotonic, but offset 1 is 4 and offset 2 is 3",
+                message
+            ),
+            _ => panic!("expected an invalid argument error"),
+        }
+    }
+
+    #[test]
+    fn test_list_array_value_offsets_and_range() {
+        // [[0, 1, 2], [3, 4, 5], [6, 7], []]
+        let value_data = ArrayData::builder(DataType::Int32)
+            .len(8)
+            .add_buffer(Buffer::from(&[0, 1, 2, 3, 4, 5, 6, 7].to_byte_slice()))
+            .build();
+        let value_offsets = Buffer::from(&[0, 3, 6, 8, 8].to_byte_slice());
+        let list_data_type = DataType::List(Box::new(DataType::Int32));
+        let list_data = ArrayData::builder(list_data_type.clone())
+            .len(4)
+            .add_buffer(value_offsets.clone())
+            .add_child_data(value_data.clone())
+            .build();
+        let list_array = ListArray::from(list_data);
+        assert_eq!(&[0, 3, 6, 8, 8], list_array.value_offsets());
+
+        let list_data = ArrayData::builder(list_data_type)
+            .len(3)
+            .offset(1)
+            .add_buffer(value_offsets)
+            .add_child_data(value_data)
+            .build();
+        let list_array = ListArray::from(list_data);
+        assert_eq!(&[3, 6, 8, 8], list_array.value_offsets());
+        assert_eq!(8, list_array.values_len());
+        assert_eq!((3, 8), list_array.value_range(0, 2));
+        assert_eq!((6, 6), list_array.value_range(1, 1));
+        assert_eq!((3, 8), list_array.value_range(0, 3));
+        for i in 0..=list_array.len() {
+            assert_eq!(list_array.value_offset(i), list_array.value_offsets()[i]);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "ListArray range 1..4 is out of bounds")]
+    fn test_list_array_value_range_out_of_bounds() {
+        let mut builder = ListBuilder::new(Int32Builder::new(4));
+        for _ in 0..3 {
+            builder.values().append_value(1).unwrap();
+            builder.append(true).unwrap();
+        }
+        builder.finish().value_range(1, 4);
+    }
+
     #[test]
     #[should_panic(
         expected = "ListArray data should contain a single buffer only (value offsets)"
@@ -1402,6 +3834,125 @@ mod tests {
         ListArray::from(list_data);
     }
 
+    #[test]
+    fn test_fixed_size_list_array() {
+        let value_data = ArrayData::builder(DataType::Int32)
+            .len(9)
+            .add_buffer(Buffer::from(&[0, 1, 2, 3, 4, 5, 6, 7, 8].to_byte_slice()))
+            .build();
+
+        // [[0, 1, 2], null, [6, 7, 8]]
+        let list_data_type = DataType::FixedSizeList(Box::new(DataType::Int32), 3);
+        let list_data = ArrayData::builder(list_data_type.clone())
+            .len(3)
+            .null_bit_buffer(Buffer::from([0b0000_0101]))
+            .add_child_data(value_data.clone())
+            .build();
+        let list_array = FixedSizeListArray::from(list_data);
+
+        assert_eq!(value_data, list_array.values().data());
+        assert_eq!(DataType::Int32, list_array.value_type());
+        assert_eq!(3, list_array.len());
+        assert_eq!(1, list_array.null_count());
+        assert!(list_array.is_null(1));
+        assert_eq!(3, list_array.value_length());
+        assert_eq!(6, list_array.value_offset(2));
+
+        let value = list_array.value(2);
+        assert_eq!(3, value.len());
+        let value = value.as_any().downcast_ref::<Int32Array>().unwrap();
+        assert_eq!(&[6, 7, 8], value.value_slice(0, 3));
+        assert_eq!("[0, 1, 2]", list_array.value_to_string(0));
+
+        // with a non-zero offset
+        let list_data = ArrayData::builder(list_data_type)
+            .len(2)
+            .offset(1)
+            .add_child_data(value_data)
+            .build();
+        let list_array = make_array(list_data);
+        let list_array = list_array
+            .as_any()
+            .downcast_ref::<FixedSizeListArray>()
+            .unwrap();
+        assert_eq!(3, list_array.value_offset(0));
+        let value = list_array.value(1);
+        let value = value.as_any().downcast_ref::<Int32Array>().unwrap();
+        assert_eq!(6, value.value(0));
+    }
+
+    #[test]
+    #[cfg(feature = "ndarray")]
+    fn test_fixed_size_list_array_ndarray2_round_trip() {
+        let matrix = ndarray::arr2(&[[1.5, 2.0, -3.0], [4.0, 5.5, 6.0]]);
+        let list_array = FixedSizeListArray::from_ndarray2::<Float64Type>(matrix.view());
+        assert_eq!(2, list_array.len());
+        assert_eq!(3, list_array.value_length());
+        assert_eq!(DataType::Float64, list_array.value_type());
+        assert_eq!("[4.0, 5.5, 6.0]", list_array.value_to_string(1));
+        assert_eq!(matrix, list_array.to_ndarray2::<Float64Type>().unwrap());
+        assert!(list_array.to_ndarray2::<Int64Type>().is_err());
+
+        // [[0, 1], [null, 3]]
+        let value_data = ArrayData::builder(DataType::Int32)
+            .len(4)
+            .null_bit_buffer(Buffer::from([0b1011]))
+            .add_buffer(Buffer::from(&[0, 1, 2, 3].to_byte_slice()))
+            .build();
+        let list_data =
+            ArrayData::builder(DataType::FixedSizeList(Box::new(DataType::Int32), 2))
+                .len(2)
+                .add_child_data(value_data.clone())
+                .build();
+        let list_array = FixedSizeListArray::from(list_data);
+        assert!(list_array.to_ndarray2::<Int32Type>().is_err());
+
+        // [[0, 1]] only covers valid values
+        let list_data =
+            ArrayData::builder(DataType::FixedSizeList(Box::new(DataType::Int32), 2))
+                .len(1)
+                .add_child_data(value_data)
+                .build();
+        let list_array = FixedSizeListArray::from(list_data);
+        assert_eq!(
+            ndarray::arr2(&[[0, 1]]),
+            list_array.to_ndarray2::<Int32Type>().unwrap()
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "FixedSizeListArray child array length must be a multiple of the list size"
+    )]
+    fn test_fixed_size_list_array_invalid_child_len() {
+        let value_data = ArrayData::builder(DataType::Int32)
+            .len(8)
+            .add_buffer(Buffer::from(&[0, 1, 2, 3, 4, 5, 6, 7].to_byte_slice()))
+            .build();
+        let list_data =
+            ArrayData::builder(DataType::FixedSizeList(Box::new(DataType::Int32), 3))
+                .len(2)
+                .add_child_data(value_data)
+                .build();
+        FixedSizeListArray::from(list_data);
+    }
+
+    #[test]
+    #[should_panic(expected = "FixedSizeListArray value offset doesn't fit in an i32")]
+    fn test_fixed_size_list_array_value_offset_overflow() {
+        let value_data = ArrayData::builder(DataType::Int32)
+            .len(3)
+            .add_buffer(Buffer::from(&[0, 1, 2].to_byte_slice()))
+            .build();
+        let list_data =
+            ArrayData::builder(DataType::FixedSizeList(Box::new(DataType::Int32), 3))
+                .len(1)
+                .add_child_data(value_data)
+                .build();
+        let list_array = FixedSizeListArray::from(list_data);
+        list_array.value_offset(i32::max_value() as usize);
+    }
+
     #[test]
     fn test_binary_array() {
         let values: [u8; 12] = [
@@ -1453,6 +4004,74 @@ mod tests {
         assert_eq!(7, binary_array.value_length(1));
     }
 
+    #[test]
+    fn test_binary_array_iter() {
+        let array = BinaryArray::from(vec![Some("hello"), None, Some(""), Some("arrow")]);
+        assert_eq!(
+            vec![
+                Some(&b"hello"[..]),
+                None,
+                Some(&b""[..]),
+                Some(&b"arrow"[..])
+            ],
+            array.iter().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![Some("hello"), None, Some(""), Some("arrow")],
+            array.string_iter().collect::<Vec<_>>()
+        );
+
+        // the iterators start at the offset of the array
+        let sliced = BinaryArray::from(array.data().slice(1, 2));
+        assert_eq!(
+            vec![None, Some(&b""[..])],
+            sliced.iter().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![None, Some("")],
+            sliced.string_iter().collect::<Vec<_>>()
+        );
+
+        let large_array = LargeBinaryArray::from(vec!["a", "bc"]);
+        assert_eq!(
+            vec![Some("a"), Some("bc")],
+            large_array.string_iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "BinaryArray element 1 is not valid UTF-8")]
+    fn test_binary_array_string_iter_invalid_utf8() {
+        let values: Vec<&[u8]> = vec![b"ok", &[0xff, 0xfe]];
+        let array = BinaryArray::from(values);
+        array.string_iter().for_each(drop);
+    }
+
+    #[test]
+    fn test_binary_array_value_offsets_and_data() {
+        let array = BinaryArray::from(vec!["hello", "", "parquet", "arrow"]);
+        assert_eq!(&[0, 5, 5, 12, 17], array.value_offsets());
+        assert_eq!(b"helloparquetarrow", array.value_data().data());
+
+        // with an offset, the offsets of the elements of the array are returned
+        let data = ArrayData::builder(DataType::Utf8)
+            .len(2)
+            .offset(1)
+            .buffers(array.data().buffers().to_vec())
+            .build();
+        let array = BinaryArray::from(data);
+        assert_eq!(&[5, 5, 12], array.value_offsets());
+        let value_data = array.value_data();
+        let (start, end) = (
+            array.value_offset(1) as usize,
+            array.value_offset(2) as usize,
+        );
+        assert_eq!(b"parquet", &value_data.data()[start..end]);
+        for i in 0..=array.len() {
+            assert_eq!(array.value_offset(i), array.value_offsets()[i]);
+        }
+    }
+
     #[test]
     fn test_binary_array_from_list_array() {
         let values: [u8; 12] = [
@@ -1493,6 +4112,106 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_binary_array_from_list_array_with_sliced_values() {
+        // the values "hello" and "parquet" start after two bytes of the buffer
+        let values_data = ArrayData::builder(DataType::UInt8)
+            .len(12)
+            .offset(2)
+            .add_buffer(Buffer::from(&b"xyhelloparquet"[..]))
+            .build();
+        let offsets: [i32; 3] = [0, 5, 12];
+        let list_data = ArrayData::builder(DataType::List(Box::new(DataType::UInt8)))
+            .len(2)
+            .add_buffer(Buffer::from(offsets.to_byte_slice()))
+            .add_child_data(values_data)
+            .build();
+        let binary_array = BinaryArray::from(ListArray::from(list_data));
+        assert_eq!("hello", binary_array.get_string(0));
+        assert_eq!("parquet", binary_array.get_string(1));
+    }
+
+    #[test]
+    fn test_binary_array_from_option_str() {
+        let array = BinaryArray::from(vec![Some("a"), None, Some(""), Some("c")]);
+        assert_eq!(4, array.len());
+        assert_eq!(1, array.null_count());
+        assert!(array.is_valid(0));
+        assert!(array.is_null(1));
+        assert!(array.is_valid(2));
+        assert_eq!("a", array.get_string(0));
+        assert_eq!(0, array.value_length(1));
+        assert_eq!("", array.get_string(2));
+        assert_eq!("c", array.get_string(3));
+        assert_eq!(1, array.value_offset(3));
+    }
+
+    #[test]
+    fn test_binary_array_from_list_array_with_nulls() {
+        let values_data = ArrayData::builder(DataType::UInt8)
+            .len(2)
+            .add_buffer(Buffer::from(&b"ab"[..]))
+            .build();
+        let offsets: [i32; 4] = [0, 1, 1, 2];
+        let list_data = ArrayData::builder(DataType::List(Box::new(DataType::UInt8)))
+            .len(3)
+            .add_buffer(Buffer::from(offsets.to_byte_slice()))
+            .null_bit_buffer(Buffer::from([0b0000_0101]))
+            .add_child_data(values_data)
+            .build();
+        let binary_array = BinaryArray::from(ListArray::from(list_data));
+        assert_eq!(1, binary_array.null_count());
+        assert!(binary_array.is_null(1));
+        assert_eq!("b", binary_array.get_string(2));
+    }
+
+    #[test]
+    fn test_large_binary_array() {
+        let array = LargeBinaryArray::from(vec![Some("hello"), None, Some("arrow")]);
+        assert_eq!(&DataType::LargeUtf8, array.data_type());
+        assert_eq!(3, array.len());
+        assert_eq!(1, array.null_count());
+        assert!(array.is_null(1));
+        assert_eq!("hello", array.get_string(0));
+        assert_eq!("arrow", array.get_string(2));
+        assert_eq!(5i64, array.value_offset(2));
+        assert_eq!(5i64, array.value_length(2));
+        // offsets are 64 bits wide
+        assert_eq!(4 * 8, array.data().buffers()[0].len());
+
+        let array = make_array(array.data());
+        let array = array.as_any().downcast_ref::<LargeBinaryArray>().unwrap();
+        assert_eq!("arrow", array.value_to_string(2));
+    }
+
+    #[test]
+    fn test_large_binary_array_from_large_list_array() {
+        let values_data = ArrayData::builder(DataType::UInt8)
+            .len(7)
+            .add_buffer(Buffer::from(&b"parquet"[..]))
+            .build();
+        let offsets: [i64; 3] = [0, 4, 7];
+        let list_data =
+            ArrayData::builder(DataType::LargeList(Box::new(DataType::UInt8)))
+                .len(2)
+                .add_buffer(Buffer::from(offsets.to_byte_slice()))
+                .add_child_data(values_data)
+                .build();
+        let binary_array = LargeBinaryArray::from(LargeListArray::from(list_data));
+        assert_eq!(&DataType::LargeUtf8, binary_array.data_type());
+        assert_eq!("parq", binary_array.get_string(0));
+        assert_eq!("uet", binary_array.get_string(1));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "LargeBinaryArray data should contain 2 buffers only (offsets and values)"
+    )]
+    fn test_large_binary_array_invalid_buffer_len() {
+        let data = ArrayData::builder(DataType::LargeUtf8).len(0).build();
+        LargeBinaryArray::from(data);
+    }
+
     #[test]
     fn test_binary_array_from_u8_slice() {
         let values: Vec<&[u8]> = vec![
@@ -1632,6 +4351,259 @@ mod tests {
         assert_eq!(int_data, struct_array.column(1).data());
     }
 
+    #[test]
+    fn test_struct_array_from_arrays_unchecked() {
+        let fields = vec![
+            Field::new("b", DataType::Boolean, false),
+            Field::new("c", DataType::Int32, true),
+        ];
+        let arrays: Vec<ArrayRef> = vec![
+            Arc::new(BooleanArray::from(vec![false, true, true])),
+            Arc::new(Int32Array::from(vec![Some(42), None, Some(19)])),
+        ];
+        let checked = StructArray::from(
+            fields
+                .iter()
+                .cloned()
+                .zip(arrays.iter().cloned())
+                .collect::<Vec<_>>(),
+        );
+        let unchecked = StructArray::from_arrays_unchecked(fields, arrays.clone());
+
+        assert_eq!(checked.data(), unchecked.data());
+        assert_eq!(3, unchecked.len());
+        assert_eq!(3, unchecked.data().len());
+        // the child arrays are reused rather than rebuilt from their data
+        assert!(Arc::ptr_eq(&arrays[1], unchecked.column(1)));
+    }
+
+    #[test]
+    fn test_struct_array_rename_fields() {
+        let struct_array = StructArray::from(vec![
+            (
+                Field::new("a", DataType::Boolean, false),
+                Arc::new(BooleanArray::from(vec![false, true])) as Arc<dyn Array>,
+            ),
+            (
+                Field::new("b", DataType::Int32, true),
+                Arc::new(Int32Array::from(vec![Some(42), None])),
+            ),
+        ]);
+        let renamed = struct_array.rename_fields(&[("a", "x")]).unwrap();
+
+        assert_eq!(
+            &DataType::Struct(vec![
+                Field::new("x", DataType::Boolean, false),
+                Field::new("b", DataType::Int32, true),
+            ]),
+            renamed.data_type()
+        );
+        assert_eq!(2, renamed.len());
+        assert!(Arc::ptr_eq(struct_array.column(0), renamed.column(0)));
+        assert!(Arc::ptr_eq(struct_array.column(1), renamed.column(1)));
+    }
+
+    #[test]
+    fn test_struct_array_rename_fields_invalid() {
+        let struct_array = StructArray::from(vec![
+            (
+                Field::new("a", DataType::Boolean, false),
+                Arc::new(BooleanArray::from(vec![false, true])) as Arc<dyn Array>,
+            ),
+            (
+                Field::new("b", DataType::Int32, false),
+                Arc::new(Int32Array::from(vec![42, 28])),
+            ),
+        ]);
+        assert!(struct_array.rename_fields(&[("c", "x")]).is_err());
+        assert!(struct_array.rename_fields(&[("a", "b")]).is_err());
+
+        // names are matched against the original fields, so swapping is allowed
+        let renamed = struct_array
+            .rename_fields(&[("a", "b"), ("b", "a")])
+            .unwrap();
+        assert_eq!(
+            &DataType::Struct(vec![
+                Field::new("b", DataType::Boolean, false),
+                Field::new("a", DataType::Int32, false),
+            ]),
+            renamed.data_type()
+        );
+    }
+
+    fn dense_union_data(offsets: Vec<i32>) -> ArrayDataRef {
+        let fields = vec![
+            Field::new("i", DataType::Int32, false),
+            Field::new("s", DataType::Utf8, false),
+        ];
+        let type_ids: Vec<i8> = vec![0, 1, 0, 1, 0];
+        ArrayData::builder(DataType::Union(fields, UnionMode::Dense))
+            .len(5)
+            .add_buffer(Buffer::from(type_ids.to_byte_slice()))
+            .add_buffer(Buffer::from(offsets.to_byte_slice()))
+            .add_child_data(Int32Array::from(vec![1, 2, 3]).data())
+            .add_child_data(BinaryArray::from(vec!["a", "b"]).data())
+            .build()
+    }
+
+    #[test]
+    fn test_dense_union_array() {
+        let array = make_array(dense_union_data(vec![0, 0, 1, 1, 2]));
+        let union = array.as_any().downcast_ref::<UnionArray>().unwrap();
+        assert_eq!(5, union.len());
+        assert_eq!(&UnionMode::Dense, union.mode());
+        assert_eq!(
+            vec![0, 1, 0, 1, 0],
+            (0..5).map(|i| union.type_id(i)).collect::<Vec<_>>()
+        );
+        assert_eq!(2, union.value_offset(4));
+        assert_eq!(&DataType::Utf8, union.child(1).data_type());
+
+        let value = union.value(3);
+        assert_eq!(&DataType::Utf8, value.data_type());
+        assert_eq!(1, value.len());
+        let value = value.as_any().downcast_ref::<BinaryArray>().unwrap();
+        assert_eq!("b", value.get_string(0));
+        let value = union.value(4);
+        let value = value.as_any().downcast_ref::<Int32Array>().unwrap();
+        assert_eq!(3, value.value(0));
+
+        let strings: Vec<String> = (0..5).map(|i| union.value_to_string(i)).collect();
+        assert_eq!(vec!["1", "a", "2", "b", "3"], strings);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "UnionArray value offset 2 is out of the bounds of child 1"
+    )]
+    fn test_dense_union_array_invalid_offset() {
+        UnionArray::from(dense_union_data(vec![0, 0, 1, 2, 2]));
+    }
+
+    fn sparse_union_data(len: usize) -> ArrayDataRef {
+        let fields = vec![
+            Field::new("i", DataType::Int32, false),
+            Field::new("s", DataType::Utf8, false),
+        ];
+        let type_ids: Vec<i8> = vec![0, 1, 0];
+        ArrayData::builder(DataType::Union(fields, UnionMode::Sparse))
+            .len(len)
+            .add_buffer(Buffer::from(type_ids.to_byte_slice()))
+            .add_child_data(Int32Array::from(vec![1, 2, 3]).data())
+            .add_child_data(BinaryArray::from(vec!["a", "b"]).data())
+            .build()
+    }
+
+    #[test]
+    fn test_sparse_union_array() {
+        let union = UnionArray::from(sparse_union_data(2));
+        assert_eq!(&UnionMode::Sparse, union.mode());
+        assert_eq!(1, union.value_offset(1));
+        let strings: Vec<String> = (0..2).map(|i| union.value_to_string(i)).collect();
+        assert_eq!(vec!["1", "b"], strings);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "UnionArray child 1 has 2 elements, but the sparse union has 3"
+    )]
+    fn test_sparse_union_array_short_child() {
+        // the child of type id 1 is too short even though no element selects it at
+        // index 2
+        make_array(sparse_union_data(3));
+    }
+
+    #[test]
+    fn test_struct_array_sort_by_field() {
+        let struct_array = StructArray::from(vec![
+            (
+                Field::new("key", DataType::Int32, false),
+                Arc::new(Int32Array::from(vec![2, 7, 0])) as ArrayRef,
+            ),
+            (
+                Field::new("value", DataType::Utf8, false),
+                Arc::new(BinaryArray::from(vec!["two", "seven", "zero"])) as ArrayRef,
+            ),
+        ]);
+
+        let sorted = struct_array.sort_by_field("key", false).unwrap();
+        assert_eq!(struct_array.data_type(), sorted.data_type());
+        let keys = sorted
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int32Array>()
+            .unwrap();
+        assert_eq!(&[0, 2, 7], keys.value_slice(0, 3));
+        let values = sorted
+            .column(1)
+            .as_any()
+            .downcast_ref::<BinaryArray>()
+            .unwrap();
+        assert_eq!("seven", values.get_string(2));
+
+        let sorted = struct_array.sort_by_field("key", true).unwrap();
+        let keys = sorted
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int32Array>()
+            .unwrap();
+        assert_eq!(&[7, 2, 0], keys.value_slice(0, 3));
+        let values = sorted
+            .column(1)
+            .as_any()
+            .downcast_ref::<BinaryArray>()
+            .unwrap();
+        assert_eq!("seven", values.get_string(0));
+        assert_eq!("zero", values.get_string(2));
+
+        assert!(struct_array.sort_by_field("missing", false).is_err());
+    }
+
+    #[test]
+    fn test_struct_array_try_new() {
+        let struct_array = StructArray::try_new(
+            vec![
+                (
+                    Field::new("a", DataType::Int32, false),
+                    Arc::new(Int32Array::from(vec![1, 2])) as ArrayRef,
+                ),
+                (
+                    Field::new("b", DataType::Boolean, false),
+                    Arc::new(BooleanArray::from(vec![true, false])) as ArrayRef,
+                ),
+            ],
+            2,
+        )
+        .unwrap();
+        assert_eq!(2, struct_array.len());
+        assert_eq!(2, struct_array.data().child_data().len());
+        assert_eq!(
+            &BooleanArray::from(vec![true, false]),
+            as_boolean_array(struct_array.column(1))
+        );
+
+        let empty = StructArray::try_new(vec![], 3).unwrap();
+        assert_eq!(3, empty.len());
+        assert_eq!(&DataType::Struct(vec![]), empty.data_type());
+        assert_eq!(0, empty.null_count());
+        assert!(empty.data().child_data().is_empty());
+    }
+
+    #[test]
+    fn test_struct_array_try_new_invalid_length() {
+        let fields = vec![(
+            Field::new("a", DataType::Int32, false),
+            Arc::new(Int32Array::from(vec![1, 2])) as ArrayRef,
+        )];
+        match StructArray::try_new(fields, 3) {
+            Err(ArrowError::InvalidArgumentError(message)) => assert_eq!(
+                "StructArray of length 3 has child array 'a' of length 2",
+                message
+            ),
+            _ => panic!("expected an invalid argument error"),
+        }
+    }
+
     #[test]
     #[should_panic(
         expected = "all child arrays of a StructArray must have the same length"
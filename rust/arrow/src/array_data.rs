@@ -18,11 +18,13 @@
 //! Contains `ArrayData`, a generic representation of Arrow array data which encapsulates
 //! common attributes and operations for Arrow array.
 
+use std::mem;
 use std::sync::Arc;
 
 use crate::bitmap::Bitmap;
 use crate::buffer::Buffer;
 use crate::datatypes::DataType;
+use crate::error::{ArrowError, Result};
 use crate::util::bit_util;
 
 /// An generic representation of Arrow array data which encapsulates common attributes and
@@ -69,14 +71,7 @@ impl ArrayData {
         child_data: Vec<ArrayDataRef>,
     ) -> Self {
         let null_count = match null_count {
-            None => {
-                if let Some(ref buf) = null_bit_buffer {
-                    len.checked_sub(bit_util::count_set_bits_offset(buf.data(), offset))
-                        .unwrap()
-                } else {
-                    0
-                }
-            }
+            None => count_nulls(null_bit_buffer.as_ref(), offset, len),
             Some(null_count) => null_count,
         };
         let null_bitmap = null_bit_buffer.map(Bitmap::from);
@@ -114,7 +109,7 @@ impl ArrayData {
     /// Returns whether the element at index `i` is null
     pub fn is_null(&self, i: usize) -> bool {
         if let Some(ref b) = self.null_bitmap {
-            return !b.is_set(i);
+            return !b.is_set(self.offset + i);
         }
         false
     }
@@ -127,7 +122,7 @@ impl ArrayData {
     /// Returns whether the element at index `i` is not null
     pub fn is_valid(&self, i: usize) -> bool {
         if let Some(ref b) = self.null_bitmap {
-            return b.is_set(i);
+            return b.is_set(self.offset + i);
         }
         true
     }
@@ -146,6 +141,100 @@ impl ArrayData {
     pub fn null_count(&self) -> usize {
         self.null_count
     }
+
+    /// Checks that the null count of this array data matches its null bitmap, i.e. that
+    /// it is the number of unset bits in the `len` slots starting at `offset`.
+    ///
+    /// Array data without a null bitmap must have no nulls, except array data of type
+    /// `Null`, in which all elements are null. Returns an `InvalidArgumentError`
+    /// otherwise.
+    pub fn validate(&self) -> Result<()> {
+        let expected = match self.null_bitmap {
+            Some(ref bitmap) => count_nulls(Some(&bitmap.bits), self.offset, self.len),
+            None if self.data_type == DataType::Null => self.len,
+            None => 0,
+        };
+        if self.null_count != expected {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "Array data of type {:?} declares {} nulls, but its null bitmap has {}",
+                self.data_type, self.null_count, expected
+            )));
+        }
+        Ok(())
+    }
+
+    /// Returns a zero-copy slice of this array data with `length` elements starting at
+    /// `offset`, relative to the offset of this array data.
+    ///
+    /// The slice shares the buffers and the children of this array data, and its null
+    /// count is recomputed from the null bitmap over the sliced range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `offset + length` is greater than the length of this array data.
+    pub fn slice(&self, offset: usize, length: usize) -> ArrayDataRef {
+        assert!(
+            offset
+                .checked_add(length)
+                .map_or(false, |end| end <= self.len),
+            "the slice {}..{} is out of bounds of array data of length {}",
+            offset,
+            offset as u128 + length as u128,
+            self.len
+        );
+        let offset = self.offset + offset;
+        let null_bit_buffer = self.null_bitmap.as_ref().map(|b| b.bits.clone());
+        let null_count = match null_bit_buffer {
+            // all the elements of array data of type `Null` are null, without a null
+            // bitmap
+            None if self.data_type == DataType::Null => length,
+            _ => count_nulls(null_bit_buffer.as_ref(), offset, length),
+        };
+        Arc::new(Self {
+            data_type: self.data_type.clone(),
+            len: length,
+            null_count,
+            offset,
+            buffers: self.buffers.clone(),
+            child_data: self.child_data.clone(),
+            null_bitmap: null_bit_buffer.map(Bitmap::from),
+        })
+    }
+
+    /// Returns the total number of bytes of memory allocated for the buffers and the
+    /// null bitmap of this array data, not including its children.
+    pub fn get_buffer_memory_size(&self) -> usize {
+        let bitmap_size = match self.null_bitmap {
+            Some(ref bitmap) => bitmap.bits.capacity(),
+            None => 0,
+        };
+        self.buffers.iter().map(|b| b.capacity()).sum::<usize>() + bitmap_size
+    }
+
+    /// Returns the total number of bytes of memory occupied by this array data,
+    /// including the `ArrayData` struct itself and, recursively, its children.
+    pub fn get_array_memory_size(&self) -> usize {
+        mem::size_of::<Self>()
+            + self.get_buffer_memory_size()
+            + self
+                .child_data
+                .iter()
+                .map(|child| child.get_array_memory_size())
+                .sum::<usize>()
+    }
+}
+
+/// Returns the number of unset bits of `null_bit_buffer` in the `len` slots starting at
+/// `offset`, counting the slots past the end of the buffer as unset.
+fn count_nulls(null_bit_buffer: Option<&Buffer>, offset: usize, len: usize) -> usize {
+    match null_bit_buffer {
+        Some(buf) => {
+            let data = buf.data();
+            let end = (offset + len).min(data.len() * 8);
+            len - bit_util::count_set_bits(data, offset, end.saturating_sub(offset))
+        }
+        None => 0,
+    }
 }
 
 /// Builder for `ArrayData` type
@@ -300,4 +389,122 @@ mod tests {
             .build();
         assert_eq!(14, arr_data.null_count());
     }
+
+    #[test]
+    fn test_slice() {
+        let mut bit_v: [u8; 2] = [0; 2];
+        bit_util::set_bit(&mut bit_v, 0);
+        bit_util::set_bit(&mut bit_v, 3);
+        bit_util::set_bit(&mut bit_v, 10);
+        let values = Buffer::from(&[0u8; 16][..]);
+        let arr_data = ArrayData::builder(DataType::UInt8)
+            .len(14)
+            .offset(2)
+            .null_bit_buffer(Buffer::from(bit_v))
+            .add_buffer(values.clone())
+            .build();
+        assert_eq!(12, arr_data.null_count());
+
+        let sliced = arr_data.slice(1, 8);
+        assert_eq!(8, sliced.len());
+        assert_eq!(3, sliced.offset());
+        // bits 3 and 10 are the only ones set among bits 3..11
+        assert_eq!(6, sliced.null_count());
+        assert!(sliced.is_valid(0));
+        assert!(sliced.is_null(1));
+        assert_eq!(&values, &sliced.buffers()[0]);
+        assert_eq!(arr_data.data_type(), sliced.data_type());
+
+        // slicing a slice is relative to its offset
+        let sliced = sliced.slice(7, 1);
+        assert_eq!(10, sliced.offset());
+        assert_eq!(0, sliced.null_count());
+
+        let sliced = arr_data.slice(14, 0);
+        assert_eq!(0, sliced.len());
+        assert_eq!(0, sliced.null_count());
+    }
+
+    #[test]
+    fn test_slice_without_null_bitmap() {
+        let arr_data = ArrayData::builder(DataType::Int32)
+            .len(4)
+            .add_buffer(Buffer::from(&[1, 2, 3, 4][..]))
+            .build();
+        let sliced = arr_data.slice(1, 2);
+        assert_eq!(0, sliced.null_count());
+        assert!(sliced.null_bitmap().is_none());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "the slice 3..6 is out of bounds of array data of length 5"
+    )]
+    fn test_slice_out_of_bounds() {
+        let arr_data = ArrayData::builder(DataType::Int32).len(5).build();
+        arr_data.slice(3, 3);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "the slice 3..18446744073709551618 is out of bounds of array data of length 5"
+    )]
+    fn test_slice_overflowing_bounds() {
+        let arr_data = ArrayData::builder(DataType::Int32).len(5).build();
+        arr_data.slice(3, std::usize::MAX);
+    }
+
+    #[test]
+    fn test_slice_null() {
+        let arr_data = ArrayData::builder(DataType::Null).len(5).build();
+        let sliced = arr_data.slice(1, 2);
+        assert_eq!(2, sliced.null_count());
+        assert!(sliced.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut bit_v: [u8; 2] = [0; 2];
+        bit_util::set_bit(&mut bit_v, 0);
+        bit_util::set_bit(&mut bit_v, 3);
+        bit_util::set_bit(&mut bit_v, 10);
+        let arr_data = ArrayData::builder(DataType::Int32)
+            .len(14)
+            .offset(2)
+            .null_bit_buffer(Buffer::from(bit_v))
+            .build();
+        assert!(arr_data.validate().is_ok());
+        assert!(arr_data.slice(3, 9).validate().is_ok());
+
+        let arr_data = ArrayData::builder(DataType::Int32)
+            .len(14)
+            .null_count(1)
+            .offset(2)
+            .null_bit_buffer(Buffer::from(bit_v))
+            .build();
+        match arr_data.validate() {
+            Err(ArrowError::InvalidArgumentError(message)) => assert_eq!(
+                "Array data of type Int32 declares 1 nulls, but its null bitmap has 12",
+                message
+            ),
+            _ => panic!("expected an invalid argument error"),
+        }
+    }
+
+    #[test]
+    fn test_validate_without_null_bitmap() {
+        let arr_data = ArrayData::builder(DataType::Int32).len(3).build();
+        assert!(arr_data.validate().is_ok());
+        let arr_data =
+            ArrayData::new(DataType::Boolean, 10, Some(1), None, 2, vec![], vec![]);
+        assert!(arr_data.validate().is_err());
+
+        let arr_data = ArrayData::builder(DataType::Null)
+            .len(3)
+            .null_count(3)
+            .build();
+        assert!(arr_data.validate().is_ok());
+        let arr_data = ArrayData::builder(DataType::Null).len(3).build();
+        assert!(arr_data.validate().is_err());
+    }
 }
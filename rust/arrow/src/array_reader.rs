@@ -0,0 +1,246 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the `ArrayReader` trait for reading a column as a sequence of array chunks,
+//! and functions to assemble chunks of several columns into nested arrays.
+
+use crate::array::*;
+use crate::array_data::ArrayData;
+use crate::datatypes::*;
+use crate::error::{ArrowError, Result};
+
+/// A reader producing the values of a single column as a sequence of arrays.
+pub trait ArrayReader {
+    /// Returns the data type of the arrays produced by this reader.
+    fn data_type(&self) -> &DataType;
+
+    /// Reads the next chunk of at most `batch_size` values, or returns `None` if the
+    /// reader is exhausted.
+    fn next_batch(&mut self, batch_size: usize) -> Result<Option<ArrayRef>>;
+}
+
+/// Reading through a mutable reference, e.g. to pass a boxed `&mut` reader to
+/// `struct_from_chunk_readers` while keeping the reader for the next chunks.
+impl<R: ArrayReader + ?Sized> ArrayReader for &mut R {
+    fn data_type(&self) -> &DataType {
+        (**self).data_type()
+    }
+
+    fn next_batch(&mut self, batch_size: usize) -> Result<Option<ArrayRef>> {
+        (**self).next_batch(batch_size)
+    }
+}
+
+/// An `ArrayReader` producing consecutive slices of an in-memory array.
+///
+/// The slices share the buffers of the array, so no data is copied.
+pub struct SliceArrayReader {
+    array: ArrayRef,
+    data_type: DataType,
+    position: usize,
+}
+
+impl SliceArrayReader {
+    /// Creates a new reader over `array`.
+    pub fn new(array: ArrayRef) -> Self {
+        let data_type = array.data_type().clone();
+        Self {
+            array,
+            data_type,
+            position: 0,
+        }
+    }
+}
+
+impl ArrayReader for SliceArrayReader {
+    fn data_type(&self) -> &DataType {
+        &self.data_type
+    }
+
+    fn next_batch(&mut self, batch_size: usize) -> Result<Option<ArrayRef>> {
+        let remaining = self.array.len() - self.position;
+        if remaining == 0 {
+            return Ok(None);
+        }
+        let len = batch_size.min(remaining);
+        let data = self.array.data_ref();
+        let mut builder = ArrayData::builder(self.data_type.clone())
+            .len(len)
+            .offset(data.offset() + self.position)
+            .buffers(data.buffers().to_vec())
+            .child_data(data.child_data().to_vec());
+        if let Some(bitmap) = data.null_bitmap() {
+            builder = builder.null_bit_buffer(bitmap.bits.clone());
+        }
+        self.position += len;
+        Ok(Some(make_array(builder.build())))
+    }
+}
+
+/// Reads one chunk of at most `batch_size` values from each reader and assembles the
+/// chunks into a `StructArray` with the given `fields`.
+///
+/// To read all the chunks, pass readers that borrow the column readers mutably, e.g.
+/// `vec![Box::new(&mut reader)]`, on every call.
+///
+/// Returns `None` once all readers are exhausted. Returns an error if the number of
+/// readers doesn't match the number of fields, if a reader produces arrays of another
+/// type than its field, or if the readers produce chunks of different lengths.
+pub fn struct_from_chunk_readers(
+    fields: Vec<Field>,
+    readers: Vec<Box<dyn ArrayReader + '_>>,
+    batch_size: usize,
+) -> Result<Option<StructArray>> {
+    if fields.len() != readers.len() {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "Expected {} array readers to assemble a struct, but got {}",
+            fields.len(),
+            readers.len()
+        )));
+    }
+    if fields.is_empty() {
+        return Err(ArrowError::InvalidArgumentError(
+            "Cannot assemble a struct without any fields".to_string(),
+        ));
+    }
+
+    let mut chunks = Vec::with_capacity(readers.len());
+    for (field, mut reader) in fields.iter().zip(readers) {
+        if reader.data_type() != field.data_type() {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "Array reader for field '{}' produces {:?}, expected {:?}",
+                field.name(),
+                reader.data_type(),
+                field.data_type()
+            )));
+        }
+        chunks.push(reader.next_batch(batch_size)?);
+    }
+
+    if chunks.iter().all(|c| c.is_none()) {
+        return Ok(None);
+    }
+    let mut columns = Vec::with_capacity(chunks.len());
+    for (field, chunk) in fields.into_iter().zip(chunks) {
+        match chunk {
+            Some(array) => columns.push((field, array)),
+            None => {
+                return Err(ArrowError::InvalidArgumentError(format!(
+                    "Array reader for field '{}' was exhausted before the others",
+                    field.name()
+                )));
+            }
+        }
+    }
+    let len = columns[0].1.len();
+    if let Some((field, array)) = columns.iter().find(|(_, a)| a.len() != len) {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "Array reader for field '{}' produced {} values, expected {}",
+            field.name(),
+            array.len(),
+            len
+        )));
+    }
+    Ok(Some(StructArray::from(columns)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    fn readers<'a>(
+        a: &'a mut SliceArrayReader,
+        b: &'a mut SliceArrayReader,
+    ) -> Vec<Box<dyn ArrayReader + 'a>> {
+        vec![Box::new(a), Box::new(b)]
+    }
+
+    #[test]
+    fn test_struct_from_chunk_readers() {
+        let fields = vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Utf8, false),
+        ];
+        let mut a = SliceArrayReader::new(Arc::new(Int32Array::from(vec![
+            Some(1),
+            None,
+            Some(3),
+            Some(4),
+            Some(5),
+        ])));
+        let mut b = SliceArrayReader::new(Arc::new(BinaryArray::from(vec![
+            "one", "two", "three", "four", "five",
+        ])));
+
+        let mut lengths = vec![];
+        let mut null_counts = vec![];
+        let mut values = vec![];
+        let mut strings = vec![];
+        while let Some(s) =
+            struct_from_chunk_readers(fields.clone(), readers(&mut a, &mut b), 2).unwrap()
+        {
+            assert_eq!(&DataType::Struct(fields.clone()), s.data_type());
+            lengths.push(s.len());
+            null_counts.push(s.column(0).null_count());
+            let a = s.column(0).as_any().downcast_ref::<Int32Array>().unwrap();
+            let b = s.column(1).as_any().downcast_ref::<BinaryArray>().unwrap();
+            for i in 0..s.len() {
+                values.push(if a.is_valid(i) {
+                    Some(a.value(i))
+                } else {
+                    None
+                });
+                strings.push(b.get_string(i));
+            }
+        }
+        assert_eq!(vec![2, 2, 1], lengths);
+        assert_eq!(vec![1, 0, 0], null_counts);
+        assert_eq!(vec![Some(1), None, Some(3), Some(4), Some(5)], values);
+        assert_eq!(vec!["one", "two", "three", "four", "five"], strings);
+    }
+
+    #[test]
+    fn test_struct_from_chunk_readers_unaligned() {
+        let fields = vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Int32, false),
+        ];
+        let mut a = SliceArrayReader::new(Arc::new(Int32Array::from(vec![1, 2, 3])));
+        let mut b = SliceArrayReader::new(Arc::new(Int32Array::from(vec![1, 2])));
+        assert!(
+            struct_from_chunk_readers(fields.clone(), readers(&mut a, &mut b), 2)
+                .unwrap()
+                .is_some()
+        );
+        assert!(struct_from_chunk_readers(fields, readers(&mut a, &mut b), 2).is_err());
+    }
+
+    #[test]
+    fn test_struct_from_chunk_readers_type_mismatch() {
+        let fields = vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Int64, false),
+        ];
+        let readers: Vec<Box<dyn ArrayReader>> = vec![
+            Box::new(SliceArrayReader::new(Arc::new(Int32Array::from(vec![1])))),
+            Box::new(SliceArrayReader::new(Arc::new(Int32Array::from(vec![1])))),
+        ];
+        assert!(struct_from_chunk_readers(fields, readers, 2).is_err());
+    }
+}
@@ -21,20 +21,29 @@
 //!
 use packed_simd::u8x64;
 
+use std::any::Any;
 use std::cmp;
+use std::fmt;
 use std::io::{Error as IoError, ErrorKind, Result as IoResult, Write};
+use std::iter::FromIterator;
 use std::mem;
 use std::ops::{BitAnd, BitOr, Not};
+use std::ptr::NonNull;
 use std::slice::{from_raw_parts, from_raw_parts_mut};
 use std::sync::Arc;
 
 use crate::builder::{BufferBuilderTrait, UInt8BufferBuilder};
+use crate::datatypes::{ArrowNativeType, ArrowPlainNativeType, ToByteSlice};
 use crate::error::{ArrowError, Result};
 use crate::memory;
 use crate::util::bit_util;
 
 /// Buffer is a contiguous memory region of fixed size and is aligned at a 64-byte
 /// boundary. Buffer is immutable.
+///
+/// Buffers are compared for equality by their full byte contents, including any bits
+/// of the last byte that are only padding. Use `bit_equals` to compare bit-packed
+/// buffers, e.g. boolean values or null bitmaps, by their first bits only.
 #[derive(PartialEq, Debug)]
 pub struct Buffer {
     /// Reference-counted pointer to the internal byte buffer.
@@ -44,13 +53,31 @@ pub struct Buffer {
     offset: usize,
 }
 
-#[derive(Debug)]
 struct BufferData {
     /// The raw pointer into the buffer bytes
     ptr: *const u8,
 
     /// The length (num of bytes) of the buffer
     len: usize,
+
+    /// The number of bytes allocated for the buffer
+    capacity: usize,
+
+    /// The owner of the memory of the buffer when it was allocated outside of this
+    /// crate, in which case the memory is released by dropping the owner rather than
+    /// freed here
+    owner: Option<Arc<dyn Any + Send + Sync>>,
+}
+
+impl fmt::Debug for BufferData {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BufferData")
+            .field("ptr", &self.ptr)
+            .field("len", &self.len)
+            .field("capacity", &self.capacity)
+            .field("foreign", &self.owner.is_some())
+            .finish()
+    }
 }
 
 impl PartialEq for BufferData {
@@ -65,15 +92,67 @@ impl PartialEq for BufferData {
 /// Release the underlying memory when the current buffer goes out of scope
 impl Drop for BufferData {
     fn drop(&mut self) {
-        memory::free_aligned(self.ptr);
+        if self.owner.is_none() {
+            memory::free_aligned(self.ptr);
+        }
     }
 }
 
 impl Buffer {
-    /// Creates a buffer from an existing memory region (must already be byte-aligned)
+    /// Creates a buffer from an existing memory region (must already be byte-aligned).
+    /// The capacity of the buffer is assumed to be `len`.
     pub fn from_raw_parts(ptr: *const u8, len: usize) -> Self {
+        Self::from_raw_parts_with_capacity(ptr, len, len)
+    }
+
+    /// Creates a buffer from an existing memory region of `capacity` bytes (must already
+    /// be byte-aligned), whose first `len` bytes are the buffer data.
+    fn from_raw_parts_with_capacity(ptr: *const u8, len: usize, capacity: usize) -> Self {
         assert!(memory::is_aligned(ptr, 64), "memory not aligned");
-        let buf_data = BufferData { ptr, len };
+        let buf_data = BufferData {
+            ptr,
+            len,
+            capacity,
+            owner: None,
+        };
+        Buffer {
+            data: Arc::new(buf_data),
+            offset: 0,
+        }
+    }
+
+    /// Creates a buffer from `len` bytes of memory at `ptr` that was allocated outside of
+    /// this crate, e.g. a memory-mapped file or memory shared over FFI, without copying
+    /// it.
+    ///
+    /// The buffer holds on to `owner` until it and all the buffers cloned or sliced from
+    /// it are dropped, and never frees the memory itself: `owner` is responsible for
+    /// releasing it when dropped. Unlike `from_raw_parts`, `ptr` doesn't need to be
+    /// aligned to 64 bytes, but arrays built from the buffer still require it to be
+    /// aligned to their value type.
+    ///
+    /// # Safety
+    ///
+    /// Unless `len` is zero, `ptr` must be non-null and point to `len` initialized bytes
+    /// which stay valid and unchanged as long as `owner` is alive. If `len` is zero,
+    /// `ptr` is ignored and may be null.
+    pub unsafe fn from_foreign(
+        ptr: *const u8,
+        len: usize,
+        owner: Arc<dyn Any + Send + Sync>,
+    ) -> Self {
+        // slices can't be made from a null pointer, even empty ones
+        let ptr = if len == 0 {
+            NonNull::dangling().as_ptr()
+        } else {
+            ptr
+        };
+        let buf_data = BufferData {
+            ptr,
+            len,
+            capacity: len,
+            owner: Some(owner),
+        };
         Buffer {
             data: Arc::new(buf_data),
             offset: 0,
@@ -90,11 +169,63 @@ impl Buffer {
         self.data.len - self.offset == 0
     }
 
+    /// Returns the number of bytes allocated for this buffer.
+    ///
+    /// This is the capacity of the whole memory region, which is shared with the buffers
+    /// sliced from or cloned from this buffer.
+    pub fn capacity(&self) -> usize {
+        self.data.capacity
+    }
+
     /// Returns the byte slice stored in this buffer
     pub fn data(&self) -> &[u8] {
         unsafe { ::std::slice::from_raw_parts(self.raw_data(), self.len()) }
     }
 
+    /// Returns the data stored in this buffer as a slice of `T`, whose length is the
+    /// number of whole values of `T` in the buffer.
+    ///
+    /// `T` is restricted to the types for which any bytes are a valid value, so `bool`
+    /// buffers can't be read this way. Panics if the data isn't aligned to `T`, e.g. for
+    /// a buffer sliced at an offset that isn't a multiple of the size of `T`.
+    pub fn typed_data<T: ArrowPlainNativeType>(&self) -> &[T] {
+        let len = self.len() / mem::size_of::<T>();
+        if len == 0 {
+            return &[];
+        }
+        assert!(
+            memory::is_aligned(self.raw_data(), mem::align_of::<T>()),
+            "Buffer data is not aligned to {}",
+            std::any::type_name::<T>()
+        );
+        unsafe { from_raw_parts(self.raw_data() as *const T, len) }
+    }
+
+    /// Returns whether the first `bit_len` bits of this buffer are equal to those of
+    /// `other`, ignoring the remaining bits.
+    ///
+    /// Panics if either buffer holds fewer than `bit_len` bits.
+    pub fn bit_equals(&self, other: &Buffer, bit_len: usize) -> bool {
+        let (left, right) = (self.data(), other.data());
+        assert!(
+            bit_len <= left.len() * 8 && bit_len <= right.len() * 8,
+            "cannot compare {} bits of buffers of {} and {} bytes",
+            bit_len,
+            left.len(),
+            right.len()
+        );
+        let whole_bytes = bit_len / 8;
+        if left[..whole_bytes] != right[..whole_bytes] {
+            return false;
+        }
+        let remaining_bits = bit_len % 8;
+        if remaining_bits == 0 {
+            return true;
+        }
+        let mask = (1u8 << remaining_bits) - 1;
+        (left[whole_bytes] ^ right[whole_bytes]) & mask == 0
+    }
+
     /// Returns a slice of this buffer, starting from `offset`.
     pub fn slice(&self, offset: usize) -> Self {
         assert!(
@@ -107,6 +238,35 @@ impl Buffer {
         }
     }
 
+    /// Returns the bits of this buffer starting from bit `offset_bits`, as a buffer whose
+    /// first bit is bit `offset_bits` of this one, e.g. for kernels working on the
+    /// bit-packed values or validity of a sliced array.
+    ///
+    /// If `offset_bits` is a multiple of 8 this is a zero-copy slice, otherwise the bits
+    /// are copied and shifted into a new buffer, whose last bits past the end of this
+    /// buffer are unset.
+    pub fn bit_slice(&self, offset_bits: usize) -> Self {
+        let data = self.data();
+        assert!(
+            offset_bits <= data.len() * 8,
+            "the bit offset {} of the new Buffer cannot exceed the {} bits of the existing one",
+            offset_bits,
+            data.len() * 8
+        );
+        let (byte_offset, shift) = (offset_bits / 8, offset_bits % 8);
+        if shift == 0 {
+            return self.slice(byte_offset);
+        }
+        let data = &data[byte_offset..];
+        let shifted: Vec<u8> = (0..bit_util::ceil(data.len() * 8 - shift, 8))
+            .map(|i| {
+                let high = data.get(i + 1).map_or(0, |b| b << (8 - shift));
+                (data[i] >> shift) | high
+            })
+            .collect();
+        Buffer::from(shifted)
+    }
+
     /// Returns a raw pointer for this buffer.
     ///
     /// Note that this should be used cautiously, and the returned pointer should not be
@@ -142,7 +302,24 @@ impl<T: AsRef<[u8]>> From<T> for Buffer {
         unsafe {
             memory::memcpy(buffer, slice.as_ptr(), len);
         }
-        Buffer::from_raw_parts(buffer, len)
+        Buffer::from_raw_parts_with_capacity(buffer, len, capacity)
+    }
+}
+
+/// Creating a `Buffer` instance by collecting native values into a newly allocated
+/// memory region, e.g. with `(0..10i32).collect::<Buffer>()`.
+///
+/// Like any buffer, the memory is allocated with `memory::allocate_aligned`, so it is
+/// aligned for all native types.
+impl<T: ArrowNativeType> FromIterator<T> for Buffer {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let size = mem::size_of::<T>();
+        let mut buffer = MutableBuffer::new(iter.size_hint().0 * size);
+        for v in iter {
+            buffer.extend_from_slice(v.to_byte_slice());
+        }
+        buffer.freeze()
     }
 }
 
@@ -348,14 +525,15 @@ impl MutableBuffer {
 
     /// Resizes the buffer so that the `len` will equal to the `new_len`.
     ///
-    /// If `new_len` is greater than `len`, the buffer's length is simply adjusted to be
-    /// the former, optionally extending the capacity. The data between `len` and
-    /// `new_len` will remain unchanged.
+    /// If `new_len` is greater than `len`, the buffer's length is adjusted to be the
+    /// former, optionally extending the capacity, and the bytes between `len` and
+    /// `new_len` are set to zero.
     ///
     /// If `new_len` is less than `len`, the buffer will be truncated.
     pub fn resize(&mut self, new_len: usize) -> Result<()> {
         if new_len > self.len {
             self.reserve(new_len)?;
+            self.set_null_bits(self.len, new_len - self.len);
         } else {
             let new_capacity = bit_util::round_upto_multiple_of_64(new_len);
             if new_capacity < self.capacity {
@@ -369,6 +547,50 @@ impl MutableBuffer {
         Ok(())
     }
 
+    /// Shrinks the capacity of this buffer to the multiple of 64 bytes closest to its
+    /// `len`, so that `freeze` doesn't retain the unused memory of an over-allocated
+    /// buffer.
+    pub fn shrink_to_fit(&mut self) -> Result<()> {
+        let new_capacity = bit_util::round_upto_multiple_of_64(self.len);
+        if new_capacity < self.capacity {
+            let new_data = memory::reallocate(self.capacity, new_capacity, self.data)?;
+            self.data = new_data as *mut u8;
+            self.capacity = new_capacity;
+        }
+        Ok(())
+    }
+
+    /// Sets the `len` of this buffer to `new_len` without initializing the bytes
+    /// between `len` and `new_len`, for builders that write past `len` through
+    /// `raw_data` and only update the length once they are done.
+    pub(crate) fn set_len(&mut self, new_len: usize) {
+        assert!(new_len <= self.capacity);
+        self.len = new_len;
+    }
+
+    /// Appends all of `bytes` to this buffer, growing its capacity as needed.
+    ///
+    /// Unlike `write`, which fails if the buffer is not big enough, this always writes
+    /// the whole slice. Panics if the memory cannot be allocated.
+    pub fn extend_from_slice(&mut self, bytes: &[u8]) {
+        let new_len = self.len + bytes.len();
+        self.reserve(new_len).unwrap();
+        unsafe {
+            memory::memcpy(
+                self.data.offset(self.len as isize),
+                bytes.as_ptr(),
+                bytes.len(),
+            );
+        }
+        self.len = new_len;
+    }
+
+    /// Appends the bytes of all of `items` to this buffer, growing its capacity as
+    /// needed, like `extend_from_slice`.
+    pub fn extend_from_typed_slice<T: ArrowNativeType>(&mut self, items: &[T]) {
+        self.extend_from_slice(items.to_byte_slice())
+    }
+
     /// Returns whether this buffer is empty or not.
     pub fn is_empty(&self) -> bool {
         self.len == 0
@@ -414,6 +636,8 @@ impl MutableBuffer {
         let buffer_data = BufferData {
             ptr: self.data,
             len: self.len,
+            capacity: self.capacity,
+            owner: None,
         };
         ::std::mem::forget(self);
         Buffer {
@@ -466,6 +690,9 @@ mod tests {
     use std::thread;
 
     use super::*;
+    use crate::array::Int32Array;
+    use crate::array_data::ArrayData;
+    use crate::datatypes::{DataType, ToByteSlice};
 
     #[test]
     fn test_buffer_data_equality() {
@@ -498,6 +725,8 @@ mod tests {
         let buf = Buffer::from(&[0, 1, 2, 3, 4]);
         assert_eq!(5, buf.len());
         assert!(!buf.raw_data().is_null());
+        assert_eq!(64, buf.capacity());
+        assert_eq!(64, buf.slice(2).capacity());
         assert_eq!(&[0, 1, 2, 3, 4], buf.data());
     }
 
@@ -518,6 +747,58 @@ mod tests {
         assert_eq!(&[0, 1, 2, 3, 4], buf2.data());
     }
 
+    #[test]
+    fn test_from_foreign() {
+        let owner = Arc::new(vec![1u8, 2, 3, 4, 5]);
+        let buf =
+            unsafe { Buffer::from_foreign(owner.as_ptr(), owner.len(), owner.clone()) };
+        assert_eq!(&[1, 2, 3, 4, 5], buf.data());
+        assert_eq!(owner.as_ptr(), buf.raw_data());
+        assert_eq!(5, buf.capacity());
+        assert_eq!(2, Arc::strong_count(&owner));
+
+        // slices and clones keep the owner alive
+        let sliced = buf.slice(3);
+        let cloned = buf.clone();
+        drop(buf);
+        assert_eq!(&[4, 5], sliced.data());
+        assert_eq!(&[1, 2, 3, 4, 5], cloned.data());
+        assert_eq!(2, Arc::strong_count(&owner));
+
+        drop(sliced);
+        drop(cloned);
+        assert_eq!(1, Arc::strong_count(&owner));
+        assert_eq!(&[1, 2, 3, 4, 5], &owner[..]);
+    }
+
+    #[test]
+    fn test_from_foreign_empty() {
+        let buf = unsafe { Buffer::from_foreign(std::ptr::null(), 0, Arc::new(())) };
+        assert!(buf.is_empty());
+        assert_eq!(&[] as &[u8], buf.data());
+        assert_eq!(buf, Buffer::from(&[]));
+    }
+
+    #[test]
+    fn test_from_foreign_array() {
+        let values: Vec<i32> = vec![7, 8, 9];
+        let owner = Arc::new(values);
+        let buf = unsafe {
+            Buffer::from_foreign(
+                owner.as_ptr() as *const u8,
+                owner.len() * mem::size_of::<i32>(),
+                owner.clone(),
+            )
+        };
+        let data = ArrayData::builder(DataType::Int32)
+            .len(3)
+            .add_buffer(buf)
+            .build();
+        let array = Int32Array::from(data);
+        assert_eq!(&[7, 8, 9], array.value_slice(0, 3));
+        assert_eq!(owner.as_ptr(), array.raw_values());
+    }
+
     #[test]
     fn test_slice() {
         let buf = Buffer::from(&[2, 4, 6, 8, 10]);
@@ -548,15 +829,53 @@ mod tests {
         buf.slice(6);
     }
 
+    #[test]
+    fn test_bit_slice() {
+        let buf = Buffer::from([0b10110100, 0b01101011, 0b00000001]);
+
+        // byte aligned offsets share the memory of the buffer
+        let aligned = buf.bit_slice(8);
+        assert_eq!(&[0b01101011, 0b00000001], aligned.data());
+        assert_eq!(unsafe { buf.raw_data().offset(1) }, aligned.raw_data());
+        assert_eq!(buf.data(), buf.bit_slice(0).data());
+
+        let unaligned = buf.bit_slice(3);
+        assert_eq!(&[0b01110110, 0b00101101, 0b00000000], unaligned.data());
+        for i in 0..21 {
+            assert_eq!(
+                bit_util::get_bit(buf.data(), i + 3),
+                bit_util::get_bit(unaligned.data(), i)
+            );
+        }
+
+        let unaligned = buf.bit_slice(13);
+        assert_eq!(&[0b00001011, 0b00000000], unaligned.data());
+
+        // slicing a sliced buffer
+        let unaligned = buf.slice(1).bit_slice(1);
+        assert_eq!(&[0b10110101, 0b00000000], unaligned.data());
+
+        assert!(buf.bit_slice(24).is_empty());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "the bit offset 25 of the new Buffer cannot exceed the 24 bits of the existing one"
+    )]
+    fn test_bit_slice_offset_out_of_bound() {
+        let buf = Buffer::from([1, 2, 3]);
+        buf.bit_slice(25);
+    }
+
     #[test]
     fn test_with_bitset() {
         let mut_buf = MutableBuffer::new(64).with_bitset(64, false);
         let buf = mut_buf.freeze();
-        assert_eq!(0, bit_util::count_set_bits(buf.data()));
+        assert_eq!(0, bit_util::count_set_bits(buf.data(), 0, buf.len() * 8));
 
         let mut_buf = MutableBuffer::new(64).with_bitset(64, true);
         let buf = mut_buf.freeze();
-        assert_eq!(512, bit_util::count_set_bits(buf.data()));
+        assert_eq!(512, bit_util::count_set_bits(buf.data(), 0, buf.len() * 8));
     }
 
     #[test]
@@ -564,12 +883,12 @@ mod tests {
         let mut mut_buf = MutableBuffer::new(64).with_bitset(64, true);
         mut_buf.set_null_bits(0, 64);
         let buf = mut_buf.freeze();
-        assert_eq!(0, bit_util::count_set_bits(buf.data()));
+        assert_eq!(0, bit_util::count_set_bits(buf.data(), 0, buf.len() * 8));
 
         let mut mut_buf = MutableBuffer::new(64).with_bitset(64, true);
         mut_buf.set_null_bits(32, 32);
         let buf = mut_buf.freeze();
-        assert_eq!(256, bit_util::count_set_bits(buf.data()));
+        assert_eq!(256, bit_util::count_set_bits(buf.data(), 0, buf.len() * 8));
     }
 
     #[test]
@@ -600,6 +919,28 @@ mod tests {
         let _buf3 = (&buf1 | &buf2).unwrap();
     }
 
+    #[test]
+    fn test_bit_equals() {
+        let buf1 = Buffer::from([0b1010_1010, 0b0000_0101]);
+        let buf2 = Buffer::from([0b1010_1010, 0b1111_1101]);
+        // the buffers differ in padding, but are still unequal as a whole
+        assert_ne!(buf1, buf2);
+        assert!(buf1.bit_equals(&buf2, 11));
+        assert!(buf1.bit_equals(&buf2, 8));
+        assert!(!buf1.bit_equals(&buf2, 12));
+        assert!(buf1.bit_equals(&buf2, 0));
+
+        let buf3 = Buffer::from([0b1010_1011]);
+        assert!(!buf1.bit_equals(&buf3, 1));
+        assert!(buf1.slice(1).bit_equals(&Buffer::from([0b0010_0101]), 4));
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot compare 9 bits of buffers of 2 and 1 bytes")]
+    fn test_bit_equals_out_of_bounds() {
+        Buffer::from([0, 0]).bit_equals(&Buffer::from([0]), 9);
+    }
+
     #[test]
     fn test_mutable_new() {
         let buf = MutableBuffer::new(63);
@@ -626,6 +967,35 @@ mod tests {
         assert_eq!("hello arrow".as_bytes(), buf.data());
     }
 
+    #[test]
+    fn test_mutable_extend_from_slice() {
+        let mut buf = MutableBuffer::new(4);
+        buf.extend_from_slice("hello".as_bytes());
+        assert_eq!(5, buf.len());
+        assert_eq!(64, buf.capacity());
+
+        // grows the buffer instead of failing like `write`
+        let long = vec![b'!'; 100];
+        assert!(buf.write(&long).is_err());
+        buf.extend_from_slice(&long);
+        assert_eq!(105, buf.len());
+        assert_eq!(128, buf.capacity());
+        assert_eq!("hello".as_bytes(), &buf.data()[..5]);
+        assert_eq!(&long[..], &buf.data()[5..]);
+
+        buf.extend_from_slice(&[]);
+        assert_eq!(105, buf.len());
+    }
+
+    #[test]
+    fn test_mutable_extend_from_typed_slice() {
+        let mut buf = MutableBuffer::new(0);
+        buf.extend_from_typed_slice(&[1i32, -2]);
+        buf.extend_from_typed_slice(&[3i32]);
+        assert_eq!(12, buf.len());
+        assert_eq!(&[1, -2, 3], buf.freeze().typed_data::<i32>());
+    }
+
     #[test]
     #[should_panic(expected = "Buffer not big enough")]
     fn test_mutable_write_overflow() {
@@ -651,6 +1021,42 @@ mod tests {
         assert_eq!(128, buf.capacity());
     }
 
+    #[test]
+    fn test_typed_data() {
+        let buf = Buffer::from(&[1i32, -2, 3].to_byte_slice());
+        assert_eq!(&[1, -2, 3], buf.typed_data::<i32>());
+        assert_eq!(1, buf.typed_data::<i64>().len());
+        assert_eq!(12, buf.typed_data::<u8>().len());
+        assert_eq!(&[-2, 3], buf.slice(4).typed_data::<i32>());
+        assert!(Buffer::from(&[]).typed_data::<f64>().is_empty());
+    }
+
+    #[test]
+    fn test_from_iter() {
+        let buf: Buffer = (0..100i64).map(|i| i * 3).collect();
+        assert_eq!(800, buf.len());
+        assert!(memory::is_aligned(buf.raw_data(), mem::align_of::<i64>()));
+        assert_eq!(
+            (0..100i64).map(|i| i * 3).collect::<Vec<_>>(),
+            buf.typed_data::<i64>()
+        );
+
+        // iterators without size hint grow the buffer as needed
+        let buf: Buffer = (0..1000u16).filter(|i| i % 2 == 0).collect();
+        assert_eq!(500, buf.typed_data::<u16>().len());
+        assert_eq!(998, buf.typed_data::<u16>()[499]);
+
+        let buf: Buffer = Vec::<f32>::new().into_iter().collect();
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Buffer data is not aligned to i32")]
+    fn test_typed_data_unaligned() {
+        let buf = Buffer::from(&[1i32, -2, 3].to_byte_slice());
+        buf.slice(2).typed_data::<i32>();
+    }
+
     #[test]
     fn test_mutable_resize() {
         let mut buf = MutableBuffer::new(1);
@@ -678,6 +1084,37 @@ mod tests {
         assert_eq!(0, buf.len());
     }
 
+    #[test]
+    fn test_mutable_resize_zero_fills() {
+        let mut buf = MutableBuffer::new(1);
+        buf.write(&[1, 2, 3, 4]).expect("write should be OK");
+        buf.resize(2).expect("resize should be OK");
+        buf.resize(6).expect("resize should be OK");
+        assert_eq!(&[1, 2, 0, 0, 0, 0], buf.data());
+
+        buf.resize(100).expect("resize should be OK");
+        assert!(buf.data()[2..].iter().all(|b| *b == 0));
+    }
+
+    #[test]
+    fn test_mutable_shrink_to_fit() {
+        let mut buf = MutableBuffer::new(1000);
+        buf.write(&[1, 2, 3]).expect("write should be OK");
+        assert_eq!(1024, buf.capacity());
+
+        buf.shrink_to_fit().expect("shrink_to_fit should be OK");
+        assert_eq!(64, buf.capacity());
+        assert_eq!(&[1, 2, 3], buf.data());
+
+        let immutable_buf = buf.freeze();
+        assert_eq!(64, immutable_buf.capacity());
+        assert_eq!(&[1, 2, 3], immutable_buf.data());
+
+        let mut buf = MutableBuffer::new(0);
+        buf.shrink_to_fit().expect("shrink_to_fit should be OK");
+        assert_eq!(0, buf.capacity());
+    }
+
     #[test]
     fn test_mutable_freeze() {
         let mut buf = MutableBuffer::new(1);
@@ -689,6 +1126,7 @@ mod tests {
         let immutable_buf = buf.freeze();
         assert_eq!(19, immutable_buf.len());
         assert_eq!("aaaa bbbb cccc dddd".as_bytes(), immutable_buf.data());
+        assert_eq!(64, immutable_buf.capacity());
     }
 
     #[test]
@@ -19,6 +19,7 @@
 //! internal buffer in an `ArrayData` object.
 
 use std::any::Any;
+use std::collections::HashMap;
 use std::io::Write;
 use std::marker::PhantomData;
 use std::mem;
@@ -72,6 +73,7 @@ pub trait BufferBuilderTrait<T: ArrowPrimitiveType> {
     fn reserve(&mut self, n: usize) -> Result<()>;
     fn append(&mut self, v: T::Native) -> Result<()>;
     fn append_slice(&mut self, slice: &[T::Native]) -> Result<()>;
+    fn append_n(&mut self, n: usize, v: T::Native) -> Result<()>;
     fn finish(&mut self) -> Buffer;
 }
 
@@ -126,6 +128,16 @@ impl<T: ArrowPrimitiveType> BufferBuilderTrait<T> for BufferBuilder<T> {
         self.write_bytes(slice.to_byte_slice(), array_slots)
     }
 
+    /// Appends `n` times the value `v`, growing the internal buffer at most once.
+    default fn append_n(&mut self, n: usize, v: T::Native) -> Result<()> {
+        self.reserve(n)?;
+        let bytes = v.to_byte_slice();
+        for _ in 0..n {
+            self.write_bytes(bytes, 1)?;
+        }
+        Ok(())
+    }
+
     /// Reset this builder and returns an immutable `Buffer`.
     default fn finish(&mut self) -> Buffer {
         let buf = ::std::mem::replace(&mut self.buffer, MutableBuffer::new(0));
@@ -169,9 +181,10 @@ impl BufferBuilderTrait<BooleanType> for BufferBuilder<BooleanType> {
 
     // Advances the `len` of the underlying `Buffer` by `i` slots of type T
     fn advance(&mut self, i: usize) -> Result<()> {
-        let new_buffer_len = bit_util::ceil(self.len + i, 8);
-        self.buffer.resize(new_buffer_len)?;
+        // the reserved bits are zeroed, so the new slots are unset
+        self.reserve(i)?;
         self.len += i;
+        self.buffer.set_len(bit_util::ceil(self.len, 8));
         Ok(())
     }
 
@@ -198,6 +211,18 @@ impl BufferBuilderTrait<BooleanType> for BufferBuilder<BooleanType> {
         Ok(())
     }
 
+    /// Appends `n` times the value `v`, setting whole bytes of bits at once.
+    fn append_n(&mut self, n: usize, v: bool) -> Result<()> {
+        // the reserved bits are zeroed, so only set bits need to be written
+        self.reserve(n)?;
+        if v {
+            self.buffer.set_len(bit_util::ceil(self.len + n, 8));
+            bit_util::set_bits(self.buffer.data_mut(), self.len, n);
+        }
+        self.len += n;
+        Ok(())
+    }
+
     /// Reserves memory for `n` elements of type `T`.
     fn reserve(&mut self, n: usize) -> Result<()> {
         let new_capacity = self.len + n;
@@ -218,12 +243,45 @@ impl BufferBuilderTrait<BooleanType> for BufferBuilder<BooleanType> {
         debug_assert!(new_buffer_len >= self.buffer.len());
         let mut buf = ::std::mem::replace(&mut self.buffer, MutableBuffer::new(0));
         self.len = 0;
-        buf.resize(new_buffer_len).unwrap();
+        buf.set_len(new_buffer_len);
         buf.freeze()
     }
 }
 
 /// Trait for dealing with different array builders at runtime
+///
+/// This allows builders of different types to be stored together, for example one
+/// builder per column of a table. The concrete builder can be retrieved with
+/// `as_any_mut` and `downcast_mut` in order to append values to it:
+///
+/// ```
+/// use arrow::array::{Array, BinaryArray, Int32Array};
+/// use arrow::builder::{ArrayBuilder, BinaryBuilder, Int32Builder};
+///
+/// let mut builders: Vec<Box<dyn ArrayBuilder>> = vec![
+///     Box::new(Int32Builder::new(2)),
+///     Box::new(BinaryBuilder::new(8)),
+/// ];
+///
+/// builders[0]
+///     .as_any_mut()
+///     .downcast_mut::<Int32Builder>()
+///     .unwrap()
+///     .append_value(42)
+///     .unwrap();
+/// builders[1]
+///     .as_any_mut()
+///     .downcast_mut::<BinaryBuilder>()
+///     .unwrap()
+///     .append_string("arrow")
+///     .unwrap();
+///
+/// let arrays: Vec<_> = builders.iter_mut().map(|b| b.finish()).collect();
+/// let ints = arrays[0].as_any().downcast_ref::<Int32Array>().unwrap();
+/// assert_eq!(42, ints.value(0));
+/// let strings = arrays[1].as_any().downcast_ref::<BinaryArray>().unwrap();
+/// assert_eq!("arrow", strings.get_string(0));
+/// ```
 pub trait ArrayBuilder: Any {
     /// Returns the number of array slots in the builder
     fn len(&self) -> usize;
@@ -319,6 +377,14 @@ impl<T: ArrowPrimitiveType> PrimitiveBuilder<T> {
         self.values_builder.capacity()
     }
 
+    /// Reserves memory for at least `additional` more slots, so that appending them
+    /// grows the underlying buffers at most once
+    pub fn reserve(&mut self, additional: usize) -> Result<()> {
+        self.values_builder.reserve(additional)?;
+        self.bitmap_builder.reserve(additional)?;
+        Ok(())
+    }
+
     /// Appends a value of type `T` into the builder
     pub fn append_value(&mut self, v: T::Native) -> Result<()> {
         self.bitmap_builder.append(true)?;
@@ -333,6 +399,21 @@ impl<T: ArrowPrimitiveType> PrimitiveBuilder<T> {
         Ok(())
     }
 
+    /// Appends `n` times the value `v` into the builder, growing the underlying buffers
+    /// at most once
+    pub fn append_n(&mut self, n: usize, v: T::Native) -> Result<()> {
+        self.bitmap_builder.append_n(n, true)?;
+        self.values_builder.append_n(n, v)?;
+        Ok(())
+    }
+
+    /// Appends `n` null slots into the builder
+    pub fn append_n_nulls(&mut self, n: usize) -> Result<()> {
+        self.bitmap_builder.append_n(n, false)?;
+        self.values_builder.advance(n)?;
+        Ok(())
+    }
+
     /// Appends an `Option<T>` into the builder
     pub fn append_option(&mut self, v: Option<T::Native>) -> Result<()> {
         match v {
@@ -344,16 +425,37 @@ impl<T: ArrowPrimitiveType> PrimitiveBuilder<T> {
 
     /// Appends a slice of type `T` into the builder
     pub fn append_slice(&mut self, v: &[T::Native]) -> Result<()> {
-        self.bitmap_builder.append_slice(&vec![true; v.len()][..])?;
+        self.bitmap_builder.append_n(v.len(), true)?;
         self.values_builder.append_slice(v)?;
         Ok(())
     }
 
+    /// Appends `values` into the builder, with the slots where `is_valid` is false
+    /// being null.
+    ///
+    /// Returns an error if `values` and `is_valid` have different lengths.
+    pub fn append_values(
+        &mut self,
+        values: &[T::Native],
+        is_valid: &[bool],
+    ) -> Result<()> {
+        if values.len() != is_valid.len() {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "Value and validity lengths must be equal, got {} and {}",
+                values.len(),
+                is_valid.len()
+            )));
+        }
+        self.bitmap_builder.append_slice(is_valid)?;
+        self.values_builder.append_slice(values)?;
+        Ok(())
+    }
+
     /// Builds the `PrimitiveArray` and reset this builder.
     pub fn finish(&mut self) -> PrimitiveArray<T> {
         let len = self.len();
         let null_bit_buffer = self.bitmap_builder.finish();
-        let null_count = len - bit_util::count_set_bits(null_bit_buffer.data());
+        let null_count = len - bit_util::count_set_bits(null_bit_buffer.data(), 0, len);
         let mut builder = ArrayData::builder(T::get_data_type())
             .len(len)
             .add_buffer(self.values_builder.finish());
@@ -367,6 +469,27 @@ impl<T: ArrowPrimitiveType> PrimitiveBuilder<T> {
     }
 }
 
+impl<T: ArrowNumericType> PrimitiveBuilder<T> {
+    /// Creates a builder holding a copy of the values and validity of `array`, so that
+    /// more values can be appended to them.
+    ///
+    /// The buffers of `array` are copied rather than reused: they are immutable and may
+    /// be shared with other arrays, slices of `array` or record batches, so handing them
+    /// over to a builder that writes into them would be unsound.
+    pub fn from_array(array: &PrimitiveArray<T>) -> Result<Self> {
+        let len = array.len();
+        let mut builder = Self::new(len);
+        let values = array.value_slice(0, len);
+        if array.null_count() == 0 {
+            builder.append_slice(values)?;
+        } else {
+            let is_valid: Vec<bool> = (0..len).map(|i| array.is_valid(i)).collect();
+            builder.append_values(values, &is_valid)?;
+        }
+        Ok(builder)
+    }
+}
+
 ///  Array builder for `ListArray`
 pub struct ListBuilder<T: ArrayBuilder> {
     offsets_builder: Int32BufferBuilder,
@@ -431,6 +554,20 @@ where
         &mut self.values_builder
     }
 
+    /// Returns the capacity of this builder measured in list slots
+    pub fn capacity(&self) -> usize {
+        // the offsets have one more slot than the lists
+        self.offsets_builder.capacity() - 1
+    }
+
+    /// Reserves memory for at least `additional` more list slots. The values builder
+    /// is left as is.
+    pub fn reserve(&mut self, additional: usize) -> Result<()> {
+        self.offsets_builder.reserve(additional)?;
+        self.bitmap_builder.reserve(additional)?;
+        Ok(())
+    }
+
     /// Finish the current variable-length list array slot
     pub fn append(&mut self, is_valid: bool) -> Result<()> {
         self.offsets_builder
@@ -454,17 +591,20 @@ where
 
         let offset_buffer = self.offsets_builder.finish();
         let null_bit_buffer = self.bitmap_builder.finish();
+        let null_count = len - bit_util::count_set_bits(null_bit_buffer.data(), 0, len);
         self.offsets_builder.append(0).unwrap();
-        let data =
+        let mut builder =
             ArrayData::builder(DataType::List(Box::new(values_data.data_type().clone())))
                 .len(len)
-                .null_count(len - bit_util::count_set_bits(null_bit_buffer.data()))
                 .add_buffer(offset_buffer)
-                .add_child_data(values_data)
-                .null_bit_buffer(null_bit_buffer)
-                .build();
+                .add_child_data(values_data);
+        if null_count > 0 {
+            builder = builder
+                .null_count(null_count)
+                .null_bit_buffer(null_bit_buffer);
+        }
 
-        ListArray::from(data)
+        ListArray::from(builder.build())
     }
 }
 
@@ -519,14 +659,22 @@ impl BinaryBuilder {
         Ok(())
     }
 
+    /// Appends a byte slice into the builder.
+    ///
+    /// Automatically calls the `append` method to delimit the slice appended in as a
+    /// distinct array element. The bytes don't need to be valid UTF-8.
+    pub fn append_bytes(&mut self, value: &[u8]) -> Result<()> {
+        self.builder.values().append_slice(value)?;
+        self.builder.append(true)?;
+        Ok(())
+    }
+
     /// Appends a `&String` or `&str` into the builder.
     ///
     /// Automatically calls the `append` method to delimit the string appended in as a
     /// distinct array element.
     pub fn append_string(&mut self, value: &str) -> Result<()> {
-        self.builder.values().append_slice(value.as_bytes())?;
-        self.builder.append(true)?;
-        Ok(())
+        self.append_bytes(value.as_bytes())
     }
 
     /// Finish the current variable-length list array slot.
@@ -534,6 +682,14 @@ impl BinaryBuilder {
         self.builder.append(is_valid)
     }
 
+    /// Appends a byte slice or string, or a null slot if `value` is `None`
+    pub fn append_option<T: AsRef<[u8]>>(&mut self, value: Option<T>) -> Result<()> {
+        match value {
+            None => self.append_null(),
+            Some(v) => self.append_bytes(v.as_ref()),
+        }
+    }
+
     /// Append a null value to the array.
     pub fn append_null(&mut self) -> Result<()> {
         self.append(false)
@@ -545,6 +701,120 @@ impl BinaryBuilder {
     }
 }
 
+/// Array builder for strings that chooses the encoding of the array at `finish` time.
+///
+/// The builder keeps track of the number of distinct non-null values appended to it, and
+/// dictionary-encodes them as they are appended. If there are at most `max_distinct` of
+/// them, `finish` returns an `Int32DictionaryArray` of `Utf8` values, otherwise it returns
+/// a plain `BinaryArray`.
+pub struct AdaptiveStringBuilder {
+    builder: BinaryBuilder,
+    keys_builder: Int32Builder,
+    values_builder: BinaryBuilder,
+    dictionary: HashMap<Vec<u8>, i32>,
+    max_distinct: usize,
+}
+
+impl ArrayBuilder for AdaptiveStringBuilder {
+    /// Returns the builder as an non-mutable `Any` reference.
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    /// Returns the builder as an mutable `Any` reference.
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    /// Returns the boxed builder as a box of `Any`.
+    fn into_box_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+
+    /// Returns the number of array slots in the builder
+    fn len(&self) -> usize {
+        self.builder.len()
+    }
+
+    /// Builds the array and reset this builder.
+    fn finish(&mut self) -> ArrayRef {
+        self.finish()
+    }
+}
+
+impl AdaptiveStringBuilder {
+    /// Creates a new `AdaptiveStringBuilder`, `capacity` is the number of bytes in the
+    /// values array, and `max_distinct` the maximum number of distinct values for which
+    /// the array is dictionary-encoded.
+    pub fn new(capacity: usize, max_distinct: usize) -> Self {
+        Self {
+            builder: BinaryBuilder::new(capacity),
+            keys_builder: Int32Builder::new(capacity),
+            values_builder: BinaryBuilder::new(capacity),
+            dictionary: HashMap::new(),
+            max_distinct,
+        }
+    }
+
+    /// Whether the values appended so far are still dictionary-encoded, i.e. there are
+    /// at most `max_distinct` distinct values
+    fn is_dictionary(&self) -> bool {
+        self.dictionary.len() <= self.max_distinct
+    }
+
+    /// Appends a `&String` or `&str` into the builder.
+    pub fn append_string(&mut self, value: &str) -> Result<()> {
+        // once the threshold is exceeded the array won't be dictionary-encoded, so stop
+        // encoding the values
+        if self.is_dictionary() {
+            let key = match self.dictionary.get(value.as_bytes()) {
+                Some(key) => *key,
+                None => {
+                    let key = self.dictionary.len() as i32;
+                    self.values_builder.append_string(value)?;
+                    self.dictionary.insert(value.as_bytes().to_vec(), key);
+                    key
+                }
+            };
+            self.keys_builder.append_value(key)?;
+        }
+        self.builder.append_string(value)
+    }
+
+    /// Append a null value to the array.
+    pub fn append_null(&mut self) -> Result<()> {
+        if self.is_dictionary() {
+            self.keys_builder.append_null()?;
+        }
+        self.builder.append_null()
+    }
+
+    /// Builds either a dictionary or a plain array depending on the number of distinct
+    /// values, and reset this builder.
+    pub fn finish(&mut self) -> ArrayRef {
+        let is_dictionary = self.is_dictionary();
+        let array = self.builder.finish();
+        let keys = self.keys_builder.finish();
+        let values = self.values_builder.finish();
+        self.dictionary.clear();
+        if !is_dictionary {
+            return Arc::new(array);
+        }
+        let mut builder = ArrayData::builder(DataType::Dictionary(
+            Box::new(DataType::Int32),
+            Box::new(DataType::Utf8),
+        ))
+        .len(keys.len())
+        .null_count(keys.null_count())
+        .add_buffer(keys.values())
+        .add_child_data(values.data());
+        if let Some(bitmap) = keys.data().null_bitmap() {
+            builder = builder.null_bit_buffer(bitmap.bits.clone());
+        }
+        Arc::new(Int32DictionaryArray::from(builder.build()))
+    }
+}
+
 /// Array builder for Struct types.
 ///
 /// Note that callers should make sure that methods of all the child field builders are
@@ -597,6 +867,8 @@ impl ArrayBuilder for StructBuilder {
 }
 
 impl StructBuilder {
+    /// Creates a new `StructBuilder` for a struct with the given `fields`, using one
+    /// child builder per field.
     pub fn new(fields: Vec<Field>, builders: Vec<Box<ArrayBuilder>>) -> Self {
         let mut field_anys = Vec::with_capacity(builders.len());
         let mut field_builders = Vec::with_capacity(builders.len());
@@ -622,6 +894,8 @@ impl StructBuilder {
         }
     }
 
+    /// Creates a new `StructBuilder` with a child builder of the matching type for each
+    /// field of `schema`.
     pub fn from_schema(schema: Schema, capacity: usize) -> Self {
         let fields = schema.fields();
         let mut builders = Vec::with_capacity(fields.len());
@@ -679,17 +953,50 @@ impl StructBuilder {
     }
 
     /// Builds the `StructArray` and reset this builder.
+    ///
+    /// If `append` was never called, the struct has one valid element per element of
+    /// the child field builders.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the child field builders don't all have the same number of elements, or
+    /// if `append` was called and that number differs from the number of `append` calls.
     pub fn finish(&mut self) -> StructArray {
+        let mut len = self.len;
+        if let Some((first, rest)) = self.field_builders.split_first() {
+            for (i, f) in rest.iter().enumerate() {
+                assert_eq!(
+                    first.len(),
+                    f.len(),
+                    "StructBuilder field builder {} has a different length than field builder 0",
+                    i + 1
+                );
+            }
+            if len == 0 {
+                len = first.len();
+            }
+            assert_eq!(
+                first.len(),
+                len,
+                "StructBuilder field builders have {} elements, but {} elements were appended",
+                first.len(),
+                len
+            );
+        }
+
         let mut child_data = Vec::with_capacity(self.field_builders.len());
         for f in &mut self.field_builders {
             let arr = f.finish();
             child_data.push(arr.data());
         }
 
+        let appended = self.len;
+        self.len = 0;
         let null_bit_buffer = self.bitmap_builder.finish();
-        let null_count = self.len - bit_util::count_set_bits(null_bit_buffer.data());
+        let null_count =
+            appended - bit_util::count_set_bits(null_bit_buffer.data(), 0, appended);
         let mut builder = ArrayData::builder(DataType::Struct(self.fields.clone()))
-            .len(self.len)
+            .len(len)
             .child_data(child_data);
         if null_count > 0 {
             builder = builder
@@ -989,6 +1296,86 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_primitive_array_builder_append_values() {
+        let mut builder = Int32Builder::new(5);
+        builder.append_value(0).unwrap();
+        builder
+            .append_values(&[1, 2, 3, 4], &[true, false, true, false])
+            .unwrap();
+        assert!(builder.append_values(&[5, 6], &[true]).is_err());
+
+        let array = builder.finish();
+        assert_eq!(5, array.len());
+        assert_eq!(2, array.null_count());
+        assert_eq!(&[0, 1, 2, 3, 4], array.value_slice(0, 5));
+        for (i, is_null) in [false, false, true, false, true].iter().enumerate() {
+            assert_eq!(*is_null, array.is_null(i));
+        }
+    }
+
+    #[test]
+    fn test_primitive_array_builder_append_n() {
+        let mut builder = Int32Builder::new(2);
+        builder.append_value(7).unwrap();
+        builder.append_n(1000, 3).unwrap();
+        builder.append_n_nulls(21).unwrap();
+        builder.append_n(0, 5).unwrap();
+        builder.append_n(2, 5).unwrap();
+
+        let array = builder.finish();
+        assert_eq!(1024, array.len());
+        assert_eq!(21, array.null_count());
+        assert_eq!(7, array.value(0));
+        assert!((1..1001).all(|i| array.is_valid(i) && array.value(i) == 3));
+        assert!((1001..1022).all(|i| array.is_null(i)));
+        assert_eq!(&[5, 5], array.value_slice(1022, 2));
+    }
+
+    #[test]
+    fn test_boolean_buffer_builder_append_n() {
+        // runs starting and ending at every bit offset of a byte
+        for start in 0..10 {
+            for n in 0..20 {
+                let mut builder = BooleanBufferBuilder::new(0);
+                builder.append_n(start, false).unwrap();
+                builder.append_n(n, true).unwrap();
+                builder.append(false).unwrap();
+                let buffer = builder.finish();
+                for i in 0..start + n + 1 {
+                    let expected = i >= start && i < start + n;
+                    assert_eq!(expected, bit_util::get_bit(buffer.data(), i));
+                }
+            }
+        }
+
+        let mut builder = BooleanBuilder::new(0);
+        builder.append_n(3, true).unwrap();
+        builder.append_n_nulls(2).unwrap();
+        builder.append_n(4, false).unwrap();
+        let array = builder.finish();
+        assert_eq!(9, array.len());
+        assert_eq!(2, array.null_count());
+        assert!(array.value(2) && array.is_null(3) && !array.value(8));
+    }
+
+    #[test]
+    fn test_primitive_array_builder_reserve() {
+        let mut builder = Int64Builder::new(4);
+        builder.append_value(1).unwrap();
+        builder.reserve(1000).unwrap();
+        assert!(builder.capacity() >= 1001);
+        let capacity = builder.capacity();
+        for i in 0..1000 {
+            builder.append_value(i).unwrap();
+        }
+        assert_eq!(capacity, builder.capacity());
+
+        let array = builder.finish();
+        assert_eq!(1001, array.len());
+        assert_eq!(999, array.value(1000));
+    }
+
     #[test]
     fn test_primitive_array_builder_append_slice() {
         let arr1 = Int32Array::from(vec![Some(0), Some(2), None, None, Some(4)]);
@@ -1012,6 +1399,38 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_primitive_builder_from_array() {
+        let array = Int32Array::from(vec![Some(1), None, Some(3), Some(4)]);
+        let mut builder = Int32Builder::from_array(&array).unwrap();
+        assert_eq!(4, builder.len());
+        builder.append_value(5).unwrap();
+        builder.append_null().unwrap();
+        let extended = builder.finish();
+        assert_eq!(6, extended.len());
+        assert_eq!(2, extended.null_count());
+        for (i, v) in [Some(1), None, Some(3), Some(4), Some(5), None]
+            .iter()
+            .enumerate()
+        {
+            match v {
+                Some(v) => assert_eq!(*v, extended.value(i)),
+                None => assert!(extended.is_null(i)),
+            }
+        }
+        // the array is left untouched
+        assert_eq!(4, array.len());
+        assert_eq!(&[1, 0, 3, 4], array.value_slice(0, 4));
+
+        // only the elements of a sliced array are copied
+        let sliced = Int32Array::from(array.data().slice(2, 2));
+        let mut builder = Int32Builder::from_array(&sliced).unwrap();
+        builder.append_value(6).unwrap();
+        let rebuilt = builder.finish();
+        assert_eq!(0, rebuilt.null_count());
+        assert_eq!(&[3, 4, 6], rebuilt.value_slice(0, 3));
+    }
+
     #[test]
     fn test_primitive_array_builder_finish() {
         let mut builder = Int32Builder::new(5);
@@ -1065,6 +1484,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_list_array_builder_reserve() {
+        let mut builder = ListBuilder::new(Int32Builder::new(0));
+        builder.reserve(100).unwrap();
+        assert!(builder.capacity() >= 100);
+        builder.append(true).unwrap();
+        builder.append(false).unwrap();
+        assert_eq!(2, builder.finish().len());
+    }
+
     #[test]
     fn test_list_array_builder_nulls() {
         let values_builder = Int32Builder::new(10);
@@ -1092,6 +1521,50 @@ mod tests {
         assert_eq!(3, list_array.value_length(2));
     }
 
+    #[test]
+    fn test_list_array_builder_empty_and_null_lists() {
+        let values_builder = Int32Builder::new(5);
+        let mut builder = ListBuilder::new(values_builder);
+
+        //  [[0, 1, 2], [], null, [3, 4]]
+        builder.values().append_slice(&[0, 1, 2]).unwrap();
+        builder.append(true).unwrap();
+        builder.append(true).unwrap();
+        builder.append(false).unwrap();
+        builder.values().append_slice(&[3, 4]).unwrap();
+        builder.append(true).unwrap();
+        let list_array = builder.finish();
+
+        assert_eq!(4, list_array.len());
+        assert_eq!(1, list_array.null_count());
+        assert_eq!(
+            Buffer::from(&[0, 3, 3, 3, 5].to_byte_slice()),
+            list_array.data().buffers()[0].clone()
+        );
+        assert!(list_array.is_valid(1));
+        assert_eq!(0, list_array.value_length(1));
+        assert!(list_array.is_null(2));
+        assert_eq!(0, list_array.value_length(2));
+        assert_eq!(
+            Buffer::from([0b00001011]),
+            list_array.data().null_bitmap().as_ref().unwrap().bits
+        );
+        assert_eq!(
+            Buffer::from(&[0, 1, 2, 3, 4].to_byte_slice()),
+            list_array.values().data().buffers()[0].clone()
+        );
+    }
+
+    #[test]
+    fn test_list_array_builder_no_nulls() {
+        let mut builder = ListBuilder::new(Int32Builder::new(2));
+        builder.values().append_value(1).unwrap();
+        builder.append(true).unwrap();
+        let list_array = builder.finish();
+        assert_eq!(0, list_array.null_count());
+        assert!(list_array.data().null_bitmap().is_none());
+    }
+
     #[test]
     fn test_list_array_builder_finish() {
         let values_builder = Int32Array::builder(5);
@@ -1168,6 +1641,53 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_adaptive_string_builder_low_cardinality() {
+        let mut builder = AdaptiveStringBuilder::new(64, 2);
+        for s in &["red", "blue", "red", "red"] {
+            builder.append_string(s).unwrap();
+        }
+        builder.append_null().unwrap();
+        assert_eq!(5, ArrayBuilder::len(&builder));
+
+        let array = builder.finish();
+        assert_eq!(
+            &DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)),
+            array.data_type()
+        );
+        assert_eq!(5, array.len());
+        assert_eq!(1, array.null_count());
+        let dict = array
+            .as_any()
+            .downcast_ref::<Int32DictionaryArray>()
+            .unwrap();
+        assert_eq!(2, dict.values().len());
+        assert_eq!("blue", array.value_to_string(1));
+        assert_eq!("red", array.value_to_string(3));
+    }
+
+    #[test]
+    fn test_adaptive_string_builder_high_cardinality() {
+        let mut builder = AdaptiveStringBuilder::new(64, 2);
+        for s in &["a", "b", "c", "a"] {
+            builder.append_string(s).unwrap();
+        }
+        let array = builder.finish();
+        assert_eq!(&DataType::Utf8, array.data_type());
+        let binary = array.as_any().downcast_ref::<BinaryArray>().unwrap();
+        assert_eq!(4, binary.len());
+        assert_eq!("c", binary.get_string(2));
+
+        // the builder is reset, so the next array is classified on its own values
+        builder.append_string("a").unwrap();
+        let array = builder.finish();
+        assert_eq!(1, array.len());
+        assert_eq!(
+            &DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)),
+            array.data_type()
+        );
+    }
+
     #[test]
     fn test_binary_array_builder() {
         let mut builder = BinaryBuilder::new(20);
@@ -1244,6 +1764,67 @@ mod tests {
         assert_eq!(5, binary_array.value_length(2));
     }
 
+    #[test]
+    fn test_binary_array_builder_append_option() {
+        let mut builder = BinaryBuilder::new(10);
+        builder.append_option(Some("ab")).unwrap();
+        builder.append_option(None::<&str>).unwrap();
+        builder.append_option(Some(&[0xffu8][..])).unwrap();
+
+        let binary_array = builder.finish();
+        assert_eq!(3, binary_array.len());
+        assert_eq!(1, binary_array.null_count());
+        assert_eq!("ab", binary_array.get_string(0));
+        assert!(binary_array.is_null(1));
+        assert_eq!(0, binary_array.value_length(1));
+        assert_eq!([0xff], binary_array.value(2));
+    }
+
+    #[test]
+    fn test_boolean_array_builder_append_option() {
+        let mut builder = BooleanBuilder::new(3);
+        builder.append_option(Some(true)).unwrap();
+        builder.append_option(None).unwrap();
+        builder.append_option(Some(false)).unwrap();
+
+        let array = builder.finish();
+        assert_eq!(1, array.null_count());
+        assert!(array.value(0));
+        assert!(array.is_null(1));
+        assert!(!array.value(2));
+    }
+
+    #[test]
+    fn test_binary_array_builder_append_bytes() {
+        let mut builder = BinaryBuilder::new(20);
+
+        builder.append_bytes(&[0xff, 0x00, 0xfe]).unwrap();
+        builder.append_null().unwrap();
+        builder.append_string("").unwrap();
+        builder.append_null().unwrap();
+        builder.append_bytes(b"arrow").unwrap();
+
+        let binary_array = builder.finish();
+
+        assert_eq!(5, binary_array.len());
+        assert_eq!(2, binary_array.null_count());
+        assert_eq!(
+            Buffer::from(&[0, 3, 3, 3, 3, 8].to_byte_slice()),
+            binary_array.data().buffers()[0].clone()
+        );
+        assert_eq!([0xff, 0x00, 0xfe], binary_array.value(0));
+        assert!(binary_array.is_null(1));
+        assert_eq!(0, binary_array.value_length(1));
+        assert!(binary_array.is_valid(2));
+        assert_eq!("", binary_array.get_string(2));
+        assert!(binary_array.is_null(3));
+        assert_eq!("arrow", binary_array.get_string(4));
+        assert_eq!(
+            Buffer::from([0b00010101]),
+            binary_array.data().null_bitmap().as_ref().unwrap().bits
+        );
+    }
+
     #[test]
     fn test_struct_array_builder() {
         let string_builder = BinaryBuilder::new(4);
@@ -1375,6 +1956,160 @@ mod tests {
         assert_eq!(0, builder.len());
     }
 
+    #[test]
+    fn test_struct_array_builder_boolean_int64() {
+        let fields = vec![
+            Field::new("a", DataType::Boolean, true),
+            Field::new("b", DataType::Int64, false),
+        ];
+        let field_builders = vec![
+            Box::new(BooleanBuilder::new(3)) as Box<dyn ArrayBuilder>,
+            Box::new(Int64Builder::new(3)) as Box<dyn ArrayBuilder>,
+        ];
+        let mut builder = StructBuilder::new(fields.clone(), field_builders);
+
+        //  [{a: true, b: 1}, null, {a: null, b: 3}]
+        for (a, b, is_valid) in vec![
+            (Some(true), 1, true),
+            (Some(false), 2, false),
+            (None, 3, true),
+        ] {
+            builder
+                .field_builder::<BooleanBuilder>(0)
+                .unwrap()
+                .append_option(a)
+                .unwrap();
+            builder
+                .field_builder::<Int64Builder>(1)
+                .unwrap()
+                .append_value(b)
+                .unwrap();
+            builder.append(is_valid).unwrap();
+        }
+        assert_eq!(3, builder.len());
+
+        let arr = builder.finish();
+        assert_eq!(0, builder.len());
+        assert_eq!(&DataType::Struct(fields), arr.data_type());
+        assert_eq!(3, arr.len());
+        assert_eq!(1, arr.null_count());
+        assert!(arr.is_null(1));
+
+        let a = arr
+            .column(0)
+            .as_any()
+            .downcast_ref::<BooleanArray>()
+            .unwrap();
+        assert_eq!(true, a.value(0));
+        assert!(a.is_null(2));
+        let b = arr.column(1).as_any().downcast_ref::<Int64Array>().unwrap();
+        assert_eq!(&[1, 2, 3], b.value_slice(0, 3));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "StructBuilder field builder 1 has a different length than field builder 0"
+    )]
+    fn test_struct_array_builder_unequal_field_builders_lengths() {
+        let fields = vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Boolean, false),
+        ];
+        let field_builders = vec![
+            Box::new(Int32Builder::new(2)) as Box<dyn ArrayBuilder>,
+            Box::new(BooleanBuilder::new(2)) as Box<dyn ArrayBuilder>,
+        ];
+        let mut builder = StructBuilder::new(fields, field_builders);
+        builder
+            .field_builder::<Int32Builder>(0)
+            .unwrap()
+            .append_slice(&[1, 2])
+            .unwrap();
+        builder
+            .field_builder::<BooleanBuilder>(1)
+            .unwrap()
+            .append_value(true)
+            .unwrap();
+        builder.append(true).unwrap();
+        builder.append(true).unwrap();
+
+        builder.finish();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "StructBuilder field builders have 2 elements, but 1 elements were appended"
+    )]
+    fn test_struct_array_builder_field_builders_longer_than_appended() {
+        let fields = vec![Field::new("a", DataType::Int32, false)];
+        let field_builders =
+            vec![Box::new(Int32Builder::new(2)) as Box<dyn ArrayBuilder>];
+        let mut builder = StructBuilder::new(fields, field_builders);
+        builder
+            .field_builder::<Int32Builder>(0)
+            .unwrap()
+            .append_slice(&[1, 2])
+            .unwrap();
+        builder.append(true).unwrap();
+
+        builder.finish();
+    }
+
+    #[test]
+    fn test_array_builder_trait_objects() {
+        let mut builders: Vec<Box<dyn ArrayBuilder>> = vec![
+            Box::new(BooleanBuilder::new(2)),
+            Box::new(Float64Builder::new(2)),
+            Box::new(ListBuilder::new(Int32Builder::new(2))),
+        ];
+        for b in builders.iter_mut() {
+            assert_eq!(0, b.len());
+        }
+
+        builders[0]
+            .as_any_mut()
+            .downcast_mut::<BooleanBuilder>()
+            .unwrap()
+            .append_slice(&[true, false])
+            .unwrap();
+        builders[1]
+            .as_any_mut()
+            .downcast_mut::<Float64Builder>()
+            .unwrap()
+            .append_null()
+            .unwrap();
+        {
+            let list_builder = builders[2]
+                .as_any_mut()
+                .downcast_mut::<ListBuilder<Int32Builder>>()
+                .unwrap();
+            list_builder.values().append_value(1).unwrap();
+            list_builder.append(true).unwrap();
+        }
+        assert!(builders[1]
+            .as_any()
+            .downcast_ref::<Int32Builder>()
+            .is_none());
+        assert_eq!(
+            vec![2, 1, 1],
+            builders.iter().map(|b| b.len()).collect::<Vec<_>>()
+        );
+
+        let arrays: Vec<ArrayRef> = builders.iter_mut().map(|b| b.finish()).collect();
+        assert_eq!(&DataType::Boolean, arrays[0].data_type());
+        assert_eq!(2, arrays[0].len());
+        assert_eq!(&DataType::Float64, arrays[1].data_type());
+        assert_eq!(1, arrays[1].null_count());
+        assert_eq!(
+            &DataType::List(Box::new(DataType::Int32)),
+            arrays[2].data_type()
+        );
+        assert!(builders.iter().all(|b| b.len() == 0));
+
+        let boxed = builders.remove(0);
+        assert!(boxed.into_box_any().downcast::<BooleanBuilder>().is_ok());
+    }
+
     #[test]
     fn test_struct_array_builder_from_schema() {
         let mut fields = Vec::new();
@@ -1416,5 +2151,4 @@ mod tests {
         let mut builder = StructBuilder::new(fields, field_builders);
         assert!(builder.field_builder::<BinaryBuilder>(0).is_none());
     }
-
 }
@@ -210,6 +210,227 @@ where
     })
 }
 
+/// Helper function to apply `op` to every non-null value of `array`, with null values
+/// staying null.
+fn scalar_op<T, F>(array: &PrimitiveArray<T>, op: F) -> Result<PrimitiveArray<T>>
+where
+    T: datatypes::ArrowNumericType,
+    F: Fn(T::Native) -> T::Native,
+{
+    let mut b = PrimitiveBuilder::<T>::new(array.len());
+    for i in 0..array.len() {
+        if array.is_null(i) {
+            b.append_null()?;
+        } else {
+            b.append_value(op(array.value(i)))?;
+        }
+    }
+    Ok(b.finish())
+}
+
+/// Perform `left + right` operation on every value of an array and a scalar. If a
+/// value is null then the result is also null.
+pub fn add_scalar<T>(
+    left: &PrimitiveArray<T>,
+    right: T::Native,
+) -> Result<PrimitiveArray<T>>
+where
+    T: datatypes::ArrowNumericType,
+    T::Native: Add<Output = T::Native>,
+{
+    scalar_op(left, |a| a + right)
+}
+
+/// Perform `left - right` operation on every value of an array and a scalar. If a
+/// value is null then the result is also null.
+pub fn subtract_scalar<T>(
+    left: &PrimitiveArray<T>,
+    right: T::Native,
+) -> Result<PrimitiveArray<T>>
+where
+    T: datatypes::ArrowNumericType,
+    T::Native: Sub<Output = T::Native>,
+{
+    scalar_op(left, |a| a - right)
+}
+
+/// Perform `left * right` operation on every value of an array and a scalar. If a
+/// value is null then the result is also null.
+pub fn multiply_scalar<T>(
+    left: &PrimitiveArray<T>,
+    right: T::Native,
+) -> Result<PrimitiveArray<T>>
+where
+    T: datatypes::ArrowNumericType,
+    T::Native: Mul<Output = T::Native>,
+{
+    scalar_op(left, |a| a * right)
+}
+
+/// A native type whose values can be divided without panicking.
+///
+/// Dividing the minimum value of a signed integer type by `-1` overflows, so this
+/// operation returns `None` instead of panicking.
+pub trait ArrowDivType: Copy {
+    /// Returns `self / rhs`, or `None` on overflow
+    fn checked_divide(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! make_integer_div_type {
+    ($native_ty:ty) => {
+        impl ArrowDivType for $native_ty {
+            fn checked_divide(self, rhs: Self) -> Option<Self> {
+                self.checked_div(rhs)
+            }
+        }
+    };
+}
+
+make_integer_div_type!(i8);
+make_integer_div_type!(i16);
+make_integer_div_type!(i32);
+make_integer_div_type!(i64);
+make_integer_div_type!(u8);
+make_integer_div_type!(u16);
+make_integer_div_type!(u32);
+make_integer_div_type!(u64);
+
+macro_rules! make_float_div_type {
+    ($native_ty:ty) => {
+        impl ArrowDivType for $native_ty {
+            fn checked_divide(self, rhs: Self) -> Option<Self> {
+                Some(self / rhs)
+            }
+        }
+    };
+}
+
+make_float_div_type!(f32);
+make_float_div_type!(f64);
+
+/// Perform `left / right` operation on every value of an array and a scalar. If a value
+/// is null then the result is also null. If `right` is zero then the result of this
+/// operation will be `Err(ArrowError::DivideByZero)`.
+///
+/// Dividing the minimum value of a signed integer type, e.g. `i32::MIN`, by `-1`
+/// overflows and returns an error rather than panicking.
+pub fn divide_scalar<T>(
+    left: &PrimitiveArray<T>,
+    right: T::Native,
+) -> Result<PrimitiveArray<T>>
+where
+    T: datatypes::ArrowNumericType,
+    T::Native: Zero + ArrowDivType,
+{
+    if right.is_zero() {
+        return Err(ArrowError::DivideByZero);
+    }
+    checked_unary_op(left, "division", |a| a.checked_divide(right))
+}
+
+/// A signed native type, whose values can be negated.
+///
+/// Negating the minimum value of a signed integer type overflows, so these operations
+/// return `None` instead of wrapping around.
+pub trait ArrowSignedType: Copy {
+    /// Returns `-self`, or `None` on overflow
+    fn checked_negate(self) -> Option<Self>;
+
+    /// Returns the absolute value of `self`, or `None` on overflow
+    fn checked_absolute(self) -> Option<Self>;
+}
+
+macro_rules! make_signed_integer_type {
+    ($native_ty:ty) => {
+        impl ArrowSignedType for $native_ty {
+            fn checked_negate(self) -> Option<Self> {
+                self.checked_neg()
+            }
+
+            fn checked_absolute(self) -> Option<Self> {
+                self.checked_abs()
+            }
+        }
+    };
+}
+
+make_signed_integer_type!(i8);
+make_signed_integer_type!(i16);
+make_signed_integer_type!(i32);
+make_signed_integer_type!(i64);
+
+macro_rules! make_signed_float_type {
+    ($native_ty:ty) => {
+        impl ArrowSignedType for $native_ty {
+            fn checked_negate(self) -> Option<Self> {
+                Some(-self)
+            }
+
+            fn checked_absolute(self) -> Option<Self> {
+                Some(self.abs())
+            }
+        }
+    };
+}
+
+make_signed_float_type!(f32);
+make_signed_float_type!(f64);
+
+/// Helper function to apply the checked operation `op` to every non-null value of
+/// `array`, returning an error if it overflows.
+fn checked_unary_op<T, F>(
+    array: &PrimitiveArray<T>,
+    name: &str,
+    op: F,
+) -> Result<PrimitiveArray<T>>
+where
+    T: datatypes::ArrowNumericType,
+    F: Fn(T::Native) -> Option<T::Native>,
+{
+    let mut b = PrimitiveBuilder::<T>::new(array.len());
+    for i in 0..array.len() {
+        if array.is_null(i) {
+            b.append_null()?;
+        } else {
+            let value = op(array.value(i)).ok_or_else(|| {
+                ArrowError::ComputeError(format!(
+                    "Overflow when computing {} of {:?}",
+                    name,
+                    array.value(i)
+                ))
+            })?;
+            b.append_value(value)?;
+        }
+    }
+    Ok(b.finish())
+}
+
+/// Perform `-value` operation on every value of an array of a signed type. If a value is
+/// null then the result is also null.
+///
+/// Negating the minimum value of a signed integer type, e.g. `i32::MIN`, overflows and
+/// returns an error rather than wrapping around.
+pub fn negate<T>(array: &PrimitiveArray<T>) -> Result<PrimitiveArray<T>>
+where
+    T: datatypes::ArrowNumericType,
+    T::Native: ArrowSignedType,
+{
+    checked_unary_op(array, "negation", |a| a.checked_negate())
+}
+
+/// Computes the absolute value of every value of an array of a signed type. If a value
+/// is null then the result is also null.
+///
+/// The absolute value of the minimum value of a signed integer type, e.g. `i32::MIN`,
+/// overflows and returns an error rather than wrapping around.
+pub fn abs<T>(array: &PrimitiveArray<T>) -> Result<PrimitiveArray<T>>
+where
+    T: datatypes::ArrowNumericType,
+    T::Native: ArrowSignedType,
+{
+    checked_unary_op(array, "absolute value", |a| a.checked_absolute())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -308,4 +529,84 @@ mod tests {
         assert_eq!(13, c.value(2));
     }
 
+    #[test]
+    fn test_primitive_array_scalar_ops() {
+        let a = Int32Array::from(vec![Some(5), None, Some(-7), Some(10)]);
+        let c = add_scalar(&a, 3).unwrap();
+        assert_eq!(1, c.null_count());
+        assert!(c.is_null(1));
+        assert_eq!(8, c.value(0));
+        assert_eq!(-4, c.value(2));
+        assert_eq!(13, c.value(3));
+
+        let c = subtract_scalar(&a, 3).unwrap();
+        assert_eq!((2, -10), (c.value(0), c.value(2)));
+        let c = multiply_scalar(&a, 100).unwrap();
+        assert_eq!((500, -700), (c.value(0), c.value(2)));
+        let c = divide_scalar(&a, 5).unwrap();
+        assert_eq!((1, -1, 2), (c.value(0), c.value(2), c.value(3)));
+        assert!(c.is_null(1));
+    }
+
+    #[test]
+    fn test_primitive_array_divide_scalar_by_zero() {
+        let a = Int32Array::from(vec![15]);
+        assert_eq!(
+            ArrowError::DivideByZero,
+            divide_scalar(&a, 0)
+                .err()
+                .expect("divide by zero should fail")
+        );
+        let a = Float64Array::from(vec![Some(1.5), None]);
+        assert!(divide_scalar(&a, 0.0).is_err());
+        let c = divide_scalar(&a, 0.5).unwrap();
+        assert_eq!(3.0, c.value(0));
+        assert!(c.is_null(1));
+    }
+
+    #[test]
+    fn test_primitive_array_divide_scalar_overflow() {
+        let a = Int32Array::from(vec![Some(6), None, Some(std::i32::MIN)]);
+        assert!(divide_scalar(&a, -1).is_err());
+        let c = divide_scalar(&a, 2).unwrap();
+        assert_eq!((3, std::i32::MIN / 2), (c.value(0), c.value(2)));
+        assert!(c.is_null(1));
+
+        let a = Int32Array::from(vec![Some(6), None, Some(std::i32::MAX)]);
+        let c = divide_scalar(&a, -1).unwrap();
+        assert_eq!((-6, -std::i32::MAX), (c.value(0), c.value(2)));
+        assert!(c.is_null(1));
+    }
+
+    #[test]
+    fn test_primitive_array_negate() {
+        let a = Int32Array::from(vec![Some(5), None, Some(-7), Some(0)]);
+        let c = negate(&a).unwrap();
+        assert!(c.is_null(1));
+        assert_eq!((-5, 7, 0), (c.value(0), c.value(2), c.value(3)));
+
+        let a = Int8Array::from(vec![Some(std::i8::MIN), None]);
+        assert!(negate(&a).is_err());
+        let a = Int8Array::from(vec![Some(std::i8::MAX), None]);
+        assert_eq!(-std::i8::MAX, negate(&a).unwrap().value(0));
+
+        let a = Float64Array::from(vec![1.5, -0.5]);
+        let c = negate(&a).unwrap();
+        assert_eq!((-1.5, 0.5), (c.value(0), c.value(1)));
+    }
+
+    #[test]
+    fn test_primitive_array_abs() {
+        let a = Int64Array::from(vec![Some(-5), None, Some(7)]);
+        let c = abs(&a).unwrap();
+        assert_eq!(1, c.null_count());
+        assert_eq!((5, 7), (c.value(0), c.value(2)));
+
+        let a = Int64Array::from(vec![std::i64::MIN]);
+        assert!(abs(&a).is_err());
+
+        let a = Float32Array::from(vec![-2.5, 3.0]);
+        let c = abs(&a).unwrap();
+        assert_eq!((2.5, 3.0), (c.value(0), c.value(1)));
+    }
 }
@@ -17,18 +17,25 @@
 
 //! Defines primitive computations on arrays, e.g. addition, equality, boolean logic.
 
-use std::ops::Add;
+use std::convert::TryFrom;
 use std::sync::Arc;
 
 use crate::array::{
-    Array, ArrayRef, BinaryArray, BooleanArray, Float32Array, Float64Array, Int16Array,
-    Int32Array, Int64Array, Int8Array, PrimitiveArray, UInt16Array, UInt32Array,
-    UInt64Array, UInt8Array,
+    make_array, Array, ArrayRef, BinaryArray, BooleanArray, Float32Array, Float64Array,
+    Int16Array, Int32Array, Int64Array, Int8Array, LargeListArray, ListArray,
+    PrimitiveArray, UInt16Array, UInt32Array, UInt64Array, UInt8Array,
 };
-use crate::datatypes::{ArrowNumericType, DataType};
+use crate::array_data::ArrayData;
+use crate::buffer::Buffer;
+use crate::builder::BinaryBuilder;
+use crate::datatypes::{ArrowNumericType, DataType, ToByteSlice};
 use crate::error::{ArrowError, Result};
+use crate::util::bit_util;
 
 /// Returns the minimum value in the array, according to the natural order.
+///
+/// Null values are skipped. Returns `None` if the array is empty or only contains null
+/// values.
 pub fn min<T>(array: &PrimitiveArray<T>) -> Option<T::Native>
 where
     T: ArrowNumericType,
@@ -37,6 +44,9 @@ where
 }
 
 /// Returns the maximum value in the array, according to the natural order.
+///
+/// Null values are skipped. Returns `None` if the array is empty or only contains null
+/// values.
 pub fn max<T>(array: &PrimitiveArray<T>) -> Option<T::Native>
 where
     T: ArrowNumericType,
@@ -51,7 +61,7 @@ where
     F: Fn(T::Native, T::Native) -> bool,
 {
     let mut n: Option<T::Native> = None;
-    let data = array.data();
+    let data = array.data_ref();
     for i in 0..data.len() {
         if data.is_null(i) {
             continue;
@@ -69,37 +79,363 @@ where
     n
 }
 
-/// Returns the sum of values in the array.
+/// Returns the lexicographically smallest value in the array.
+///
+/// Null values are skipped. Returns `None` if the array is empty or only contains null
+/// values.
+pub fn min_binary(array: &BinaryArray) -> Option<&[u8]> {
+    min_max_binary_helper(array, |a, b| a < b)
+}
+
+/// Returns the lexicographically largest value in the array.
+///
+/// Null values are skipped. Returns `None` if the array is empty or only contains null
+/// values.
+pub fn max_binary(array: &BinaryArray) -> Option<&[u8]> {
+    min_max_binary_helper(array, |a, b| a > b)
+}
+
+/// Helper function to perform min/max lambda function on values from a binary array.
+fn min_max_binary_helper<F>(array: &BinaryArray, cmp: F) -> Option<&[u8]>
+where
+    F: Fn(&[u8], &[u8]) -> bool,
+{
+    let mut n: Option<&[u8]> = None;
+    for i in 0..array.len() {
+        if array.is_null(i) {
+            continue;
+        }
+        let m = array.value(i);
+        match n {
+            Some(nn) if !cmp(m, nn) => {}
+            _ => n = Some(m),
+        }
+    }
+    n
+}
+
+/// A native type whose values are summed in a wider accumulator type.
 ///
-/// Returns `None` if the array is empty or only contains null values.
+/// Integers of up to 32 bits are accumulated as 64-bit integers, 64-bit integers as
+/// 128-bit integers and `f32` as `f64`, so that summing many values does not overflow or
+/// lose precision in intermediate results. Integer accumulation wraps on overflow, which
+/// can only happen for arrays of more than `2^32` values.
+pub trait ArrowSumType: Copy {
+    /// The type in which values are accumulated.
+    type Accumulator: Copy;
+
+    /// The initial value of the accumulator.
+    fn zero() -> Self::Accumulator;
+
+    /// Adds `self` to the accumulator `acc`.
+    fn accumulate(self, acc: Self::Accumulator) -> Self::Accumulator;
+
+    /// Converts the accumulated sum back into this type.
+    fn from_accumulator(acc: Self::Accumulator) -> Self;
+
+    /// Adds `self` to the accumulator `acc`, returning `None` on overflow.
+    fn checked_accumulate(self, acc: Self::Accumulator) -> Option<Self::Accumulator>;
+
+    /// Converts the accumulated sum back into this type, returning `None` if it does not
+    /// fit.
+    fn checked_from_accumulator(acc: Self::Accumulator) -> Option<Self>;
+}
+
+macro_rules! make_integer_sum_type {
+    ($native_ty:ty, $acc_ty:ty) => {
+        impl ArrowSumType for $native_ty {
+            type Accumulator = $acc_ty;
+
+            fn zero() -> $acc_ty {
+                0
+            }
+
+            fn accumulate(self, acc: $acc_ty) -> $acc_ty {
+                acc.wrapping_add(self as $acc_ty)
+            }
+
+            fn from_accumulator(acc: $acc_ty) -> Self {
+                acc as $native_ty
+            }
+
+            fn checked_accumulate(self, acc: $acc_ty) -> Option<$acc_ty> {
+                acc.checked_add(self as $acc_ty)
+            }
+
+            fn checked_from_accumulator(acc: $acc_ty) -> Option<Self> {
+                <$native_ty>::try_from(acc).ok()
+            }
+        }
+    };
+}
+
+make_integer_sum_type!(i8, i64);
+make_integer_sum_type!(i16, i64);
+make_integer_sum_type!(i32, i64);
+make_integer_sum_type!(i64, i128);
+make_integer_sum_type!(u8, u64);
+make_integer_sum_type!(u16, u64);
+make_integer_sum_type!(u32, u64);
+make_integer_sum_type!(u64, u128);
+
+macro_rules! make_float_sum_type {
+    ($native_ty:ty) => {
+        impl ArrowSumType for $native_ty {
+            type Accumulator = f64;
+
+            fn zero() -> f64 {
+                0.0
+            }
+
+            fn accumulate(self, acc: f64) -> f64 {
+                acc + self as f64
+            }
+
+            fn from_accumulator(acc: f64) -> Self {
+                acc as $native_ty
+            }
+
+            fn checked_accumulate(self, acc: f64) -> Option<f64> {
+                Some(self.accumulate(acc))
+            }
+
+            fn checked_from_accumulator(acc: f64) -> Option<Self> {
+                Some(acc as $native_ty)
+            }
+        }
+    };
+}
+
+make_float_sum_type!(f32);
+make_float_sum_type!(f64);
+
+/// Returns the sum of values in the array, skipping null values.
+///
+/// Returns `None` if the array is empty or only contains null values. Values are summed
+/// in a wider accumulator (see `ArrowSumType`), so intermediate sums may exceed the range
+/// of the native type as long as the total fits, but the result is converted back to the
+/// native type unchecked: an integer sum that does not fit wraps around. Use
+/// `sum_checked` to detect overflow, or `sum_wide` to get the sum in the accumulator
+/// type.
 pub fn sum<T>(array: &PrimitiveArray<T>) -> Option<T::Native>
 where
     T: ArrowNumericType,
-    T::Native: Add<Output = T::Native>,
+    T::Native: ArrowSumType,
 {
     let null_count = array.null_count();
 
     if null_count == array.len() {
-        None
-    } else if null_count == 0 {
+        return None;
+    }
+
+    let data = array.data_ref();
+    let m = array.value_slice(0, data.len());
+    let mut n = T::Native::zero();
+    if null_count == 0 {
         // optimized path for arrays without null values
-        let mut n: T::Native = T::default_value();
-        let data = array.data();
-        let m = array.value_slice(0, data.len());
-        for i in 0..data.len() {
-            n = n + m[i];
+        for v in m {
+            n = v.accumulate(n);
         }
-        Some(n)
     } else {
-        let mut n: T::Native = T::default_value();
-        let data = array.data();
-        let m = array.value_slice(0, data.len());
-        for i in 0..data.len() {
+        for (i, v) in m.iter().enumerate() {
             if data.is_valid(i) {
-                n = n + m[i];
+                n = v.accumulate(n);
             }
         }
-        Some(n)
+    }
+    Some(T::Native::from_accumulator(n))
+}
+
+/// Returns the sum of values in the array, skipping null values, in the accumulator type
+/// of `ArrowSumType`.
+///
+/// Unlike `sum`, the result is not converted back to the native type of the array, so
+/// that e.g. the sum of an `Int32Array` is returned as an `i64` and the sum of an
+/// `Int64Array` as an `i128`, which may exceed the range of the native type. The sum
+/// only wraps around for arrays of more than `2^32` values. Returns `None` if the array
+/// is empty or only contains null values.
+pub fn sum_wide<T>(
+    array: &PrimitiveArray<T>,
+) -> Option<<T::Native as ArrowSumType>::Accumulator>
+where
+    T: ArrowNumericType,
+    T::Native: ArrowSumType,
+{
+    if array.null_count() == array.len() {
+        return None;
+    }
+    let data = array.data_ref();
+    let mut n = T::Native::zero();
+    for (i, v) in array.value_slice(0, data.len()).iter().enumerate() {
+        if data.is_valid(i) {
+            n = v.accumulate(n);
+        }
+    }
+    Some(n)
+}
+
+/// Returns the sum of values in the array, skipping null values, or an error if the sum
+/// overflows the native type of the array.
+///
+/// Values are summed in the accumulator type of `ArrowSumType`, so an intermediate sum
+/// may exceed the range of the native type as long as the total fits. `sum` silently
+/// wraps around on integer overflow instead. Floating point sums never fail, and
+/// overflow to infinity. Returns `Ok(None)` if the array is empty or only
+/// contains null values.
+pub fn sum_checked<T>(array: &PrimitiveArray<T>) -> Result<Option<T::Native>>
+where
+    T: ArrowNumericType,
+    T::Native: ArrowSumType,
+{
+    if array.null_count() == array.len() {
+        return Ok(None);
+    }
+    let overflow = || {
+        ArrowError::ComputeError(format!(
+            "Overflow when summing {:?} values",
+            T::get_data_type()
+        ))
+    };
+    let data = array.data_ref();
+    let mut n = T::Native::zero();
+    for (i, v) in array.value_slice(0, data.len()).iter().enumerate() {
+        if data.is_valid(i) {
+            n = v.checked_accumulate(n).ok_or_else(overflow)?;
+        }
+    }
+    T::Native::checked_from_accumulator(n)
+        .map(Some)
+        .ok_or_else(overflow)
+}
+
+/// Returns the `q`-th quantile of the values in the array, skipping null and `NaN`
+/// values.
+///
+/// The quantile is linearly interpolated between the two closest order statistics, so
+/// that `q = 0.5` returns the median, `q = 0.0` the minimum and `q = 1.0` the maximum.
+/// Values of `q` outside of `[0.0, 1.0]` are clamped to that range. Returns `None` if the
+/// array is empty or only contains null and `NaN` values.
+pub fn quantile(array: &Float64Array, q: f64) -> Option<f64> {
+    let mut values: Vec<f64> = (0..array.len())
+        .filter(|i| array.is_valid(*i))
+        .map(|i| array.value(i))
+        .filter(|v| !v.is_nan())
+        .collect();
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let q = q.max(0.0).min(1.0);
+    let position = q * (values.len() - 1) as f64;
+    let lower = position.floor() as usize;
+    let upper = position.ceil() as usize;
+    let fraction = position - lower as f64;
+    Some(values[lower] + (values[upper] - values[lower]) * fraction)
+}
+
+/// Returns an approximation of the `q`-th quantile of the values in the array, skipping
+/// null and `NaN` values, without sorting the array.
+///
+/// The values are streamed into a Greenwald-Khanna summary, which keeps
+/// `O(log(epsilon * n) / epsilon)` values instead of all `n` values. The rank of the
+/// returned value differs from the exact rank `q * n` by at most `epsilon * n`. Values of
+/// `q` outside of `[0.0, 1.0]` are clamped to that range, and a non-positive `epsilon`
+/// computes the exact `quantile`. Returns `None` if the array is empty or only contains
+/// null and `NaN` values.
+pub fn approx_quantile(array: &Float64Array, q: f64, epsilon: f64) -> Option<f64> {
+    if epsilon.is_nan() || epsilon <= 0.0 {
+        return quantile(array, q);
+    }
+    let mut summary = GkSummary::new(epsilon);
+    for i in 0..array.len() {
+        if array.is_valid(i) && !array.value(i).is_nan() {
+            summary.insert(array.value(i));
+        }
+    }
+    summary.query(q.max(0.0).min(1.0))
+}
+
+/// A tuple of a Greenwald-Khanna summary.
+struct GkTuple {
+    /// A value of the input
+    value: f64,
+    /// The difference between the minimum rank of `value` and that of the previous tuple
+    g: u64,
+    /// The difference between the maximum and minimum ranks of `value`
+    delta: u64,
+}
+
+/// A Greenwald-Khanna summary, answering quantile queries over a stream of values with a
+/// rank error of at most `epsilon * n`.
+struct GkSummary {
+    epsilon: f64,
+    count: u64,
+    tuples: Vec<GkTuple>,
+}
+
+impl GkSummary {
+    fn new(epsilon: f64) -> Self {
+        Self {
+            epsilon,
+            count: 0,
+            tuples: vec![],
+        }
+    }
+
+    /// The maximum allowed rank uncertainty of a tuple, `floor(2 * epsilon * n)`.
+    fn max_uncertainty(&self) -> u64 {
+        (2.0 * self.epsilon * self.count as f64).floor() as u64
+    }
+
+    fn insert(&mut self, value: f64) {
+        let position = self
+            .tuples
+            .iter()
+            .position(|t| value < t.value)
+            .unwrap_or(self.tuples.len());
+        // the minimum and maximum values are known exactly
+        let delta = if position == 0 || position == self.tuples.len() {
+            0
+        } else {
+            self.max_uncertainty()
+        };
+        self.tuples.insert(position, GkTuple { value, g: 1, delta });
+        self.count += 1;
+
+        let period = (1.0 / (2.0 * self.epsilon)).floor().max(1.0) as u64;
+        if self.count % period == 0 {
+            self.compress();
+        }
+    }
+
+    /// Merges adjacent tuples whose combined rank uncertainty stays within bounds.
+    fn compress(&mut self) {
+        let max_uncertainty = self.max_uncertainty();
+        let mut i = self.tuples.len().saturating_sub(2);
+        while i > 0 {
+            let (g, next) = (self.tuples[i].g, &self.tuples[i + 1]);
+            if g + next.g + next.delta <= max_uncertainty {
+                self.tuples[i + 1].g += g;
+                self.tuples.remove(i);
+            }
+            i -= 1;
+        }
+    }
+
+    fn query(&self, q: f64) -> Option<f64> {
+        let last = self.tuples.last()?;
+        let rank = (q * self.count as f64).ceil().max(1.0);
+        let bound = self.epsilon * self.count as f64;
+        let mut min_rank = 0;
+        for t in &self.tuples {
+            min_rank += t.g;
+            let max_rank = min_rank + t.delta;
+            if rank - min_rank as f64 <= bound && max_rank as f64 - rank <= bound {
+                return Some(t.value);
+            }
+        }
+        Some(last.value)
     }
 }
 
@@ -139,9 +475,9 @@ where
 macro_rules! filter_array {
     ($array:expr, $filter:expr, $array_type:ident) => {{
         let b = $array.as_any().downcast_ref::<$array_type>().unwrap();
-        let mut builder = $array_type::builder(b.len());
+        let mut builder = $array_type::builder(count_matches($filter));
         for i in 0..b.len() {
-            if $filter.value(i) {
+            if $filter.is_valid(i) && $filter.value(i) {
                 if b.is_null(i) {
                     builder.append_null()?;
                 } else {
@@ -153,7 +489,40 @@ macro_rules! filter_array {
     }};
 }
 
-pub fn filter(array: &Array, filter: &BooleanArray) -> Result<ArrayRef> {
+/// Returns the number of positions of `mask` that are both valid and `true`, i.e. the
+/// number of elements kept when filtering an array with `mask`.
+///
+/// This can be used to estimate the selectivity of a filter, or to pre-size buffers for
+/// its output, without running the filter itself.
+pub fn count_matches(mask: &BooleanArray) -> usize {
+    let data = mask.data();
+    let (offset, len) = (data.offset(), data.len());
+    let values = data.buffers()[0].data();
+    match data.null_bitmap() {
+        Some(bitmap) => {
+            // the bytes of the values ANDed with the validity, from the byte of `offset`
+            let validity = bitmap.bits.data();
+            let start = offset >> 3;
+            let matches: Vec<u8> = (start..bit_util::ceil(offset + len, 8))
+                .map(|i| values[i] & validity[i])
+                .collect();
+            bit_util::count_set_bits(&matches, offset & 7, len)
+        }
+        None => bit_util::count_set_bits(values, offset, len),
+    }
+}
+
+/// Returns a new array containing only the elements of `array` for which the
+/// corresponding value in `filter` is `true`.
+///
+/// A null value in `filter` is treated as `false`, i.e. the element is dropped. Null
+/// values of `array` that are kept remain null in the result.
+pub fn filter(array: &ArrayRef, filter: &BooleanArray) -> Result<ArrayRef> {
+    if array.len() != filter.len() {
+        return Err(ArrowError::ComputeError(
+            "Cannot filter an array with a filter of different length".to_string(),
+        ));
+    }
     match array.data_type() {
         DataType::UInt8 => filter_array!(array, filter, UInt8Array),
         DataType::UInt16 => filter_array!(array, filter, UInt16Array),
@@ -168,13 +537,17 @@ pub fn filter(array: &Array, filter: &BooleanArray) -> Result<ArrayRef> {
         DataType::Boolean => filter_array!(array, filter, BooleanArray),
         DataType::Utf8 => {
             let b = array.as_any().downcast_ref::<BinaryArray>().unwrap();
-            let mut values: Vec<&[u8]> = Vec::with_capacity(b.len());
+            let mut builder = BinaryBuilder::new(count_matches(filter));
             for i in 0..b.len() {
-                if filter.value(i) {
-                    values.push(b.value(i));
+                if filter.is_valid(i) && filter.value(i) {
+                    if b.is_null(i) {
+                        builder.append_null()?;
+                    } else {
+                        builder.append_bytes(b.value(i))?;
+                    }
                 }
             }
-            Ok(Arc::new(BinaryArray::from(values)))
+            Ok(Arc::new(builder.finish()))
         }
         other => Err(ArrowError::ComputeError(format!(
             "filter not supported for {:?}",
@@ -183,61 +556,85 @@ pub fn filter(array: &Array, filter: &BooleanArray) -> Result<ArrayRef> {
     }
 }
 
-macro_rules! limit_array {
-    ($array:expr, $num_elements:expr, $array_type:ident) => {{
-        let b = $array.as_any().downcast_ref::<$array_type>().unwrap();
-        let mut builder = $array_type::builder($num_elements);
-        for i in 0..$num_elements {
-            if b.is_null(i) {
-                builder.append_null()?;
-            } else {
-                builder.append_value(b.value(i))?;
-            }
-        }
-        Ok(Arc::new(builder.finish()))
-    }};
-}
-
-/// Returns the array, taking only the number of elements specified
+/// Returns the first `num_elements` elements of the array, or the whole array if it has
+/// fewer elements.
 ///
-/// Returns the whole array if the number of elements specified is larger than the length
-/// of the array
-pub fn limit(array: &ArrayRef, num_elements: usize) -> Result<ArrayRef> {
+/// This is zero-copy: the returned array shares the buffers, null bitmap and children
+/// of `array`, with a shorter length, so it works for arrays of any type. The values of
+/// lists are limited as well, to the values of the remaining lists.
+pub fn limit(array: &ArrayRef, num_elements: usize) -> ArrayRef {
     if num_elements >= array.len() {
-        return Ok(array.clone());
+        return array.clone();
     }
+    let data = array.data_ref();
+    let child_data = match data.data_type() {
+        DataType::List(_) => {
+            let list = array.as_any().downcast_ref::<ListArray>().unwrap();
+            let end = list.value_offset(num_elements) as usize;
+            vec![limit(&list.values(), end).data()]
+        }
+        DataType::LargeList(_) => {
+            let list = array.as_any().downcast_ref::<LargeListArray>().unwrap();
+            let end = list.value_offset(num_elements) as usize;
+            vec![limit(&list.values(), end).data()]
+        }
+        _ => data.child_data().to_vec(),
+    };
+    let null_bit_buffer = data.null_bitmap().as_ref().map(|b| b.bits.clone());
+    make_array(Arc::new(ArrayData::new(
+        data.data_type().clone(),
+        num_elements,
+        None,
+        null_bit_buffer,
+        data.offset(),
+        data.buffers().to_vec(),
+        child_data,
+    )))
+}
 
-    match array.data_type() {
-        DataType::UInt8 => limit_array!(array, num_elements, UInt8Array),
-        DataType::UInt16 => limit_array!(array, num_elements, UInt16Array),
-        DataType::UInt32 => limit_array!(array, num_elements, UInt32Array),
-        DataType::UInt64 => limit_array!(array, num_elements, UInt64Array),
-        DataType::Int8 => limit_array!(array, num_elements, Int8Array),
-        DataType::Int16 => limit_array!(array, num_elements, Int16Array),
-        DataType::Int32 => limit_array!(array, num_elements, Int32Array),
-        DataType::Int64 => limit_array!(array, num_elements, Int64Array),
-        DataType::Float32 => limit_array!(array, num_elements, Float32Array),
-        DataType::Float64 => limit_array!(array, num_elements, Float64Array),
-        DataType::Boolean => limit_array!(array, num_elements, BooleanArray),
-        DataType::Utf8 => {
-            let b = array.as_any().downcast_ref::<BinaryArray>().unwrap();
-            let mut values: Vec<&[u8]> = Vec::with_capacity(num_elements);
-            for i in 0..num_elements {
-                values.push(b.value(i));
+/// Returns a copy of `array` in which every null value is replaced by `value`.
+///
+/// The returned array has the same length as `array` and no null values.
+pub fn fill_null<T>(array: &PrimitiveArray<T>, value: T::Native) -> PrimitiveArray<T>
+where
+    T: ArrowNumericType,
+{
+    let values: Vec<T::Native> = (0..array.len())
+        .map(|i| {
+            if array.is_null(i) {
+                value
+            } else {
+                array.value(i)
             }
-            Ok(Arc::new(BinaryArray::from(values)))
+        })
+        .collect();
+    let data = ArrayData::builder(T::get_data_type())
+        .len(values.len())
+        .add_buffer(Buffer::from(values.to_byte_slice()))
+        .build();
+    PrimitiveArray::from(data)
+}
+
+/// Returns a copy of `array` in which every null value is replaced by `value`.
+///
+/// The returned array has the same length as `array` and no null values.
+pub fn fill_null_binary(array: &BinaryArray, value: &[u8]) -> Result<BinaryArray> {
+    let mut builder = BinaryBuilder::new(array.len());
+    for i in 0..array.len() {
+        if array.is_null(i) {
+            builder.append_bytes(value)?;
+        } else {
+            builder.append_bytes(array.value(i))?;
         }
-        other => Err(ArrowError::ComputeError(format!(
-            "limit not supported for {:?}",
-            other
-        ))),
     }
+    Ok(builder.finish())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::array::{ArrayRef, Float64Array, Int32Array};
+    use crate::builder::{Int32Builder, ListBuilder};
 
     use std::sync::Arc;
 
@@ -265,6 +662,83 @@ mod tests {
         assert_eq!(None, sum(&a));
     }
 
+    #[test]
+    fn test_primitive_array_sum_empty() {
+        let a = Int32Array::from(Vec::<i32>::new());
+        assert_eq!(None, sum(&a));
+        assert_eq!(None, min(&a));
+        assert_eq!(None, max(&a));
+    }
+
+    #[test]
+    fn test_primitive_array_sum_single_element() {
+        let a = Int64Array::from(vec![-7]);
+        assert_eq!(Some(-7), sum(&a));
+        assert_eq!(Some(-7), min(&a));
+        assert_eq!(Some(-7), max(&a));
+    }
+
+    #[test]
+    fn test_primitive_array_sum_wide_intermediate() {
+        // the intermediate sum exceeds `i32::MAX`, but the total fits
+        let a = Int32Array::from(vec![std::i32::MAX, 10, -20]);
+        assert_eq!(Some(std::i32::MAX - 10), sum(&a));
+
+        let a = UInt8Array::from(vec![Some(200), None, Some(100), Some(0)]);
+        assert_eq!(Some(44), sum(&a));
+    }
+
+    #[test]
+    fn test_primitive_array_sum_wraps_on_overflow() {
+        let a = Int32Array::from(vec![std::i32::MAX, 1]);
+        assert_eq!(Some(std::i32::MIN), sum(&a));
+        assert!(sum_checked(&a).is_err());
+    }
+
+    #[test]
+    fn test_primitive_array_sum_checked() {
+        let a = Int32Array::from(vec![std::i32::MAX, 10, -20]);
+        assert_eq!(Some(std::i32::MAX - 10), sum_checked(&a).unwrap());
+
+        let a = Int32Array::from(vec![Some(std::i32::MAX), None, Some(1)]);
+        assert!(sum_checked(&a).is_err());
+
+        let a = Int64Array::from(vec![std::i64::MAX, 1, -1]);
+        assert_eq!(Some(std::i64::MAX), sum_checked(&a).unwrap());
+
+        let a = Int64Array::from(vec![std::i64::MAX, 1]);
+        assert!(sum_checked(&a).is_err());
+
+        let a = Int32Array::from(vec![None, None]);
+        assert_eq!(None, sum_checked(&a).unwrap());
+    }
+
+    #[test]
+    fn test_primitive_array_sum_wide() {
+        let a = Int32Array::from(vec![std::i32::MAX; 1_000_000]);
+        assert_eq!(Some(std::i32::MAX as i64 * 1_000_000), sum_wide(&a));
+        assert!(sum_checked(&a).is_err());
+
+        let a = UInt8Array::from(vec![Some(200), None, Some(100)]);
+        assert_eq!(Some(300u64), sum_wide(&a));
+
+        let a = Int64Array::from(vec![std::i64::MAX, 1]);
+        assert_eq!(Some(std::i64::MAX as i128 + 1), sum_wide(&a));
+
+        let a = UInt64Array::from(vec![std::u64::MAX, std::u64::MAX]);
+        assert_eq!(Some(std::u64::MAX as u128 * 2), sum_wide(&a));
+
+        let a = Float32Array::from(vec![Some(0.5), None, Some(1.25)]);
+        assert_eq!(Some(1.75f64), sum_wide(&a));
+        assert_eq!(None, sum_wide(&Float32Array::from(vec![None])));
+    }
+
+    #[test]
+    fn test_primitive_array_float32_sum() {
+        let a = Float32Array::from(vec![Some(0.5), None, Some(1.25)]);
+        assert_eq!(Some(1.75), sum(&a));
+    }
+
     #[test]
     fn test_buffer_array_min_max() {
         let a = Int32Array::from(vec![5, 6, 7, 8, 9]);
@@ -279,9 +753,163 @@ mod tests {
         assert_eq!(9, max(&a).unwrap());
     }
 
+    #[test]
+    fn test_buffer_array_min_max_all_nulls() {
+        let a = Float64Array::from(vec![None, None]);
+        assert_eq!(None, min(&a));
+        assert_eq!(None, max(&a));
+    }
+
+    #[test]
+    fn test_buffer_array_min_max_float() {
+        let a = Float64Array::from(vec![Some(-1.5), None, Some(3.25), Some(0.0)]);
+        assert_eq!(Some(-1.5), min(&a));
+        assert_eq!(Some(3.25), max(&a));
+    }
+
+    #[test]
+    fn test_binary_array_min_max() {
+        let a =
+            BinaryArray::from(vec![Some("b"), None, Some("ab"), Some("ba"), Some("")]);
+        assert_eq!(Some(&b""[..]), min_binary(&a));
+        assert_eq!(Some(&b"ba"[..]), max_binary(&a));
+
+        let a = BinaryArray::from(vec![None, Some("x")]);
+        assert_eq!(Some(&b"x"[..]), min_binary(&a));
+        assert_eq!(Some(&b"x"[..]), max_binary(&a));
+    }
+
+    #[test]
+    fn test_binary_array_min_max_all_nulls() {
+        let a = BinaryArray::from(Vec::<Option<&str>>::from(vec![None, None]));
+        assert_eq!(None, min_binary(&a));
+        assert_eq!(None, max_binary(&a));
+        let a = BinaryArray::from(Vec::<&str>::new());
+        assert_eq!(None, min_binary(&a));
+    }
+
+    #[test]
+    fn test_quantile() {
+        let a = Float64Array::from(vec![
+            Some(7.0),
+            None,
+            Some(1.0),
+            Some(std::f64::NAN),
+            Some(3.0),
+            Some(9.0),
+            Some(5.0),
+        ]);
+        assert_eq!(Some(5.0), quantile(&a, 0.5));
+        assert_eq!(Some(8.2), quantile(&a, 0.9));
+        assert_eq!(Some(1.0), quantile(&a, 0.0));
+        assert_eq!(Some(9.0), quantile(&a, 1.0));
+        // out of range quantiles are clamped
+        assert_eq!(Some(1.0), quantile(&a, -0.5));
+        assert_eq!(Some(9.0), quantile(&a, 1.5));
+    }
+
+    #[test]
+    fn test_quantile_even_length() {
+        let a = Float64Array::from(vec![4.0, 1.0, 3.0, 2.0]);
+        assert_eq!(Some(2.5), quantile(&a, 0.5));
+        assert_eq!(Some(3.7), quantile(&a, 0.9));
+    }
+
+    #[test]
+    fn test_quantile_empty() {
+        assert_eq!(None, quantile(&Float64Array::from(vec![] as Vec<f64>), 0.5));
+        let a = Float64Array::from(vec![None, Some(std::f64::NAN)]);
+        assert_eq!(None, quantile(&a, 0.5));
+    }
+
+    #[test]
+    fn test_approx_quantile() {
+        let n = 100_000;
+        // a permutation of 0..n, so that values are not inserted in order
+        let values: Vec<Option<f64>> = (0..n)
+            .map(|i| {
+                if i % 10 == 0 {
+                    None
+                } else {
+                    Some(((i * 7919) % n) as f64)
+                }
+            })
+            .collect();
+        let a = Float64Array::from(values);
+        let epsilon = 0.01;
+        for q in &[0.1, 0.5, 0.9] {
+            let exact = quantile(&a, *q).unwrap();
+            let approx = approx_quantile(&a, *q, epsilon).unwrap();
+            assert!(
+                (approx - exact).abs() <= epsilon * n as f64,
+                "approximate quantile {} is not within {} of {}",
+                approx,
+                epsilon,
+                exact
+            );
+        }
+        assert_eq!(Some(1.0), approx_quantile(&a, 0.0, epsilon));
+        assert_eq!(Some(99_999.0), approx_quantile(&a, 1.0, epsilon));
+    }
+
+    #[test]
+    fn test_approx_quantile_small() {
+        let a = Float64Array::from(vec![Some(3.0), None, Some(std::f64::NAN), Some(1.0)]);
+        assert_eq!(Some(1.0), approx_quantile(&a, 0.5, 0.1));
+        assert_eq!(Some(2.0), approx_quantile(&a, 0.5, 0.0));
+        let a = Float64Array::from(vec![None, None]);
+        assert_eq!(None, approx_quantile(&a, 0.5, 0.1));
+    }
+
+    #[test]
+    fn test_count_matches() {
+        let mask = BooleanArray::from(vec![
+            Some(true),
+            None,
+            Some(false),
+            Some(true),
+            None,
+            Some(true),
+        ]);
+        assert_eq!(3, count_matches(&mask));
+        assert_eq!(0, count_matches(&BooleanArray::from(Vec::<bool>::new())));
+    }
+
+    #[test]
+    fn test_count_matches_null_true() {
+        // the value bit is set at a null position, which must not be counted
+        let data = ArrayData::builder(DataType::Boolean)
+            .len(3)
+            .null_bit_buffer(Buffer::from([0b00000101]))
+            .add_buffer(Buffer::from([0b00000111]))
+            .build();
+        let mask = BooleanArray::from(data);
+        assert_eq!(2, count_matches(&mask));
+    }
+
+    #[test]
+    fn test_count_matches_with_offset() {
+        let data = ArrayData::builder(DataType::Boolean)
+            .len(10)
+            .offset(3)
+            .null_bit_buffer(Buffer::from([0b11110111, 0b11111110]))
+            .add_buffer(Buffer::from([0b10101000, 0b11111111]))
+            .build();
+        let mask = BooleanArray::from(data);
+        // bits 3..13 of the values are 1010111111, with bits 3 and 8 null
+        assert_eq!(6, count_matches(&mask));
+
+        let data = ArrayData::builder(DataType::Boolean)
+            .len(10)
+            .offset(3)
+            .add_buffer(Buffer::from([0b10101000, 0b11111111]))
+            .build();
+        assert_eq!(8, count_matches(&BooleanArray::from(data)));
+    }
+
     #[test]
     fn test_filter_array() {
-        let a = Int32Array::from(vec![5, 6, 7, 8, 9]);
+        let a: ArrayRef = Arc::new(Int32Array::from(vec![5, 6, 7, 8, 9]));
         let b = BooleanArray::from(vec![true, false, false, true, false]);
         let c = filter(&a, &b).unwrap();
         let d = c.as_ref().as_any().downcast_ref::<Int32Array>().unwrap();
@@ -292,7 +920,7 @@ mod tests {
 
     #[test]
     fn test_filter_binary_array() {
-        let a = BinaryArray::from(vec!["hello", " ", "world", "!"]);
+        let a: ArrayRef = Arc::new(BinaryArray::from(vec!["hello", " ", "world", "!"]));
         let b = BooleanArray::from(vec![true, false, true, false]);
         let c = filter(&a, &b).unwrap();
         let d = c.as_ref().as_any().downcast_ref::<BinaryArray>().unwrap();
@@ -303,7 +931,7 @@ mod tests {
 
     #[test]
     fn test_filter_array_with_null() {
-        let a = Int32Array::from(vec![Some(5), None]);
+        let a: ArrayRef = Arc::new(Int32Array::from(vec![Some(5), None]));
         let b = BooleanArray::from(vec![false, true]);
         let c = filter(&a, &b).unwrap();
         let d = c.as_ref().as_any().downcast_ref::<Int32Array>().unwrap();
@@ -311,10 +939,98 @@ mod tests {
         assert_eq!(true, d.is_null(0));
     }
 
+    #[test]
+    fn test_filter_array_with_null_filter() {
+        let a: ArrayRef = Arc::new(Int32Array::from(vec![
+            Some(5),
+            None,
+            Some(7),
+            Some(8),
+            None,
+        ]));
+        let b = BooleanArray::from(vec![Some(true), Some(true), None, Some(false), None]);
+        let c = filter(&a, &b).unwrap();
+        let d = c.as_ref().as_any().downcast_ref::<Int32Array>().unwrap();
+        assert_eq!(2, d.len());
+        assert_eq!(1, d.null_count());
+        assert_eq!(5, d.value(0));
+        assert_eq!(true, d.is_null(1));
+    }
+
+    #[test]
+    fn test_filter_boolean_array_with_null_filter() {
+        let a: ArrayRef = Arc::new(BooleanArray::from(vec![
+            Some(true),
+            None,
+            Some(false),
+            Some(true),
+        ]));
+        let b = BooleanArray::from(vec![Some(true), Some(true), Some(true), None]);
+        let c = filter(&a, &b).unwrap();
+        let d = c.as_ref().as_any().downcast_ref::<BooleanArray>().unwrap();
+        assert_eq!(3, d.len());
+        assert_eq!(true, d.value(0));
+        assert_eq!(true, d.is_null(1));
+        assert_eq!(false, d.value(2));
+    }
+
+    #[test]
+    fn test_filter_binary_array_with_null() {
+        let mut builder = BinaryBuilder::new(16);
+        builder.append_string("hello").unwrap();
+        builder.append_null().unwrap();
+        builder.append_string("world").unwrap();
+        builder.append_string("!").unwrap();
+        let a: ArrayRef = Arc::new(builder.finish());
+        let b = BooleanArray::from(vec![Some(false), Some(true), Some(true), None]);
+        let c = filter(&a, &b).unwrap();
+        let d = c.as_ref().as_any().downcast_ref::<BinaryArray>().unwrap();
+        assert_eq!(2, d.len());
+        assert_eq!(true, d.is_null(0));
+        assert_eq!("world", d.get_string(1));
+        assert_eq!(0, d.value_offset(0));
+        assert_eq!(0, d.value_offset(1));
+        assert_eq!(5, d.value_length(1));
+    }
+
+    #[test]
+    fn test_filter_sliced_binary_array() {
+        let a = BinaryArray::from(vec![
+            Some("hello"),
+            Some(" "),
+            None,
+            Some("world"),
+            Some("!"),
+        ]);
+        // [" ", null, "world", "!"]
+        let a = make_array(a.data().slice(1, 4));
+        // [null, true, true, false]
+        let b = BooleanArray::from(vec![
+            Some(true),
+            None,
+            Some(true),
+            Some(true),
+            Some(false),
+        ]);
+        let b = BooleanArray::from(b.data().slice(1, 4));
+        let c = filter(&a, &b).unwrap();
+        let d = c.as_ref().as_any().downcast_ref::<BinaryArray>().unwrap();
+        assert_eq!(2, d.len());
+        assert_eq!(true, d.is_null(0));
+        assert_eq!("world", d.get_string(1));
+    }
+
+    #[test]
+    fn test_filter_array_mismatched_length() {
+        let a: ArrayRef = Arc::new(Int32Array::from(vec![5, 6, 7]));
+        let b = BooleanArray::from(vec![true, false]);
+        assert!(filter(&a, &b).is_err());
+    }
+
     #[test]
     fn test_limit_array() {
         let a: ArrayRef = Arc::new(Int32Array::from(vec![5, 6, 7, 8, 9]));
-        let b = limit(&a, 3).unwrap();
+        let b = limit(&a, 3);
         let c = b.as_ref().as_any().downcast_ref::<Int32Array>().unwrap();
         assert_eq!(3, c.len());
         assert_eq!(5, c.value(0));
@@ -325,7 +1041,7 @@ mod tests {
     #[test]
     fn test_limit_binary_array() {
         let a: ArrayRef = Arc::new(BinaryArray::from(vec!["hello", " ", "world", "!"]));
-        let b = limit(&a, 2).unwrap();
+        let b = limit(&a, 2);
         let c = b.as_ref().as_any().downcast_ref::<BinaryArray>().unwrap();
         assert_eq!(2, c.len());
         assert_eq!("hello", c.get_string(0));
@@ -335,17 +1051,57 @@ mod tests {
     #[test]
     fn test_limit_array_with_null() {
         let a: ArrayRef = Arc::new(Int32Array::from(vec![None, Some(5)]));
-        let b = limit(&a, 1).unwrap();
+        let b = limit(&a, 1);
         let c = b.as_ref().as_any().downcast_ref::<Int32Array>().unwrap();
         assert_eq!(1, c.len());
         assert_eq!(true, c.is_null(0));
     }
 
+    #[test]
+    fn test_limit_array_with_offset() {
+        let data = ArrayData::builder(DataType::Int32)
+            .len(4)
+            .offset(1)
+            .null_bit_buffer(Buffer::from([0b11010]))
+            .add_buffer(Buffer::from([0, 1, 2, 3, 4].to_byte_slice()))
+            .build();
+        let a: ArrayRef = Arc::new(Int32Array::from(data));
+        let b = limit(&a, 3);
+        let c = b.as_ref().as_any().downcast_ref::<Int32Array>().unwrap();
+        assert_eq!(3, c.len());
+        assert_eq!(1, c.null_count());
+        assert_eq!(1, c.value(0));
+        assert!(c.is_null(1));
+        assert_eq!(3, c.value(2));
+        // the values are not copied
+        assert_eq!(a.data_ref().buffers()[0], c.data_ref().buffers()[0]);
+        assert_eq!(
+            a.data_ref().buffers()[0].raw_data(),
+            c.data_ref().buffers()[0].raw_data()
+        );
+    }
+
+    #[test]
+    fn test_limit_nested_array() {
+        let mut builder = ListBuilder::new(Int32Builder::new(5));
+        builder.values().append_slice(&[1, 2]).unwrap();
+        builder.append(true).unwrap();
+        builder.append(false).unwrap();
+        builder.values().append_value(3).unwrap();
+        builder.append(true).unwrap();
+        let a: ArrayRef = Arc::new(builder.finish());
+        let b = limit(&a, 2);
+        assert_eq!(2, b.len());
+        assert_eq!(1, b.null_count());
+        assert_eq!("[1, 2]", b.value_to_string(0));
+        assert_eq!("null", b.value_to_string(1));
+    }
+
     #[test]
     fn test_limit_array_with_limit_too_large() {
         let a = Int32Array::from(vec![5, 6, 7, 8, 9]);
         let a_ref: ArrayRef = Arc::new(a);
-        let b = limit(&a_ref, 6).unwrap();
+        let b = limit(&a_ref, 6);
         let c = b.as_ref().as_any().downcast_ref::<Int32Array>().unwrap();
 
         assert_eq!(5, c.len());
@@ -355,4 +1111,26 @@ mod tests {
         assert_eq!(8, c.value(3));
         assert_eq!(9, c.value(4));
     }
+
+    #[test]
+    fn test_fill_null() {
+        let a = Int32Array::from(vec![Some(1), None, Some(3), None]);
+        let b = fill_null(&a, -1);
+        assert_eq!(4, b.len());
+        assert_eq!(0, b.null_count());
+        assert_eq!(&[1, -1, 3, -1], b.value_slice(0, 4));
+
+        let a = Float64Array::from(vec![1.5, 2.0]);
+        assert_eq!(&[1.5, 2.0], fill_null(&a, 0.0).value_slice(0, 2));
+    }
+
+    #[test]
+    fn test_fill_null_binary() {
+        let a = BinaryArray::from(vec![None, Some("a"), None, Some("bc")]);
+        let b = fill_null_binary(&a, b"?").unwrap();
+        assert_eq!(4, b.len());
+        assert_eq!(0, b.null_count());
+        let values: Vec<&[u8]> = (0..4).map(|i| b.value(i)).collect();
+        assert_eq!(vec![&b"?"[..], b"a", b"?", b"bc"], values);
+    }
 }
@@ -17,94 +17,199 @@
 
 //! Defines boolean kernels on Arrow `BooleanArray`'s, e.g. `AND`, `OR` and `NOT`.
 //!
-//! These kernels can leverage SIMD if available on your system.  Currently no runtime
-//! detection is provided, you should enable the specific SIMD intrinsics using
-//! `RUSTFLAGS="-C target-feature=+avx2"` for example.  See the documentation
-//! [here] (https://doc.rust-lang.org/stable/std/arch/) for more information.
+//! These kernels operate on the bit-packed values and null bitmaps directly, 64 values
+//! at a time.
 
-use std::sync::Arc;
-
-use crate::array::{Array, BooleanArray};
+use crate::array::{Array, ArrayRef, BooleanArray};
 use crate::array_data::ArrayData;
 use crate::buffer::Buffer;
-use crate::compute::util::apply_bin_op_to_option_bitmap;
-use crate::datatypes::DataType;
+use crate::datatypes::{DataType, ToByteSlice};
 use crate::error::{ArrowError, Result};
+use crate::util::bit_util;
+
+/// Returns the values and the validity of `array` as 64-bit words, see
+/// `bit_util::get_bit_chunks`.
+fn boolean_words(array: &BooleanArray) -> (Vec<u64>, Vec<u64>) {
+    let (values, bit_offset) = array.bit_values();
+    let values = bit_util::get_bit_chunks(values.data(), bit_offset, array.len());
+    (values, validity_words(array.data_ref()))
+}
+
+/// Returns the validity of `data` as 64-bit words, see `bit_util::get_bit_chunks`. All
+/// elements of an array without null bitmap are valid, except for `NullArray`s.
+fn validity_words(data: &ArrayData) -> Vec<u64> {
+    let len = data.len();
+    match data.null_bitmap() {
+        Some(bitmap) => bit_util::get_bit_chunks(bitmap.bits.data(), data.offset(), len),
+        None if data.data_type() == &DataType::Null => vec![0; bit_util::ceil(len, 64)],
+        None => all_set_words(len),
+    }
+}
+
+/// Returns the words of `len` set bits.
+fn all_set_words(len: usize) -> Vec<u64> {
+    let mut words = vec![!0u64; bit_util::ceil(len, 64)];
+    if len % 64 != 0 {
+        let last = words.len() - 1;
+        words[last] = (1u64 << (len % 64)) - 1;
+    }
+    words
+}
+
+/// Creates a boolean array of length `len` from the words of its values and validity.
+fn words_to_boolean(len: usize, values: Vec<u64>, validity: Vec<u64>) -> BooleanArray {
+    let valid_count: usize = validity.iter().map(|w| w.count_ones() as usize).sum();
+    let mut builder = ArrayData::builder(DataType::Boolean)
+        .len(len)
+        .null_count(len - valid_count)
+        .add_buffer(Buffer::from(values.to_byte_slice()));
+    if valid_count < len {
+        builder = builder.null_bit_buffer(Buffer::from(validity.to_byte_slice()));
+    }
+    BooleanArray::from(builder.build())
+}
 
-/// Helper function to implement binary kernels
+/// Helper function to implement binary kernels, applying `op` to the words of the
+/// values and validity of both arrays and returning the words of the result.
 fn binary_boolean_kernel<F>(
     left: &BooleanArray,
     right: &BooleanArray,
     op: F,
 ) -> Result<BooleanArray>
 where
-    F: Fn(&Buffer, &Buffer) -> Result<Buffer>,
+    F: Fn(u64, u64, u64, u64) -> (u64, u64),
 {
-    if left.offset() != right.offset() {
+    if left.len() != right.len() {
         return Err(ArrowError::ComputeError(
-            "Cannot apply Bitwise binary op when arrays have different offsets."
-                .to_string(),
+            "Cannot perform bitwise operation on arrays of different length".to_string(),
         ));
     }
-
-    let left_data = left.data();
-    let right_data = right.data();
-    let null_bit_buffer = apply_bin_op_to_option_bitmap(
-        left_data.null_bitmap(),
-        right_data.null_bitmap(),
-        |a, b| a & b,
-    )?;
-    let values = op(&left_data.buffers()[0], &right_data.buffers()[0])?;
-    let data = ArrayData::new(
-        DataType::Boolean,
-        left.len(),
-        None,
-        null_bit_buffer,
-        left.offset(),
-        vec![values],
-        vec![],
-    );
-    Ok(BooleanArray::from(Arc::new(data)))
+    let (left_values, left_validity) = boolean_words(left);
+    let (right_values, right_validity) = boolean_words(right);
+    let mut values = Vec::with_capacity(left_values.len());
+    let mut validity = Vec::with_capacity(left_values.len());
+    for i in 0..left_values.len() {
+        let (v, valid) = op(
+            left_values[i],
+            left_validity[i],
+            right_values[i],
+            right_validity[i],
+        );
+        values.push(v & valid);
+        validity.push(valid);
+    }
+    Ok(words_to_boolean(left.len(), values, validity))
 }
 
-/// Performs `AND` operation on two arrays. If either left or right value is null then the
-/// result is also null.
+/// Performs `AND` operation on two arrays, following SQL three-valued logic: the result
+/// is `false` if either value is `false`, even if the other value is null, and is null
+/// if either value is null otherwise.
+///
+/// The arrays are processed 64 values at a time, and can have different offsets.
 pub fn and(left: &BooleanArray, right: &BooleanArray) -> Result<BooleanArray> {
-    binary_boolean_kernel(&left, &right, |a, b| a & b)
+    binary_boolean_kernel(&left, &right, |l, l_valid, r, r_valid| {
+        let valid = (l_valid & r_valid) | (l_valid & !l) | (r_valid & !r);
+        (l & r, valid)
+    })
 }
 
-/// Performs `OR` operation on two arrays. If either left or right value is null then the
-/// result is also null.
+/// Performs `OR` operation on two arrays, following SQL three-valued logic: the result
+/// is `true` if either value is `true`, even if the other value is null, and is null if
+/// either value is null otherwise.
+///
+/// The arrays are processed 64 values at a time, and can have different offsets.
 pub fn or(left: &BooleanArray, right: &BooleanArray) -> Result<BooleanArray> {
-    binary_boolean_kernel(&left, &right, |a, b| a | b)
+    binary_boolean_kernel(&left, &right, |l, l_valid, r, r_valid| {
+        let valid = (l_valid & r_valid) | (l_valid & l) | (r_valid & r);
+        ((l & l_valid) | (r & r_valid), valid)
+    })
 }
 
 /// Performs unary `NOT` operation on an arrays. If value is null then the result is also
 /// null.
 pub fn not(left: &BooleanArray) -> Result<BooleanArray> {
-    let data = left.data();
-    let null_bit_buffer = match *data.null_bitmap() {
-        None => None,
-        Some(ref b) => Some(b.bits.clone()),
-    };
-
-    let values = !&data.buffers()[0];
-    let data = ArrayData::new(
-        DataType::Boolean,
-        left.len(),
-        None,
-        null_bit_buffer,
-        left.offset(),
-        vec![values],
-        vec![],
-    );
-    Ok(BooleanArray::from(Arc::new(data)))
+    let (values, validity) = boolean_words(left);
+    let values = values
+        .iter()
+        .zip(validity.iter())
+        .map(|(v, valid)| !v & valid)
+        .collect();
+    Ok(words_to_boolean(left.len(), values, validity))
+}
+
+/// Returns whether each element of `array` is null, as a boolean array without null
+/// values. Works for arrays of any type.
+pub fn is_null(array: &ArrayRef) -> BooleanArray {
+    let len = array.len();
+    let values = validity_words(array.data_ref())
+        .iter()
+        .zip(all_set_words(len))
+        .map(|(valid, mask)| !valid & mask)
+        .collect();
+    words_to_boolean(len, values, all_set_words(len))
+}
+
+/// Returns whether each element of `array` is not null, as a boolean array without
+/// null values. Works for arrays of any type.
+pub fn is_valid(array: &ArrayRef) -> BooleanArray {
+    let len = array.len();
+    words_to_boolean(len, validity_words(array.data_ref()), all_set_words(len))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use crate::array::{BinaryArray, Int32Array, NullArray};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_is_null_is_valid() {
+        let a: ArrayRef = Arc::new(Int32Array::from(vec![Some(1), None, Some(3), None]));
+        let nulls = is_null(&a);
+        assert_eq!(0, nulls.null_count());
+        assert_eq!(
+            vec![false, true, false, true],
+            (0..4).map(|i| nulls.value(i)).collect::<Vec<_>>()
+        );
+        let valid = is_valid(&a);
+        assert_eq!(0, valid.null_count());
+        assert_eq!(
+            vec![true, false, true, false],
+            (0..4).map(|i| valid.value(i)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_is_null_is_valid_without_null_bitmap() {
+        let a: ArrayRef = Arc::new(BinaryArray::from(vec!["a"; 70]));
+        assert!((0..70).all(|i| !is_null(&a).value(i) && is_valid(&a).value(i)));
+
+        let a: ArrayRef = Arc::new(NullArray::new(3));
+        assert!((0..3).all(|i| is_null(&a).value(i) && !is_valid(&a).value(i)));
+    }
+
+    #[test]
+    fn test_is_null_sliced() {
+        let values: Vec<Option<i32>> = (0..100)
+            .map(|i| if i % 3 == 0 { None } else { Some(i) })
+            .collect();
+        let data = Int32Array::from(values).data();
+        let a: ArrayRef = Arc::new(Int32Array::from(
+            ArrayData::builder(DataType::Int32)
+                .len(90)
+                .offset(5)
+                .null_bit_buffer(data.null_bitmap().as_ref().unwrap().bits.clone())
+                .buffers(data.buffers().to_vec())
+                .build(),
+        ));
+        let nulls = is_null(&a);
+        assert_eq!(90, nulls.len());
+        for i in 0..90 {
+            assert_eq!((i + 5) % 3 == 0, nulls.value(i));
+        }
+    }
+
     #[test]
     fn test_bool_array_and() {
         let a = BooleanArray::from(vec![false, false, true, true]);
@@ -154,8 +259,107 @@ mod tests {
         let b = BooleanArray::from(vec![None, None, Some(false), Some(false)]);
         let c = and(&a, &b).unwrap();
         assert_eq!(true, c.is_null(0));
-        assert_eq!(true, c.is_null(1));
-        assert_eq!(true, c.is_null(2));
+        assert_eq!(false, c.is_null(1));
+        assert_eq!(false, c.is_null(2));
         assert_eq!(false, c.is_null(3));
+        assert_eq!(false, c.value(1));
+        assert_eq!(false, c.value(2));
+        assert_eq!(false, c.value(3));
+    }
+
+    #[test]
+    fn test_bool_array_three_valued_logic() {
+        let values = vec![Some(true), Some(false), None];
+        let (mut left, mut right) = (vec![], vec![]);
+        for l in &values {
+            for r in &values {
+                left.push(*l);
+                right.push(*r);
+            }
+        }
+        let a = BooleanArray::from(left.clone());
+        let b = BooleanArray::from(right.clone());
+        let and_result = and(&a, &b).unwrap();
+        let or_result = or(&a, &b).unwrap();
+        let value = |array: &BooleanArray, i| {
+            if array.is_null(i) {
+                None
+            } else {
+                Some(array.value(i))
+            }
+        };
+        for i in 0..left.len() {
+            let expected_and = match (left[i], right[i]) {
+                (Some(false), _) | (_, Some(false)) => Some(false),
+                (Some(true), Some(true)) => Some(true),
+                _ => None,
+            };
+            let expected_or = match (left[i], right[i]) {
+                (Some(true), _) | (_, Some(true)) => Some(true),
+                (Some(false), Some(false)) => Some(false),
+                _ => None,
+            };
+            assert_eq!(expected_and, value(&and_result, i));
+            assert_eq!(expected_or, value(&or_result, i));
+        }
+    }
+
+    #[test]
+    fn test_bool_array_offsets() {
+        let values: Vec<Option<bool>> = (0..150)
+            .map(|i| match i % 5 {
+                0 => None,
+                1 | 3 => Some(true),
+                _ => Some(false),
+            })
+            .collect();
+        let full = BooleanArray::from(values.clone());
+        let sliced = |offset: usize, len: usize| {
+            let data = full.data();
+            let array_data = ArrayData::builder(DataType::Boolean)
+                .len(len)
+                .offset(offset)
+                .add_buffer(data.buffers()[0].clone())
+                .null_bit_buffer(data.null_bitmap().as_ref().unwrap().bits.clone())
+                .build();
+            BooleanArray::from(array_data)
+        };
+        let a = sliced(3, 140);
+        let b = sliced(9, 140);
+        let value = |array: &BooleanArray, i| {
+            if array.is_null(i) {
+                None
+            } else {
+                Some(array.value(i))
+            }
+        };
+        let and_result = and(&a, &b).unwrap();
+        let or_result = or(&a, &b).unwrap();
+        let not_result = not(&a).unwrap();
+        for i in 0..140 {
+            let (l, r) = (values[i + 3], values[i + 9]);
+            let expected_and = match (l, r) {
+                (Some(false), _) | (_, Some(false)) => Some(false),
+                (Some(true), Some(true)) => Some(true),
+                _ => None,
+            };
+            let expected_or = match (l, r) {
+                (Some(true), _) | (_, Some(true)) => Some(true),
+                (Some(false), Some(false)) => Some(false),
+                _ => None,
+            };
+            assert_eq!(expected_and, value(&and_result, i));
+            assert_eq!(expected_or, value(&or_result, i));
+            assert_eq!(l.map(|v| !v), value(&not_result, i));
+        }
+        assert_eq!(a.null_count(), not_result.null_count());
+    }
+
+    #[test]
+    fn test_bool_array_different_length() {
+        let a = BooleanArray::from(vec![true, false]);
+        let b = BooleanArray::from(vec![true]);
+        assert!(and(&a, &b).is_err());
+        assert!(or(&a, &b).is_err());
     }
 }
@@ -0,0 +1,489 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines accumulators that aggregate a stream of arrays into a single value.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::array::*;
+use crate::compute::array_ops::{max, min, sum};
+use crate::compute::kernels::row::encode_rows;
+use crate::compute::kernels::take::take;
+use crate::datatypes::*;
+use crate::error::{ArrowError, Result};
+
+/// A single value of a primitive type, e.g. the result of an aggregation
+#[derive(Clone, Debug, PartialEq)]
+pub enum ScalarValue {
+    Null,
+    Int8(i8),
+    Int16(i16),
+    Int32(i32),
+    Int64(i64),
+    UInt8(u8),
+    UInt16(u16),
+    UInt32(u32),
+    UInt64(u64),
+    Float32(f32),
+    Float64(f64),
+}
+
+impl ScalarValue {
+    /// Returns the data type of the value, or `None` for `ScalarValue::Null`
+    pub fn data_type(&self) -> Option<DataType> {
+        match self {
+            ScalarValue::Null => None,
+            ScalarValue::Int8(_) => Some(DataType::Int8),
+            ScalarValue::Int16(_) => Some(DataType::Int16),
+            ScalarValue::Int32(_) => Some(DataType::Int32),
+            ScalarValue::Int64(_) => Some(DataType::Int64),
+            ScalarValue::UInt8(_) => Some(DataType::UInt8),
+            ScalarValue::UInt16(_) => Some(DataType::UInt16),
+            ScalarValue::UInt32(_) => Some(DataType::UInt32),
+            ScalarValue::UInt64(_) => Some(DataType::UInt64),
+            ScalarValue::Float32(_) => Some(DataType::Float32),
+            ScalarValue::Float64(_) => Some(DataType::Float64),
+        }
+    }
+}
+
+/// Aggregates the values of a sequence of arrays, one array at a time
+pub trait Accumulator {
+    /// Folds the non-null values of `array` into the state of the accumulator
+    fn update(&mut self, array: &ArrayRef) -> Result<()>;
+
+    /// Returns the aggregate of the values seen so far, or `ScalarValue::Null` if there
+    /// were none
+    fn finish(&self) -> ScalarValue;
+}
+
+/// Applies the aggregation kernel `$kernel` to numeric `$array`, returning the result as
+/// an optional `ScalarValue`
+macro_rules! aggregate_array {
+    ($array:expr, $kernel:ident) => {{
+        let array = $array;
+        match array.data_type() {
+            DataType::Int8 => {
+                $kernel(as_primitive_array::<Int8Type>(array)).map(ScalarValue::Int8)
+            }
+            DataType::Int16 => {
+                $kernel(as_primitive_array::<Int16Type>(array)).map(ScalarValue::Int16)
+            }
+            DataType::Int32 => {
+                $kernel(as_primitive_array::<Int32Type>(array)).map(ScalarValue::Int32)
+            }
+            DataType::Int64 => {
+                $kernel(as_primitive_array::<Int64Type>(array)).map(ScalarValue::Int64)
+            }
+            DataType::UInt8 => {
+                $kernel(as_primitive_array::<UInt8Type>(array)).map(ScalarValue::UInt8)
+            }
+            DataType::UInt16 => {
+                $kernel(as_primitive_array::<UInt16Type>(array)).map(ScalarValue::UInt16)
+            }
+            DataType::UInt32 => {
+                $kernel(as_primitive_array::<UInt32Type>(array)).map(ScalarValue::UInt32)
+            }
+            DataType::UInt64 => {
+                $kernel(as_primitive_array::<UInt64Type>(array)).map(ScalarValue::UInt64)
+            }
+            DataType::Float32 => $kernel(as_primitive_array::<Float32Type>(array))
+                .map(ScalarValue::Float32),
+            DataType::Float64 => $kernel(as_primitive_array::<Float64Type>(array))
+                .map(ScalarValue::Float64),
+            other => {
+                return Err(ArrowError::ComputeError(format!(
+                    "{} is not supported for {:?}",
+                    stringify!($kernel),
+                    other
+                )))
+            }
+        }
+    }};
+}
+
+/// Combines two values of the same variant with `$int` for integers and `$float` for
+/// floating point numbers, both of which combine `$a` and `$b`
+macro_rules! combine {
+    ($left:expr, $right:expr, |$a:ident, $b:ident| $int:expr, $float:expr) => {
+        match ($left, $right) {
+            (ScalarValue::Int8($a), ScalarValue::Int8($b)) => ScalarValue::Int8($int),
+            (ScalarValue::Int16($a), ScalarValue::Int16($b)) => ScalarValue::Int16($int),
+            (ScalarValue::Int32($a), ScalarValue::Int32($b)) => ScalarValue::Int32($int),
+            (ScalarValue::Int64($a), ScalarValue::Int64($b)) => ScalarValue::Int64($int),
+            (ScalarValue::UInt8($a), ScalarValue::UInt8($b)) => ScalarValue::UInt8($int),
+            (ScalarValue::UInt16($a), ScalarValue::UInt16($b)) => {
+                ScalarValue::UInt16($int)
+            }
+            (ScalarValue::UInt32($a), ScalarValue::UInt32($b)) => {
+                ScalarValue::UInt32($int)
+            }
+            (ScalarValue::UInt64($a), ScalarValue::UInt64($b)) => {
+                ScalarValue::UInt64($int)
+            }
+            (ScalarValue::Float32($a), ScalarValue::Float32($b)) => {
+                ScalarValue::Float32($float)
+            }
+            (ScalarValue::Float64($a), ScalarValue::Float64($b)) => {
+                ScalarValue::Float64($float)
+            }
+            (left, _) => left,
+        }
+    };
+}
+
+/// The running state of the accumulators of numeric values
+#[derive(Debug, Default)]
+struct NumericState {
+    /// The type of the arrays, set by the first update
+    data_type: Option<DataType>,
+    value: Option<ScalarValue>,
+}
+
+impl NumericState {
+    /// Checks that `array` has the type of the previous arrays, and combines `value`,
+    /// the aggregate of `array`, with the aggregate of the previous arrays with `f`
+    fn update<F>(
+        &mut self,
+        array: &ArrayRef,
+        value: Option<ScalarValue>,
+        f: F,
+    ) -> Result<()>
+    where
+        F: Fn(ScalarValue, ScalarValue) -> ScalarValue,
+    {
+        match &self.data_type {
+            Some(data_type) if data_type != array.data_type() => {
+                return Err(ArrowError::ComputeError(format!(
+                    "Cannot aggregate {:?} with {:?}",
+                    array.data_type(),
+                    data_type
+                )))
+            }
+            Some(_) => {}
+            None => self.data_type = Some(array.data_type().clone()),
+        }
+        self.value = match (self.value.take(), value) {
+            (Some(a), Some(b)) => Some(f(a, b)),
+            (a, b) => a.or(b),
+        };
+        Ok(())
+    }
+
+    fn finish(&self) -> ScalarValue {
+        self.value.clone().unwrap_or(ScalarValue::Null)
+    }
+}
+
+/// Accumulates the sum of numeric arrays, like `sum`: integer sums wrap on overflow
+#[derive(Debug, Default)]
+pub struct SumAccumulator {
+    state: NumericState,
+}
+
+impl SumAccumulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Accumulator for SumAccumulator {
+    fn update(&mut self, array: &ArrayRef) -> Result<()> {
+        let value = aggregate_array!(array, sum);
+        self.state.update(array, value, |a, b| {
+            combine!(a, b, |x, y| x.wrapping_add(y), x + y)
+        })
+    }
+
+    fn finish(&self) -> ScalarValue {
+        self.state.finish()
+    }
+}
+
+/// Accumulates the minimum of numeric arrays
+#[derive(Debug, Default)]
+pub struct MinAccumulator {
+    state: NumericState,
+}
+
+impl MinAccumulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Accumulator for MinAccumulator {
+    fn update(&mut self, array: &ArrayRef) -> Result<()> {
+        let value = aggregate_array!(array, min);
+        self.state.update(array, value, |a, b| {
+            combine!(a, b, |x, y| x.min(y), if y < x { y } else { x })
+        })
+    }
+
+    fn finish(&self) -> ScalarValue {
+        self.state.finish()
+    }
+}
+
+/// Accumulates the maximum of numeric arrays
+#[derive(Debug, Default)]
+pub struct MaxAccumulator {
+    state: NumericState,
+}
+
+impl MaxAccumulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Accumulator for MaxAccumulator {
+    fn update(&mut self, array: &ArrayRef) -> Result<()> {
+        let value = aggregate_array!(array, max);
+        self.state.update(array, value, |a, b| {
+            combine!(a, b, |x, y| x.max(y), if y > x { y } else { x })
+        })
+    }
+
+    fn finish(&self) -> ScalarValue {
+        self.state.finish()
+    }
+}
+
+/// Accumulates the number of non-null values of arrays of any type, as a `UInt64`
+#[derive(Debug, Default)]
+pub struct CountAccumulator {
+    count: u64,
+}
+
+impl CountAccumulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Accumulator for CountAccumulator {
+    fn update(&mut self, array: &ArrayRef) -> Result<()> {
+        self.count += (array.len() - array.null_count()) as u64;
+        Ok(())
+    }
+
+    fn finish(&self) -> ScalarValue {
+        ScalarValue::UInt64(self.count)
+    }
+}
+
+/// Builds an array of `$array_type` from `$values`, with a null for every value that is
+/// not a `ScalarValue::$variant`
+macro_rules! scalars_to_array {
+    ($values:expr, $variant:ident, $array_type:ident) => {{
+        let values: Vec<_> = $values
+            .iter()
+            .map(|v| match v {
+                ScalarValue::$variant(v) => Some(*v),
+                _ => None,
+            })
+            .collect();
+        Arc::new($array_type::from(values)) as ArrayRef
+    }};
+}
+
+/// Groups the rows of `values` by the value of `keys` on the same row, and aggregates
+/// the values of each group with a fresh accumulator created by `agg`.
+///
+/// Returns the distinct keys, in the order of their first row, and the aggregate of the
+/// values of each group. Null keys form a group of their own. Keys may be of any type
+/// supported by `encode_rows`, and values of any type supported by `take` and the
+/// accumulator.
+///
+/// ```
+/// use std::sync::Arc;
+/// use arrow::array::{ArrayRef, BinaryArray, Int32Array};
+/// use arrow::compute::{group_aggregate, Accumulator, SumAccumulator};
+///
+/// let keys: ArrayRef = Arc::new(BinaryArray::from(vec!["a", "b", "a"]));
+/// let values: ArrayRef = Arc::new(Int32Array::from(vec![1, 2, 3]));
+/// let (keys, sums) =
+///     group_aggregate(&keys, &values, || Box::new(SumAccumulator::new())).unwrap();
+/// let sums = sums.as_any().downcast_ref::<Int32Array>().unwrap();
+/// assert_eq!(2, keys.len());
+/// assert_eq!(4, sums.value(0));
+/// assert_eq!(2, sums.value(1));
+/// ```
+pub fn group_aggregate(
+    keys: &ArrayRef,
+    values: &ArrayRef,
+    agg: fn() -> Box<dyn Accumulator>,
+) -> Result<(ArrayRef, ArrayRef)> {
+    if keys.len() != values.len() {
+        return Err(ArrowError::ComputeError(format!(
+            "Cannot group {} values by {} keys",
+            values.len(),
+            keys.len()
+        )));
+    }
+    let rows = encode_rows(&[(keys.clone(), false)])?;
+
+    // the indices of the rows of each group, in the order of their first row
+    let mut groups: HashMap<&[u8], usize> = HashMap::new();
+    let mut group_rows: Vec<Vec<u32>> = vec![];
+    for i in 0..rows.len() {
+        let group = *groups.entry(rows.value(i)).or_insert_with(|| {
+            group_rows.push(vec![]);
+            group_rows.len() - 1
+        });
+        group_rows[group].push(i as u32);
+    }
+
+    let first_rows: Vec<u32> = group_rows.iter().map(|rows| rows[0]).collect();
+    let group_keys = take(keys, &UInt32Array::from(first_rows))?;
+
+    let mut aggregates = Vec::with_capacity(group_rows.len());
+    for rows in group_rows {
+        let mut accumulator = agg();
+        accumulator.update(&take(values, &UInt32Array::from(rows))?)?;
+        aggregates.push(accumulator.finish());
+    }
+
+    // groups of null values aggregate to null, with the data type of the values
+    let data_type = aggregates
+        .iter()
+        .find_map(|v| v.data_type())
+        .unwrap_or_else(|| values.data_type().clone());
+    let aggregates = match data_type {
+        DataType::Int8 => scalars_to_array!(aggregates, Int8, Int8Array),
+        DataType::Int16 => scalars_to_array!(aggregates, Int16, Int16Array),
+        DataType::Int32 => scalars_to_array!(aggregates, Int32, Int32Array),
+        DataType::Int64 => scalars_to_array!(aggregates, Int64, Int64Array),
+        DataType::UInt8 => scalars_to_array!(aggregates, UInt8, UInt8Array),
+        DataType::UInt16 => scalars_to_array!(aggregates, UInt16, UInt16Array),
+        DataType::UInt32 => scalars_to_array!(aggregates, UInt32, UInt32Array),
+        DataType::UInt64 => scalars_to_array!(aggregates, UInt64, UInt64Array),
+        DataType::Float32 => scalars_to_array!(aggregates, Float32, Float32Array),
+        DataType::Float64 => scalars_to_array!(aggregates, Float64, Float64Array),
+        other => {
+            return Err(ArrowError::ComputeError(format!(
+                "Cannot build an array of {:?} aggregates",
+                other
+            )))
+        }
+    };
+    Ok((group_keys, aggregates))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn int32(values: Vec<Option<i32>>) -> ArrayRef {
+        Arc::new(Int32Array::from(values))
+    }
+
+    #[test]
+    fn test_sum_accumulator() {
+        let mut acc = SumAccumulator::new();
+        assert_eq!(ScalarValue::Null, acc.finish());
+        acc.update(&int32(vec![Some(1), Some(2), None])).unwrap();
+        acc.update(&int32(vec![None, None])).unwrap();
+        acc.update(&int32(vec![Some(10), Some(-4)])).unwrap();
+        assert_eq!(ScalarValue::Int32(9), acc.finish());
+    }
+
+    #[test]
+    fn test_min_max_count_accumulators() {
+        let arrays = vec![
+            int32(vec![Some(5), None, Some(3)]),
+            int32(vec![]),
+            int32(vec![Some(8), Some(-1)]),
+        ];
+        let mut min = MinAccumulator::new();
+        let mut max = MaxAccumulator::new();
+        let mut count = CountAccumulator::new();
+        for array in &arrays {
+            min.update(array).unwrap();
+            max.update(array).unwrap();
+            count.update(array).unwrap();
+        }
+        assert_eq!(ScalarValue::Int32(-1), min.finish());
+        assert_eq!(ScalarValue::Int32(8), max.finish());
+        assert_eq!(ScalarValue::UInt64(4), count.finish());
+    }
+
+    #[test]
+    fn test_float_accumulators() {
+        let a: ArrayRef = Arc::new(Float64Array::from(vec![1.5, -2.0]));
+        let b: ArrayRef = Arc::new(Float64Array::from(vec![Some(4.0), None]));
+        let mut sum = SumAccumulator::new();
+        let mut max = MaxAccumulator::new();
+        for array in &[a, b] {
+            sum.update(array).unwrap();
+            max.update(array).unwrap();
+        }
+        assert_eq!(ScalarValue::Float64(3.5), sum.finish());
+        assert_eq!(ScalarValue::Float64(4.0), max.finish());
+    }
+
+    #[test]
+    fn test_accumulator_type_errors() {
+        let mut sum = SumAccumulator::new();
+        sum.update(&int32(vec![Some(1)])).unwrap();
+        let other: ArrayRef = Arc::new(Int64Array::from(vec![1]));
+        assert!(sum.update(&other).is_err());
+
+        let strings: ArrayRef = Arc::new(BinaryArray::from(vec!["a"]));
+        assert!(MinAccumulator::new().update(&strings).is_err());
+        let mut count = CountAccumulator::new();
+        count.update(&strings).unwrap();
+        assert_eq!(ScalarValue::UInt64(1), count.finish());
+    }
+
+    #[test]
+    fn test_group_aggregate() {
+        let keys: ArrayRef = Arc::new(BinaryArray::from(vec!["a", "b", "a"]));
+        let values = int32(vec![Some(1), Some(2), Some(3)]);
+        let (keys, sums) =
+            group_aggregate(&keys, &values, || Box::new(SumAccumulator::new())).unwrap();
+        let keys = keys.as_any().downcast_ref::<BinaryArray>().unwrap();
+        let sums = sums.as_any().downcast_ref::<Int32Array>().unwrap();
+        assert_eq!(2, keys.len());
+        assert_eq!(b"a", keys.value(0));
+        assert_eq!(4, sums.value(0));
+        assert_eq!(b"b", keys.value(1));
+        assert_eq!(2, sums.value(1));
+    }
+
+    #[test]
+    fn test_group_aggregate_nulls() {
+        let keys = int32(vec![Some(1), None, Some(2), None, Some(1)]);
+        let values = int32(vec![Some(5), Some(1), None, Some(2), Some(-3)]);
+
+        let (keys, mins) =
+            group_aggregate(&keys, &values, || Box::new(MinAccumulator::new())).unwrap();
+        assert!(keys.equals(&*int32(vec![Some(1), None, Some(2)])));
+        assert!(mins.equals(&*int32(vec![Some(-3), Some(1), None])));
+
+        let (_, counts) =
+            group_aggregate(&keys, &keys, || Box::new(CountAccumulator::new())).unwrap();
+        let counts = counts.as_any().downcast_ref::<UInt64Array>().unwrap();
+        assert_eq!(vec![1, 0, 1], counts.value_slice(0, 3).to_vec());
+
+        assert!(
+            group_aggregate(&keys, &values, || Box::new(SumAccumulator::new())).is_err()
+        );
+    }
+}
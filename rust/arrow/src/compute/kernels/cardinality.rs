@@ -0,0 +1,140 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines kernels estimating the number of distinct values of an array.
+
+use crate::array::ArrayRef;
+use crate::compute::kernels::hash::hash_array;
+use crate::error::{ArrowError, Result};
+
+/// Minimum precision supported by `approx_count_distinct`
+const MIN_PRECISION: u8 = 4;
+/// Maximum precision supported by `approx_count_distinct`
+const MAX_PRECISION: u8 = 16;
+
+/// Estimates the number of distinct non-null values of `array` with HyperLogLog.
+///
+/// `precision` must be between 4 and 16: the estimator uses `2^precision` one-byte
+/// registers, and its relative standard error is about `1.04 / sqrt(2^precision)`, e.g.
+/// 1.6% for a precision of 12. Values are hashed with `hash_array`, so all types
+/// supported there are supported here.
+pub fn approx_count_distinct(array: &ArrayRef, precision: u8) -> Result<u64> {
+    if !(MIN_PRECISION..=MAX_PRECISION).contains(&precision) {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "HyperLogLog precision must be between {} and {}, got {}",
+            MIN_PRECISION, MAX_PRECISION, precision
+        )));
+    }
+    let mut hashes = vec![0; array.len()];
+    hash_array(array, 0, &mut hashes)?;
+
+    let p = u32::from(precision);
+    let mut registers = vec![0u8; 1 << p];
+    for (i, h) in hashes.iter().enumerate() {
+        if array.is_null(i) {
+            continue;
+        }
+        // the first `p` bits select the register, and the position of the first set bit
+        // among the remaining ones is the rank of the value
+        let index = (h >> (64 - p)) as usize;
+        let rank = ((h << p).leading_zeros().min(64 - p) + 1) as u8;
+        if rank > registers[index] {
+            registers[index] = rank;
+        }
+    }
+
+    let m = registers.len() as f64;
+    let alpha = match registers.len() {
+        16 => 0.673,
+        32 => 0.697,
+        64 => 0.709,
+        _ => 0.7213 / (1.0 + 1.079 / m),
+    };
+    let sum: f64 = registers.iter().map(|r| 2f64.powi(-i32::from(*r))).sum();
+    let estimate = alpha * m * m / sum;
+
+    // use linear counting for small cardinalities, where it is more accurate
+    let zeros = registers.iter().filter(|r| **r == 0).count();
+    if estimate <= 2.5 * m && zeros > 0 {
+        Ok((m * (m / zeros as f64).ln()).round() as u64)
+    } else {
+        Ok(estimate.round() as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    use crate::array::{BinaryArray, Int32Array, Int64Array};
+
+    fn assert_within(expected: u64, actual: u64, tolerance: f64) {
+        let error = (actual as f64 - expected as f64).abs() / expected as f64;
+        assert!(
+            error <= tolerance,
+            "estimate {} is not within {} of {}",
+            actual,
+            tolerance,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_approx_count_distinct_primitive() {
+        let values: Vec<Option<i64>> = (0..20_000)
+            .map(|i| if i % 7 == 0 { None } else { Some(i % 1_000) })
+            .collect();
+        let array: ArrayRef = Arc::new(Int64Array::from(values));
+        assert_within(1_000, approx_count_distinct(&array, 12).unwrap(), 0.05);
+    }
+
+    #[test]
+    fn test_approx_count_distinct_large() {
+        let array: ArrayRef =
+            Arc::new(Int32Array::from((0..100_000).collect::<Vec<_>>()));
+        assert_within(100_000, approx_count_distinct(&array, 14).unwrap(), 0.05);
+    }
+
+    #[test]
+    fn test_approx_count_distinct_binary() {
+        let strings: Vec<String> =
+            (0..5_000).map(|i| format!("s{}", i % 1_000)).collect();
+        let array: ArrayRef = Arc::new(BinaryArray::from(
+            strings.iter().map(|s| s.as_str()).collect::<Vec<_>>(),
+        ));
+        assert_within(1_000, approx_count_distinct(&array, 12).unwrap(), 0.05);
+    }
+
+    #[test]
+    fn test_approx_count_distinct_small_and_empty() {
+        let array: ArrayRef =
+            Arc::new(Int32Array::from(vec![Some(1), None, Some(2), Some(1)]));
+        assert_eq!(2, approx_count_distinct(&array, 8).unwrap());
+
+        let array: ArrayRef = Arc::new(Int32Array::from(vec![None, None]));
+        assert_eq!(0, approx_count_distinct(&array, 8).unwrap());
+    }
+
+    #[test]
+    fn test_approx_count_distinct_invalid_precision() {
+        let array: ArrayRef = Arc::new(Int32Array::from(vec![1]));
+        assert!(approx_count_distinct(&array, 3).is_err());
+        assert!(approx_count_distinct(&array, 17).is_err());
+    }
+}
@@ -0,0 +1,1053 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines cast kernels for `ArrayRef`, allowing casting arrays between supported
+//! datatypes.
+//!
+//! Timestamps with timezones can be changed in two different ways:
+//!
+//! * `cast` to a timestamp type with another timezone *reinterprets* the values: the
+//!   stored values, and therefore the instants they represent, are unchanged, and only
+//!   the timezone label of the data type differs. `2019-01-01T12:00:00+02:00` becomes
+//!   `2019-01-01T15:00:00+05:00`.
+//! * `convert_timezone` *converts* the values: the wall-clock reading of each value is
+//!   kept, so the stored values are shifted by the difference between the two timezones.
+//!   `2019-01-01T12:00:00+02:00` becomes `2019-01-01T12:00:00+05:00`.
+
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+use crate::array::*;
+use crate::array_data::{ArrayData, ArrayDataRef};
+use crate::buffer::Buffer;
+use crate::builder::{BinaryBuilder, BooleanBuilder, PrimitiveBuilder};
+use crate::datatypes::*;
+use crate::error::{ArrowError, Result};
+
+/// Number of milliseconds in a day
+const MILLISECONDS_IN_DAY: i64 = 86_400_000;
+
+/// Options that define how `cast_with_options` handles values that cannot be cast.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CastOptions {
+    /// Whether values that cannot be cast become null (safe mode), instead of returning
+    /// an error (strict mode)
+    pub safe: bool,
+}
+
+impl Default for CastOptions {
+    fn default() -> Self {
+        Self { safe: true }
+    }
+}
+
+/// Cast `array` to the provided data type and return a new `ArrayRef`, in safe mode:
+/// values that cannot be cast become null.
+///
+/// Currently supported casts are:
+///
+/// * casts to the same data type, which return a copy of the reference to `array`
+/// * timestamp to timestamp with the same time unit, changing (or adding or removing) the
+///   timezone label without changing the values. Use `convert_timezone` to keep the
+///   wall-clock reading instead.
+/// * timestamp to timestamp with another time unit, multiplying the values or dividing
+///   them, truncating, by the ratio of the units. Returns an error if a value overflows
+///   when converting to a finer unit.
+/// * timestamp, date and time to their backing integer type, `Int64` or `Int32`, and
+///   back, reusing the values without copying them
+/// * `Date32(Day)` to `Date64(Millisecond)` and back, flooring milliseconds to the day.
+///   Returns an error if a day is out of the range of `Date32`.
+/// * `List` to `LargeList` and back, with the same value type
+/// * numeric to `Utf8`, formatting each value with `to_string`, so floats are never in
+///   scientific notation
+/// * `Utf8` and `LargeUtf8` to numeric, parsing each value with `str::parse`
+/// * numeric to `Boolean`, with zero as `false` and any other value, including `NaN`, as
+///   `true`
+/// * `Utf8` and `LargeUtf8` to `Boolean`, parsing `true`, `t`, `yes` and `1` as `true`
+///   and `false`, `f`, `no` and `0` as `false`, ignoring case
+pub fn cast(array: &ArrayRef, to_type: &DataType) -> Result<ArrayRef> {
+    cast_with_options(array, to_type, &CastOptions::default())
+}
+
+/// Cast `array` to the provided data type like `cast`, with `options` defining whether
+/// values that cannot be cast become null or return an error.
+pub fn cast_with_options(
+    array: &ArrayRef,
+    to_type: &DataType,
+    options: &CastOptions,
+) -> Result<ArrayRef> {
+    use DataType::*;
+    let from_type = array.data_type();
+
+    if from_type == to_type {
+        return Ok(array.clone());
+    }
+    match (from_type, to_type) {
+        (Timestamp(from_unit, _), Timestamp(to_unit, _)) if from_unit == to_unit => {
+            Ok(reinterpret(&array.data(), to_type.clone()))
+        }
+        (Timestamp(from_unit, _), Timestamp(to_unit, _)) => {
+            convert_timestamp_unit(array, from_unit, to_unit, to_type)
+        }
+        _ if temporal_integer_type(from_type).as_ref() == Some(to_type)
+            || temporal_integer_type(to_type).as_ref() == Some(from_type) =>
+        {
+            Ok(reinterpret(&array.data(), to_type.clone()))
+        }
+        (Date32(DateUnit::Day), Date64(DateUnit::Millisecond)) => {
+            let dates = try_map_values::<Date32Type, Date64Type, _>(array, |days| {
+                Ok(i64::from(days) * MILLISECONDS_IN_DAY)
+            })?;
+            Ok(Arc::new(dates))
+        }
+        (Date64(DateUnit::Millisecond), Date32(DateUnit::Day)) => {
+            let dates = try_map_values::<Date64Type, Date32Type, _>(array, |ms| {
+                let days = ms.div_euclid(MILLISECONDS_IN_DAY);
+                i32::try_from(days).map_err(|_| {
+                    ArrowError::ComputeError(format!(
+                        "Overflow when casting date {} from {:?} to {:?}",
+                        ms, from_type, to_type
+                    ))
+                })
+            })?;
+            Ok(Arc::new(dates))
+        }
+        (List(from_value_type), LargeList(to_value_type))
+            if from_value_type == to_value_type =>
+        {
+            let list = array.as_any().downcast_ref::<ListArray>().unwrap();
+            Ok(Arc::new(list_to_large_list(list)?))
+        }
+        (LargeList(from_value_type), List(to_value_type))
+            if from_value_type == to_value_type =>
+        {
+            let list = array.as_any().downcast_ref::<LargeListArray>().unwrap();
+            Ok(Arc::new(large_list_to_list(list)?))
+        }
+        (Int8, Utf8) => numeric_to_string::<Int8Type>(array),
+        (Int16, Utf8) => numeric_to_string::<Int16Type>(array),
+        (Int32, Utf8) => numeric_to_string::<Int32Type>(array),
+        (Int64, Utf8) => numeric_to_string::<Int64Type>(array),
+        (UInt8, Utf8) => numeric_to_string::<UInt8Type>(array),
+        (UInt16, Utf8) => numeric_to_string::<UInt16Type>(array),
+        (UInt32, Utf8) => numeric_to_string::<UInt32Type>(array),
+        (UInt64, Utf8) => numeric_to_string::<UInt64Type>(array),
+        (Float32, Utf8) => numeric_to_string::<Float32Type>(array),
+        (Float64, Utf8) => numeric_to_string::<Float64Type>(array),
+        (Utf8, _) if to_type.is_numeric() => {
+            let strings = array.as_any().downcast_ref::<BinaryArray>().unwrap();
+            string_to_numeric_array(strings, to_type, options)
+        }
+        (LargeUtf8, _) if to_type.is_numeric() => {
+            let strings = array.as_any().downcast_ref::<LargeBinaryArray>().unwrap();
+            string_to_numeric_array(strings, to_type, options)
+        }
+        (Int8, Boolean) => numeric_to_boolean::<Int8Type>(array),
+        (Int16, Boolean) => numeric_to_boolean::<Int16Type>(array),
+        (Int32, Boolean) => numeric_to_boolean::<Int32Type>(array),
+        (Int64, Boolean) => numeric_to_boolean::<Int64Type>(array),
+        (UInt8, Boolean) => numeric_to_boolean::<UInt8Type>(array),
+        (UInt16, Boolean) => numeric_to_boolean::<UInt16Type>(array),
+        (UInt32, Boolean) => numeric_to_boolean::<UInt32Type>(array),
+        (UInt64, Boolean) => numeric_to_boolean::<UInt64Type>(array),
+        (Float32, Boolean) => numeric_to_boolean::<Float32Type>(array),
+        (Float64, Boolean) => numeric_to_boolean::<Float64Type>(array),
+        (Utf8, Boolean) => {
+            let strings = array.as_any().downcast_ref::<BinaryArray>().unwrap();
+            Ok(Arc::new(string_to_boolean(strings, options)?))
+        }
+        (LargeUtf8, Boolean) => {
+            let strings = array.as_any().downcast_ref::<LargeBinaryArray>().unwrap();
+            Ok(Arc::new(string_to_boolean(strings, options)?))
+        }
+        (_, _) => Err(ArrowError::ComputeError(format!(
+            "Casting from {:?} to {:?} not supported",
+            from_type, to_type,
+        ))),
+    }
+}
+
+/// Converts a timestamp array to the timezone `tz`, keeping the wall-clock reading of
+/// each value.
+///
+/// Values of an array without a timezone are read as UTC. Unlike casting to a timestamp
+/// with another timezone, which only relabels the values, this shifts every value by the
+/// difference between the two timezones, so the instants represented change.
+///
+/// Timezones are either `UTC` or a fixed offset from UTC such as `+05:30` or `-0800`.
+pub fn convert_timezone(array: &ArrayRef, tz: &str) -> Result<ArrayRef> {
+    let (unit, from_tz) = match array.data_type() {
+        DataType::Timestamp(unit, from_tz) => (unit.clone(), from_tz.clone()),
+        dt => {
+            return Err(ArrowError::ComputeError(format!(
+                "Cannot convert the timezone of a non-timestamp array of type {:?}",
+                dt
+            )));
+        }
+    };
+    let from_offset = match from_tz {
+        Some(ref from_tz) => timezone_offset_seconds(from_tz)?,
+        None => 0,
+    };
+    let shift_seconds = from_offset - timezone_offset_seconds(tz)?;
+    let to_type = DataType::Timestamp(unit.clone(), Some(Arc::new(tz.to_string())));
+
+    match unit {
+        TimeUnit::Second => {
+            shift_timestamps::<TimestampSecondType>(array, shift_seconds, to_type)
+        }
+        TimeUnit::Millisecond => shift_timestamps::<TimestampMillisecondType>(
+            array,
+            shift_seconds * 1_000,
+            to_type,
+        ),
+        TimeUnit::Microsecond => shift_timestamps::<TimestampMicrosecondType>(
+            array,
+            shift_seconds * 1_000_000,
+            to_type,
+        ),
+        TimeUnit::Nanosecond => shift_timestamps::<TimestampNanosecondType>(
+            array,
+            shift_seconds * 1_000_000_000,
+            to_type,
+        ),
+    }
+}
+
+/// Converts a `ListArray` to a `LargeListArray` by widening its offsets to 64 bits.
+///
+/// The returned array shares the values and the null bitmap of `list`.
+pub fn list_to_large_list(list: &ListArray) -> Result<LargeListArray> {
+    let data = list.data_ref();
+    let offsets: Vec<i64> = list_offsets::<i32>(data)
+        .iter()
+        .map(|o| *o as i64)
+        .collect();
+    let data_type = DataType::LargeList(Box::new(list.value_type()));
+    Ok(LargeListArray::from(with_offsets(
+        data,
+        data_type,
+        Buffer::from(offsets.to_byte_slice()),
+    )))
+}
+
+/// Converts a `LargeListArray` to a `ListArray` by narrowing its offsets to 32 bits.
+///
+/// The returned array shares the values and the null bitmap of `list`. Returns an error
+/// if an offset doesn't fit in 32 bits, i.e. if the lists hold more than
+/// `i32::max_value()` values, or if the offsets are negative or decreasing.
+pub fn large_list_to_list(list: &LargeListArray) -> Result<ListArray> {
+    let data = list.data_ref();
+    let large_offsets = list_offsets::<i64>(data);
+    let mut offsets: Vec<i32> = Vec::with_capacity(large_offsets.len());
+    for offset in large_offsets {
+        let previous = offsets.last().map_or(0, |o| *o);
+        match i32::try_from(*offset) {
+            Ok(o) if o >= previous => offsets.push(o),
+            _ => {
+                return Err(ArrowError::ComputeError(format!(
+                    "Cannot convert a LargeListArray with offset {} to a ListArray",
+                    offset
+                )));
+            }
+        }
+    }
+    let data_type = DataType::List(Box::new(list.value_type()));
+    Ok(ListArray::from(with_offsets(
+        data,
+        data_type,
+        Buffer::from(offsets.to_byte_slice()),
+    )))
+}
+
+/// Renders each list of `list` as a string, by formatting its values like
+/// `Array::value_to_string` and joining them with `separator`.
+///
+/// Null lists are null in the returned `Utf8` array, while null values within a list
+/// are rendered as `null`.
+pub fn list_to_string(list: &ListArray, separator: &str) -> Result<BinaryArray> {
+    let values = list.values();
+    let mut b = BinaryBuilder::new(list.len());
+    for i in 0..list.len() {
+        if list.is_null(i) {
+            b.append_null()?;
+            continue;
+        }
+        let start = list.value_offset(i) as usize;
+        let end = start + list.value_length(i) as usize;
+        let strings: Vec<String> =
+            (start..end).map(|j| values.value_to_string(j)).collect();
+        b.append_string(&strings.join(separator))?;
+    }
+    Ok(b.finish())
+}
+
+/// Returns all the offsets of list array `data`, from the start of its offsets buffer to
+/// the end of the last list of the array.
+fn list_offsets<T: ArrowPlainNativeType>(data: &ArrayData) -> &[T] {
+    let len = data.offset() + data.len() + 1;
+    &data.buffers()[0].typed_data::<T>()[..len]
+}
+
+/// Returns list array data of type `data_type` with the offsets replaced by `offsets`,
+/// sharing everything else with `data`.
+fn with_offsets(data: &ArrayData, data_type: DataType, offsets: Buffer) -> ArrayDataRef {
+    let mut builder = ArrayData::builder(data_type)
+        .len(data.len())
+        .null_count(data.null_count())
+        .offset(data.offset())
+        .add_buffer(offsets)
+        .child_data(data.child_data().to_vec());
+    if let Some(bitmap) = data.null_bitmap() {
+        builder = builder.null_bit_buffer(bitmap.bits.clone());
+    }
+    builder.build()
+}
+
+/// Adds `shift` to each non-null value of a timestamp array, returning an array of type
+/// `to_type`.
+fn shift_timestamps<T>(
+    array: &ArrayRef,
+    shift: i64,
+    to_type: DataType,
+) -> Result<ArrayRef>
+where
+    T: ArrowNumericType<Native = i64>,
+{
+    let array = array.as_any().downcast_ref::<PrimitiveArray<T>>().unwrap();
+    let mut b = PrimitiveBuilder::<T>::new(array.len());
+    for i in 0..array.len() {
+        if array.is_null(i) {
+            b.append_null()?;
+        } else {
+            let value = array.value(i).checked_add(shift).ok_or_else(|| {
+                ArrowError::ComputeError(format!(
+                    "Overflow when shifting the timestamp {} by {}",
+                    array.value(i),
+                    shift
+                ))
+            })?;
+            b.append_value(value)?;
+        }
+    }
+    Ok(reinterpret(&b.finish().data(), to_type))
+}
+
+/// Returns the number of `unit`s in a second.
+fn time_unit_multiple(unit: &TimeUnit) -> i64 {
+    match unit {
+        TimeUnit::Second => 1,
+        TimeUnit::Millisecond => MILLISECONDS,
+        TimeUnit::Microsecond => MICROSECONDS,
+        TimeUnit::Nanosecond => NANOSECONDS,
+    }
+}
+
+/// Converts the values of a timestamp array from `from_unit` to `to_unit`, returning an
+/// array of type `to_type`.
+fn convert_timestamp_unit(
+    array: &ArrayRef,
+    from_unit: &TimeUnit,
+    to_unit: &TimeUnit,
+    to_type: &DataType,
+) -> Result<ArrayRef> {
+    let from_multiple = time_unit_multiple(from_unit);
+    let to_multiple = time_unit_multiple(to_unit);
+    let values = reinterpret(&array.data(), DataType::Int64);
+    let converted = if from_multiple < to_multiple {
+        let factor = to_multiple / from_multiple;
+        try_map_values::<Int64Type, Int64Type, _>(&values, |v| {
+            v.checked_mul(factor).ok_or_else(|| {
+                ArrowError::ComputeError(format!(
+                    "Overflow when casting timestamp {} from {:?} to {:?}",
+                    v, from_unit, to_unit
+                ))
+            })
+        })?
+    } else {
+        let factor = from_multiple / to_multiple;
+        try_map_values::<Int64Type, Int64Type, _>(&values, |v| Ok(v / factor))?
+    };
+    Ok(reinterpret(&converted.data(), to_type.clone()))
+}
+
+/// Maps the valid values of the primitive `array` with `op` to an array of type `O`,
+/// preserving nulls.
+fn try_map_values<I, O, F>(array: &ArrayRef, op: F) -> Result<PrimitiveArray<O>>
+where
+    I: ArrowNumericType,
+    O: ArrowPrimitiveType,
+    F: Fn(I::Native) -> Result<O::Native>,
+{
+    let array = array.as_any().downcast_ref::<PrimitiveArray<I>>().unwrap();
+    let mut b = PrimitiveBuilder::<O>::new(array.len());
+    for i in 0..array.len() {
+        if array.is_null(i) {
+            b.append_null()?;
+        } else {
+            b.append_value(op(array.value(i))?)?;
+        }
+    }
+    Ok(b.finish())
+}
+
+/// Formats the values of the primitive `array` as strings, preserving nulls.
+fn numeric_to_string<T>(array: &ArrayRef) -> Result<ArrayRef>
+where
+    T: ArrowNumericType,
+    T::Native: std::fmt::Display,
+{
+    let array = array.as_any().downcast_ref::<PrimitiveArray<T>>().unwrap();
+    let mut b = BinaryBuilder::new(array.len());
+    for i in 0..array.len() {
+        if array.is_null(i) {
+            b.append_null()?;
+        } else {
+            b.append_string(&array.value(i).to_string())?;
+        }
+    }
+    Ok(Arc::new(b.finish()))
+}
+
+/// Parses the strings of `array` as numbers of type `to_type`, preserving nulls.
+fn string_to_numeric_array<OffsetSize: OffsetSizeTrait>(
+    array: &GenericBinaryArray<OffsetSize>,
+    to_type: &DataType,
+    options: &CastOptions,
+) -> Result<ArrayRef> {
+    match to_type {
+        DataType::Int8 => string_to_numeric::<OffsetSize, Int8Type>(array, options),
+        DataType::Int16 => string_to_numeric::<OffsetSize, Int16Type>(array, options),
+        DataType::Int32 => string_to_numeric::<OffsetSize, Int32Type>(array, options),
+        DataType::Int64 => string_to_numeric::<OffsetSize, Int64Type>(array, options),
+        DataType::UInt8 => string_to_numeric::<OffsetSize, UInt8Type>(array, options),
+        DataType::UInt16 => string_to_numeric::<OffsetSize, UInt16Type>(array, options),
+        DataType::UInt32 => string_to_numeric::<OffsetSize, UInt32Type>(array, options),
+        DataType::UInt64 => string_to_numeric::<OffsetSize, UInt64Type>(array, options),
+        DataType::Float32 => string_to_numeric::<OffsetSize, Float32Type>(array, options),
+        DataType::Float64 => string_to_numeric::<OffsetSize, Float64Type>(array, options),
+        _ => Err(ArrowError::ComputeError(format!(
+            "Casting from {:?} to {:?} not supported",
+            array.data_type(),
+            to_type,
+        ))),
+    }
+}
+
+/// Parses the strings of `array` as numbers of type `T`, preserving nulls.
+fn string_to_numeric<OffsetSize, T>(
+    array: &GenericBinaryArray<OffsetSize>,
+    options: &CastOptions,
+) -> Result<ArrayRef>
+where
+    OffsetSize: OffsetSizeTrait,
+    T: ArrowNumericType,
+    T::Native: std::str::FromStr,
+{
+    let mut b = PrimitiveBuilder::<T>::new(array.len());
+    for i in 0..array.len() {
+        if array.is_null(i) {
+            b.append_null()?;
+            continue;
+        }
+        let value = array.get_string(i);
+        match value.parse::<T::Native>() {
+            Ok(v) => b.append_value(v)?,
+            Err(_) if options.safe => b.append_null()?,
+            Err(_) => {
+                return Err(ArrowError::ComputeError(format!(
+                    "Cannot cast string '{}' to {:?}",
+                    value,
+                    T::get_data_type()
+                )));
+            }
+        }
+    }
+    Ok(Arc::new(b.finish()))
+}
+
+/// Converts the values of the primitive `array` to `false` if they are zero and to `true`
+/// otherwise, preserving nulls.
+fn numeric_to_boolean<T: ArrowNumericType>(array: &ArrayRef) -> Result<ArrayRef> {
+    let array = array.as_any().downcast_ref::<PrimitiveArray<T>>().unwrap();
+    let zero = T::default_value();
+    let mut b = BooleanBuilder::new(array.len());
+    for i in 0..array.len() {
+        if array.is_null(i) {
+            b.append_null()?;
+        } else {
+            b.append_value(array.value(i) != zero)?;
+        }
+    }
+    Ok(Arc::new(b.finish()))
+}
+
+/// Parses the strings of `array` as booleans, preserving nulls.
+fn string_to_boolean<OffsetSize: OffsetSizeTrait>(
+    array: &GenericBinaryArray<OffsetSize>,
+    options: &CastOptions,
+) -> Result<BooleanArray> {
+    let mut b = BooleanBuilder::new(array.len());
+    for i in 0..array.len() {
+        if array.is_null(i) {
+            b.append_null()?;
+            continue;
+        }
+        let value = array.get_string(i);
+        match value.to_lowercase().as_str() {
+            "true" | "t" | "yes" | "1" => b.append_value(true)?,
+            "false" | "f" | "no" | "0" => b.append_value(false)?,
+            _ if options.safe => b.append_null()?,
+            _ => {
+                return Err(ArrowError::ComputeError(format!(
+                    "Cannot cast string '{}' to Boolean",
+                    value
+                )));
+            }
+        }
+    }
+    Ok(b.finish())
+}
+
+/// Returns the integer type of the values of temporal type `data_type`, or `None` if
+/// it isn't a temporal type with an array implementation.
+fn temporal_integer_type(data_type: &DataType) -> Option<DataType> {
+    match data_type {
+        DataType::Timestamp(_, _)
+        | DataType::Date64(DateUnit::Millisecond)
+        | DataType::Time64(TimeUnit::Microsecond)
+        | DataType::Time64(TimeUnit::Nanosecond) => Some(DataType::Int64),
+        DataType::Date32(DateUnit::Day)
+        | DataType::Time32(TimeUnit::Second)
+        | DataType::Time32(TimeUnit::Millisecond) => Some(DataType::Int32),
+        _ => None,
+    }
+}
+
+/// Returns an array of type `to_type` sharing all buffers and child data with `data`.
+fn reinterpret(data: &ArrayData, to_type: DataType) -> ArrayRef {
+    let mut builder = ArrayData::builder(to_type)
+        .len(data.len())
+        .null_count(data.null_count())
+        .offset(data.offset())
+        .buffers(data.buffers().to_vec())
+        .child_data(data.child_data().to_vec());
+    if let Some(bitmap) = data.null_bitmap() {
+        builder = builder.null_bit_buffer(bitmap.bits.clone());
+    }
+    make_array(builder.build())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::builder::{Int32Builder, ListBuilder};
+
+    fn tz(tz: &str) -> Option<Arc<String>> {
+        Some(Arc::new(tz.to_string()))
+    }
+
+    #[test]
+    fn test_cast_same_type() {
+        let a: ArrayRef = Arc::new(Int32Array::from(vec![1, 2]));
+        let b = cast(&a, &DataType::Int32).unwrap();
+        assert!(Arc::ptr_eq(&a, &b));
+    }
+
+    #[test]
+    fn test_cast_temporal_to_integer() {
+        let a: ArrayRef = Arc::new(TimestampMillisecondArray::from(vec![
+            Some(1_546_336_800_000),
+            None,
+        ]));
+        let b = cast(&a, &DataType::Int64).unwrap();
+        assert_eq!(a.data().buffers(), b.data().buffers());
+        let c = b.as_any().downcast_ref::<Int64Array>().unwrap();
+        assert_eq!(1_546_336_800_000, c.value(0));
+        assert!(c.is_null(1));
+
+        let a: ArrayRef = Arc::new(Date32Array::from(vec![Some(17_897), None]));
+        let b = cast(&a, &DataType::Int32).unwrap();
+        let c = b.as_any().downcast_ref::<Int32Array>().unwrap();
+        assert_eq!(17_897, c.value(0));
+        assert!(c.is_null(1));
+
+        let a: ArrayRef = Arc::new(Time64NanosecondArray::from(vec![1_000]));
+        assert!(cast(&a, &DataType::Int64).is_ok());
+        // the widths of the values must match
+        assert!(cast(&a, &DataType::Int32).is_err());
+    }
+
+    #[test]
+    fn test_cast_integer_to_temporal() {
+        let a: ArrayRef = Arc::new(Int64Array::from(vec![Some(86_400_000), None]));
+        let to_type = DataType::Timestamp(TimeUnit::Millisecond, tz("UTC"));
+        let b = cast(&a, &to_type).unwrap();
+        assert_eq!(&to_type, b.data_type());
+        assert_eq!(a.data().buffers(), b.data().buffers());
+        let c = b
+            .as_any()
+            .downcast_ref::<TimestampMillisecondArray>()
+            .unwrap();
+        assert_eq!(
+            "1970-01-02T00:00:00",
+            format!("{:?}", c.value_as_datetime(0).unwrap())
+        );
+        assert!(c.is_null(1));
+
+        let a: ArrayRef = Arc::new(Int32Array::from(vec![3_600]));
+        let b = cast(&a, &DataType::Time32(TimeUnit::Second)).unwrap();
+        let c = b.as_any().downcast_ref::<Time32SecondArray>().unwrap();
+        assert_eq!("01:00:00", format!("{:?}", c.value_as_time(0).unwrap()));
+
+        // there is no array of dates in milliseconds of type `Date32`
+        assert!(cast(&a, &DataType::Date32(DateUnit::Millisecond)).is_err());
+    }
+
+    #[test]
+    fn test_cast_date32_to_date64() {
+        let a: ArrayRef =
+            Arc::new(Date32Array::from(vec![Some(12356), None, Some(13548)]));
+        let b = cast(&a, &DataType::Date64(DateUnit::Millisecond)).unwrap();
+        let c = b.as_any().downcast_ref::<Date64Array>().unwrap();
+        assert_eq!(1_067_558_400_000, c.value(0));
+        assert!(c.is_null(1));
+        assert_eq!("2007-02-04", format!("{:?}", c.value_as_date(2).unwrap()));
+    }
+
+    #[test]
+    fn test_cast_date64_to_date32() {
+        let a: ArrayRef = Arc::new(Date64Array::from(vec![
+            Some(1_067_558_400_000),
+            Some(1_170_633_599_999),
+            None,
+            Some(-1),
+            Some(-MILLISECONDS_IN_DAY),
+            Some(-MILLISECONDS_IN_DAY - 1),
+        ]));
+        let b = cast(&a, &DataType::Date32(DateUnit::Day)).unwrap();
+        let c = b.as_any().downcast_ref::<Date32Array>().unwrap();
+        assert_eq!(12356, c.value(0));
+        // the last millisecond of 2007-02-04
+        assert_eq!(13548, c.value(1));
+        assert!(c.is_null(2));
+        // dates before the epoch are floored toward negative infinity
+        assert_eq!(-1, c.value(3));
+        assert_eq!(-1, c.value(4));
+        assert_eq!(-2, c.value(5));
+    }
+
+    #[test]
+    fn test_cast_date64_to_date32_overflow() {
+        let a: ArrayRef = Arc::new(Date64Array::from(vec![Some(0), Some(std::i64::MAX)]));
+        assert!(cast(&a, &DataType::Date32(DateUnit::Day)).is_err());
+        let a: ArrayRef = Arc::new(Date64Array::from(vec![Some(std::i64::MIN)]));
+        assert!(cast(&a, &DataType::Date32(DateUnit::Day)).is_err());
+    }
+
+    #[test]
+    fn test_cast_timestamp_to_finer_unit() {
+        let a = TimestampSecondArray::from(vec![1_546_336_800, 0, -1]);
+        let a = reinterpret(
+            &a.data(),
+            DataType::Timestamp(TimeUnit::Second, tz("+01:00")),
+        );
+        let to_type = DataType::Timestamp(TimeUnit::Microsecond, tz("+01:00"));
+        let b = cast(&a, &to_type).unwrap();
+        assert_eq!(&to_type, b.data_type());
+        let c = b
+            .as_any()
+            .downcast_ref::<TimestampMicrosecondArray>()
+            .unwrap();
+        assert_eq!(&[1_546_336_800_000_000, 0, -1_000_000], c.value_slice(0, 3));
+
+        let a: ArrayRef = Arc::new(TimestampSecondArray::from(vec![
+            Some(i64::max_value() / 1_000),
+            None,
+        ]));
+        let to_type = DataType::Timestamp(TimeUnit::Millisecond, None);
+        let b = cast(&a, &to_type).unwrap();
+        assert!(b.is_null(1));
+        let to_type = DataType::Timestamp(TimeUnit::Nanosecond, None);
+        assert!(cast(&a, &to_type).is_err());
+    }
+
+    #[test]
+    fn test_cast_timestamp_to_coarser_unit() {
+        let a: ArrayRef = Arc::new(TimestampNanosecondArray::from(vec![
+            Some(1_546_336_800_123_456_789),
+            None,
+            Some(-1_500_000_000),
+        ]));
+        let b = cast(&a, &DataType::Timestamp(TimeUnit::Millisecond, None)).unwrap();
+        let c = b
+            .as_any()
+            .downcast_ref::<TimestampMillisecondArray>()
+            .unwrap();
+        assert_eq!(1_546_336_800_123, c.value(0));
+        assert!(c.is_null(1));
+        assert_eq!(-1_500, c.value(2));
+
+        let b = cast(&a, &DataType::Timestamp(TimeUnit::Second, None)).unwrap();
+        let c = b.as_any().downcast_ref::<TimestampSecondArray>().unwrap();
+        assert_eq!(1_546_336_800, c.value(0));
+        assert!(c.is_null(1));
+        // values are truncated toward zero
+        assert_eq!(-1, c.value(2));
+    }
+
+    #[test]
+    fn test_cast_timestamp_reinterpret_timezone() {
+        let a = TimestampMillisecondArray::from(vec![Some(1_546_336_800_000), None]);
+        let a = reinterpret(
+            &a.data(),
+            DataType::Timestamp(TimeUnit::Millisecond, tz("+02:00")),
+        );
+        let to_type = DataType::Timestamp(TimeUnit::Millisecond, tz("+05:00"));
+        let b = cast(&a, &to_type).unwrap();
+        assert_eq!(&to_type, b.data_type());
+        assert_eq!(a.data().buffers(), b.data().buffers());
+
+        let c = b
+            .as_any()
+            .downcast_ref::<TimestampMillisecondArray>()
+            .unwrap();
+        assert_eq!(1_546_336_800_000, c.value(0));
+        assert!(c.is_null(1));
+    }
+
+    #[test]
+    fn test_cast_timestamp_add_timezone() {
+        let a: ArrayRef = Arc::new(TimestampSecondArray::from(vec![1, 2, 3]));
+        let to_type = DataType::Timestamp(TimeUnit::Second, tz("UTC"));
+        let b = cast(&a, &to_type).unwrap();
+        assert_eq!(&to_type, b.data_type());
+        let c = b.as_any().downcast_ref::<TimestampSecondArray>().unwrap();
+        assert_eq!(&[1, 2, 3], c.value_slice(0, 3));
+    }
+
+    #[test]
+    fn test_cast_unsupported() {
+        let a: ArrayRef = Arc::new(TimestampSecondArray::from(vec![1]));
+        let to_type = DataType::Date32(DateUnit::Day);
+        assert!(cast(&a, &to_type).is_err());
+    }
+
+    #[test]
+    fn test_cast_integer_to_utf8() {
+        let a: ArrayRef = Arc::new(Int32Array::from(vec![Some(-7), None, Some(2_000)]));
+        let b = cast(&a, &DataType::Utf8).unwrap();
+        assert_eq!(&DataType::Utf8, b.data_type());
+        let c = b.as_any().downcast_ref::<BinaryArray>().unwrap();
+        assert_eq!(3, c.len());
+        assert_eq!(1, c.null_count());
+        assert_eq!("-7", c.get_string(0));
+        assert!(c.is_null(1));
+        assert_eq!("2000", c.get_string(2));
+        assert_eq!(&[0, 2, 2, 6], c.value_offsets());
+
+        let a: ArrayRef = Arc::new(UInt64Array::from(vec![u64::max_value()]));
+        let b = cast(&a, &DataType::Utf8).unwrap();
+        let c = b.as_any().downcast_ref::<BinaryArray>().unwrap();
+        assert_eq!("18446744073709551615", c.get_string(0));
+    }
+
+    #[test]
+    fn test_cast_float_to_utf8() {
+        let a: ArrayRef = Arc::new(Float64Array::from(vec![
+            Some(1.5),
+            Some(-2.0),
+            None,
+            Some(1e21),
+            Some(0.000_125),
+        ]));
+        let b = cast(&a, &DataType::Utf8).unwrap();
+        let c = b.as_any().downcast_ref::<BinaryArray>().unwrap();
+        assert_eq!("1.5", c.get_string(0));
+        assert_eq!("-2", c.get_string(1));
+        assert!(c.is_null(2));
+        assert_eq!("1000000000000000000000", c.get_string(3));
+        assert_eq!("0.000125", c.get_string(4));
+
+        let a: ArrayRef = Arc::new(Float32Array::from(vec![0.1]));
+        let b = cast(&a, &DataType::Utf8).unwrap();
+        let c = b.as_any().downcast_ref::<BinaryArray>().unwrap();
+        assert_eq!("0.1", c.get_string(0));
+    }
+
+    #[test]
+    fn test_cast_utf8_to_integer() {
+        let a: ArrayRef = Arc::new(BinaryArray::from(vec![
+            Some("42"),
+            Some("-9000000000"),
+            Some(""),
+            None,
+            Some("1.5"),
+            Some("abc"),
+            Some("+7"),
+        ]));
+        let b = cast(&a, &DataType::Int64).unwrap();
+        let c = b.as_any().downcast_ref::<Int64Array>().unwrap();
+        assert_eq!(7, c.len());
+        assert_eq!(4, c.null_count());
+        assert_eq!(42, c.value(0));
+        assert_eq!(-9_000_000_000, c.value(1));
+        assert!(c.is_null(2));
+        assert!(c.is_null(3));
+        assert!(c.is_null(4));
+        assert!(c.is_null(5));
+        assert_eq!(7, c.value(6));
+
+        // values that don't fit in the target type become null
+        let b = cast(&a, &DataType::Int32).unwrap();
+        assert!(b.is_null(1));
+        let b = cast(&a, &DataType::UInt8).unwrap();
+        assert_eq!(5, b.null_count());
+    }
+
+    #[test]
+    fn test_cast_utf8_to_float() {
+        let a: ArrayRef = Arc::new(LargeBinaryArray::from(vec![
+            Some("1.5"),
+            Some("-2e3"),
+            Some("7"),
+            Some(""),
+            None,
+            Some("one"),
+        ]));
+        let b = cast(&a, &DataType::Float64).unwrap();
+        let c = b.as_any().downcast_ref::<Float64Array>().unwrap();
+        assert_eq!(1.5, c.value(0));
+        assert_eq!(-2000.0, c.value(1));
+        assert_eq!(7.0, c.value(2));
+        assert!(c.is_null(3));
+        assert!(c.is_null(4));
+        assert!(c.is_null(5));
+    }
+
+    #[test]
+    fn test_cast_utf8_to_numeric_strict() {
+        let options = CastOptions { safe: false };
+        let a: ArrayRef = Arc::new(BinaryArray::from(vec![Some("1"), None, Some("2")]));
+        let b = cast_with_options(&a, &DataType::Int16, &options).unwrap();
+        assert_eq!(1, b.null_count());
+
+        let a: ArrayRef = Arc::new(BinaryArray::from(vec!["1", "x"]));
+        match cast_with_options(&a, &DataType::Int16, &options) {
+            Err(ArrowError::ComputeError(message)) => {
+                assert_eq!("Cannot cast string 'x' to Int16", message)
+            }
+            _ => panic!("expected a compute error"),
+        }
+        assert!(cast(&a, &DataType::Float16).is_err());
+    }
+
+    #[test]
+    fn test_cast_numeric_to_boolean() {
+        let a: ArrayRef =
+            Arc::new(Int32Array::from(vec![Some(0), Some(-3), None, Some(1)]));
+        let b = cast(&a, &DataType::Boolean).unwrap();
+        let c = b.as_any().downcast_ref::<BooleanArray>().unwrap();
+        assert_eq!(4, c.len());
+        assert!(!c.value(0));
+        assert!(c.value(1));
+        assert!(c.is_null(2));
+        assert!(c.value(3));
+
+        let a: ArrayRef =
+            Arc::new(Float64Array::from(vec![0.0, -0.0, 0.5, std::f64::NAN]));
+        let b = cast(&a, &DataType::Boolean).unwrap();
+        let c = b.as_any().downcast_ref::<BooleanArray>().unwrap();
+        assert_eq!(0, c.null_count());
+        assert!(!c.value(0));
+        assert!(!c.value(1));
+        assert!(c.value(2));
+        assert!(c.value(3));
+    }
+
+    #[test]
+    fn test_cast_utf8_to_boolean() {
+        let a: ArrayRef = Arc::new(BinaryArray::from(vec![
+            Some("True"),
+            Some("0"),
+            Some("maybe"),
+            None,
+            Some("YES"),
+            Some("f"),
+        ]));
+        let b = cast(&a, &DataType::Boolean).unwrap();
+        let c = b.as_any().downcast_ref::<BooleanArray>().unwrap();
+        assert_eq!(6, c.len());
+        assert_eq!(2, c.null_count());
+        assert_eq!(true, c.value(0));
+        assert_eq!(false, c.value(1));
+        assert!(c.is_null(2));
+        assert!(c.is_null(3));
+        assert_eq!(true, c.value(4));
+        assert_eq!(false, c.value(5));
+
+        let strict = CastOptions { safe: false };
+        match cast_with_options(&a, &DataType::Boolean, &strict) {
+            Err(ArrowError::ComputeError(message)) => {
+                assert_eq!("Cannot cast string 'maybe' to Boolean", message)
+            }
+            _ => panic!("expected the strict cast to fail"),
+        }
+    }
+
+    #[test]
+    fn test_cast_large_utf8_to_boolean_strict() {
+        let a: ArrayRef = Arc::new(LargeBinaryArray::from(vec![Some("t"), None]));
+        let strict = CastOptions { safe: false };
+        let b = cast_with_options(&a, &DataType::Boolean, &strict).unwrap();
+        let c = b.as_any().downcast_ref::<BooleanArray>().unwrap();
+        assert_eq!(true, c.value(0));
+        assert!(c.is_null(1));
+    }
+
+    #[test]
+    fn test_list_to_large_list_round_trip() {
+        let mut builder = ListBuilder::new(Int32Builder::new(6));
+        builder.values().append_slice(&[0, 1, 2]).unwrap();
+        builder.append(true).unwrap();
+        builder.append(false).unwrap();
+        builder.append(true).unwrap();
+        builder.values().append_null().unwrap();
+        builder.values().append_value(3).unwrap();
+        builder.append(true).unwrap();
+        let list: ArrayRef = Arc::new(builder.finish());
+
+        let large_type = DataType::LargeList(Box::new(DataType::Int32));
+        let large = cast(&list, &large_type).unwrap();
+        assert_eq!(&large_type, large.data_type());
+        let large_list = large.as_any().downcast_ref::<LargeListArray>().unwrap();
+        assert_eq!(4, large_list.len());
+        assert_eq!(1, large_list.null_count());
+        assert!(large_list.is_null(1));
+        assert_eq!(3, large_list.value_offset(3));
+        assert_eq!(2, large_list.value_length(3));
+        assert_eq!(list.data().child_data(), large.data().child_data());
+
+        let back = cast(&large, list.data_type()).unwrap();
+        assert_eq!(list.data(), back.data());
+        for i in 0..list.len() {
+            assert_eq!(list.value_to_string(i), back.value_to_string(i));
+        }
+    }
+
+    #[test]
+    fn test_list_to_string() {
+        let mut builder = ListBuilder::new(Int32Builder::new(6));
+        builder.values().append_slice(&[1, 2, 3]).unwrap();
+        builder.append(true).unwrap();
+        builder.append(false).unwrap();
+        builder.values().append_value(4).unwrap();
+        builder.append(true).unwrap();
+        builder.append(true).unwrap();
+        builder.values().append_null().unwrap();
+        builder.values().append_value(5).unwrap();
+        builder.append(true).unwrap();
+        let list = builder.finish();
+
+        let strings = list_to_string(&list, ",").unwrap();
+        assert_eq!(5, strings.len());
+        assert_eq!("1,2,3", strings.get_string(0));
+        assert!(strings.is_null(1));
+        assert_eq!("4", strings.get_string(2));
+        assert!(strings.is_valid(3));
+        assert_eq!("", strings.get_string(3));
+        assert_eq!(
+            "null; 5",
+            list_to_string(&list, "; ").unwrap().get_string(4)
+        );
+    }
+
+    #[test]
+    fn test_large_list_to_list_overflow() {
+        let values = ArrayData::builder(DataType::Int32)
+            .len(1)
+            .add_buffer(Buffer::from(&[1].to_byte_slice()))
+            .build();
+        // the second list claims to start beyond the range of 32-bit offsets
+        let offsets: Vec<i64> = vec![0, 1 << 32, 1];
+        let data = ArrayData::builder(DataType::LargeList(Box::new(DataType::Int32)))
+            .len(2)
+            .add_buffer(Buffer::from(offsets.to_byte_slice()))
+            .add_child_data(values)
+            .build();
+        let large_list = LargeListArray::from(data);
+        assert!(large_list_to_list(&large_list).is_err());
+    }
+
+    #[test]
+    fn test_large_list_to_list_invalid_offsets() {
+        let values = ArrayData::builder(DataType::Int32)
+            .len(2)
+            .add_buffer(Buffer::from(&[1, 2].to_byte_slice()))
+            .build();
+        for offsets in vec![vec![0i64, -1, 2], vec![0, 2, 1], vec![0, -1 << 32, 2]] {
+            let data = ArrayData::builder(DataType::LargeList(Box::new(DataType::Int32)))
+                .len(2)
+                .add_buffer(Buffer::from(offsets.to_byte_slice()))
+                .add_child_data(values.clone())
+                .build();
+            let large_list = LargeListArray::from(data);
+            assert!(large_list_to_list(&large_list).is_err());
+        }
+    }
+
+    #[test]
+    fn test_convert_timezone() {
+        // 2019-01-01T12:00:00+02:00
+        let a = TimestampSecondArray::from(vec![Some(1_546_336_800), None]);
+        let a = reinterpret(
+            &a.data(),
+            DataType::Timestamp(TimeUnit::Second, tz("+02:00")),
+        );
+        // 2019-01-01T12:00:00+05:00
+        let b = convert_timezone(&a, "+05:00").unwrap();
+        assert_eq!(
+            &DataType::Timestamp(TimeUnit::Second, tz("+05:00")),
+            b.data_type()
+        );
+        let c = b.as_any().downcast_ref::<TimestampSecondArray>().unwrap();
+        assert_eq!(1_546_336_800 - 3 * 3_600, c.value(0));
+        assert!(c.is_null(1));
+    }
+
+    #[test]
+    fn test_convert_timezone_without_source_timezone() {
+        let a: ArrayRef = Arc::new(TimestampMillisecondArray::from(vec![0]));
+        let b = convert_timezone(&a, "-01:30").unwrap();
+        let c = b
+            .as_any()
+            .downcast_ref::<TimestampMillisecondArray>()
+            .unwrap();
+        assert_eq!(5_400_000, c.value(0));
+    }
+
+    #[test]
+    fn test_convert_timezone_overflow() {
+        let a: ArrayRef =
+            Arc::new(TimestampSecondArray::from(vec![Some(std::i64::MAX), None]));
+        assert!(convert_timezone(&a, "-01:00").is_err());
+        let b = convert_timezone(&a, "+01:00").unwrap();
+        let c = b.as_any().downcast_ref::<TimestampSecondArray>().unwrap();
+        assert_eq!(std::i64::MAX - 3_600, c.value(0));
+    }
+}
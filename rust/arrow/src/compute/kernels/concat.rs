@@ -0,0 +1,589 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines concat kernel for `ArrayRef`, and the concatenation of record batches
+
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+use crate::array::*;
+use crate::array_data::{ArrayData, ArrayDataRef};
+use crate::buffer::{Buffer, MutableBuffer};
+use crate::datatypes::{ArrowPlainNativeType, DataType, Schema, ToByteSlice};
+use crate::error::{ArrowError, Result};
+use crate::record_batch::RecordBatch;
+use crate::util::bit_util;
+
+/// Concatenates `arrays`, which must all have the same data type, into a single array
+/// holding the elements of each array one after the other.
+///
+/// The value buffers and offsets of the arrays are concatenated according to the layout
+/// of their data type, recursively for nested types. Dictionary arrays can only be
+/// concatenated if they share the same dictionary. Returns an error if `arrays` is
+/// empty, their data types differ, or their data type is a union.
+///
+/// # Example
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use arrow::array::{Array, ArrayRef, Int32Array};
+/// # use arrow::compute::concat;
+/// let a: ArrayRef = Arc::new(Int32Array::from(vec![1, 2]));
+/// let b: ArrayRef = Arc::new(Int32Array::from(vec![Some(3), None]));
+/// let c = concat(&[a, b]).unwrap();
+/// let c = c.as_any().downcast_ref::<Int32Array>().unwrap();
+/// assert_eq!(4, c.len());
+/// assert_eq!(3, c.value(2));
+/// assert!(c.is_null(3));
+/// ```
+pub fn concat(arrays: &[ArrayRef]) -> Result<ArrayRef> {
+    let data_type = match arrays.first() {
+        Some(first) => first.data_type().clone(),
+        None => {
+            return Err(ArrowError::InvalidArgumentError(
+                "concat requires at least one array".to_string(),
+            ))
+        }
+    };
+    if let Some(other) = arrays.iter().find(|a| a.data_type() != &data_type) {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "Cannot concatenate arrays of different data types {:?} and {:?}",
+            data_type,
+            other.data_type()
+        )));
+    }
+    let data: Vec<ArrayDataRef> = arrays.iter().map(|a| a.data()).collect();
+    Ok(make_array(concat_data(&data)?))
+}
+
+/// Concatenates array data of the same data type, taking the offset of each into
+/// account.
+fn concat_data(data: &[ArrayDataRef]) -> Result<ArrayDataRef> {
+    let data_type = data[0].data_type().clone();
+    let len = data.iter().map(|d| d.len()).sum();
+    let null_count = data.iter().map(|d| d.null_count()).sum();
+    let mut builder = ArrayData::builder(data_type.clone())
+        .len(len)
+        .null_count(null_count);
+    if null_count > 0 && data_type != DataType::Null {
+        builder = builder.null_bit_buffer(concat_validity(data, len));
+    }
+
+    match data_type {
+        DataType::Null => {}
+        DataType::Boolean => {
+            builder = builder.add_buffer(concat_bits(data, len));
+        }
+        DataType::Utf8 => {
+            let (offsets, ranges) = concat_offsets::<i32>(data)?;
+            builder = builder
+                .add_buffer(offsets)
+                .add_buffer(concat_values(data, &ranges, 1));
+        }
+        DataType::LargeUtf8 => {
+            let (offsets, ranges) = concat_offsets::<i64>(data)?;
+            builder = builder
+                .add_buffer(offsets)
+                .add_buffer(concat_values(data, &ranges, 1));
+        }
+        DataType::List(_) => {
+            let (offsets, ranges) = concat_offsets::<i32>(data)?;
+            builder = builder
+                .add_buffer(offsets)
+                .add_child_data(concat_children(data, 0, &ranges)?);
+        }
+        DataType::LargeList(_) => {
+            let (offsets, ranges) = concat_offsets::<i64>(data)?;
+            builder = builder
+                .add_buffer(offsets)
+                .add_child_data(concat_children(data, 0, &ranges)?);
+        }
+        DataType::FixedSizeList(_, size) => {
+            let size = size as usize;
+            let ranges: Vec<(usize, usize)> = data
+                .iter()
+                .map(|d| (d.offset() * size, (d.offset() + d.len()) * size))
+                .collect();
+            builder = builder.add_child_data(concat_children(data, 0, &ranges)?);
+        }
+        DataType::Struct(ref fields) => {
+            let ranges: Vec<(usize, usize)> = data
+                .iter()
+                .map(|d| (d.offset(), d.offset() + d.len()))
+                .collect();
+            for i in 0..fields.len() {
+                builder = builder.add_child_data(concat_children(data, i, &ranges)?);
+            }
+        }
+        DataType::Dictionary(ref key_type, _) => {
+            let dictionary = &data[0].child_data()[0];
+            if data.iter().any(|d| &d.child_data()[0] != dictionary) {
+                return Err(ArrowError::ComputeError(
+                    "Cannot concatenate dictionary arrays with different dictionaries"
+                        .to_string(),
+                ));
+            }
+            // the keys are laid out as an array of the key type
+            let width = key_type.primitive_width().unwrap();
+            builder = builder
+                .add_buffer(concat_values(data, &value_ranges(data), width))
+                .add_child_data(dictionary.clone());
+        }
+        ref other => match other.primitive_width() {
+            Some(width) => {
+                builder =
+                    builder.add_buffer(concat_values(data, &value_ranges(data), width));
+            }
+            None => {
+                return Err(ArrowError::ComputeError(format!(
+                    "concat not supported for {:?}",
+                    other
+                )));
+            }
+        },
+    }
+    Ok(builder.build())
+}
+
+/// Returns the range of the values of each array data of a fixed-width type.
+fn value_ranges(data: &[ArrayDataRef]) -> Vec<(usize, usize)> {
+    data.iter()
+        .map(|d| (d.offset(), d.offset() + d.len()))
+        .collect()
+}
+
+/// Concatenates the validity of `data` into a null bitmap of `len` bits. Array data
+/// without a null bitmap is all valid, except array data of type `Null`.
+fn concat_validity(data: &[ArrayDataRef], len: usize) -> Buffer {
+    let num_bytes = bit_util::ceil(len, 8);
+    let mut buffer = MutableBuffer::new(num_bytes).with_bitset(num_bytes, false);
+    let bits = buffer.data_mut();
+    let mut start = 0;
+    for d in data {
+        match d.null_bitmap() {
+            Some(bitmap) => {
+                copy_bits(bits, start, bitmap.bits.data(), d.offset(), d.len())
+            }
+            None => bit_util::set_bits(bits, start, d.len()),
+        }
+        start += d.len();
+    }
+    buffer.freeze()
+}
+
+/// Concatenates the bit-packed values of `data`, of type `Boolean`, into a buffer of
+/// `len` bits.
+fn concat_bits(data: &[ArrayDataRef], len: usize) -> Buffer {
+    let num_bytes = bit_util::ceil(len, 8);
+    let mut buffer = MutableBuffer::new(num_bytes).with_bitset(num_bytes, false);
+    let bits = buffer.data_mut();
+    let mut start = 0;
+    for d in data {
+        copy_bits(bits, start, d.buffers()[0].data(), d.offset(), d.len());
+        start += d.len();
+    }
+    buffer.freeze()
+}
+
+/// Sets the bits of `dst` starting at `dst_offset` that are set among the `len` bits of
+/// `src` starting at `src_offset`. The bits of `dst` must be unset.
+fn copy_bits(
+    dst: &mut [u8],
+    dst_offset: usize,
+    src: &[u8],
+    src_offset: usize,
+    len: usize,
+) {
+    for i in 0..len {
+        if bit_util::get_bit(src, src_offset + i) {
+            bit_util::set_bit(dst, dst_offset + i);
+        }
+    }
+}
+
+/// Concatenates the `ranges` of the values buffer of each of `data`, i.e. its last buffer
+/// after the offsets if there are any, where ranges are in values of `width` bytes.
+fn concat_values(
+    data: &[ArrayDataRef],
+    ranges: &[(usize, usize)],
+    width: usize,
+) -> Buffer {
+    let capacity = ranges
+        .iter()
+        .map(|(first, last)| (last - first) * width)
+        .sum();
+    let mut buffer = MutableBuffer::new(capacity);
+    for (d, (first, last)) in data.iter().zip(ranges) {
+        let values = d.buffers()[d.buffers().len() - 1].data();
+        buffer.extend_from_slice(&values[first * width..last * width]);
+    }
+    buffer.freeze()
+}
+
+/// Concatenates the `ranges` of the `i`-th child of each of `data`.
+fn concat_children(
+    data: &[ArrayDataRef],
+    i: usize,
+    ranges: &[(usize, usize)],
+) -> Result<ArrayDataRef> {
+    let children: Vec<ArrayDataRef> = data
+        .iter()
+        .zip(ranges)
+        .map(|(d, (first, last))| d.child_data()[i].slice(*first, last - first))
+        .collect();
+    concat_data(&children)
+}
+
+/// An offset type of variable-size layouts, `i32` or `i64`.
+trait OffsetType: ArrowPlainNativeType + Into<i64> {
+    /// Converts `offset` to this type, returning `None` if it doesn't fit.
+    fn from_i64(offset: i64) -> Option<Self>;
+}
+
+impl OffsetType for i32 {
+    fn from_i64(offset: i64) -> Option<Self> {
+        i32::try_from(offset).ok()
+    }
+}
+
+impl OffsetType for i64 {
+    fn from_i64(offset: i64) -> Option<Self> {
+        Some(offset)
+    }
+}
+
+/// Concatenates the offsets of `data` into offsets starting at zero, and returns them
+/// along with the range of the values or child elements spanned by each array data.
+fn concat_offsets<T: OffsetType>(
+    data: &[ArrayDataRef],
+) -> Result<(Buffer, Vec<(usize, usize)>)> {
+    let len: usize = data.iter().map(|d| d.len()).sum();
+    let mut offsets = Vec::with_capacity(len + 1);
+    offsets.push(T::from_i64(0).unwrap());
+    let mut ranges = Vec::with_capacity(data.len());
+    let mut base = 0i64;
+    for d in data {
+        let array_offsets =
+            &d.buffers()[0].typed_data::<T>()[d.offset()..=d.offset() + d.len()];
+        let first: i64 = array_offsets[0].into();
+        let last: i64 = array_offsets[d.len()].into();
+        for offset in &array_offsets[1..] {
+            let offset: i64 = (*offset).into();
+            offsets.push(T::from_i64(base + offset - first).ok_or_else(|| {
+                ArrowError::ComputeError(
+                    "Concatenated values are too large for the offsets of the array"
+                        .to_string(),
+                )
+            })?);
+        }
+        base += last - first;
+        ranges.push((first as usize, last as usize));
+    }
+    Ok((Buffer::from(offsets.to_byte_slice()), ranges))
+}
+
+/// Concatenates `batches`, which must all have the schema `schema`, into a single
+/// record batch holding the rows of each batch one after the other.
+///
+/// Each column is concatenated with `concat`. Returns an error if `batches` is empty,
+/// or if the schema of a batch differs from `schema`.
+pub fn concat_batches(
+    schema: &Arc<Schema>,
+    batches: &[RecordBatch],
+) -> Result<RecordBatch> {
+    if batches.is_empty() {
+        return Err(ArrowError::InvalidArgumentError(
+            "concat_batches requires at least one record batch".to_string(),
+        ));
+    }
+    if let Some(i) = batches.iter().position(|b| b.schema() != schema) {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "Cannot concatenate record batches with different schemas, expected {:?} but found {:?} at batch index {}",
+            schema,
+            batches[i].schema(),
+            i
+        )));
+    }
+    let mut columns = Vec::with_capacity(schema.fields().len());
+    for i in 0..schema.fields().len() {
+        let arrays: Vec<ArrayRef> = batches.iter().map(|b| b.column(i).clone()).collect();
+        columns.push(concat(&arrays)?);
+    }
+    RecordBatch::try_new(schema.clone(), columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::builder::{Int32Builder, ListBuilder};
+    use crate::compute::cast;
+    use crate::datatypes::{Field, Int32Type, TimeUnit, TimestampMillisecondType};
+
+    #[test]
+    fn test_concat_primitive() {
+        let a: ArrayRef = Arc::new(Int32Array::from(vec![Some(1), None]));
+        let b: ArrayRef = Arc::new(Int32Array::from(vec![3]));
+        let sliced: ArrayRef = Arc::new(Int32Array::from(a.data().slice(1, 1)));
+        let c = concat(&[a, b, sliced]).unwrap();
+        let c = c.as_any().downcast_ref::<Int32Array>().unwrap();
+        assert_eq!(4, c.len());
+        assert_eq!(2, c.null_count());
+        assert_eq!(1, c.value(0));
+        assert!(c.is_null(1));
+        assert_eq!(3, c.value(2));
+        assert!(c.is_null(3));
+    }
+
+    #[test]
+    fn test_concat_boolean_and_binary() {
+        let a: ArrayRef = Arc::new(BooleanArray::from(vec![Some(true), None]));
+        let b: ArrayRef = Arc::new(BooleanArray::from(vec![false]));
+        let c = concat(&[a, b]).unwrap();
+        let c = c.as_any().downcast_ref::<BooleanArray>().unwrap();
+        assert_eq!(&BooleanArray::from(vec![Some(true), None, Some(false)]), c);
+
+        let a: ArrayRef = Arc::new(BinaryArray::from(vec!["one", ""]));
+        let b: ArrayRef = Arc::new(BinaryArray::from(vec![None, Some("three")]));
+        let c = concat(&[a, b]).unwrap();
+        let c = c.as_any().downcast_ref::<BinaryArray>().unwrap();
+        assert_eq!(
+            vec![Some("one"), Some(""), None, Some("three")],
+            c.string_iter().collect::<Vec<_>>()
+        );
+    }
+
+    fn slice(array: &ArrayRef, offset: usize, len: usize) -> ArrayRef {
+        make_array(array.data().slice(offset, len))
+    }
+
+    fn strings(array: &ArrayRef) -> Vec<String> {
+        (0..array.len()).map(|i| array.value_to_string(i)).collect()
+    }
+
+    #[test]
+    fn test_concat_sliced_boolean() {
+        let a: ArrayRef = Arc::new(BooleanArray::from(vec![
+            Some(true),
+            None,
+            Some(false),
+            Some(true),
+            Some(true),
+            None,
+            Some(false),
+            Some(true),
+            Some(false),
+            None,
+        ]));
+        let c = concat(&[slice(&a, 3, 6), slice(&a, 1, 3), a.clone()]).unwrap();
+        let expected = BooleanArray::from(vec![
+            Some(true),
+            Some(true),
+            None,
+            Some(false),
+            Some(true),
+            Some(false),
+            None,
+            Some(false),
+            Some(true),
+        ]);
+        assert!(slice(&c, 0, 9).equals(&expected));
+        assert_eq!(19, c.len());
+        assert_eq!(5, c.null_count());
+        assert!(slice(&c, 9, 10).equals(a.as_ref()));
+    }
+
+    #[test]
+    fn test_concat_temporal_and_fixed_width() {
+        let tz = DataType::Timestamp(
+            TimeUnit::Millisecond,
+            Some(Arc::new("+01:00".to_string())),
+        );
+        let values = TimestampMillisecondArray::from(vec![Some(1), None, Some(3)]);
+        let a = make_array(
+            ArrayData::builder(tz.clone())
+                .len(3)
+                .null_count(1)
+                .null_bit_buffer(
+                    values.data().null_bitmap().as_ref().unwrap().bits.clone(),
+                )
+                .add_buffer(values.values())
+                .build(),
+        );
+        let c = concat(&[a.clone(), slice(&a, 2, 1)]).unwrap();
+        assert_eq!(&tz, c.data_type());
+        let c = as_primitive_array::<TimestampMillisecondType>(&c);
+        assert_eq!((1, 3, 3), (c.value(0), c.value(2), c.value(3)));
+        assert!(c.is_null(1));
+
+        let a: ArrayRef = Arc::new(Date32Array::from(vec![Some(10), None]));
+        let b: ArrayRef = Arc::new(Date32Array::from(vec![20]));
+        let c = concat(&[a, b]).unwrap();
+        assert!(c.equals(&Date32Array::from(vec![Some(10), None, Some(20)])));
+
+        let a: ArrayRef = Arc::new(
+            DecimalArray::from_values(vec![Some(12), None, Some(-3)], 5, 1).unwrap(),
+        );
+        let c = concat(&[slice(&a, 1, 2), a.clone()]).unwrap();
+        assert_eq!(vec!["null", "-0.3", "1.2", "null", "-0.3"], strings(&c));
+
+        let a: ArrayRef = Arc::new(
+            FixedSizeBinaryArray::from_values(vec![Some(b"ab"), None, Some(b"cd")], 2)
+                .unwrap(),
+        );
+        let c = concat(&[slice(&a, 2, 1), slice(&a, 0, 2)]).unwrap();
+        let c = c.as_any().downcast_ref::<FixedSizeBinaryArray>().unwrap();
+        assert_eq!(3, c.len());
+        assert_eq!(b"cd", c.value(0));
+        assert_eq!(b"ab", c.value(1));
+        assert!(c.is_null(2));
+    }
+
+    #[test]
+    fn test_concat_large_utf8_and_lists() {
+        let a: ArrayRef =
+            Arc::new(LargeBinaryArray::from(vec![Some("a"), None, Some("bc")]));
+        let b: ArrayRef = Arc::new(LargeBinaryArray::from(vec!["def"]));
+        let c = concat(&[slice(&a, 1, 2), b]).unwrap();
+        assert_eq!(&DataType::LargeUtf8, c.data_type());
+        assert!(c.equals(&LargeBinaryArray::from(vec![None, Some("bc"), Some("def")])));
+
+        let mut builder = ListBuilder::new(Int32Builder::new(8));
+        builder.values().append_slice(&[1, 2]).unwrap();
+        builder.append(true).unwrap();
+        builder.append(false).unwrap();
+        builder.values().append_slice(&[3, 4, 5]).unwrap();
+        builder.append(true).unwrap();
+        let a: ArrayRef = Arc::new(builder.finish());
+        let c = concat(&[slice(&a, 1, 2), slice(&a, 0, 1)]).unwrap();
+        assert_eq!(vec!["null", "[3, 4, 5]", "[1, 2]"], strings(&c));
+        assert_eq!(1, c.null_count());
+
+        let large_type = DataType::LargeList(Box::new(DataType::Int32));
+        let a = cast(&a, &large_type).unwrap();
+        let c = concat(&[slice(&a, 2, 1), a.clone()]).unwrap();
+        assert_eq!(&large_type, c.data_type());
+        assert_eq!(
+            vec!["[3, 4, 5]", "[1, 2]", "null", "[3, 4, 5]"],
+            strings(&c)
+        );
+    }
+
+    #[test]
+    fn test_concat_struct() {
+        let a: ArrayRef = Arc::new(StructArray::from(vec![
+            (
+                Field::new("x", DataType::Int32, true),
+                Arc::new(Int32Array::from(vec![Some(1), None, Some(3)])) as ArrayRef,
+            ),
+            (
+                Field::new("y", DataType::Utf8, false),
+                Arc::new(BinaryArray::from(vec!["a", "b", "c"])) as ArrayRef,
+            ),
+        ]));
+        let c = concat(&[slice(&a, 1, 2), slice(&a, 0, 1)]).unwrap();
+        assert_eq!(a.data_type(), c.data_type());
+        assert_eq!(
+            vec!["{x: null, y: b}", "{x: 3, y: c}", "{x: 1, y: a}"],
+            strings(&c)
+        );
+    }
+
+    #[test]
+    fn test_concat_dictionary_and_null() {
+        let dict: ArrayRef = Arc::new(
+            crate::compute::dictionary_encode(&BinaryArray::from(vec![
+                Some("x"),
+                None,
+                Some("y"),
+                Some("x"),
+            ]))
+            .unwrap(),
+        );
+        let c = concat(&[slice(&dict, 2, 2), slice(&dict, 0, 2)]).unwrap();
+        assert_eq!(dict.data_type(), c.data_type());
+        assert_eq!(vec!["y", "x", "x", "null"], strings(&c));
+
+        let other: ArrayRef = Arc::new(
+            crate::compute::dictionary_encode(&BinaryArray::from(vec!["y"])).unwrap(),
+        );
+        assert!(concat(&[dict, other]).is_err());
+
+        let a: ArrayRef = Arc::new(NullArray::new(2));
+        let c = concat(&[a.clone(), slice(&a, 1, 1)]).unwrap();
+        assert_eq!(&DataType::Null, c.data_type());
+        assert_eq!(3, c.len());
+        assert_eq!(3, c.null_count());
+    }
+
+    #[test]
+    fn test_concat_invalid() {
+        assert!(concat(&[]).is_err());
+        let a: ArrayRef = Arc::new(Int32Array::from(vec![1]));
+        let b: ArrayRef = Arc::new(Int64Array::from(vec![1]));
+        assert!(concat(&[a, b]).is_err());
+    }
+
+    fn batch(schema: &Arc<Schema>, ids: Vec<i32>, names: Vec<&str>) -> RecordBatch {
+        RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int32Array::from(ids)),
+                Arc::new(BinaryArray::from(names)),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_concat_batches() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, false),
+        ]));
+        let batches = vec![
+            batch(&schema, vec![1, 2], vec!["a", "b"]),
+            batch(&schema, vec![3], vec!["c"]),
+        ];
+        let concatenated = concat_batches(&schema, &batches).unwrap();
+        assert_eq!(&schema, concatenated.schema());
+        assert_eq!(3, concatenated.num_rows());
+        let ids = as_primitive_array::<Int32Type>(concatenated.column(0));
+        assert_eq!(&[1, 2, 3], ids.value_slice(0, 3));
+        let names = as_string_array(concatenated.column(1));
+        assert_eq!("c", names.get_string(2));
+    }
+
+    #[test]
+    fn test_concat_batches_invalid() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, false),
+        ]));
+        assert!(concat_batches(&schema, &[]).is_err());
+
+        let other_schema = Arc::new(Schema::new(vec![
+            Field::new("key", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, false),
+        ]));
+        let batches = vec![
+            batch(&schema, vec![1], vec!["a"]),
+            batch(&other_schema, vec![2], vec!["b"]),
+        ];
+        assert!(concat_batches(&schema, &batches).is_err());
+    }
+}
@@ -0,0 +1,144 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines kernels for delta encoding of integer arrays, where each value is stored as
+//! the difference from the previous one.
+
+use crate::array::{Array, Int64Array};
+use crate::error::{ArrowError, Result};
+
+/// Delta-encodes `array`, returning its first value and the differences between each
+/// value and the previous one.
+///
+/// The differences have `array.len() - 1` elements, and `decode_delta` reconstructs
+/// `array` from the result. Returns an error if `array` is empty or contains nulls, or if
+/// a difference overflows an `i64`.
+pub fn encode_delta(array: &Int64Array) -> Result<(i64, Int64Array)> {
+    if array.len() == 0 {
+        return Err(ArrowError::ComputeError(
+            "Cannot delta-encode an empty array".to_string(),
+        ));
+    }
+    if array.null_count() > 0 {
+        return Err(ArrowError::ComputeError(
+            "Cannot delta-encode an array containing nulls".to_string(),
+        ));
+    }
+    let mut deltas = Vec::with_capacity(array.len() - 1);
+    for i in 1..array.len() {
+        let delta = array
+            .value(i)
+            .checked_sub(array.value(i - 1))
+            .ok_or_else(|| {
+                ArrowError::ComputeError(format!(
+                    "Overflow delta-encoding the value at index {}",
+                    i
+                ))
+            })?;
+        deltas.push(delta);
+    }
+    Ok((array.value(0), Int64Array::from(deltas)))
+}
+
+/// Reconstructs absolute values from a delta-encoded sequence.
+///
+/// The returned array starts with `first`, followed by the cumulative sums of `first`
+/// and `deltas`, so it has `deltas.len() + 1` elements. Returns an error if `deltas`
+/// contains nulls, or if a value overflows an `i64`.
+///
+/// ```
+/// use arrow::array::Int64Array;
+/// use arrow::compute::decode_delta;
+///
+/// let deltas = Int64Array::from(vec![3, 5, 2]);
+/// let values = decode_delta(100, &deltas).unwrap();
+/// assert_eq!(&[100, 103, 108, 110], values.value_slice(0, 4));
+/// ```
+pub fn decode_delta(first: i64, deltas: &Int64Array) -> Result<Int64Array> {
+    if deltas.null_count() > 0 {
+        return Err(ArrowError::ComputeError(
+            "Cannot decode a delta-encoded array containing nulls".to_string(),
+        ));
+    }
+    let mut values = Vec::with_capacity(deltas.len() + 1);
+    let mut current = first;
+    values.push(current);
+    for i in 0..deltas.len() {
+        current = current.checked_add(deltas.value(i)).ok_or_else(|| {
+            ArrowError::ComputeError(format!(
+                "Overflow decoding the delta-encoded value at index {}",
+                i + 1
+            ))
+        })?;
+        values.push(current);
+    }
+    Ok(Int64Array::from(values))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_delta() {
+        let deltas = Int64Array::from(vec![3, 5, 2]);
+        let values = decode_delta(100, &deltas).unwrap();
+        assert_eq!(4, values.len());
+        assert_eq!(0, values.null_count());
+        assert_eq!(&[100, 103, 108, 110], values.value_slice(0, 4));
+    }
+
+    #[test]
+    fn test_decode_delta_empty_and_negative() {
+        let values = decode_delta(7, &Int64Array::from(Vec::<i64>::new())).unwrap();
+        assert_eq!(&[7], values.value_slice(0, 1));
+
+        let values = decode_delta(0, &Int64Array::from(vec![-4, 1])).unwrap();
+        assert_eq!(&[0, -4, -3], values.value_slice(0, 3));
+    }
+
+    #[test]
+    fn test_encode_delta_round_trip() {
+        let values: Vec<i64> = (0..100).map(|i| i * i - 50).collect();
+        let array = Int64Array::from(values.clone());
+        let (first, deltas) = encode_delta(&array).unwrap();
+        assert_eq!(-50, first);
+        assert_eq!(99, deltas.len());
+        assert_eq!(1, deltas.value(0));
+        assert_eq!(197, deltas.value(98));
+
+        let decoded = decode_delta(first, &deltas).unwrap();
+        assert_eq!(array.data(), decoded.data());
+        assert_eq!(&values[..], decoded.value_slice(0, decoded.len()));
+    }
+
+    #[test]
+    fn test_encode_delta_errors() {
+        assert!(encode_delta(&Int64Array::from(Vec::<i64>::new())).is_err());
+        assert!(encode_delta(&Int64Array::from(vec![Some(1), None])).is_err());
+        assert!(encode_delta(&Int64Array::from(vec![i64::min_value(), 1])).is_err());
+    }
+
+    #[test]
+    fn test_decode_delta_errors() {
+        let deltas = Int64Array::from(vec![Some(1), None]);
+        assert!(decode_delta(0, &deltas).is_err());
+
+        let deltas = Int64Array::from(vec![1]);
+        assert!(decode_delta(i64::max_value(), &deltas).is_err());
+    }
+}
@@ -0,0 +1,168 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines kernels for dictionary-encoding arrays.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use crate::array::*;
+use crate::array_data::ArrayData;
+use crate::builder::{BinaryBuilder, Int32Builder};
+use crate::datatypes::DataType;
+use crate::error::{ArrowError, Result};
+
+/// Dictionary-encodes a binary array.
+///
+/// The values of the returned array are the distinct non-null elements of `array`, in
+/// order of first appearance. Null elements of `array` are null keys in the result.
+pub fn dictionary_encode(array: &BinaryArray) -> Result<Int32DictionaryArray> {
+    let mut keys_builder = Int32Builder::new(array.len());
+    let mut values_builder = BinaryBuilder::new(array.len());
+    let mut dictionary: HashMap<&[u8], i32> = HashMap::new();
+    for i in 0..array.len() {
+        if array.is_null(i) {
+            keys_builder.append_null()?;
+            continue;
+        }
+        let value = array.value(i);
+        let key = match dictionary.get(value) {
+            Some(key) => *key,
+            None => {
+                let key = dictionary.len() as i32;
+                values_builder.append_bytes(value)?;
+                dictionary.insert(value, key);
+                key
+            }
+        };
+        keys_builder.append_value(key)?;
+    }
+
+    let keys = keys_builder.finish();
+    let values = values_builder.finish();
+    let mut builder = ArrayData::builder(DataType::Dictionary(
+        Box::new(DataType::Int32),
+        Box::new(DataType::Utf8),
+    ))
+    .len(keys.len())
+    .null_count(keys.null_count())
+    .add_buffer(keys.values())
+    .add_child_data(values.data());
+    if let Some(bitmap) = keys.data().null_bitmap() {
+        builder = builder.null_bit_buffer(bitmap.bits.clone());
+    }
+    Ok(Int32DictionaryArray::from(builder.build()))
+}
+
+/// Dictionary-encodes `array` if the ratio of its number of distinct values to its
+/// length is at most `max_cardinality_ratio`, otherwise returns `array` unchanged.
+///
+/// Nulls are not counted as distinct values. An empty array is returned unchanged.
+pub fn maybe_dictionary_encode(
+    array: &BinaryArray,
+    max_cardinality_ratio: f64,
+) -> Result<ArrayRef> {
+    if max_cardinality_ratio < 0.0 || max_cardinality_ratio.is_nan() {
+        return Err(ArrowError::ComputeError(format!(
+            "Invalid cardinality ratio {}, expected a non-negative number",
+            max_cardinality_ratio
+        )));
+    }
+    if array.len() == 0 {
+        return Ok(Arc::new(BinaryArray::from(array.data())));
+    }
+
+    let mut distinct = HashSet::new();
+    for i in 0..array.len() {
+        if array.is_valid(i) {
+            distinct.insert(array.value(i));
+        }
+    }
+    if distinct.len() as f64 / array.len() as f64 <= max_cardinality_ratio {
+        Ok(Arc::new(dictionary_encode(array)?))
+    } else {
+        Ok(Arc::new(BinaryArray::from(array.data())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dictionary_encode() {
+        let mut builder = BinaryBuilder::new(16);
+        builder.append_string("foo").unwrap();
+        builder.append_string("bar").unwrap();
+        builder.append_null().unwrap();
+        builder.append_string("foo").unwrap();
+        let array = builder.finish();
+
+        let dict = dictionary_encode(&array).unwrap();
+        assert_eq!(4, dict.len());
+        assert_eq!(1, dict.null_count());
+        assert_eq!(
+            &DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)),
+            dict.data_type()
+        );
+
+        let keys = dict.keys();
+        assert_eq!(0, keys.value(0));
+        assert_eq!(1, keys.value(1));
+        assert!(keys.is_null(2));
+        assert_eq!(0, keys.value(3));
+
+        let values = dict.values();
+        let values = values.as_any().downcast_ref::<BinaryArray>().unwrap();
+        assert_eq!(2, values.len());
+        assert_eq!("foo", values.get_string(0));
+        assert_eq!("bar", values.get_string(1));
+    }
+
+    #[test]
+    fn test_maybe_dictionary_encode_low_cardinality() {
+        let array = BinaryArray::from(vec!["a", "b", "a", "a", "b", "a"]);
+        let result = maybe_dictionary_encode(&array, 0.5).unwrap();
+        assert_eq!(
+            &DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)),
+            result.data_type()
+        );
+        let dict = result
+            .as_any()
+            .downcast_ref::<Int32DictionaryArray>()
+            .unwrap();
+        assert_eq!(6, dict.len());
+        assert_eq!(2, dict.values().len());
+        assert_eq!(1, dict.keys().value(4));
+    }
+
+    #[test]
+    fn test_maybe_dictionary_encode_high_cardinality() {
+        let array = BinaryArray::from(vec!["a", "b", "c", "d", "a"]);
+        let result = maybe_dictionary_encode(&array, 0.5).unwrap();
+        assert_eq!(&DataType::Utf8, result.data_type());
+        let binary = result.as_any().downcast_ref::<BinaryArray>().unwrap();
+        assert_eq!(5, binary.len());
+        assert_eq!("c", binary.get_string(2));
+    }
+
+    #[test]
+    fn test_maybe_dictionary_encode_invalid_ratio() {
+        let array = BinaryArray::from(vec!["a"]);
+        assert!(maybe_dictionary_encode(&array, -1.0).is_err());
+    }
+}
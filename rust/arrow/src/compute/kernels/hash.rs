@@ -0,0 +1,282 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines kernels to hash array elements, e.g. to partition rows by key.
+//!
+//! The hashes are stable: they only depend on the seed and on the value of each element,
+//! not on the process or the platform, so they can be used to route rows across
+//! machines.
+
+use crate::array::*;
+use crate::datatypes::*;
+use crate::error::{ArrowError, Result};
+
+/// Offset basis of the 64-bit FNV-1a hash
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+/// Prime of the 64-bit FNV-1a hash
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+/// Hash of null elements, before mixing in the seed
+const NULL_HASH: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// Hashes `bytes` with FNV-1a, and mixes the result to spread it over all bits.
+fn hash_bytes(seed: u64, bytes: &[u8]) -> u64 {
+    let mut h = FNV_OFFSET_BASIS ^ seed;
+    for b in bytes {
+        h ^= u64::from(*b);
+        h = h.wrapping_mul(FNV_PRIME);
+    }
+    mix(h)
+}
+
+/// The finalizer of SplitMix64, so that the low bits of a hash depend on all input bits.
+fn mix(mut h: u64) -> u64 {
+    h = (h ^ (h >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    h = (h ^ (h >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    h ^ (h >> 31)
+}
+
+macro_rules! hash_primitive {
+    ($array:expr, $seed:expr, $hashes:expr, $array_type:ident) => {{
+        let a = $array.as_any().downcast_ref::<$array_type>().unwrap();
+        for (i, h) in $hashes.iter_mut().enumerate() {
+            *h = if a.is_null(i) {
+                mix(NULL_HASH ^ $seed)
+            } else {
+                hash_bytes($seed, a.value(i).to_byte_slice())
+            };
+        }
+    }};
+}
+
+/// Writes the hash of each element of `array` into `hashes`, which must have the same
+/// length as `array`.
+///
+/// Equal values of arrays of the same type hash to equal values for the same `seed`,
+/// and all null elements hash to the same value. Primitive values are hashed by their
+/// binary representation, so for example `0.0` and `-0.0` hash differently.
+pub fn hash_array(array: &ArrayRef, seed: u64, hashes: &mut [u64]) -> Result<()> {
+    if array.len() != hashes.len() {
+        return Err(ArrowError::ComputeError(format!(
+            "Cannot write the hashes of {} elements into a buffer of length {}",
+            array.len(),
+            hashes.len()
+        )));
+    }
+    match array.data_type() {
+        DataType::Boolean => hash_primitive!(array, seed, hashes, BooleanArray),
+        DataType::Int8 => hash_primitive!(array, seed, hashes, Int8Array),
+        DataType::Int16 => hash_primitive!(array, seed, hashes, Int16Array),
+        DataType::Int32 => hash_primitive!(array, seed, hashes, Int32Array),
+        DataType::Int64 => hash_primitive!(array, seed, hashes, Int64Array),
+        DataType::UInt8 => hash_primitive!(array, seed, hashes, UInt8Array),
+        DataType::UInt16 => hash_primitive!(array, seed, hashes, UInt16Array),
+        DataType::UInt32 => hash_primitive!(array, seed, hashes, UInt32Array),
+        DataType::UInt64 => hash_primitive!(array, seed, hashes, UInt64Array),
+        DataType::Float32 => hash_primitive!(array, seed, hashes, Float32Array),
+        DataType::Float64 => hash_primitive!(array, seed, hashes, Float64Array),
+        DataType::Timestamp(TimeUnit::Second, _) => {
+            hash_primitive!(array, seed, hashes, TimestampSecondArray)
+        }
+        DataType::Timestamp(TimeUnit::Millisecond, _) => {
+            hash_primitive!(array, seed, hashes, TimestampMillisecondArray)
+        }
+        DataType::Timestamp(TimeUnit::Microsecond, _) => {
+            hash_primitive!(array, seed, hashes, TimestampMicrosecondArray)
+        }
+        DataType::Timestamp(TimeUnit::Nanosecond, _) => {
+            hash_primitive!(array, seed, hashes, TimestampNanosecondArray)
+        }
+        DataType::Date32(DateUnit::Day) => {
+            hash_primitive!(array, seed, hashes, Date32Array)
+        }
+        DataType::Date64(DateUnit::Millisecond) => {
+            hash_primitive!(array, seed, hashes, Date64Array)
+        }
+        DataType::Utf8 => {
+            let a = array.as_any().downcast_ref::<BinaryArray>().unwrap();
+            for (i, h) in hashes.iter_mut().enumerate() {
+                *h = if a.is_null(i) {
+                    mix(NULL_HASH ^ seed)
+                } else {
+                    hash_bytes(seed, a.value(i))
+                };
+            }
+        }
+        other => {
+            return Err(ArrowError::ComputeError(format!(
+                "hash_array not supported for {:?}",
+                other
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Returns the hash of the element at `index` of `array`, the same as the one written
+/// by `hash_array` for that element with the same `seed`.
+///
+/// This is meant for hashing single elements, e.g. to probe a hash table: prefer
+/// `hash_array` to hash all the elements of an array. Returns an error if `index` is out
+/// of bounds, or if the type of `array` is not supported by `hash_array`.
+pub fn hash(array: &ArrayRef, index: usize, seed: u64) -> Result<u64> {
+    if index >= array.len() {
+        return Err(ArrowError::ComputeError(format!(
+            "Array index out of bounds, cannot hash item at index {} from {} entries",
+            index,
+            array.len()
+        )));
+    }
+    let element = make_array(array.data().slice(index, 1));
+    let mut hashes = [0];
+    hash_array(&element, seed, &mut hashes)?;
+    Ok(hashes[0])
+}
+
+/// Splits the rows of `keys` into `num_partitions` partitions by the hash of each key.
+///
+/// Returns, for each partition, the indices of the rows whose key hash modulo
+/// `num_partitions` equals the partition number, in increasing order. Rows with a null
+/// key are always assigned to partition 0.
+pub fn hash_partition(
+    keys: &ArrayRef,
+    num_partitions: usize,
+    seed: u64,
+) -> Result<Vec<UInt32Array>> {
+    if num_partitions == 0 {
+        return Err(ArrowError::ComputeError(
+            "Cannot hash partition into zero partitions".to_string(),
+        ));
+    }
+    let mut hashes = vec![0; keys.len()];
+    hash_array(keys, seed, &mut hashes)?;
+
+    let mut partitions: Vec<Vec<u32>> = vec![vec![]; num_partitions];
+    for (i, h) in hashes.iter().enumerate() {
+        let partition = if keys.is_null(i) {
+            0
+        } else {
+            (h % num_partitions as u64) as usize
+        };
+        partitions[partition].push(i as u32);
+    }
+    Ok(partitions.into_iter().map(UInt32Array::from).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    fn hashes(array: ArrayRef, seed: u64) -> Vec<u64> {
+        let mut hashes = vec![0; array.len()];
+        hash_array(&array, seed, &mut hashes).unwrap();
+        hashes
+    }
+
+    #[test]
+    fn test_hash_array_primitive() {
+        let a = hashes(Arc::new(Int32Array::from(vec![Some(1), None, Some(1)])), 0);
+        let b = hashes(Arc::new(Int32Array::from(vec![None, Some(1), Some(2)])), 0);
+        assert_eq!(a[0], a[2]);
+        assert_eq!(a[0], b[1]);
+        assert_eq!(a[1], b[0]);
+        assert_ne!(a[0], b[2]);
+        assert_ne!(a[0], a[1]);
+
+        let c = hashes(Arc::new(Int32Array::from(vec![1])), 42);
+        assert_ne!(a[0], c[0]);
+    }
+
+    #[test]
+    fn test_hash_array_binary() {
+        let a = hashes(Arc::new(BinaryArray::from(vec!["foo", "", "foo"])), 7);
+        let b = hashes(Arc::new(BinaryArray::from(vec!["bar", "foo"])), 7);
+        assert_eq!(a[0], a[2]);
+        assert_eq!(a[0], b[1]);
+        assert_ne!(a[0], a[1]);
+        assert_ne!(a[0], b[0]);
+    }
+
+    #[test]
+    fn test_hash() {
+        let array: ArrayRef = Arc::new(BinaryArray::from(vec![
+            Some("foo"),
+            None,
+            Some(""),
+            Some("foo"),
+        ]));
+        let all = hashes(array.clone(), 3);
+        for i in 0..array.len() {
+            assert_eq!(all[i], hash(&array, i, 3).unwrap());
+        }
+        assert_eq!(hash(&array, 0, 3).unwrap(), hash(&array, 3, 3).unwrap());
+
+        // nulls hash to the same value whatever the type
+        let other: ArrayRef = Arc::new(BooleanArray::from(vec![Some(true), None]));
+        assert_eq!(hash(&array, 1, 3).unwrap(), hash(&other, 1, 3).unwrap());
+        assert_eq!(hashes(other.clone(), 3)[0], hash(&other, 0, 3).unwrap());
+
+        assert!(hash(&array, 4, 3).is_err());
+    }
+
+    #[test]
+    fn test_hash_array_length_mismatch() {
+        let a: ArrayRef = Arc::new(BooleanArray::from(vec![true, false]));
+        let mut hashes = vec![0; 3];
+        assert!(hash_array(&a, 0, &mut hashes).is_err());
+    }
+
+    #[test]
+    fn test_hash_partition() {
+        let keys: ArrayRef = Arc::new(Int32Array::from(
+            (0..100)
+                .map(|i| if i % 10 == 0 { None } else { Some(i % 7) })
+                .collect::<Vec<_>>(),
+        ));
+        let partitions = hash_partition(&keys, 4, 0).unwrap();
+        assert_eq!(4, partitions.len());
+
+        let mut seen = vec![0; 100];
+        for (p, partition) in partitions.iter().enumerate() {
+            for j in 0..partition.len() {
+                let i = partition.value(j) as usize;
+                seen[i] += 1;
+                if i % 10 == 0 {
+                    assert_eq!(0, p);
+                }
+            }
+        }
+        assert!(seen.iter().all(|count| *count == 1));
+
+        // equal keys end up in the same partition
+        let partition_of = |i: u32| {
+            partitions
+                .iter()
+                .position(|p| (0..p.len()).any(|j| p.value(j) == i))
+                .unwrap()
+        };
+        assert_eq!(partition_of(1), partition_of(8));
+        assert_eq!(partition_of(3), partition_of(17));
+    }
+
+    #[test]
+    fn test_hash_partition_zero_partitions() {
+        let keys: ArrayRef = Arc::new(Int32Array::from(vec![1]));
+        assert!(hash_partition(&keys, 0, 0).is_err());
+    }
+}
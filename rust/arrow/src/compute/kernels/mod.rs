@@ -17,4 +17,17 @@
 
 //! Computation kernels on Arrow Arrays
 
+pub mod aggregate;
+pub mod cardinality;
+pub mod cast;
+pub mod concat;
+pub mod delta;
+pub mod dictionary;
+pub mod hash;
+pub mod nest;
+pub mod row;
+pub mod sort;
+pub mod sparse;
+pub mod take;
 pub mod temporal;
+pub mod unique;
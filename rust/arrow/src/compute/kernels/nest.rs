@@ -0,0 +1,393 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines kernels converting between columns identified by dotted paths, such as
+//! `address.city`, and nested `StructArray`s.
+
+use std::sync::Arc;
+
+use crate::array::*;
+use crate::array_data::ArrayData;
+use crate::buffer::MutableBuffer;
+use crate::datatypes::{DataType, Field};
+use crate::error::{ArrowError, Result};
+use crate::util::bit_util;
+
+/// A node of the tree of column paths
+enum PathNode {
+    Leaf(ArrayRef),
+    Branch(Vec<(String, PathNode)>),
+}
+
+/// Builds a struct array from columns identified by dotted paths, nesting the columns
+/// that share a path prefix in struct fields.
+///
+/// For example, the columns `id`, `address.city` and `address.zip` become the fields
+/// `id` and `address`, where `address` is a struct with the fields `city` and `zip`.
+/// Fields are ordered by the first column of their path. As the nullability of a column
+/// isn't known from its values, the fields of the columns are nullable, so that columns
+/// with the same paths and types always produce the same data type, while the struct
+/// fields that nest columns are never null. All columns must have the same length, and
+/// returns an error if a path is both a column and the prefix of another
+/// column, or if two columns have the same path.
+pub fn nest_columns(columns: Vec<(String, ArrayRef)>) -> Result<StructArray> {
+    let len = match columns.first() {
+        Some((_, column)) => column.len(),
+        None => {
+            return Err(ArrowError::ComputeError(
+                "Cannot nest an empty list of columns".to_string(),
+            ));
+        }
+    };
+    let mut root = vec![];
+    for (path, column) in columns {
+        if column.len() != len {
+            return Err(ArrowError::ComputeError(format!(
+                "Column '{}' has length {}, expected {}",
+                path,
+                column.len(),
+                len
+            )));
+        }
+        let segments: Vec<&str> = path.split('.').collect();
+        if segments.iter().any(|s| s.is_empty()) {
+            return Err(ArrowError::ComputeError(format!(
+                "Invalid column path '{}'",
+                path
+            )));
+        }
+        insert_path(&mut root, &segments, column, &path)?;
+    }
+    Ok(StructArray::from(build_fields(root)))
+}
+
+/// Inserts `column` at the path made of `segments` in the children of a node.
+fn insert_path(
+    children: &mut Vec<(String, PathNode)>,
+    segments: &[&str],
+    column: ArrayRef,
+    path: &str,
+) -> Result<()> {
+    let conflict = || {
+        ArrowError::ComputeError(format!(
+            "Column path '{}' conflicts with another column",
+            path
+        ))
+    };
+    let position = children.iter().position(|(name, _)| name == segments[0]);
+    if segments.len() == 1 {
+        if position.is_some() {
+            return Err(conflict());
+        }
+        children.push((segments[0].to_string(), PathNode::Leaf(column)));
+        return Ok(());
+    }
+    let position = match position {
+        Some(position) => position,
+        None => {
+            children.push((segments[0].to_string(), PathNode::Branch(vec![])));
+            children.len() - 1
+        }
+    };
+    match children[position].1 {
+        PathNode::Branch(ref mut grandchildren) => {
+            insert_path(grandchildren, &segments[1..], column, path)
+        }
+        PathNode::Leaf(_) => Err(conflict()),
+    }
+}
+
+/// Converts the children of a node to the fields of a struct array.
+fn build_fields(children: Vec<(String, PathNode)>) -> Vec<(Field, ArrayRef)> {
+    children
+        .into_iter()
+        .map(|(name, node)| match node {
+            PathNode::Leaf(column) => {
+                (Field::new(&name, column.data_type().clone(), true), column)
+            }
+            PathNode::Branch(grandchildren) => {
+                let array = StructArray::from(build_fields(grandchildren));
+                (
+                    Field::new(&name, array.data_type().clone(), false),
+                    Arc::new(array) as ArrayRef,
+                )
+            }
+        })
+        .collect()
+}
+
+/// Flattens a struct array into its leaf columns, identified by their paths made of the
+/// names of the nested fields joined with `separator`.
+///
+/// This is the reverse of `nest_columns` with a separator of `"."`: a struct with the
+/// fields `id` and `address`, where `address` is a struct with the fields `city` and
+/// `zip`, is flattened into the columns `id`, `address.city` and `address.zip`. As the
+/// leaf columns don't have the null bitmaps of their parent structs anymore, a value of
+/// a leaf column is null if it is null in any of its parent structs.
+pub fn flatten_to_paths(
+    array: &StructArray,
+    separator: &str,
+) -> Result<Vec<(String, ArrayRef)>> {
+    let mut columns = vec![];
+    flatten_struct(array, "", separator, None, &mut columns)?;
+    Ok(columns)
+}
+
+/// Appends the leaf columns of `array` to `columns`, prefixing their paths with `prefix`.
+/// `parent_validity` is the validity of the elements of the parent structs, if any of
+/// them has nulls.
+fn flatten_struct(
+    array: &StructArray,
+    prefix: &str,
+    separator: &str,
+    parent_validity: Option<&[bool]>,
+    columns: &mut Vec<(String, ArrayRef)>,
+) -> Result<()> {
+    let fields = match array.data_type() {
+        DataType::Struct(fields) => fields,
+        _ => unreachable!("StructArray must have a Struct data type"),
+    };
+    let validity = if array.null_count() > 0 || parent_validity.is_some() {
+        Some(
+            (0..array.len())
+                .map(|i| array.is_valid(i) && parent_validity.map_or(true, |v| v[i]))
+                .collect::<Vec<bool>>(),
+        )
+    } else {
+        None
+    };
+    for (i, field) in fields.iter().enumerate() {
+        let path = format!("{}{}", prefix, field.name());
+        // the children of a sliced struct start at its offset, but can extend past its
+        // end
+        let column = &match array.column(i) {
+            column if column.len() > array.len() => {
+                make_array(column.data_ref().slice(0, array.len()))
+            }
+            column => column.clone(),
+        };
+        match column.data_type() {
+            DataType::Struct(_) => {
+                let child = column.as_any().downcast_ref::<StructArray>().unwrap();
+                let child_prefix = format!("{}{}", path, separator);
+                flatten_struct(
+                    child,
+                    &child_prefix,
+                    separator,
+                    validity.as_ref().map(|v| &v[..]),
+                    columns,
+                )?;
+            }
+            _ => {
+                let column = match validity {
+                    Some(ref validity) => with_validity(column, validity)?,
+                    None => column.clone(),
+                };
+                columns.push((path, column));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns `array` with the elements that are not valid in `validity` made null. The
+/// returned array shares the buffers and children of `array`.
+fn with_validity(array: &ArrayRef, validity: &[bool]) -> Result<ArrayRef> {
+    if array.len() != validity.len() {
+        return Err(ArrowError::ComputeError(format!(
+            "Struct child array has length {}, expected {}",
+            array.len(),
+            validity.len()
+        )));
+    }
+    let data = array.data_ref();
+    let offset = data.offset();
+    let num_bytes = bit_util::ceil(offset + data.len(), 8);
+    let mut null_buffer = MutableBuffer::new(num_bytes).with_bitset(num_bytes, false);
+    {
+        let null_slice = null_buffer.data_mut();
+        for (i, valid) in validity.iter().enumerate() {
+            if *valid && array.is_valid(i) {
+                bit_util::set_bit(null_slice, offset + i);
+            }
+        }
+    }
+    Ok(make_array(Arc::new(ArrayData::new(
+        data.data_type().clone(),
+        data.len(),
+        None,
+        Some(null_buffer.freeze()),
+        offset,
+        data.buffers().to_vec(),
+        data.child_data().to_vec(),
+    ))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::buffer::Buffer;
+
+    #[test]
+    fn test_nest_columns() {
+        let columns: Vec<(String, ArrayRef)> = vec![
+            (
+                "address.city".to_string(),
+                Arc::new(BinaryArray::from(vec!["Paris", "Oslo"])),
+            ),
+            ("id".to_string(), Arc::new(Int32Array::from(vec![1, 2]))),
+            (
+                "address.zip".to_string(),
+                Arc::new(Int32Array::from(vec![Some(75001), None])),
+            ),
+        ];
+        let nested = nest_columns(columns).unwrap();
+        assert_eq!(2, nested.len());
+
+        let address_type = DataType::Struct(vec![
+            Field::new("city", DataType::Utf8, true),
+            Field::new("zip", DataType::Int32, true),
+        ]);
+        assert_eq!(
+            &DataType::Struct(vec![
+                Field::new("address", address_type, false),
+                Field::new("id", DataType::Int32, true),
+            ]),
+            nested.data_type()
+        );
+
+        let address = nested
+            .column(0)
+            .as_any()
+            .downcast_ref::<StructArray>()
+            .unwrap();
+        assert_eq!("{city: Oslo, zip: null}", address.value_to_string(1));
+        assert_eq!(
+            "{address: {city: Paris, zip: 75001}, id: 1}",
+            nested.value_to_string(0)
+        );
+    }
+
+    /// Returns a struct array of the fields `id` and `address`, whose second address is
+    /// null.
+    fn people() -> StructArray {
+        let address = StructArray::from(vec![
+            (
+                Field::new("city", DataType::Utf8, true),
+                Arc::new(BinaryArray::from(vec![Some("Paris"), Some("Oslo"), None]))
+                    as ArrayRef,
+            ),
+            (
+                Field::new("zip", DataType::Int32, false),
+                Arc::new(Int32Array::from(vec![75001, 150, 1234])) as ArrayRef,
+            ),
+        ]);
+        // make the second address null
+        let address_data = address.data();
+        let address_data = ArrayData::builder(address.data_type().clone())
+            .len(3)
+            .null_bit_buffer(Buffer::from([0b101]))
+            .child_data(address_data.child_data().to_vec())
+            .build();
+        StructArray::from(vec![
+            (
+                Field::new("id", DataType::Int32, false),
+                Arc::new(Int32Array::from(vec![1, 2, 3])) as ArrayRef,
+            ),
+            (
+                Field::new("address", address_data.data_type().clone(), true),
+                make_array(address_data),
+            ),
+        ])
+    }
+
+    fn values(column: &ArrayRef) -> Vec<String> {
+        (0..column.len())
+            .map(|i| column.value_to_string(i))
+            .collect()
+    }
+
+    #[test]
+    fn test_flatten_to_paths() {
+        let array = people();
+        let columns = flatten_to_paths(&array, ".").unwrap();
+        let names: Vec<&str> = columns.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(vec!["id", "address.city", "address.zip"], names);
+
+        assert_eq!(vec!["1", "2", "3"], values(&columns[0].1));
+        assert_eq!(vec!["Paris", "null", "null"], values(&columns[1].1));
+        assert_eq!(vec!["75001", "null", "1234"], values(&columns[2].1));
+        assert_eq!(1, columns[2].1.null_count());
+
+        let columns = flatten_to_paths(&array, "/").unwrap();
+        assert_eq!("address/zip", columns[2].0);
+
+        // flattening then nesting again gives back the same values
+        let nested = nest_columns(flatten_to_paths(&array, ".").unwrap()).unwrap();
+        assert_eq!(
+            "{id: 1, address: {city: Paris, zip: 75001}}",
+            nested.value_to_string(0)
+        );
+    }
+
+    #[test]
+    fn test_flatten_to_paths_sliced() {
+        let array = StructArray::from(people().data().slice(1, 2));
+        let columns = flatten_to_paths(&array, ".").unwrap();
+        assert_eq!(vec!["2", "3"], values(&columns[0].1));
+        assert_eq!(vec!["null", "null"], values(&columns[1].1));
+        assert_eq!(vec!["null", "1234"], values(&columns[2].1));
+
+        // the children of a struct sliced from its start extend past its end
+        let array = StructArray::from(people().data().slice(0, 2));
+        let columns = flatten_to_paths(&array, ".").unwrap();
+        assert!(columns.iter().all(|(_, column)| column.len() == 2));
+        assert_eq!(vec!["75001", "null"], values(&columns[2].1));
+    }
+
+    #[test]
+    fn test_nest_columns_conflicts() {
+        let column = || Arc::new(Int32Array::from(vec![1])) as ArrayRef;
+        assert!(nest_columns(vec![
+            ("a".to_string(), column()),
+            ("a.b".to_string(), column())
+        ])
+        .is_err());
+        assert!(nest_columns(vec![
+            ("a.b".to_string(), column()),
+            ("a".to_string(), column())
+        ])
+        .is_err());
+        assert!(nest_columns(vec![
+            ("a.b".to_string(), column()),
+            ("a.b".to_string(), column())
+        ])
+        .is_err());
+        assert!(nest_columns(vec![("a..b".to_string(), column())]).is_err());
+        assert!(nest_columns(vec![]).is_err());
+    }
+
+    #[test]
+    fn test_nest_columns_different_lengths() {
+        let columns: Vec<(String, ArrayRef)> = vec![
+            ("a".to_string(), Arc::new(Int32Array::from(vec![1, 2]))),
+            ("b.c".to_string(), Arc::new(Int32Array::from(vec![1]))),
+        ];
+        assert!(nest_columns(columns).is_err());
+    }
+}
@@ -0,0 +1,570 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines kernels encoding the rows of several columns into byte keys whose
+//! lexicographic order is the order of the rows, e.g. to sort or join on multiple
+//! columns by comparing a single binary value per row.
+//!
+//! The key of a row is the concatenation of the encodings of its values, one per column.
+//! Each value starts with a marker byte, `0x01` for valid values of ascending columns,
+//! `0x00` for valid values of descending columns and `0x02` for nulls, so that nulls are
+//! ordered after all other values, like `sort_to_indices` does. Valid values follow their
+//! marker:
+//!
+//! * integers are encoded big-endian, with the sign bit of signed integers flipped so
+//!   that negative values are ordered before positive ones
+//! * floating point values are encoded like integers after flipping the sign bit of
+//!   positive values and all bits of negative ones, which orders `NaN` after all other
+//!   values
+//! * booleans are encoded as a single `0x00` or `0x01` byte
+//! * strings are encoded with every `0x00` byte escaped as `0x00 0xFF`, followed by a
+//!   `0x00 0x00` terminator, so that a string is ordered before the strings it's a prefix
+//!   of
+//!
+//! Null fixed-width values are followed by as many `0x00` bytes as a valid value, while
+//! null strings are only made of their marker. The bytes of the values of descending
+//! columns are inverted, but not their markers, so nulls are still ordered last. As the
+//! markers tell whether each column is descending, `decode_rows` only needs the data
+//! types of the columns to decode the keys.
+
+use std::sync::Arc;
+
+use crate::array::*;
+use crate::builder::{BinaryBuilder, BooleanBuilder, PrimitiveBuilder};
+use crate::datatypes::*;
+use crate::error::{ArrowError, Result};
+
+/// Marker byte preceding valid values of ascending columns
+const ASCENDING_MARKER: u8 = 0x01;
+/// Marker byte preceding valid values of descending columns
+const DESCENDING_MARKER: u8 = 0x00;
+/// Marker byte of null values
+const NULL_MARKER: u8 = 0x02;
+/// Byte following `0x00` bytes of strings
+const ESCAPE: u8 = 0xFF;
+
+macro_rules! encode_integer {
+    ($column:expr, $rows:expr, $descending:expr, $array_type:ident, $sign_bit:expr) => {{
+        let a = $column.as_any().downcast_ref::<$array_type>().unwrap();
+        for (i, row) in $rows.iter_mut().enumerate() {
+            if a.is_null(i) {
+                encode_null(row, std::mem::size_of_val(&a.value(i)));
+            } else {
+                let mut bytes = a.value(i).to_be_bytes();
+                bytes[0] ^= $sign_bit;
+                encode_fixed(row, &bytes, $descending);
+            }
+        }
+    }};
+}
+
+macro_rules! decode_integer {
+    ($keys:expr, $positions:expr, $arrow_type:ident, $native:ident, $sign_bit:expr) => {{
+        let mut builder = PrimitiveBuilder::<$arrow_type>::new($keys.len());
+        for (i, pos) in $positions.iter_mut().enumerate() {
+            let mut bytes = [0u8; std::mem::size_of::<$native>()];
+            if decode_fixed($keys.value(i), pos, &mut bytes)? {
+                bytes[0] ^= $sign_bit;
+                builder.append_value($native::from_be_bytes(bytes))?;
+            } else {
+                builder.append_null()?;
+            }
+        }
+        Arc::new(builder.finish()) as ArrayRef
+    }};
+}
+
+macro_rules! decode_float {
+    ($keys:expr, $positions:expr, $arrow_type:ident, $native:ident, $bits:ident, $sign_bit:expr) => {{
+        let mut builder = PrimitiveBuilder::<$arrow_type>::new($keys.len());
+        for (i, pos) in $positions.iter_mut().enumerate() {
+            let mut bytes = [0u8; std::mem::size_of::<$native>()];
+            if decode_fixed($keys.value(i), pos, &mut bytes)? {
+                let bits = $bits::from_be_bytes(bytes);
+                let bits = if bits & $sign_bit != 0 {
+                    bits ^ $sign_bit
+                } else {
+                    !bits
+                };
+                builder.append_value($native::from_bits(bits))?;
+            } else {
+                builder.append_null()?;
+            }
+        }
+        Arc::new(builder.finish()) as ArrayRef
+    }};
+}
+
+macro_rules! encode_float {
+    ($column:expr, $rows:expr, $descending:expr, $array_type:ident, $sign_bit:expr) => {{
+        let a = $column.as_any().downcast_ref::<$array_type>().unwrap();
+        for (i, row) in $rows.iter_mut().enumerate() {
+            if a.is_null(i) {
+                encode_null(row, std::mem::size_of_val(&a.value(i)));
+            } else {
+                let bits = a.value(i).to_bits();
+                let bits = if bits & $sign_bit == 0 {
+                    bits ^ $sign_bit
+                } else {
+                    !bits
+                };
+                encode_fixed(row, &bits.to_be_bytes(), $descending);
+            }
+        }
+    }};
+}
+
+/// Encodes the rows of `columns` into binary keys, such that comparing the keys of two
+/// rows byte by byte orders them like comparing their values column by column.
+///
+/// Each column is paired with whether it is sorted in descending order. Null values are
+/// ordered after all other values in both ascending and descending columns. Returns an
+/// error if there are no columns, if they have different lengths, or if a column has an
+/// unsupported data type; booleans, integers, floating point values and `Utf8` are
+/// supported.
+pub fn encode_rows(columns: &[(ArrayRef, bool)]) -> Result<BinaryArray> {
+    if columns.is_empty() {
+        return Err(ArrowError::ComputeError(
+            "Cannot encode the rows of zero columns".to_string(),
+        ));
+    }
+    let num_rows = columns[0].0.len();
+    if let Some((column, _)) = columns.iter().find(|(c, _)| c.len() != num_rows) {
+        return Err(ArrowError::ComputeError(format!(
+            "Cannot encode rows of columns with different lengths {} and {}",
+            num_rows,
+            column.len()
+        )));
+    }
+
+    let mut rows = vec![vec![]; num_rows];
+    for (column, descending) in columns {
+        let descending = *descending;
+        match column.data_type() {
+            DataType::Boolean => {
+                let a = column.as_any().downcast_ref::<BooleanArray>().unwrap();
+                for (i, row) in rows.iter_mut().enumerate() {
+                    if a.is_null(i) {
+                        encode_null(row, 1);
+                    } else {
+                        encode_fixed(row, &[a.value(i) as u8], descending);
+                    }
+                }
+            }
+            DataType::Int8 => encode_integer!(column, rows, descending, Int8Array, 0x80),
+            DataType::Int16 => {
+                encode_integer!(column, rows, descending, Int16Array, 0x80)
+            }
+            DataType::Int32 => {
+                encode_integer!(column, rows, descending, Int32Array, 0x80)
+            }
+            DataType::Int64 => {
+                encode_integer!(column, rows, descending, Int64Array, 0x80)
+            }
+            DataType::UInt8 => encode_integer!(column, rows, descending, UInt8Array, 0),
+            DataType::UInt16 => {
+                encode_integer!(column, rows, descending, UInt16Array, 0)
+            }
+            DataType::UInt32 => {
+                encode_integer!(column, rows, descending, UInt32Array, 0)
+            }
+            DataType::UInt64 => {
+                encode_integer!(column, rows, descending, UInt64Array, 0)
+            }
+            DataType::Float32 => {
+                encode_float!(column, rows, descending, Float32Array, 1u32 << 31)
+            }
+            DataType::Float64 => {
+                encode_float!(column, rows, descending, Float64Array, 1u64 << 63)
+            }
+            DataType::Utf8 => {
+                let a = column.as_any().downcast_ref::<BinaryArray>().unwrap();
+                for (i, row) in rows.iter_mut().enumerate() {
+                    if a.is_null(i) {
+                        row.push(NULL_MARKER);
+                    } else {
+                        encode_string(row, a.value(i), descending);
+                    }
+                }
+            }
+            other => {
+                return Err(ArrowError::ComputeError(format!(
+                    "encode_rows not supported for {:?}",
+                    other
+                )));
+            }
+        }
+    }
+
+    let mut builder = BinaryBuilder::new(rows.iter().map(|row| row.len()).sum::<usize>());
+    for row in &rows {
+        builder.append_bytes(row)?;
+    }
+    Ok(builder.finish())
+}
+
+/// Decodes row keys produced by `encode_rows` back into columns of the given data types.
+///
+/// `schema` must contain the data types of the columns that were encoded, in the same
+/// order. Returns an error if a key is null, or if a key doesn't match the layout of the
+/// schema, e.g. because it has been truncated or it has extra bytes.
+pub fn decode_rows(keys: &BinaryArray, schema: &[DataType]) -> Result<Vec<ArrayRef>> {
+    if keys.null_count() > 0 {
+        return Err(ArrowError::ComputeError(
+            "Cannot decode null row keys".to_string(),
+        ));
+    }
+    let num_rows = keys.len();
+    let mut positions = vec![0; num_rows];
+    let mut columns = Vec::with_capacity(schema.len());
+    for data_type in schema {
+        let column = match data_type {
+            DataType::Boolean => {
+                let mut builder = BooleanBuilder::new(num_rows);
+                for (i, pos) in positions.iter_mut().enumerate() {
+                    let mut bytes = [0u8; 1];
+                    if decode_fixed(keys.value(i), pos, &mut bytes)? {
+                        builder.append_value(bytes[0] != 0)?;
+                    } else {
+                        builder.append_null()?;
+                    }
+                }
+                Arc::new(builder.finish()) as ArrayRef
+            }
+            DataType::Int8 => decode_integer!(keys, positions, Int8Type, i8, 0x80),
+            DataType::Int16 => decode_integer!(keys, positions, Int16Type, i16, 0x80),
+            DataType::Int32 => decode_integer!(keys, positions, Int32Type, i32, 0x80),
+            DataType::Int64 => decode_integer!(keys, positions, Int64Type, i64, 0x80),
+            DataType::UInt8 => decode_integer!(keys, positions, UInt8Type, u8, 0),
+            DataType::UInt16 => decode_integer!(keys, positions, UInt16Type, u16, 0),
+            DataType::UInt32 => decode_integer!(keys, positions, UInt32Type, u32, 0),
+            DataType::UInt64 => decode_integer!(keys, positions, UInt64Type, u64, 0),
+            DataType::Float32 => {
+                decode_float!(keys, positions, Float32Type, f32, u32, 1u32 << 31)
+            }
+            DataType::Float64 => {
+                decode_float!(keys, positions, Float64Type, f64, u64, 1u64 << 63)
+            }
+            DataType::Utf8 => {
+                let mut builder = BinaryBuilder::new(num_rows);
+                for (i, pos) in positions.iter_mut().enumerate() {
+                    match decode_string(keys.value(i), pos)? {
+                        Some(value) => builder.append_bytes(&value)?,
+                        None => builder.append_null()?,
+                    }
+                }
+                Arc::new(builder.finish()) as ArrayRef
+            }
+            other => {
+                return Err(ArrowError::ComputeError(format!(
+                    "decode_rows not supported for {:?}",
+                    other
+                )));
+            }
+        };
+        columns.push(column);
+    }
+    for (i, pos) in positions.iter().enumerate() {
+        if *pos != keys.value(i).len() {
+            return Err(ArrowError::ComputeError(format!(
+                "Row key {} has {} extra bytes after the values of the schema",
+                i,
+                keys.value(i).len() - pos
+            )));
+        }
+    }
+    Ok(columns)
+}
+
+/// Returns the marker byte of valid values.
+fn valid_marker(descending: bool) -> u8 {
+    if descending {
+        DESCENDING_MARKER
+    } else {
+        ASCENDING_MARKER
+    }
+}
+
+/// Appends a null value whose valid values are `width` bytes wide.
+fn encode_null(row: &mut Vec<u8>, width: usize) {
+    row.push(NULL_MARKER);
+    row.resize(row.len() + width, 0);
+}
+
+/// Appends a valid fixed-width value, inverting its bytes if `descending`.
+fn encode_fixed(row: &mut Vec<u8>, bytes: &[u8], descending: bool) {
+    row.push(valid_marker(descending));
+    if descending {
+        row.extend(bytes.iter().map(|b| !b));
+    } else {
+        row.extend_from_slice(bytes);
+    }
+}
+
+/// Appends a valid string, escaping its `0x00` bytes and terminating it, and inverting
+/// the resulting bytes if `descending`.
+fn encode_string(row: &mut Vec<u8>, bytes: &[u8], descending: bool) {
+    row.push(valid_marker(descending));
+    let start = row.len();
+    for b in bytes {
+        row.push(*b);
+        if *b == 0 {
+            row.push(ESCAPE);
+        }
+    }
+    row.extend_from_slice(&[0, 0]);
+    if descending {
+        for b in &mut row[start..] {
+            *b = !*b;
+        }
+    }
+}
+
+/// Returns an error for a key that ends or has an unexpected byte at `pos`.
+fn invalid_key(key: &[u8], pos: usize) -> ArrowError {
+    ArrowError::ComputeError(format!(
+        "Row key of {} bytes doesn't match the schema at byte {}",
+        key.len(),
+        pos
+    ))
+}
+
+/// Reads the byte of `key` at `pos` and advances `pos`.
+fn next_byte(key: &[u8], pos: &mut usize) -> Result<u8> {
+    match key.get(*pos) {
+        Some(b) => {
+            *pos += 1;
+            Ok(*b)
+        }
+        None => Err(invalid_key(key, *pos)),
+    }
+}
+
+/// Reads a marker byte, returning `None` for nulls or whether the value is descending.
+fn decode_marker(key: &[u8], pos: &mut usize) -> Result<Option<bool>> {
+    match next_byte(key, pos)? {
+        ASCENDING_MARKER => Ok(Some(false)),
+        DESCENDING_MARKER => Ok(Some(true)),
+        NULL_MARKER => Ok(None),
+        _ => Err(invalid_key(key, *pos - 1)),
+    }
+}
+
+/// Reads a fixed-width value into `bytes`, returning whether the value is valid.
+fn decode_fixed(key: &[u8], pos: &mut usize, bytes: &mut [u8]) -> Result<bool> {
+    let descending = decode_marker(key, pos)?;
+    if key.len() < *pos + bytes.len() {
+        return Err(invalid_key(key, key.len()));
+    }
+    bytes.copy_from_slice(&key[*pos..*pos + bytes.len()]);
+    *pos += bytes.len();
+    if descending == Some(true) {
+        for b in bytes.iter_mut() {
+            *b = !*b;
+        }
+    }
+    Ok(descending.is_some())
+}
+
+/// Reads a string, or returns `None` for a null string.
+fn decode_string(key: &[u8], pos: &mut usize) -> Result<Option<Vec<u8>>> {
+    let descending = match decode_marker(key, pos)? {
+        Some(descending) => descending,
+        None => return Ok(None),
+    };
+    let read = |pos: &mut usize| -> Result<u8> {
+        let b = next_byte(key, pos)?;
+        Ok(if descending { !b } else { b })
+    };
+    let mut value = vec![];
+    loop {
+        match read(pos)? {
+            0 => match read(pos)? {
+                0 => return Ok(Some(value)),
+                ESCAPE => value.push(0),
+                _ => return Err(invalid_key(key, *pos - 1)),
+            },
+            b => value.push(b),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::cmp::Ordering;
+
+    use crate::compute::sort_to_indices;
+
+    /// Returns the order of the rows sorted by their keys.
+    fn sorted_keys(keys: &BinaryArray) -> Vec<u32> {
+        let keys: ArrayRef = Arc::new(BinaryArray::from(keys.data()));
+        let indices = sort_to_indices(&keys, false).unwrap();
+        indices.value_slice(0, indices.len()).to_vec()
+    }
+
+    #[test]
+    fn test_encode_rows_two_columns() {
+        let a_ref: ArrayRef = Arc::new(Int32Array::from(vec![
+            Some(2),
+            Some(-1),
+            None,
+            Some(2),
+            Some(-1),
+            Some(2),
+            None,
+        ]));
+        let b_ref: ArrayRef = Arc::new(BinaryArray::from(vec![
+            Some("b"),
+            Some("a"),
+            Some("z"),
+            Some("ba"),
+            None,
+            Some(""),
+            None,
+        ]));
+        let a = a_ref.as_any().downcast_ref::<Int32Array>().unwrap();
+        let b = b_ref.as_any().downcast_ref::<BinaryArray>().unwrap();
+        let columns: Vec<(ArrayRef, bool)> =
+            vec![(a_ref.clone(), false), (b_ref.clone(), true)];
+        let keys = encode_rows(&columns).unwrap();
+        assert_eq!(7, keys.len());
+        assert_eq!(0, keys.null_count());
+
+        // a ascending then b descending, with nulls last in both columns
+        let cmp_a = |i: usize, j: usize| match (a.is_null(i), a.is_null(j)) {
+            (false, false) => a.value(i).cmp(&a.value(j)),
+            (x, y) => x.cmp(&y),
+        };
+        let cmp_b = |i: usize, j: usize| match (b.is_null(i), b.is_null(j)) {
+            (false, false) => b.value(j).cmp(b.value(i)),
+            (x, y) => x.cmp(&y),
+        };
+        let mut expected: Vec<u32> = (0..7).collect();
+        expected.sort_by(|i, j| {
+            let (i, j) = (*i as usize, *j as usize);
+            match cmp_a(i, j) {
+                Ordering::Equal => cmp_b(i, j),
+                ordering => ordering,
+            }
+        });
+        assert_eq!(vec![1, 4, 3, 0, 5, 2, 6], expected);
+        assert_eq!(expected, sorted_keys(&keys));
+    }
+
+    #[test]
+    fn test_encode_rows_floats_and_strings() {
+        let a: ArrayRef = Arc::new(Float64Array::from(vec![
+            Some(1.5),
+            Some(-0.5),
+            Some(std::f64::NAN),
+            Some(-10.0),
+            None,
+            Some(std::f64::INFINITY),
+        ]));
+        let keys = encode_rows(&[(a.clone(), false)]).unwrap();
+        assert_eq!(vec![3, 1, 0, 5, 2, 4], sorted_keys(&keys));
+        let keys = encode_rows(&[(a, true)]).unwrap();
+        assert_eq!(vec![2, 5, 0, 1, 3, 4], sorted_keys(&keys));
+
+        let s: ArrayRef = Arc::new(BinaryArray::from(vec![
+            &b"a\x00"[..],
+            &b"a"[..],
+            &b""[..],
+            &b"ab"[..],
+            &b"a\x00\x00"[..],
+        ]));
+        let keys = encode_rows(&[(s.clone(), false)]).unwrap();
+        assert_eq!(vec![2, 1, 0, 4, 3], sorted_keys(&keys));
+        let keys = encode_rows(&[(s, true)]).unwrap();
+        assert_eq!(vec![3, 4, 0, 1, 2], sorted_keys(&keys));
+    }
+
+    #[test]
+    fn test_decode_rows_round_trip() {
+        let columns: Vec<(ArrayRef, bool)> = vec![
+            (
+                Arc::new(Int64Array::from(vec![Some(-3), None, Some(7), Some(0)])),
+                false,
+            ),
+            (
+                Arc::new(BinaryArray::from(vec![
+                    Some("x\u{0}y"),
+                    Some(""),
+                    None,
+                    Some("arrow"),
+                ])),
+                true,
+            ),
+            (
+                Arc::new(Float32Array::from(vec![
+                    Some(-1.5),
+                    Some(0.0),
+                    None,
+                    Some(2.25),
+                ])),
+                true,
+            ),
+            (
+                Arc::new(BooleanArray::from(vec![
+                    Some(true),
+                    None,
+                    Some(false),
+                    None,
+                ])),
+                false,
+            ),
+            (Arc::new(UInt16Array::from(vec![0, 65535, 1, 256])), true),
+        ];
+        let keys = encode_rows(&columns).unwrap();
+        let schema: Vec<DataType> =
+            columns.iter().map(|(c, _)| c.data_type().clone()).collect();
+        let decoded = decode_rows(&keys, &schema).unwrap();
+        assert_eq!(columns.len(), decoded.len());
+        for ((column, _), decoded) in columns.iter().zip(decoded.iter()) {
+            assert_eq!(column.data_type(), decoded.data_type());
+            assert_eq!(column.len(), decoded.len());
+            assert_eq!(column.null_count(), decoded.null_count());
+            for i in 0..column.len() {
+                assert_eq!(column.value_to_string(i), decoded.value_to_string(i));
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_rows_schema_mismatch() {
+        let a: ArrayRef = Arc::new(Int32Array::from(vec![1, 2]));
+        let keys = encode_rows(&[(a, false)]).unwrap();
+        // too many bytes for the schema
+        assert!(decode_rows(&keys, &[DataType::Int16]).is_err());
+        // not enough bytes for the schema
+        assert!(decode_rows(&keys, &[DataType::Int64]).is_err());
+        assert!(decode_rows(&keys, &[DataType::Int32, DataType::Int8]).is_err());
+        // invalid marker for a string
+        assert!(decode_rows(&keys, &[DataType::Utf8]).is_err());
+        assert!(decode_rows(&keys, &[DataType::Int32]).is_ok());
+    }
+
+    #[test]
+    fn test_encode_rows_errors() {
+        assert!(encode_rows(&[]).is_err());
+        let a: ArrayRef = Arc::new(Int8Array::from(vec![1, 2]));
+        let b: ArrayRef = Arc::new(Int8Array::from(vec![1]));
+        assert!(encode_rows(&[(a, false), (b, false)]).is_err());
+    }
+}
@@ -0,0 +1,341 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines sort kernels for `ArrayRef`.
+
+use std::cmp::Ordering;
+
+use crate::array::*;
+use crate::array_data::ArrayData;
+use crate::builder::{BooleanBufferBuilder, BufferBuilderTrait};
+use crate::compute::kernels::take::take;
+use crate::datatypes::*;
+use crate::error::{ArrowError, Result};
+
+/// Compares two values, ordering values that are not comparable to themselves (i.e.
+/// `NaN`) after all other values.
+fn cmp_values<T: PartialOrd>(a: &T, b: &T) -> Ordering {
+    match a.partial_cmp(b) {
+        Some(ordering) => ordering,
+        None => match (a.partial_cmp(a).is_none(), b.partial_cmp(b).is_none()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            _ => Ordering::Less,
+        },
+    }
+}
+
+macro_rules! sort_primitive {
+    ($array:expr, $options:expr, $array_type:ident) => {{
+        let a = $array.as_any().downcast_ref::<$array_type>().unwrap();
+        sort_valid(a, $options, |i, j| cmp_values(&a.value(i), &a.value(j)))
+    }};
+}
+
+/// Options that define how `sort_to_indices_with_options` orders values.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SortOptions {
+    /// Whether to sort in descending order
+    pub descending: bool,
+    /// Whether to order null values before the other values
+    pub nulls_first: bool,
+}
+
+/// Returns the indices that would sort `array`, in ascending or descending order.
+///
+/// Null values are ordered last, and floating point `NaN` values are ordered after all
+/// other non-null values. The sort is stable, so equal values keep their relative order.
+/// Applying the returned indices with `take` yields the sorted array.
+pub fn sort_to_indices(array: &ArrayRef, descending: bool) -> Result<UInt32Array> {
+    sort_to_indices_with_options(
+        array,
+        &SortOptions {
+            descending,
+            nulls_first: false,
+        },
+    )
+}
+
+/// Returns the indices that would sort `array`, like `sort_to_indices`, with null
+/// values ordered first or last according to `options`.
+pub fn sort_to_indices_with_options(
+    array: &ArrayRef,
+    options: &SortOptions,
+) -> Result<UInt32Array> {
+    match array.data_type() {
+        DataType::Boolean => sort_primitive!(array, options, BooleanArray),
+        DataType::Int8 => sort_primitive!(array, options, Int8Array),
+        DataType::Int16 => sort_primitive!(array, options, Int16Array),
+        DataType::Int32 => sort_primitive!(array, options, Int32Array),
+        DataType::Int64 => sort_primitive!(array, options, Int64Array),
+        DataType::UInt8 => sort_primitive!(array, options, UInt8Array),
+        DataType::UInt16 => sort_primitive!(array, options, UInt16Array),
+        DataType::UInt32 => sort_primitive!(array, options, UInt32Array),
+        DataType::UInt64 => sort_primitive!(array, options, UInt64Array),
+        DataType::Float32 => sort_primitive!(array, options, Float32Array),
+        DataType::Float64 => sort_primitive!(array, options, Float64Array),
+        DataType::Utf8 => {
+            let a = array.as_any().downcast_ref::<BinaryArray>().unwrap();
+            sort_valid(a, options, |i, j| a.value(i).cmp(a.value(j)))
+        }
+        DataType::LargeUtf8 => {
+            let a = array.as_any().downcast_ref::<LargeBinaryArray>().unwrap();
+            sort_valid(a, options, |i, j| a.value(i).cmp(a.value(j)))
+        }
+        other => Err(ArrowError::ComputeError(format!(
+            "sort not supported for {:?}",
+            other
+        ))),
+    }
+}
+
+/// Stable sorts the indices of the valid elements of `array` with `cmp`, and places the
+/// indices of the null elements before or after them.
+fn sort_valid<F>(array: &dyn Array, options: &SortOptions, cmp: F) -> Result<UInt32Array>
+where
+    F: Fn(usize, usize) -> Ordering,
+{
+    let (mut valid, mut nulls): (Vec<u32>, Vec<u32>) =
+        (0..array.len() as u32).partition(|i| array.is_valid(*i as usize));
+    if options.descending {
+        valid.sort_by(|i, j| cmp(*j as usize, *i as usize));
+    } else {
+        valid.sort_by(|i, j| cmp(*i as usize, *j as usize));
+    }
+    if options.nulls_first {
+        nulls.extend(valid);
+        Ok(UInt32Array::from(nulls))
+    } else {
+        valid.extend(nulls);
+        Ok(UInt32Array::from(valid))
+    }
+}
+
+/// Returns a new struct array with the rows of `array` sorted by the values of the field
+/// named `field_name`, in ascending or descending order.
+///
+/// Rows with a null value in the sort field are ordered last, and the validity of the
+/// struct rows is reordered along with the fields. See `sort_to_indices` for the
+/// ordering of the values.
+pub fn sort_struct_by_field(
+    array: &StructArray,
+    field_name: &str,
+    descending: bool,
+) -> Result<StructArray> {
+    let fields = match array.data_type() {
+        DataType::Struct(fields) => fields,
+        _ => unreachable!("StructArray must have a Struct data type"),
+    };
+    let pos = fields
+        .iter()
+        .position(|f| f.name() == field_name)
+        .ok_or_else(|| {
+            ArrowError::InvalidArgumentError(format!(
+                "Unable to sort by field '{}': no such field in struct",
+                field_name
+            ))
+        })?;
+    // the children of a struct may be longer than the struct itself
+    let columns: Vec<ArrayRef> = (0..fields.len())
+        .map(|i| make_array(array.column(i).data().slice(0, array.len())))
+        .collect();
+    let indices = sort_to_indices(&columns[pos], descending)?;
+
+    let mut child_data = Vec::with_capacity(fields.len());
+    for column in &columns {
+        child_data.push(take(column, &indices)?.data());
+    }
+    let mut builder = ArrayData::builder(array.data_type().clone())
+        .len(indices.len())
+        .child_data(child_data);
+    if array.null_count() > 0 {
+        let mut bitmap_builder = BooleanBufferBuilder::new(indices.len());
+        for i in 0..indices.len() {
+            bitmap_builder.append(array.is_valid(indices.value(i) as usize))?;
+        }
+        builder = builder
+            .null_count(array.null_count())
+            .null_bit_buffer(bitmap_builder.finish());
+    }
+    Ok(StructArray::from(builder.build()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    use crate::buffer::Buffer;
+
+    fn indices(array: ArrayRef, descending: bool) -> Vec<u32> {
+        let indices = sort_to_indices(&array, descending).unwrap();
+        assert_eq!(0, indices.null_count());
+        indices.value_slice(0, indices.len()).to_vec()
+    }
+
+    #[test]
+    fn test_sort_to_indices_primitive() {
+        let a: ArrayRef = Arc::new(Int32Array::from(vec![
+            Some(3),
+            None,
+            Some(1),
+            Some(3),
+            None,
+            Some(-2),
+        ]));
+        assert_eq!(vec![5, 2, 0, 3, 1, 4], indices(a.clone(), false));
+        assert_eq!(vec![0, 3, 2, 5, 1, 4], indices(a, true));
+    }
+
+    #[test]
+    fn test_sort_to_indices_float_nan() {
+        let a: ArrayRef = Arc::new(Float64Array::from(vec![
+            Some(1.5),
+            Some(std::f64::NAN),
+            None,
+            Some(-0.5),
+        ]));
+        assert_eq!(vec![3, 0, 1, 2], indices(a.clone(), false));
+        assert_eq!(vec![1, 0, 3, 2], indices(a, true));
+    }
+
+    #[test]
+    fn test_sort_to_indices_binary() {
+        let a: ArrayRef = Arc::new(BinaryArray::from(vec!["b", "", "ab", "b"]));
+        assert_eq!(vec![1, 2, 0, 3], indices(a.clone(), false));
+        assert_eq!(vec![0, 3, 2, 1], indices(a, true));
+    }
+
+    #[test]
+    fn test_sort_to_indices_nulls_first() {
+        let a: ArrayRef = Arc::new(Int32Array::from(vec![
+            Some(3),
+            None,
+            Some(1),
+            None,
+            Some(2),
+        ]));
+        let sort = |descending| {
+            let options = SortOptions {
+                descending,
+                nulls_first: true,
+            };
+            let indices = sort_to_indices_with_options(&a, &options).unwrap();
+            indices.value_slice(0, indices.len()).to_vec()
+        };
+        assert_eq!(vec![1, 3, 2, 4, 0], sort(false));
+        assert_eq!(vec![1, 3, 0, 4, 2], sort(true));
+    }
+
+    #[test]
+    fn test_sort_to_indices_large_binary() {
+        let a: ArrayRef =
+            Arc::new(LargeBinaryArray::from(vec![Some("b"), None, Some("a")]));
+        assert_eq!(vec![2, 0, 1], indices(a, false));
+    }
+
+    #[test]
+    fn test_sort_to_indices_boolean() {
+        let a: ArrayRef =
+            Arc::new(BooleanArray::from(vec![Some(true), None, Some(false)]));
+        assert_eq!(vec![2, 0, 1], indices(a, false));
+    }
+
+    #[test]
+    fn test_sort_struct_by_field() {
+        let key_data = ArrayData::builder(DataType::Int32)
+            .len(4)
+            .add_buffer(Buffer::from(&[2, 7, 0, 5].to_byte_slice()))
+            .build();
+        let value_data = ArrayData::builder(DataType::Boolean)
+            .len(4)
+            .add_buffer(Buffer::from([0b00000101]))
+            .build();
+        let fields = vec![
+            Field::new("key", DataType::Int32, false),
+            Field::new("value", DataType::Boolean, false),
+        ];
+        // the third row is null
+        let struct_data = ArrayData::builder(DataType::Struct(fields.clone()))
+            .len(4)
+            .null_bit_buffer(Buffer::from([0b00001011]))
+            .add_child_data(key_data)
+            .add_child_data(value_data)
+            .build();
+        let struct_array = StructArray::from(struct_data);
+
+        let sorted = sort_struct_by_field(&struct_array, "key", true).unwrap();
+        assert_eq!(&DataType::Struct(fields), sorted.data_type());
+        assert_eq!(4, sorted.len());
+        let keys = sorted
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int32Array>()
+            .unwrap();
+        assert_eq!(&[7, 5, 2, 0], keys.value_slice(0, 4));
+        let values = sorted
+            .column(1)
+            .as_any()
+            .downcast_ref::<BooleanArray>()
+            .unwrap();
+        assert_eq!(
+            vec![false, false, true, true],
+            (0..4).map(|i| values.value(i)).collect::<Vec<_>>()
+        );
+        assert_eq!(1, sorted.null_count());
+        assert!(sorted.is_null(3));
+
+        assert!(sort_struct_by_field(&struct_array, "missing", false).is_err());
+    }
+
+    #[test]
+    fn test_sort_sliced_struct_by_field() {
+        let struct_array = StructArray::from(vec![
+            (
+                Field::new("key", DataType::Int32, false),
+                Arc::new(Int32Array::from(vec![3, 1, 2])) as ArrayRef,
+            ),
+            (
+                Field::new("value", DataType::Utf8, false),
+                Arc::new(BinaryArray::from(vec!["three", "one", "two"])) as ArrayRef,
+            ),
+        ]);
+
+        for (offset, expected_keys, expected_value) in
+            vec![(0, [1, 3], "three"), (1, [1, 2], "two")]
+        {
+            let sliced = StructArray::from(struct_array.data().slice(offset, 2));
+            let sorted = sort_struct_by_field(&sliced, "key", false).unwrap();
+            assert_eq!(2, sorted.len());
+            let keys = sorted
+                .column(0)
+                .as_any()
+                .downcast_ref::<Int32Array>()
+                .unwrap();
+            assert_eq!(2, keys.len());
+            assert_eq!(&expected_keys, keys.value_slice(0, 2));
+            let values = sorted
+                .column(1)
+                .as_any()
+                .downcast_ref::<BinaryArray>()
+                .unwrap();
+            assert_eq!(2, values.len());
+            assert_eq!(expected_value, values.get_string(1));
+        }
+    }
+}
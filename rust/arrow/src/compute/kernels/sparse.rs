@@ -0,0 +1,80 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines kernels converting boolean masks to `SparseBooleanArray`.
+
+use crate::array::*;
+
+/// Converts `mask` to a `SparseBooleanArray` holding the positions of its `true` values.
+///
+/// Null values are converted to `false`, as they are when a mask is used to filter
+/// values. See `SparseBooleanArray` for when the sparse form is worth it.
+pub fn sparsify(mask: &BooleanArray) -> SparseBooleanArray {
+    let true_indices: Vec<u32> = (0..mask.len())
+        .filter(|i| mask.is_valid(*i) && mask.value(*i))
+        .map(|i| i as u32)
+        .collect();
+    SparseBooleanArray::new(UInt32Array::from(true_indices), mask.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sparsify() {
+        let values: Vec<bool> = (0..1000).map(|i| i % 97 == 3).collect();
+        let mask = BooleanArray::from(values);
+        let sparse = sparsify(&mask);
+        assert_eq!(1000, sparse.len());
+        assert_eq!(11, sparse.true_count());
+        for i in 0..mask.len() {
+            assert_eq!(mask.value(i), sparse.value(i));
+        }
+
+        let dense = sparse.to_dense();
+        assert_eq!(mask.len(), dense.len());
+        assert_eq!(0, dense.null_count());
+        for i in 0..mask.len() {
+            assert_eq!(mask.value(i), dense.value(i));
+        }
+    }
+
+    #[test]
+    fn test_sparsify_nulls() {
+        let mask = BooleanArray::from(vec![Some(true), None, Some(false), Some(true)]);
+        let sparse = sparsify(&mask);
+        assert_eq!(vec![0, 3], sparse.true_indices().value_slice(0, 2).to_vec());
+        assert!(!sparse.value(1));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "SparseBooleanArray true indices must be strictly increasing"
+    )]
+    fn test_sparse_boolean_array_unsorted() {
+        SparseBooleanArray::new(UInt32Array::from(vec![3, 1]), 5);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "SparseBooleanArray true index 5 is out of bounds for length 5"
+    )]
+    fn test_sparse_boolean_array_out_of_bounds() {
+        SparseBooleanArray::new(UInt32Array::from(vec![1, 5]), 5);
+    }
+}
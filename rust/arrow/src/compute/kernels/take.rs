@@ -0,0 +1,346 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines take kernel for `ArrayRef`
+
+use std::sync::Arc;
+
+use crate::array::*;
+use crate::array_data::ArrayData;
+use crate::builder::{BinaryBuilder, BooleanBufferBuilder, BufferBuilderTrait};
+use crate::compute::kernels::concat::concat;
+use crate::datatypes::DataType;
+use crate::error::{ArrowError, Result};
+
+macro_rules! take_array {
+    ($array:expr, $indices:expr, $array_type:ident) => {{
+        let a = $array.as_any().downcast_ref::<$array_type>().unwrap();
+        let mut builder = $array_type::builder($indices.len());
+        for i in 0..$indices.len() {
+            match take_index(a.len(), $indices, i)? {
+                Some(index) if a.is_valid(index) => {
+                    builder.append_value(a.value(index))?
+                }
+                _ => builder.append_null()?,
+            }
+        }
+        Ok(Arc::new(builder.finish()))
+    }};
+}
+
+/// Take elements from `array` by index, returning a new array where the element at
+/// position `j` is the element of `array` at position `indices.value(j)`.
+///
+/// A null index produces a null element. An index that is out of bounds of `array`
+/// returns an error.
+///
+/// Primitive, boolean and `Utf8` arrays are taken element by element. Arrays of the
+/// other types supported by `concat`, e.g. lists, structs and temporal arrays, are taken
+/// by concatenating their sliced elements, so unions are not supported.
+///
+/// # Example
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use arrow::array::{Array, ArrayRef, Int32Array, UInt32Array};
+/// # use arrow::compute::take;
+/// let values: ArrayRef = Arc::new(Int32Array::from(vec![10, 20, 30]));
+/// let indices = UInt32Array::from(vec![Some(2), None, Some(0)]);
+/// let taken = take(&values, &indices).unwrap();
+/// let taken = taken.as_any().downcast_ref::<Int32Array>().unwrap();
+/// assert_eq!(30, taken.value(0));
+/// assert!(taken.is_null(1));
+/// assert_eq!(10, taken.value(2));
+/// ```
+pub fn take(array: &ArrayRef, indices: &UInt32Array) -> Result<ArrayRef> {
+    match array.data_type() {
+        DataType::UInt8 => take_array!(array, indices, UInt8Array),
+        DataType::UInt16 => take_array!(array, indices, UInt16Array),
+        DataType::UInt32 => take_array!(array, indices, UInt32Array),
+        DataType::UInt64 => take_array!(array, indices, UInt64Array),
+        DataType::Int8 => take_array!(array, indices, Int8Array),
+        DataType::Int16 => take_array!(array, indices, Int16Array),
+        DataType::Int32 => take_array!(array, indices, Int32Array),
+        DataType::Int64 => take_array!(array, indices, Int64Array),
+        DataType::Float32 => take_array!(array, indices, Float32Array),
+        DataType::Float64 => take_array!(array, indices, Float64Array),
+        DataType::Boolean => take_array!(array, indices, BooleanArray),
+        DataType::Utf8 => {
+            let a = array.as_any().downcast_ref::<BinaryArray>().unwrap();
+            let mut builder = BinaryBuilder::new(indices.len());
+            for i in 0..indices.len() {
+                match take_index(a.len(), indices, i)? {
+                    Some(index) if a.is_valid(index) => {
+                        builder.append_bytes(a.value(index))?;
+                    }
+                    _ => builder.append_null()?,
+                }
+            }
+            Ok(Arc::new(builder.finish()))
+        }
+        DataType::Union(_, _) => Err(ArrowError::ComputeError(format!(
+            "take not supported for {:?}",
+            array.data_type()
+        ))),
+        _ => take_by_layout(array, indices),
+    }
+}
+
+/// Takes elements from `array` by concatenating a one-element slice of `array` per
+/// index, and masking the elements of null indices.
+fn take_by_layout(array: &ArrayRef, indices: &UInt32Array) -> Result<ArrayRef> {
+    let data = array.data();
+    if indices.len() == 0 {
+        return Ok(make_array(data.slice(0, 0)));
+    }
+    let mut slices = Vec::with_capacity(indices.len());
+    let mut validity = BooleanBufferBuilder::new(indices.len());
+    let mut null_count = 0;
+    for i in 0..indices.len() {
+        match take_index(array.len(), indices, i)? {
+            Some(index) => {
+                slices.push(make_array(data.slice(index, 1)));
+                validity.append(array.is_valid(index))?;
+                if array.is_null(index) {
+                    null_count += 1;
+                }
+            }
+            // the first element stands in for the element of a null index, and is masked
+            None if array.len() > 0 => {
+                slices.push(make_array(data.slice(0, 1)));
+                validity.append(false)?;
+                null_count += 1;
+            }
+            None => {
+                return Err(ArrowError::ComputeError(format!(
+                    "Cannot take a null index from an empty array of type {:?}",
+                    array.data_type()
+                )));
+            }
+        }
+    }
+    let taken = concat(&slices)?;
+    if indices.null_count() == 0 || array.data_type() == &DataType::Null {
+        return Ok(taken);
+    }
+    let taken = taken.data();
+    let data = ArrayData::builder(taken.data_type().clone())
+        .len(taken.len())
+        .null_count(null_count)
+        .null_bit_buffer(validity.finish())
+        .buffers(taken.buffers().to_vec())
+        .child_data(taken.child_data().to_vec())
+        .build();
+    Ok(make_array(data))
+}
+
+/// Returns the index at position `i` of `indices`, or `None` if it is null.
+///
+/// Returns an error if the index is not within an array of length `len`.
+fn take_index(len: usize, indices: &UInt32Array, i: usize) -> Result<Option<usize>> {
+    if indices.is_null(i) {
+        return Ok(None);
+    }
+    let index = indices.value(i) as usize;
+    if index >= len {
+        return Err(ArrowError::ComputeError(format!(
+            "Array index out of bounds, cannot get item at index {} from {} entries",
+            index, len
+        )));
+    }
+    Ok(Some(index))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::buffer::Buffer;
+    use crate::datatypes::{Field, TimeUnit, ToByteSlice};
+
+    #[test]
+    fn test_take_primitive() {
+        let a: ArrayRef = Arc::new(Int32Array::from(vec![
+            Some(0),
+            None,
+            Some(2),
+            Some(3),
+            None,
+        ]));
+        let indices = UInt32Array::from(vec![Some(3), Some(1), None, Some(0), Some(3)]);
+        let b = take(&a, &indices).unwrap();
+        let c = b.as_any().downcast_ref::<Int32Array>().unwrap();
+        assert_eq!(5, c.len());
+        assert_eq!(2, c.null_count());
+        assert_eq!(3, c.value(0));
+        assert!(c.is_null(1));
+        assert!(c.is_null(2));
+        assert_eq!(0, c.value(3));
+        assert_eq!(3, c.value(4));
+    }
+
+    #[test]
+    fn test_take_boolean() {
+        let a: ArrayRef =
+            Arc::new(BooleanArray::from(vec![Some(true), Some(false), None]));
+        let indices = UInt32Array::from(vec![2, 1, 0, 0]);
+        let b = take(&a, &indices).unwrap();
+        let c = b.as_any().downcast_ref::<BooleanArray>().unwrap();
+        assert_eq!(4, c.len());
+        assert!(c.is_null(0));
+        assert_eq!(false, c.value(1));
+        assert_eq!(true, c.value(2));
+        assert_eq!(true, c.value(3));
+    }
+
+    #[test]
+    fn test_take_binary() {
+        let a: ArrayRef = Arc::new(BinaryArray::from(vec!["one", "", "three"]));
+        let indices = UInt32Array::from(vec![Some(2), None, Some(1), Some(0)]);
+        let b = take(&a, &indices).unwrap();
+        let c = b.as_any().downcast_ref::<BinaryArray>().unwrap();
+        assert_eq!(4, c.len());
+        assert_eq!("three", c.get_string(0));
+        assert!(c.is_null(1));
+        assert!(c.is_valid(2));
+        assert_eq!("", c.get_string(2));
+        assert_eq!("one", c.get_string(3));
+    }
+
+    #[test]
+    fn test_take_list() {
+        // [[0, 1], null, [2, 3, 4], []]
+        let a: ArrayRef = Arc::new(
+            ListArray::try_new(
+                Arc::new(Int32Array::from(vec![0, 1, 2, 3, 4])),
+                &[0, 2, 2, 5, 5],
+                Some(Buffer::from([0b00001101])),
+            )
+            .unwrap(),
+        );
+        let indices = UInt32Array::from(vec![Some(2), None, Some(1), Some(0)]);
+        let b = take(&a, &indices).unwrap();
+        let c = b.as_any().downcast_ref::<ListArray>().unwrap();
+        assert_eq!(4, c.len());
+        assert_eq!(2, c.null_count());
+        assert!(c.is_null(1));
+        assert!(c.is_null(2));
+        // the element of the null index holds the values of the first list
+        assert_eq!(&[0, 3, 5, 5, 7], c.value_offsets());
+        let values = c.values();
+        let values = values.as_any().downcast_ref::<Int32Array>().unwrap();
+        assert_eq!(&[2, 3, 4, 0, 1, 0, 1], values.value_slice(0, 7));
+    }
+
+    #[test]
+    fn test_take_struct() {
+        let a: ArrayRef = Arc::new(StructArray::from(vec![
+            (
+                Field::new("a", DataType::Int32, false),
+                Arc::new(Int32Array::from(vec![1, 2, 3])) as ArrayRef,
+            ),
+            (
+                Field::new("b", DataType::Utf8, true),
+                Arc::new(BinaryArray::from(vec![Some("one"), None, Some("three")]))
+                    as ArrayRef,
+            ),
+        ]));
+        let indices = UInt32Array::from(vec![Some(2), Some(1), None]);
+        let b = take(&a, &indices).unwrap();
+        assert_eq!(a.data_type(), b.data_type());
+        let c = b.as_any().downcast_ref::<StructArray>().unwrap();
+        assert_eq!(3, c.len());
+        assert_eq!(1, c.null_count());
+        assert!(c.is_null(2));
+        let ints = c.column(0).as_any().downcast_ref::<Int32Array>().unwrap();
+        assert_eq!(&[3, 2], ints.value_slice(0, 2));
+        let strings = c.column(1).as_any().downcast_ref::<BinaryArray>().unwrap();
+        assert_eq!("three", strings.get_string(0));
+        assert!(strings.is_null(1));
+    }
+
+    #[test]
+    fn test_take_timestamp_keeps_timezone() {
+        let data_type =
+            DataType::Timestamp(TimeUnit::Second, Some(Arc::new("+01:00".to_string())));
+        let data = ArrayData::builder(data_type.clone())
+            .len(3)
+            .add_buffer(Buffer::from(&[10i64, 20, 30].to_byte_slice()))
+            .build();
+        let a: ArrayRef = Arc::new(TimestampSecondArray::from(data));
+        let b = take(&a, &UInt32Array::from(vec![2, 0])).unwrap();
+        assert_eq!(&data_type, b.data_type());
+        let c = b.as_any().downcast_ref::<TimestampSecondArray>().unwrap();
+        assert_eq!(&[30, 10], c.value_slice(0, 2));
+    }
+
+    #[test]
+    fn test_take_null_index_from_empty_list() {
+        let a: ArrayRef = Arc::new(
+            ListArray::try_new(Arc::new(Int32Array::from(Vec::<i32>::new())), &[0], None)
+                .unwrap(),
+        );
+        assert!(take(&a, &UInt32Array::from(vec![None])).is_err());
+        assert_eq!(0, take(&a, &UInt32Array::from(vec![])).unwrap().len());
+    }
+
+    #[test]
+    fn test_take_with_offset() {
+        let data = ArrayData::builder(DataType::Int32)
+            .len(3)
+            .offset(2)
+            .add_buffer(Buffer::from(&[0, 1, 2, 3, 4].to_byte_slice()))
+            .build();
+        let a: ArrayRef = Arc::new(Int32Array::from(data));
+        let indices = UInt32Array::from(vec![2, 0]);
+        let b = take(&a, &indices).unwrap();
+        let c = b.as_any().downcast_ref::<Int32Array>().unwrap();
+        assert_eq!(4, c.value(0));
+        assert_eq!(2, c.value(1));
+    }
+
+    #[test]
+    fn test_take_with_offset_and_nulls() {
+        // values [0, null, 2, 3, null], sliced to [2, 3, null]
+        let data = ArrayData::builder(DataType::Int32)
+            .len(3)
+            .offset(2)
+            .null_bit_buffer(Buffer::from([0b00001101]))
+            .add_buffer(Buffer::from(&[0, 0, 2, 3, 0].to_byte_slice()))
+            .build();
+        let a: ArrayRef = Arc::new(Int32Array::from(data));
+        let indices = UInt32Array::from(vec![2, 1, 0]);
+        let b = take(&a, &indices).unwrap();
+        let c = b.as_any().downcast_ref::<Int32Array>().unwrap();
+        assert!(c.is_null(0));
+        assert_eq!(3, c.value(1));
+        assert_eq!(2, c.value(2));
+    }
+
+    #[test]
+    fn test_take_out_of_bounds() {
+        let a: ArrayRef = Arc::new(Int32Array::from(vec![1, 2, 3]));
+        let indices = UInt32Array::from(vec![0, 3]);
+        assert_eq!(
+            ArrowError::ComputeError(
+                "Array index out of bounds, cannot get item at index 3 from 3 entries"
+                    .to_string()
+            ),
+            take(&a, &indices).err().unwrap()
+        );
+    }
+}
@@ -17,12 +17,16 @@
 
 //! Defines temporal kernels for time and date related functions.
 
-use chrono::Timelike;
+use std::fmt::Write;
+
+use chrono::format::{Item, StrftimeItems};
+use chrono::{Datelike, Timelike};
 
 use crate::array::*;
-use crate::builder::Int32Builder;
+use crate::array_data::ArrayData;
+use crate::builder::{BinaryBuilder, Int32Builder, TimestampNanosecondBuilder};
 use crate::datatypes::*;
-use crate::error::Result;
+use crate::error::{ArrowError, Result};
 
 /// Extracts the hours of a given temporal array as an array of integers
 pub fn hour<T>(array: &PrimitiveArray<T>) -> Result<Int32Array>
@@ -53,10 +57,239 @@ where
     Ok(b.finish())
 }
 
+/// A component of a date or time, extracted by `temporal_component`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DateTimeField {
+    Year,
+    /// The month, from 1 to 12
+    Month,
+    /// The day of the month, from 1 to 31
+    Day,
+    Hour,
+    Minute,
+    Second,
+    /// The ISO 8601 day of the week, from 1 for Monday to 7 for Sunday
+    Weekday,
+    /// The day of the year, from 1 to 366
+    DayOfYear,
+}
+
+/// Downcasts the temporal `$array` and calls `$f` with it and `$arg`, or returns an
+/// error for non-temporal arrays, mentioning the operation `$op`
+macro_rules! temporal_dispatch {
+    ($array:expr, $f:ident, $arg:expr, $op:expr) => {{
+        match $array.data_type() {
+            DataType::Date32(_) => $f(as_primitive_array::<Date32Type>($array), $arg),
+            DataType::Date64(_) => $f(as_primitive_array::<Date64Type>($array), $arg),
+            DataType::Time32(TimeUnit::Second) => {
+                $f(as_primitive_array::<Time32SecondType>($array), $arg)
+            }
+            DataType::Time32(TimeUnit::Millisecond) => {
+                $f(as_primitive_array::<Time32MillisecondType>($array), $arg)
+            }
+            DataType::Time64(TimeUnit::Microsecond) => {
+                $f(as_primitive_array::<Time64MicrosecondType>($array), $arg)
+            }
+            DataType::Time64(TimeUnit::Nanosecond) => {
+                $f(as_primitive_array::<Time64NanosecondType>($array), $arg)
+            }
+            DataType::Timestamp(TimeUnit::Second, _) => {
+                $f(as_primitive_array::<TimestampSecondType>($array), $arg)
+            }
+            DataType::Timestamp(TimeUnit::Millisecond, _) => {
+                $f(as_primitive_array::<TimestampMillisecondType>($array), $arg)
+            }
+            DataType::Timestamp(TimeUnit::Microsecond, _) => {
+                $f(as_primitive_array::<TimestampMicrosecondType>($array), $arg)
+            }
+            DataType::Timestamp(TimeUnit::Nanosecond, _) => {
+                $f(as_primitive_array::<TimestampNanosecondType>($array), $arg)
+            }
+            other => Err(ArrowError::ComputeError(format!(
+                "Cannot {} non-temporal type {:?}",
+                $op, other
+            ))),
+        }
+    }};
+}
+
+/// Extracts `component` of each element of the temporal `array` as an array of
+/// integers, keeping nulls.
+///
+/// Only the time components can be extracted from `Time32` and `Time64` arrays, while
+/// dates have a time of midnight. Returns an error for non-temporal arrays.
+pub fn temporal_component(
+    array: &ArrayRef,
+    component: DateTimeField,
+) -> Result<Int32Array> {
+    temporal_dispatch!(
+        array,
+        extract_component,
+        component,
+        format!("extract {:?} from", component)
+    )
+}
+
+/// Formats each element of the temporal `array` with the chrono `format` string, e.g.
+/// `"%Y-%m-%d %H:%M:%S"`, keeping nulls.
+///
+/// Dates have a time of midnight, and formatting a date component of a `Time32` or
+/// `Time64` array is an error, as are invalid format strings and non-temporal arrays.
+pub fn strftime(array: &ArrayRef, format: &str) -> Result<BinaryArray> {
+    let items: Vec<Item> = StrftimeItems::new(format).collect();
+    if items.contains(&Item::Error) {
+        return Err(ArrowError::ComputeError(format!(
+            "Invalid format string '{}'",
+            format
+        )));
+    }
+    temporal_dispatch!(array, format_values, &items, "format")
+}
+
+fn format_values<T>(array: &PrimitiveArray<T>, items: &[Item]) -> Result<BinaryArray>
+where
+    T: ArrowTemporalType + ArrowNumericType,
+    i64: std::convert::From<T::Native>,
+{
+    let is_time = matches!(array.data_type(), DataType::Time32(_) | DataType::Time64(_));
+    let mut b = BinaryBuilder::new(array.len());
+    let mut value = String::new();
+    for i in 0..array.len() {
+        if array.is_null(i) {
+            b.append_null()?;
+            continue;
+        }
+        value.clear();
+        let formatted = if is_time {
+            array
+                .value_as_time(i)
+                .map(|t| write!(value, "{}", t.format_with_items(items.iter())))
+        } else {
+            array
+                .value_as_datetime(i)
+                .map(|dt| write!(value, "{}", dt.format_with_items(items.iter())))
+        };
+        match formatted {
+            Some(Ok(())) => b.append_string(&value)?,
+            Some(Err(_)) => {
+                return Err(ArrowError::ComputeError(format!(
+                    "Cannot format {:?} with the given format string",
+                    array.data_type()
+                )))
+            }
+            None => b.append_null()?,
+        }
+    }
+    Ok(b.finish())
+}
+
+fn extract_component<T>(
+    array: &PrimitiveArray<T>,
+    component: DateTimeField,
+) -> Result<Int32Array>
+where
+    T: ArrowTemporalType + ArrowNumericType,
+    i64: std::convert::From<T::Native>,
+{
+    let is_time = matches!(array.data_type(), DataType::Time32(_) | DataType::Time64(_));
+    let is_time_component = matches!(
+        component,
+        DateTimeField::Hour | DateTimeField::Minute | DateTimeField::Second
+    );
+    if is_time && !is_time_component {
+        return Err(ArrowError::ComputeError(format!(
+            "Cannot extract {:?} from {:?}",
+            component,
+            array.data_type()
+        )));
+    }
+
+    let mut b = Int32Builder::new(array.len());
+    for i in 0..array.len() {
+        if array.is_null(i) {
+            b.append_null()?;
+            continue;
+        }
+        let time = if is_time {
+            array.value_as_time(i)
+        } else {
+            array.value_as_datetime(i).map(|dt| dt.time())
+        };
+        let value = match component {
+            DateTimeField::Hour => time.map(|t| t.hour()),
+            DateTimeField::Minute => time.map(|t| t.minute()),
+            DateTimeField::Second => time.map(|t| t.second()),
+            _ => array.value_as_date(i).map(|date| match component {
+                DateTimeField::Year => date.year() as u32,
+                DateTimeField::Month => date.month(),
+                DateTimeField::Day => date.day(),
+                DateTimeField::Weekday => date.weekday().number_from_monday(),
+                _ => date.ordinal(),
+            }),
+        };
+        match value {
+            Some(v) => b.append_value(v as i32)?,
+            None => b.append_null()?,
+        }
+    }
+    Ok(b.finish())
+}
+
+/// Floors each timestamp of `array` to a multiple of `interval_nanos` nanoseconds since
+/// the epoch, e.g. to the start of its minute, hour or day, keeping nulls.
+///
+/// Timestamps before the epoch are floored towards negative infinity as well. The
+/// timezone of `array`, if any, is kept, so buckets are aligned with the epoch rather
+/// than with the local wall-clock time. Timestamps whose bucket start is before the
+/// minimum timestamp, e.g. `i64::MIN` with an interval of 3 nanoseconds, are null.
+/// Returns an error if `interval_nanos` isn't positive.
+pub fn time_bucket(
+    array: &TimestampNanosecondArray,
+    interval_nanos: i64,
+) -> Result<TimestampNanosecondArray> {
+    if interval_nanos <= 0 {
+        return Err(ArrowError::ComputeError(format!(
+            "time_bucket interval must be positive, got {} nanoseconds",
+            interval_nanos
+        )));
+    }
+    let mut b = TimestampNanosecondBuilder::new(array.len());
+    for i in 0..array.len() {
+        let bucket = if array.is_null(i) {
+            None
+        } else {
+            array
+                .value(i)
+                .div_euclid(interval_nanos)
+                .checked_mul(interval_nanos)
+        };
+        match bucket {
+            Some(v) => b.append_value(v)?,
+            None => b.append_null()?,
+        }
+    }
+    let buckets = b.finish();
+    if buckets.data_type() == array.data_type() {
+        return Ok(buckets);
+    }
+    // keep the timezone of `array`
+    let data = buckets.data();
+    let mut builder = ArrayData::builder(array.data_type().clone())
+        .len(data.len())
+        .null_count(data.null_count())
+        .buffers(data.buffers().to_vec());
+    if let Some(bitmap) = data.null_bitmap() {
+        builder = builder.null_bit_buffer(bitmap.bits.clone());
+    }
+    Ok(TimestampNanosecondArray::from(builder.build()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use std::sync::Arc;
+
     #[test]
     fn test_temporal_array_date64_hour() {
         let a: PrimitiveArray<Date64Type> =
@@ -78,4 +311,145 @@ mod tests {
         assert_eq!(10, b.value(0));
         assert_eq!(23, b.value(1));
     }
+
+    #[test]
+    fn test_time_bucket_hour() {
+        const HOUR: i64 = 3_600_000_000_000;
+        // 2019-02-20T04:23:45.5, 2019-02-20T05:00:00, null, 1969-12-31T23:30:00
+        let a = TimestampNanosecondArray::from(vec![
+            Some(1_550_636_625_500_000_000),
+            Some(1_550_635_200_000_000_000 + HOUR),
+            None,
+            Some(-HOUR / 2),
+        ]);
+        let b = time_bucket(&a, HOUR).unwrap();
+        assert_eq!(4, b.len());
+        assert_eq!(1_550_635_200_000_000_000, b.value(0));
+        assert_eq!(1_550_635_200_000_000_000 + HOUR, b.value(1));
+        assert!(b.is_null(2));
+        assert_eq!(-HOUR, b.value(3));
+        assert_eq!("2019-02-20T04:00:00", b.value_to_string(0));
+    }
+
+    #[test]
+    fn test_time_bucket_keeps_timezone() {
+        let data = ArrayData::builder(DataType::Timestamp(
+            TimeUnit::Nanosecond,
+            Some(std::sync::Arc::new("+02:00".to_string())),
+        ))
+        .len(1)
+        .add_buffer(crate::buffer::Buffer::from([1_500i64].to_byte_slice()))
+        .build();
+        let a = TimestampNanosecondArray::from(data);
+        let b = time_bucket(&a, 1_000).unwrap();
+        assert_eq!(a.data_type(), b.data_type());
+        assert_eq!(1_000, b.value(0));
+    }
+
+    #[test]
+    fn test_time_bucket_extreme_values() {
+        let a = TimestampNanosecondArray::from(vec![std::i64::MIN, std::i64::MAX]);
+        let b = time_bucket(&a, 3).unwrap();
+        assert!(b.is_null(0));
+        assert_eq!(std::i64::MAX - std::i64::MAX % 3, b.value(1));
+
+        let b = time_bucket(&a, 2).unwrap();
+        assert_eq!(std::i64::MIN, b.value(0));
+    }
+
+    #[test]
+    fn test_time_bucket_invalid_interval() {
+        let a = TimestampNanosecondArray::from(vec![1]);
+        assert!(time_bucket(&a, 0).is_err());
+        assert!(time_bucket(&a, -5).is_err());
+    }
+
+    #[test]
+    fn test_temporal_component_date32() {
+        // 2019-03-15 (Friday), null, 1969-12-31 (Wednesday), 2020-02-29 (Saturday)
+        let a: ArrayRef = Arc::new(Date32Array::from(vec![
+            Some(17970),
+            None,
+            Some(-1),
+            Some(18321),
+        ]));
+        let years = temporal_component(&a, DateTimeField::Year).unwrap();
+        assert_eq!(4, years.len());
+        assert_eq!(2019, years.value(0));
+        assert!(years.is_null(1));
+        assert_eq!(1969, years.value(2));
+        assert_eq!(2020, years.value(3));
+
+        let weekdays = temporal_component(&a, DateTimeField::Weekday).unwrap();
+        assert_eq!(5, weekdays.value(0));
+        assert!(weekdays.is_null(1));
+        assert_eq!(3, weekdays.value(2));
+        assert_eq!(6, weekdays.value(3));
+
+        let days = temporal_component(&a, DateTimeField::DayOfYear).unwrap();
+        assert_eq!(74, days.value(0));
+        assert_eq!(365, days.value(2));
+        assert_eq!(60, days.value(3));
+    }
+
+    #[test]
+    fn test_temporal_component_timestamp_and_time() {
+        // 2019-02-20T04:23:45
+        let a: ArrayRef = Arc::new(TimestampSecondArray::from(vec![1_550_636_625]));
+        let component = |c| temporal_component(&a, c).unwrap().value(0);
+        assert_eq!(2, component(DateTimeField::Month));
+        assert_eq!(20, component(DateTimeField::Day));
+        assert_eq!(4, component(DateTimeField::Hour));
+        assert_eq!(23, component(DateTimeField::Minute));
+        assert_eq!(45, component(DateTimeField::Second));
+
+        let a: ArrayRef = Arc::new(Time32SecondArray::from(vec![37_800]));
+        assert_eq!(
+            30,
+            temporal_component(&a, DateTimeField::Minute)
+                .unwrap()
+                .value(0)
+        );
+        assert!(temporal_component(&a, DateTimeField::Year).is_err());
+    }
+
+    #[test]
+    fn test_temporal_component_non_temporal() {
+        let a: ArrayRef = Arc::new(Int32Array::from(vec![1]));
+        assert!(temporal_component(&a, DateTimeField::Year).is_err());
+    }
+
+    #[test]
+    fn test_strftime_timestamp() {
+        // 2019-02-20T04:23:45.123, null, 1970-01-01T00:00:00
+        let a: ArrayRef = Arc::new(TimestampMillisecondArray::from(vec![
+            Some(1_550_636_625_123),
+            None,
+            Some(0),
+        ]));
+        let b = strftime(&a, "%Y/%m/%d %H:%M").unwrap();
+        assert_eq!(3, b.len());
+        assert_eq!("2019/02/20 04:23", b.get_string(0));
+        assert!(b.is_null(1));
+        assert_eq!("1970/01/01 00:00", b.get_string(2));
+    }
+
+    #[test]
+    fn test_strftime_date_and_time() {
+        let a: ArrayRef = Arc::new(Date32Array::from(vec![17970]));
+        assert_eq!("2019-03-15", strftime(&a, "%F").unwrap().get_string(0));
+
+        let a: ArrayRef = Arc::new(Time32SecondArray::from(vec![37_805]));
+        assert_eq!("10:30:05", strftime(&a, "%T").unwrap().get_string(0));
+        assert!(strftime(&a, "%Y").is_err());
+    }
+
+    #[test]
+    fn test_strftime_errors() {
+        let a: ArrayRef = Arc::new(Date32Array::from(vec![0]));
+        assert!(strftime(&a, "%Q").is_err());
+
+        let a: ArrayRef = Arc::new(Int32Array::from(vec![1]));
+        assert!(strftime(&a, "%Y").is_err());
+    }
 }
@@ -0,0 +1,160 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines kernels returning the distinct values of an array.
+
+use std::collections::HashMap;
+
+use crate::array::*;
+use crate::builder::UInt32Builder;
+use crate::compute::kernels::hash::hash_array;
+use crate::compute::kernels::take::take;
+use crate::error::Result;
+
+/// Returns the distinct non-null values of `array`, in order of first appearance.
+///
+/// See `unique_with_indices` for the supported types and how values are compared.
+pub fn unique(array: &ArrayRef) -> Result<ArrayRef> {
+    unique_with_indices(array).map(|(values, _)| values)
+}
+
+/// Returns the distinct non-null values of `array` in order of first appearance, along
+/// with the index in them of each element of `array`, which is null for null elements.
+///
+/// The returned indices can be used as the keys of a dictionary whose values are the
+/// distinct values, and `take(values, indices)` restores `array`. Elements are grouped
+/// by their hash from `hash_array` and then compared by value, so the types supported
+/// are those supported by both `hash_array` and `take`. As with `hash_array`, `0.0` and
+/// `-0.0` are distinct values, and so is every `NaN`.
+pub fn unique_with_indices(array: &ArrayRef) -> Result<(ArrayRef, UInt32Array)> {
+    let mut hashes = vec![0; array.len()];
+    hash_array(array, 0, &mut hashes)?;
+
+    // the indices in `array` of the first appearance of each distinct value, and the
+    // positions in `first_indices` of the distinct values with a given hash
+    let mut first_indices: Vec<u32> = Vec::new();
+    let mut buckets: HashMap<u64, Vec<u32>> = HashMap::new();
+    let mut indices = UInt32Builder::new(array.len());
+    for (i, h) in hashes.iter().enumerate() {
+        if array.is_null(i) {
+            indices.append_null()?;
+            continue;
+        }
+        let bucket = buckets.entry(*h).or_default();
+        let position = bucket.iter().cloned().find(|position| {
+            let j = first_indices[*position as usize] as usize;
+            array_value_equals(&**array, i, &**array, j)
+        });
+        let position = match position {
+            Some(position) => position,
+            None => {
+                let position = first_indices.len() as u32;
+                first_indices.push(i as u32);
+                bucket.push(position);
+                position
+            }
+        };
+        indices.append_value(position)?;
+    }
+
+    let values = take(array, &UInt32Array::from(first_indices))?;
+    Ok((values, indices.finish()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    use crate::datatypes::Int32Type;
+
+    #[test]
+    fn test_unique_primitive() {
+        let array: ArrayRef = Arc::new(Int32Array::from(vec![
+            Some(3),
+            None,
+            Some(1),
+            Some(3),
+            None,
+            Some(2),
+            Some(1),
+        ]));
+        let (values, indices) = unique_with_indices(&array).unwrap();
+        let values = as_primitive_array::<Int32Type>(&values);
+        assert_eq!(3, values.len());
+        assert_eq!(0, values.null_count());
+        assert_eq!(&[3, 1, 2], values.value_slice(0, 3));
+
+        assert_eq!(7, indices.len());
+        assert_eq!(2, indices.null_count());
+        assert!(indices.is_null(1));
+        assert!(indices.is_null(4));
+        for (i, expected) in [(0, 0), (2, 1), (3, 0), (5, 2), (6, 1)].iter() {
+            assert_eq!(*expected, indices.value(*i));
+        }
+
+        // the indices restore the array
+        assert!(take(&unique(&array).unwrap(), &indices)
+            .unwrap()
+            .equals(&*array));
+    }
+
+    #[test]
+    fn test_unique_binary() {
+        let array: ArrayRef = Arc::new(BinaryArray::from(vec![
+            Some("foo"),
+            Some(""),
+            None,
+            Some("bar"),
+            Some("foo"),
+            Some(""),
+        ]));
+        let values = unique(&array).unwrap();
+        assert_eq!(
+            vec![Some("foo"), Some(""), Some("bar")],
+            as_string_array(&values).string_iter().collect::<Vec<_>>()
+        );
+
+        let sliced = make_array(array.data().slice(3, 3));
+        let values = unique(&sliced).unwrap();
+        assert_eq!(
+            vec![Some("bar"), Some("foo"), Some("")],
+            as_string_array(&values).string_iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_unique_boolean_and_empty() {
+        let array: ArrayRef =
+            Arc::new(BooleanArray::from(vec![Some(false), None, Some(false)]));
+        let values = unique(&array).unwrap();
+        assert_eq!(&BooleanArray::from(vec![false]), as_boolean_array(&values));
+
+        let nulls: ArrayRef = Arc::new(Int32Array::from(vec![None, None]));
+        assert_eq!(0, unique(&nulls).unwrap().len());
+    }
+
+    #[test]
+    fn test_unique_unsupported() {
+        let array: ArrayRef = Arc::new(
+            ListArray::try_new(Arc::new(Int32Array::from(vec![1, 2])), &[0, 1, 2], None)
+                .unwrap(),
+        );
+        assert!(unique(&array).is_err());
+    }
+}
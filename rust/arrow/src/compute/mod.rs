@@ -29,4 +29,17 @@ pub use self::arithmetic_kernels::*;
 pub use self::array_ops::*;
 pub use self::boolean_kernels::*;
 pub use self::comparison_kernels::*;
+pub use self::kernels::aggregate::*;
+pub use self::kernels::cardinality::*;
+pub use self::kernels::cast::*;
+pub use self::kernels::concat::*;
+pub use self::kernels::delta::*;
+pub use self::kernels::dictionary::*;
+pub use self::kernels::hash::*;
+pub use self::kernels::nest::*;
+pub use self::kernels::row::*;
+pub use self::kernels::sort::*;
+pub use self::kernels::sparse::*;
+pub use self::kernels::take::*;
 pub use self::kernels::temporal::*;
+pub use self::kernels::unique::*;
@@ -58,14 +58,15 @@ use self::csv_crate::{StringRecord, StringRecordsIntoIter};
 
 lazy_static! {
     static ref DECIMAL_RE: Regex = Regex::new(r"^-?(\d+\.\d+)$").unwrap();
-    static ref INTEGER_RE: Regex = Regex::new(r"^-?(\d*.)$").unwrap();
+    static ref INTEGER_RE: Regex = Regex::new(r"^-?(\d+)$").unwrap();
     static ref BOOLEAN_RE: Regex = RegexBuilder::new(r"^(true)$|^(false)$")
         .case_insensitive(true)
         .build()
         .unwrap();
 }
 
-/// Infer the data type of a record
+/// Infer the data type of a record: `Boolean` for `true` and `false` in any case,
+/// `Float64` for decimal numbers, `Int64` for integers, and `Utf8` otherwise
 fn infer_field_schema(string: &str) -> DataType {
     // when quoting is enabled in the reader, these quotes aren't escaped, we default to
     // Utf8 for them
@@ -304,9 +305,10 @@ impl<R: Read> Reader<R> {
                     &DataType::Utf8 => {
                         let mut builder = BinaryBuilder::new(rows.len());
                         for row_index in 0..rows.len() {
+                            // empty fields are nulls, like for the other types
                             match rows[row_index].get(*i) {
-                                Some(s) => builder.append_string(s).unwrap(),
-                                _ => builder.append(false).unwrap(),
+                                Some(s) if !s.is_empty() => builder.append_string(s)?,
+                                _ => builder.append_null()?,
                             }
                         }
                         Ok(Arc::new(builder.finish()) as ArrayRef)
@@ -683,6 +685,44 @@ mod tests {
         assert_eq!(false, batch.column(1).is_null(4));
     }
 
+    #[test]
+    fn test_csv_inference_and_empty_fields() {
+        let data = "a;b;c;d;e\n1;1.5;true;x;\n-20;;False;;\n;3.0;;1a;\n";
+        let builder = ReaderBuilder::new()
+            .has_headers(true)
+            .with_delimiter(b';')
+            .infer_schema(Some(10))
+            .with_batch_size(2);
+        let mut csv = builder.build(Cursor::new(data)).unwrap();
+
+        let batch = csv.next().unwrap().unwrap();
+        assert_eq!(2, batch.num_rows());
+        let schema = batch.schema();
+        assert_eq!(&DataType::Int64, schema.field(0).data_type());
+        assert_eq!(&DataType::Float64, schema.field(1).data_type());
+        assert_eq!(&DataType::Boolean, schema.field(2).data_type());
+        assert_eq!(&DataType::Utf8, schema.field(3).data_type());
+        // a column of empty fields only is a column of strings
+        assert_eq!(&DataType::Utf8, schema.field(4).data_type());
+        assert!(schema.field(0).is_nullable());
+
+        let d = batch
+            .column(3)
+            .as_any()
+            .downcast_ref::<BinaryArray>()
+            .unwrap();
+        assert_eq!("x", d.get_string(0));
+        assert!(d.is_null(1));
+        assert_eq!(2, batch.column(4).null_count());
+        assert!(batch.column(1).is_null(1));
+
+        let batch = csv.next().unwrap().unwrap();
+        assert_eq!(1, batch.num_rows());
+        assert!(batch.column(0).is_null(0));
+        assert!(batch.column(2).is_null(0));
+        assert!(csv.next().unwrap().is_none());
+    }
+
     #[test]
     fn test_nulls_with_inference() {
         let file = File::open("test/data/various_types.csv").unwrap();
@@ -21,11 +21,13 @@
 //! information regarding data-types and memory layouts see
 //! [here](https://arrow.apache.org/docs/memory_layout.html).
 
+use std::collections::HashMap;
 use std::fmt;
 use std::mem::size_of;
 use std::ops::{Add, Div, Mul, Sub};
 use std::slice::from_raw_parts;
 use std::str::FromStr;
+use std::sync::Arc;
 
 use packed_simd::*;
 use serde_derive::{Deserialize, Serialize};
@@ -33,19 +35,29 @@ use serde_json::{json, Value};
 
 use crate::error::{ArrowError, Result};
 
+/// The maximum precision of a `Decimal`, i.e. the number of decimal digits that always
+/// fit in a 128-bit integer
+pub const DECIMAL_MAX_PRECISION: usize = 38;
+
 /// The possible relative types that are supported.
 ///
 /// The variants of this enum include primitive fixed size types as well as parametric or
 /// nested types.
 /// Currently the Rust implementation supports the following  nested types:
 ///  - `List<T>`
+///  - `LargeList<T>`
+///  - `FixedSizeList<T>`
 ///  - `Struct<T, U, V, ...>`
+///  - `Dictionary<K, V>`
+///  - `Union<T, U, V, ...>`
 ///
 /// Nested types can themselves be nested within other arrays.
 /// For more information on these types please see
 /// [here](https://arrow.apache.org/docs/memory_layout.html).
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum DataType {
+    /// The type of arrays whose elements are all null, which have no value buffers
+    Null,
     Boolean,
     Int8,
     Int16,
@@ -58,15 +70,37 @@ pub enum DataType {
     Float16,
     Float32,
     Float64,
-    Timestamp(TimeUnit),
+    /// An exact decimal number with a precision, the maximum number of digits, and a
+    /// scale, the number of digits after the decimal point. The values are stored as
+    /// 128-bit integers, scaled by `10^scale`.
+    Decimal(usize, usize),
+    /// A timestamp with a time unit and an optional timezone.
+    ///
+    /// The values are always the elapsed time since the UNIX epoch in UTC, so the
+    /// timezone only labels how the values should be read as wall-clock times.
+    Timestamp(TimeUnit, Option<Arc<String>>),
     Date32(DateUnit),
     Date64(DateUnit),
     Time32(TimeUnit),
     Time64(TimeUnit),
     Interval(IntervalUnit),
     Utf8,
+    /// A `Utf8` with 64-bit offsets, for arrays holding more than 2GB of data
+    LargeUtf8,
+    /// Opaque binary values that all have the same number of bytes, which need no
+    /// offsets
+    FixedSizeBinary(i32),
     List(Box<DataType>),
+    /// A list with 64-bit offsets, for lists whose values don't fit in a `List`
+    LargeList(Box<DataType>),
+    /// A list of a fixed number of values per element, which needs no offsets
+    FixedSizeList(Box<DataType>, i32),
     Struct(Vec<Field>),
+    /// A dictionary-encoded array, parameterized by its key type and its value type
+    Dictionary(Box<DataType>, Box<DataType>),
+    /// An array whose elements each have the type of one of its fields, selected by the
+    /// type id of the element
+    Union(Vec<Field>, UnionMode),
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -89,6 +123,17 @@ pub enum IntervalUnit {
     DayTime,
 }
 
+/// The layout of the children of a union array.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum UnionMode {
+    /// The children have the same length as the union, and the element at index `i` is
+    /// the element at index `i` of the child selected by its type id
+    Sparse,
+    /// The union has an offsets buffer, and the element at index `i` is the element at
+    /// offset `i` of the child selected by its type id
+    Dense,
+}
+
 /// Contains the meta-data for a single relative type.
 ///
 /// The `Schema` object is an ordered collection of `Field` objects.
@@ -133,6 +178,25 @@ impl ArrowNativeType for u64 {}
 impl ArrowNativeType for f32 {}
 impl ArrowNativeType for f64 {}
 
+/// Native types for which every bit pattern of their size is a valid value, i.e. the
+/// integer and floating point types but not `bool`, so that they can be read directly
+/// from the bytes of a buffer.
+///
+/// This trait is unsafe to implement, as implementing it for a type with invalid bit
+/// patterns makes `Buffer::typed_data` unsound.
+pub unsafe trait ArrowPlainNativeType: ArrowNativeType {}
+
+unsafe impl ArrowPlainNativeType for i8 {}
+unsafe impl ArrowPlainNativeType for i16 {}
+unsafe impl ArrowPlainNativeType for i32 {}
+unsafe impl ArrowPlainNativeType for i64 {}
+unsafe impl ArrowPlainNativeType for u8 {}
+unsafe impl ArrowPlainNativeType for u16 {}
+unsafe impl ArrowPlainNativeType for u32 {}
+unsafe impl ArrowPlainNativeType for u64 {}
+unsafe impl ArrowPlainNativeType for f32 {}
+unsafe impl ArrowPlainNativeType for f64 {}
+
 macro_rules! make_type {
     ($name:ident, $native_ty:ty, $data_ty:expr, $bit_width:expr, $default_val:expr) => {
         pub struct $name {}
@@ -169,28 +233,28 @@ make_type!(Float64Type, f64, DataType::Float64, 64, 0.0f64);
 make_type!(
     TimestampSecondType,
     i64,
-    DataType::Timestamp(TimeUnit::Second),
+    DataType::Timestamp(TimeUnit::Second, None),
     64,
     0i64
 );
 make_type!(
     TimestampMillisecondType,
     i64,
-    DataType::Timestamp(TimeUnit::Millisecond),
+    DataType::Timestamp(TimeUnit::Millisecond, None),
     64,
     0i64
 );
 make_type!(
     TimestampMicrosecondType,
     i64,
-    DataType::Timestamp(TimeUnit::Microsecond),
+    DataType::Timestamp(TimeUnit::Microsecond, None),
     64,
     0i64
 );
 make_type!(
     TimestampNanosecondType,
     i64,
-    DataType::Timestamp(TimeUnit::Nanosecond),
+    DataType::Timestamp(TimeUnit::Nanosecond, None),
     64,
     0i64
 );
@@ -388,6 +452,19 @@ make_numeric_type!(Time64NanosecondType, i64, i64x8, m64x8);
 make_numeric_type!(IntervalYearMonthType, i64, i64x8, m64x8);
 make_numeric_type!(IntervalDayTimeType, i64, i64x8, m64x8);
 
+/// A subtype of primitive type that can be used as the key of a dictionary-encoded
+/// array.
+pub trait ArrowDictionaryKeyType: ArrowPrimitiveType {}
+
+impl ArrowDictionaryKeyType for Int8Type {}
+impl ArrowDictionaryKeyType for Int16Type {}
+impl ArrowDictionaryKeyType for Int32Type {}
+impl ArrowDictionaryKeyType for Int64Type {}
+impl ArrowDictionaryKeyType for UInt8Type {}
+impl ArrowDictionaryKeyType for UInt16Type {}
+impl ArrowDictionaryKeyType for UInt32Type {}
+impl ArrowDictionaryKeyType for UInt64Type {}
+
 /// A subtype of primitive type that represents temporal values.
 pub trait ArrowTemporalType: ArrowPrimitiveType {}
 
@@ -404,6 +481,50 @@ impl ArrowTemporalType for Time64NanosecondType {}
 impl ArrowTemporalType for IntervalYearMonthType {}
 impl ArrowTemporalType for IntervalDayTimeType {}
 
+/// Parses the timezone of a `DataType::Timestamp` into its offset from UTC in seconds.
+///
+/// Timezones are either `UTC` or a fixed offset from UTC such as `+05:30`, `-0800` or
+/// `+01`, with at most 23 hours and 59 minutes. Named timezones are not supported.
+pub fn timezone_offset_seconds(tz: &str) -> Result<i64> {
+    let invalid = || {
+        ArrowError::ParseError(format!(
+            "Unsupported timezone '{}': expected 'UTC' or an offset such as '+05:30'",
+            tz
+        ))
+    };
+    if tz == "UTC" || tz == "Z" {
+        return Ok(0);
+    }
+    if !tz.is_ascii() {
+        return Err(invalid());
+    }
+    let sign = match tz.chars().next() {
+        Some('+') => 1,
+        Some('-') => -1,
+        _ => return Err(invalid()),
+    };
+    let rest = &tz[1..];
+    let (hours, minutes) = match rest.len() {
+        2 => (rest, "00"),
+        4 => (&rest[0..2], &rest[2..4]),
+        5 if &rest[2..3] == ":" => (&rest[0..2], &rest[3..5]),
+        _ => return Err(invalid()),
+    };
+    if !hours
+        .chars()
+        .chain(minutes.chars())
+        .all(|c| c.is_ascii_digit())
+    {
+        return Err(invalid());
+    }
+    let hours: i64 = hours.parse().map_err(|_| invalid())?;
+    let minutes: i64 = minutes.parse().map_err(|_| invalid())?;
+    if hours > 23 || minutes > 59 {
+        return Err(invalid());
+    }
+    Ok(sign * (hours * 3_600 + minutes * 60))
+}
+
 /// Allows conversion from supported Arrow types to a byte slice.
 pub trait ToByteSlice {
     /// Converts this instance into a byte slice
@@ -429,8 +550,10 @@ impl DataType {
     fn from(json: &Value) -> Result<DataType> {
         match *json {
             Value::Object(ref map) => match map.get("name") {
+                Some(s) if s == "null" => Ok(DataType::Null),
                 Some(s) if s == "bool" => Ok(DataType::Boolean),
                 Some(s) if s == "utf8" => Ok(DataType::Utf8),
+                Some(s) if s == "largeutf8" => Ok(DataType::LargeUtf8),
                 Some(s) if s == "floatingpoint" => match map.get("precision") {
                     Some(p) if p == "HALF" => Ok(DataType::Float16),
                     Some(p) if p == "SINGLE" => Ok(DataType::Float32),
@@ -439,21 +562,117 @@ impl DataType {
                         "floatingpoint precision missing or invalid".to_string(),
                     )),
                 },
-                Some(s) if s == "timestamp" => match map.get("unit") {
-                    Some(p) if p == "SECOND" => Ok(DataType::Timestamp(TimeUnit::Second)),
-                    Some(p) if p == "MILLISECOND" => {
-                        Ok(DataType::Timestamp(TimeUnit::Millisecond))
+                Some(s) if s == "decimal" => {
+                    let precision = map.get("precision").and_then(|p| p.as_u64());
+                    let scale = map.get("scale").and_then(|s| s.as_u64());
+                    match (precision, scale) {
+                        (Some(precision), Some(scale)) => {
+                            DataType::try_decimal(precision as usize, scale as usize)
+                                .map_err(|e| match e {
+                                    ArrowError::InvalidArgumentError(message) => {
+                                        ArrowError::ParseError(message)
+                                    }
+                                    e => e,
+                                })
+                        }
+                        _ => Err(ArrowError::ParseError(
+                            "decimal precision or scale missing or invalid".to_string(),
+                        )),
                     }
-                    Some(p) if p == "MICROSECOND" => {
-                        Ok(DataType::Timestamp(TimeUnit::Microsecond))
+                }
+                Some(s) if s == "fixedsizebinary" => {
+                    match map.get("byteWidth").and_then(|w| w.as_i64()) {
+                        Some(width)
+                            if width >= 0 && width <= i64::from(i32::max_value()) =>
+                        {
+                            Ok(DataType::FixedSizeBinary(width as i32))
+                        }
+                        _ => Err(ArrowError::ParseError(
+                            "fixedsizebinary byteWidth missing or invalid".to_string(),
+                        )),
                     }
-                    Some(p) if p == "NANOSECOND" => {
-                        Ok(DataType::Timestamp(TimeUnit::Nanosecond))
+                }
+                Some(s) if s == "dictionary" => {
+                    let key_type = match map.get("indexType") {
+                        Some(t) => DataType::from(t)?,
+                        None => {
+                            return Err(ArrowError::ParseError(
+                                "dictionary indexType missing".to_string(),
+                            ));
+                        }
+                    };
+                    match key_type {
+                        DataType::Int8
+                        | DataType::Int16
+                        | DataType::Int32
+                        | DataType::Int64
+                        | DataType::UInt8
+                        | DataType::UInt16
+                        | DataType::UInt32
+                        | DataType::UInt64 => {}
+                        other => {
+                            return Err(ArrowError::ParseError(format!(
+                                "dictionary indexType must be an integer type, got {:?}",
+                                other
+                            )));
+                        }
                     }
-                    _ => Err(ArrowError::ParseError(
-                        "timestamp unit missing or invalid".to_string(),
-                    )),
-                },
+                    match map.get("children") {
+                        Some(t) => Ok(DataType::Dictionary(
+                            Box::new(key_type),
+                            Box::new(DataType::from(t)?),
+                        )),
+                        None => Err(ArrowError::ParseError(
+                            "dictionary value type missing".to_string(),
+                        )),
+                    }
+                }
+                Some(s) if s == "union" => {
+                    let mode = match map.get("mode") {
+                        Some(p) if p == "SPARSE" => UnionMode::Sparse,
+                        Some(p) if p == "DENSE" => UnionMode::Dense,
+                        _ => {
+                            return Err(ArrowError::ParseError(
+                                "union mode missing or invalid".to_string(),
+                            ));
+                        }
+                    };
+                    match map.get("children") {
+                        Some(&Value::Array(ref fields_array)) => {
+                            let fields = fields_array
+                                .iter()
+                                .map(|f| Field::from(f))
+                                .collect::<Result<Vec<Field>>>();
+                            Ok(DataType::Union(fields?, mode))
+                        }
+                        _ => Err(ArrowError::ParseError(
+                            "union children missing or invalid".to_string(),
+                        )),
+                    }
+                }
+                Some(s) if s == "timestamp" => {
+                    let timezone = match map.get("timezone") {
+                        Some(Value::String(tz)) => Some(Arc::new(tz.clone())),
+                        None | Some(Value::Null) => None,
+                        _ => {
+                            return Err(ArrowError::ParseError(
+                                "timestamp timezone must be a string".to_string(),
+                            ));
+                        }
+                    };
+                    let unit = match map.get("unit") {
+                        Some(p) if p == "SECOND" => TimeUnit::Second,
+                        Some(p) if p == "MILLISECOND" => TimeUnit::Millisecond,
+                        Some(p) if p == "MICROSECOND" => TimeUnit::Microsecond,
+                        Some(p) if p == "NANOSECOND" => TimeUnit::Nanosecond,
+                        _ => {
+                            return Err(ArrowError::ParseError(
+                                "timestamp unit missing or invalid".to_string(),
+                            ));
+                        }
+                    };
+                    Ok(DataType::Timestamp(unit, timezone))
+                }
                 Some(s) if s == "date" => match map.get("unit") {
                     Some(p) if p == "DAY" => Ok(DataType::Date32(DateUnit::Day)),
                     Some(p) if p == "MILLISECOND" => {
@@ -549,6 +768,7 @@ impl DataType {
     /// Generate a JSON representation of the data type
     pub fn to_json(&self) -> Value {
         match self {
+            DataType::Null => json!({"name": "null"}),
             DataType::Boolean => json!({"name": "bool"}),
             DataType::Int8 => json!({"name": "int", "bitWidth": 8, "isSigned": true}),
             DataType::Int16 => json!({"name": "int", "bitWidth": 16, "isSigned": true}),
@@ -561,7 +781,14 @@ impl DataType {
             DataType::Float16 => json!({"name": "floatingpoint", "precision": "HALF"}),
             DataType::Float32 => json!({"name": "floatingpoint", "precision": "SINGLE"}),
             DataType::Float64 => json!({"name": "floatingpoint", "precision": "DOUBLE"}),
+            DataType::Decimal(precision, scale) => {
+                json!({"name": "decimal", "precision": precision, "scale": scale})
+            }
             DataType::Utf8 => json!({"name": "utf8"}),
+            DataType::LargeUtf8 => json!({"name": "largeutf8"}),
+            DataType::FixedSizeBinary(width) => {
+                json!({"name": "fixedsizebinary", "byteWidth": width})
+            }
             DataType::Struct(ref fields) => {
                 let field_json_array = Value::Array(
                     fields.iter().map(|f| f.to_json()).collect::<Vec<Value>>(),
@@ -572,6 +799,29 @@ impl DataType {
                 let child_json = t.to_json();
                 json!({ "name": "list", "children": child_json })
             }
+            DataType::LargeList(ref t) => {
+                let child_json = t.to_json();
+                json!({ "name": "largelist", "children": child_json })
+            }
+            DataType::FixedSizeList(ref t, size) => {
+                let child_json = t.to_json();
+                json!({ "name": "fixedsizelist", "listSize": size, "children": child_json })
+            }
+            DataType::Dictionary(ref key_type, ref value_type) => json!({
+                "name": "dictionary",
+                "indexType": key_type.to_json(),
+                "children": value_type.to_json()
+            }),
+            DataType::Union(ref fields, ref mode) => {
+                let field_json_array = Value::Array(
+                    fields.iter().map(|f| f.to_json()).collect::<Vec<Value>>(),
+                );
+                let mode = match mode {
+                    UnionMode::Sparse => "SPARSE",
+                    UnionMode::Dense => "DENSE",
+                };
+                json!({ "name": "union", "mode": mode, "children": field_json_array })
+            }
             DataType::Time32(unit) => {
                 json!({"name": "time", "bitWidth": "32", "unit": match unit {
                     TimeUnit::Second => "SECOND",
@@ -594,18 +844,112 @@ impl DataType {
                     DateUnit::Millisecond => "MILLISECOND",
                 }})
             }
-            DataType::Timestamp(unit) => json!({"name": "timestamp", "unit": match unit {
-                TimeUnit::Second => "SECOND",
-                TimeUnit::Millisecond => "MILLISECOND",
-                TimeUnit::Microsecond => "MICROSECOND",
-                TimeUnit::Nanosecond => "NANOSECOND",
-            }}),
+            DataType::Timestamp(unit, timezone) => {
+                let unit = match unit {
+                    TimeUnit::Second => "SECOND",
+                    TimeUnit::Millisecond => "MILLISECOND",
+                    TimeUnit::Microsecond => "MICROSECOND",
+                    TimeUnit::Nanosecond => "NANOSECOND",
+                };
+                match timezone {
+                    Some(tz) => {
+                        json!({"name": "timestamp", "unit": unit, "timezone": tz.as_str()})
+                    }
+                    None => json!({"name": "timestamp", "unit": unit}),
+                }
+            }
             DataType::Interval(unit) => json!({"name": "interval", "unit": match unit {
                 IntervalUnit::YearMonth => "YEAR_MONTH",
                 IntervalUnit::DayTime => "DAY_TIME",
             }}),
         }
     }
+
+    /// Returns a `Decimal` type with `precision` and `scale`, or an error if the
+    /// precision isn't between 1 and `DECIMAL_MAX_PRECISION` or if the scale is
+    /// greater than the precision.
+    pub fn try_decimal(precision: usize, scale: usize) -> Result<DataType> {
+        if precision == 0 || precision > DECIMAL_MAX_PRECISION {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "Decimal precision must be between 1 and {}, got {}",
+                DECIMAL_MAX_PRECISION, precision
+            )));
+        }
+        if scale > precision {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "Decimal scale {} is greater than its precision {}",
+                scale, precision
+            )));
+        }
+        Ok(DataType::Decimal(precision, scale))
+    }
+
+    /// Returns whether this is an integer or floating point type
+    pub fn is_numeric(&self) -> bool {
+        match self {
+            DataType::Int8
+            | DataType::Int16
+            | DataType::Int32
+            | DataType::Int64
+            | DataType::UInt8
+            | DataType::UInt16
+            | DataType::UInt32
+            | DataType::UInt64
+            | DataType::Float16
+            | DataType::Float32
+            | DataType::Float64 => true,
+            _ => false,
+        }
+    }
+
+    /// Returns whether this is a date, time, timestamp or interval type
+    pub fn is_temporal(&self) -> bool {
+        match self {
+            DataType::Timestamp(_, _)
+            | DataType::Date32(_)
+            | DataType::Date64(_)
+            | DataType::Time32(_)
+            | DataType::Time64(_)
+            | DataType::Interval(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Returns whether this type has child types, i.e. is a list, struct or union type
+    pub fn is_nested(&self) -> bool {
+        match self {
+            DataType::List(_)
+            | DataType::LargeList(_)
+            | DataType::FixedSizeList(_, _)
+            | DataType::Struct(_)
+            | DataType::Union(_, _) => true,
+            _ => false,
+        }
+    }
+
+    /// Returns the number of bytes of a value of a fixed-width primitive type, or `None`
+    /// for the other types, including `Boolean` whose values are bit-packed
+    pub fn primitive_width(&self) -> Option<usize> {
+        match self {
+            DataType::Int8 | DataType::UInt8 => Some(1),
+            DataType::Int16 | DataType::UInt16 | DataType::Float16 => Some(2),
+            DataType::Int32
+            | DataType::UInt32
+            | DataType::Float32
+            | DataType::Date32(_)
+            | DataType::Time32(_) => Some(4),
+            DataType::Int64
+            | DataType::UInt64
+            | DataType::Float64
+            | DataType::Date64(_)
+            | DataType::Time64(_)
+            | DataType::Timestamp(_, _)
+            | DataType::Interval(_) => Some(8),
+            DataType::Decimal(_, _) => Some(16),
+            DataType::FixedSizeBinary(width) => Some(*width as usize),
+            _ => None,
+        }
+    }
 }
 
 impl Field {
@@ -701,12 +1045,15 @@ impl fmt::Display for Field {
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct Schema {
     pub(crate) fields: Vec<Field>,
+    /// Custom key/value metadata of the schema
+    #[serde(default)]
+    pub(crate) metadata: HashMap<String, String>,
 }
 
 impl Schema {
     /// Creates an empty `Schema`
     pub fn empty() -> Self {
-        Self { fields: vec![] }
+        Self::new(vec![])
     }
 
     /// Creates a new `Schema` from a sequence of `Field` values
@@ -722,7 +1069,34 @@ impl Schema {
     /// let schema = Schema::new(vec![field_a, field_b]);
     /// ```
     pub fn new(fields: Vec<Field>) -> Self {
-        Self { fields }
+        Self::new_with_metadata(fields, HashMap::new())
+    }
+
+    /// Creates a new `Schema` from a sequence of `Field` values and custom key/value
+    /// metadata
+    pub fn new_with_metadata(
+        fields: Vec<Field>,
+        metadata: HashMap<String, String>,
+    ) -> Self {
+        Self { fields, metadata }
+    }
+
+    /// Creates a new `Schema` from a sequence of `Field` values, like `Schema::new`.
+    ///
+    /// If `strict` is set, returns an error if several fields have the same name, so that
+    /// every field can be looked up by name.
+    pub fn try_new(fields: Vec<Field>, strict: bool) -> Result<Self> {
+        if strict {
+            for (i, field) in fields.iter().enumerate() {
+                if fields[..i].iter().any(|f| f.name == field.name) {
+                    return Err(ArrowError::InvalidArgumentError(format!(
+                        "Schema has several fields named \"{}\"",
+                        field.name
+                    )));
+                }
+            }
+        }
+        Ok(Self::new(fields))
     }
 
     /// Returns an immutable reference of the vector of `Field` instances
@@ -736,6 +1110,21 @@ impl Schema {
         &self.fields[i]
     }
 
+    /// Returns the first field named `name`, if any
+    pub fn field_with_name(&self, name: &str) -> Option<&Field> {
+        self.column_with_name(name).map(|(_, field)| field)
+    }
+
+    /// Returns the index of the first field named `name`, if any
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        self.column_with_name(name).map(|(i, _)| i)
+    }
+
+    /// Returns the custom key/value metadata of the schema
+    pub fn metadata(&self) -> &HashMap<String, String> {
+        &self.metadata
+    }
+
     /// Look up a column by name and return a immutable reference to the column along with
     /// it's index
     pub fn column_with_name(&self, name: &str) -> Option<(usize, &Field)> {
@@ -745,11 +1134,20 @@ impl Schema {
             .find(|&(_, c)| c.name == name)
     }
 
-    /// Generate a JSON representation of the `Field`
+    /// Generate a JSON representation of the `Schema`, with its metadata if it has any
     pub fn to_json(&self) -> Value {
-        json!({
+        let mut json = json!({
             "fields": self.fields.iter().map(|field| field.to_json()).collect::<Vec<Value>>(),
-        })
+        });
+        if !self.metadata.is_empty() {
+            let mut metadata: Vec<(&String, &String)> = self.metadata.iter().collect();
+            metadata.sort();
+            json["metadata"] = metadata
+                .iter()
+                .map(|(key, value)| json!({ "key": key, "value": value }))
+                .collect();
+        }
+        json
     }
 }
 
@@ -877,6 +1275,15 @@ mod tests {
         assert_eq!(DataType::Utf8, dt);
     }
 
+    #[test]
+    fn parse_null_from_json() {
+        let json = "{\"name\":\"null\"}";
+        let value: Value = serde_json::from_str(json).unwrap();
+        let dt = DataType::from(&value).unwrap();
+        assert_eq!(DataType::Null, dt);
+        assert_eq!(value, dt.to_json());
+    }
+
     #[test]
     fn parse_int32_from_json() {
         let json = "{\"name\": \"int\", \"isSigned\": true, \"bitWidth\": 32}";
@@ -885,6 +1292,94 @@ mod tests {
         assert_eq!(DataType::Int32, dt);
     }
 
+    #[test]
+    fn parse_decimal_from_json() {
+        let json = "{\"name\": \"decimal\", \"precision\": 10, \"scale\": 2}";
+        let value: Value = serde_json::from_str(json).unwrap();
+        let dt = DataType::from(&value).unwrap();
+        assert_eq!(DataType::Decimal(10, 2), dt);
+        assert_eq!(value, dt.to_json());
+
+        let json = "{\"name\": \"decimal\", \"precision\": 39, \"scale\": 2}";
+        let value: Value = serde_json::from_str(json).unwrap();
+        assert!(DataType::from(&value).is_err());
+        let json = "{\"name\": \"decimal\", \"precision\": 10}";
+        let value: Value = serde_json::from_str(json).unwrap();
+        assert!(DataType::from(&value).is_err());
+    }
+
+    #[test]
+    fn parse_fixed_size_binary_from_json() {
+        let json = "{\"name\": \"fixedsizebinary\", \"byteWidth\": 16}";
+        let value: Value = serde_json::from_str(json).unwrap();
+        let dt = DataType::from(&value).unwrap();
+        assert_eq!(DataType::FixedSizeBinary(16), dt);
+        assert_eq!(value, dt.to_json());
+        assert_eq!(Some(16), dt.primitive_width());
+
+        let json = "{\"name\": \"fixedsizebinary\", \"byteWidth\": -1}";
+        let value: Value = serde_json::from_str(json).unwrap();
+        assert!(DataType::from(&value).is_err());
+    }
+
+    #[test]
+    fn parse_dictionary_from_json() {
+        let dt =
+            DataType::Dictionary(Box::new(DataType::Int16), Box::new(DataType::Utf8));
+        let value = dt.to_json();
+        assert_eq!(
+            "{\"name\":\"dictionary\",\"indexType\":{\"name\":\"int\",\"bitWidth\":16,\"isSigned\":true},\"children\":{\"name\":\"utf8\"}}",
+            value.to_string()
+        );
+        assert_eq!(dt, DataType::from(&value).unwrap());
+
+        let json = "{\"name\": \"dictionary\", \"indexType\": {\"name\": \"utf8\"}, \
+                    \"children\": {\"name\": \"utf8\"}}";
+        let value: Value = serde_json::from_str(json).unwrap();
+        assert!(DataType::from(&value).is_err());
+        let json = "{\"name\": \"dictionary\", \"children\": {\"name\": \"utf8\"}}";
+        let value: Value = serde_json::from_str(json).unwrap();
+        assert!(DataType::from(&value).is_err());
+    }
+
+    #[test]
+    fn parse_union_from_json() {
+        let dt = DataType::Union(
+            vec![
+                Field::new("i", DataType::Int32, false),
+                Field::new("s", DataType::Utf8, true),
+            ],
+            UnionMode::Sparse,
+        );
+        assert_eq!(dt, DataType::from(&dt.to_json()).unwrap());
+
+        let json = "{\"name\": \"union\", \"mode\": \"DENSE\", \"children\": []}";
+        let value: Value = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            DataType::Union(vec![], UnionMode::Dense),
+            DataType::from(&value).unwrap()
+        );
+        let json = "{\"name\": \"union\", \"children\": []}";
+        let value: Value = serde_json::from_str(json).unwrap();
+        assert!(DataType::from(&value).is_err());
+    }
+
+    #[test]
+    fn decimal_precision_and_scale() {
+        assert_eq!(
+            DataType::Decimal(38, 0),
+            DataType::try_decimal(38, 0).unwrap()
+        );
+        assert_eq!(
+            DataType::Decimal(5, 5),
+            DataType::try_decimal(5, 5).unwrap()
+        );
+        assert!(DataType::try_decimal(0, 0).is_err());
+        assert!(DataType::try_decimal(39, 2).is_err());
+        assert!(DataType::try_decimal(5, 6).is_err());
+        assert_eq!(Some(16), DataType::Decimal(10, 2).primitive_width());
+    }
+
     #[test]
     fn schema_json() {
         let schema = Schema::new(vec![
@@ -899,10 +1394,22 @@ mod tests {
             Field::new("c12", DataType::Time64(TimeUnit::Millisecond), false),
             Field::new("c13", DataType::Time64(TimeUnit::Microsecond), false),
             Field::new("c14", DataType::Time64(TimeUnit::Nanosecond), false),
-            Field::new("c15", DataType::Timestamp(TimeUnit::Second), false),
-            Field::new("c16", DataType::Timestamp(TimeUnit::Millisecond), false),
-            Field::new("c17", DataType::Timestamp(TimeUnit::Microsecond), false),
-            Field::new("c18", DataType::Timestamp(TimeUnit::Nanosecond), false),
+            Field::new("c15", DataType::Timestamp(TimeUnit::Second, None), false),
+            Field::new(
+                "c16",
+                DataType::Timestamp(TimeUnit::Millisecond, None),
+                false,
+            ),
+            Field::new(
+                "c17",
+                DataType::Timestamp(TimeUnit::Microsecond, None),
+                false,
+            ),
+            Field::new(
+                "c18",
+                DataType::Timestamp(TimeUnit::Nanosecond, None),
+                false,
+            ),
             Field::new("c19", DataType::Interval(IntervalUnit::DayTime), false),
             Field::new("c20", DataType::Interval(IntervalUnit::YearMonth), false),
             Field::new(
@@ -913,6 +1420,14 @@ mod tests {
                 ]),
                 false,
             ),
+            Field::new(
+                "c22",
+                DataType::Timestamp(
+                    TimeUnit::Millisecond,
+                    Some(Arc::new("+05:30".to_string())),
+                ),
+                false,
+            ),
         ]);
 
         let json = schema.to_json().to_string();
@@ -935,7 +1450,8 @@ mod tests {
         {\"name\":\"c20\",\"nullable\":false,\"type\":{\"name\":\"interval\",\"unit\":\"YEAR_MONTH\"}},\
         {\"name\":\"c21\",\"nullable\":false,\"type\":{\"fields\":[\
         {\"name\":\"a\",\"nullable\":false,\"type\":{\"name\":\"utf8\"}},\
-        {\"name\":\"b\",\"nullable\":false,\"type\":{\"name\":\"int\",\"bitWidth\":16,\"isSigned\":false}}]}}]}");
+        {\"name\":\"b\",\"nullable\":false,\"type\":{\"name\":\"int\",\"bitWidth\":16,\"isSigned\":false}}]}},\
+        {\"name\":\"c22\",\"nullable\":false,\"type\":{\"name\":\"timestamp\",\"unit\":\"MILLISECOND\",\"timezone\":\"+05:30\"}}]}");
 
         // convert back to a schema
         let value: Value = serde_json::from_str(&json).unwrap();
@@ -944,11 +1460,74 @@ mod tests {
         match schema2 {
             DataType::Struct(fields) => {
                 assert_eq!(schema.fields().len(), fields.len());
+                assert_eq!(schema.field(18).data_type(), fields[18].data_type());
             }
             _ => panic!(),
         }
     }
 
+    #[test]
+    fn test_timezone_offset_seconds() {
+        assert_eq!(0, timezone_offset_seconds("UTC").unwrap());
+        assert_eq!(19_800, timezone_offset_seconds("+05:30").unwrap());
+        assert_eq!(-28_800, timezone_offset_seconds("-0800").unwrap());
+        assert_eq!(3_600, timezone_offset_seconds("+01").unwrap());
+        assert!(timezone_offset_seconds("Europe/Paris").is_err());
+        assert!(timezone_offset_seconds("+5:30").is_err());
+        assert!(timezone_offset_seconds("+25:00").is_err());
+        assert!(timezone_offset_seconds("+01:60").is_err());
+        assert!(timezone_offset_seconds("+0:130").is_err());
+        assert!(timezone_offset_seconds("+1é0").is_err());
+        assert!(timezone_offset_seconds("+é:00").is_err());
+    }
+
+    #[test]
+    fn data_type_predicates() {
+        let list = DataType::List(Box::new(DataType::Int32));
+        let fixed_size_list = DataType::FixedSizeList(Box::new(DataType::Int32), 3);
+        let structure = DataType::Struct(vec![Field::new("a", DataType::Int32, false)]);
+        let timestamp = DataType::Timestamp(TimeUnit::Nanosecond, None);
+        let temporal = vec![
+            timestamp.clone(),
+            DataType::Date32(DateUnit::Day),
+            DataType::Date64(DateUnit::Millisecond),
+            DataType::Time32(TimeUnit::Second),
+            DataType::Time64(TimeUnit::Microsecond),
+            DataType::Interval(IntervalUnit::DayTime),
+        ];
+
+        for t in &[
+            DataType::Int8,
+            DataType::UInt64,
+            DataType::Float16,
+            DataType::Float64,
+        ] {
+            assert!(t.is_numeric() && !t.is_temporal() && !t.is_nested());
+        }
+        for t in &temporal {
+            assert!(t.is_temporal() && !t.is_numeric() && !t.is_nested());
+        }
+        for t in &[list.clone(), fixed_size_list.clone(), structure.clone()] {
+            assert!(t.is_nested() && !t.is_numeric() && !t.is_temporal());
+            assert_eq!(None, t.primitive_width());
+        }
+        for t in &[DataType::Boolean, DataType::Utf8, DataType::Null] {
+            assert!(!t.is_numeric() && !t.is_temporal() && !t.is_nested());
+            assert_eq!(None, t.primitive_width());
+        }
+
+        assert_eq!(Some(1), DataType::UInt8.primitive_width());
+        assert_eq!(Some(2), DataType::Int16.primitive_width());
+        assert_eq!(Some(4), DataType::Float32.primitive_width());
+        assert_eq!(Some(8), DataType::Int64.primitive_width());
+        assert_eq!(Some(8), timestamp.primitive_width());
+        assert_eq!(Some(4), DataType::Date32(DateUnit::Day).primitive_width());
+        assert_eq!(
+            Some(8),
+            DataType::Time64(TimeUnit::Nanosecond).primitive_width()
+        );
+    }
+
     #[test]
     fn create_schema_string() {
         let _person = Schema::new(vec![
@@ -966,6 +1545,53 @@ mod tests {
         assert_eq!(_person.to_string(), "first_name: Utf8, last_name: Utf8, address: Struct([Field { name: \"street\", data_type: Utf8, nullable: false }, Field { name: \"zip\", data_type: UInt16, nullable: false }])")
     }
 
+    #[test]
+    fn schema_lookup_by_name() {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Utf8, true),
+        ]);
+        assert_eq!(Some(1), schema.index_of("b"));
+        assert_eq!(None, schema.index_of("c"));
+        assert_eq!(
+            &DataType::Utf8,
+            schema.field_with_name("b").unwrap().data_type()
+        );
+        assert!(schema.field_with_name("c").is_none());
+    }
+
+    #[test]
+    fn schema_try_new_strict() {
+        let fields = vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("a", DataType::Utf8, true),
+        ];
+        assert!(Schema::try_new(fields.clone(), true).is_err());
+        let schema = Schema::try_new(fields.clone(), false).unwrap();
+        assert_eq!(Schema::new(fields), schema);
+        assert_eq!(Some(0), schema.index_of("a"));
+    }
+
+    #[test]
+    fn schema_metadata() {
+        let fields = vec![Field::new("a", DataType::Int32, false)];
+        let mut metadata = HashMap::new();
+        metadata.insert("source".to_string(), "sensor".to_string());
+        let schema = Schema::new_with_metadata(fields.clone(), metadata);
+        assert_eq!("sensor", schema.metadata()["source"]);
+        assert_ne!(Schema::new(fields), schema);
+
+        let json = schema.to_json();
+        assert_eq!(
+            json!([{ "key": "source", "value": "sensor" }]),
+            json["metadata"]
+        );
+        assert!(Schema::empty().to_json().get("metadata").is_none());
+
+        let serialized = serde_json::to_string(&schema).unwrap();
+        assert_eq!(schema, serde_json::from_str(&serialized).unwrap());
+    }
+
     #[test]
     fn schema_field_accessors() {
         let _person = Schema::new(vec![
@@ -0,0 +1,357 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Converts between Arrow types and the flatbuffer tables of the IPC metadata, as
+//! defined in `format/Schema.fbs` and `format/Message.fbs`.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use super::flatbuf::{Table, TableRef, Value};
+use crate::datatypes::*;
+use crate::error::{ArrowError, Result};
+
+/// The version of the metadata, i.e. `MetadataVersion.V4`
+const METADATA_VERSION: i16 = 3;
+
+/// The `MessageHeader` union tag of a `Schema` message
+pub(super) const MESSAGE_SCHEMA: u8 = 1;
+/// The `MessageHeader` union tag of a `RecordBatch` message
+pub(super) const MESSAGE_RECORD_BATCH: u8 = 3;
+
+// the `Type` union tags of the supported types
+const TYPE_INT: u8 = 2;
+const TYPE_FLOATING_POINT: u8 = 3;
+const TYPE_BINARY: u8 = 4;
+const TYPE_UTF8: u8 = 5;
+const TYPE_BOOL: u8 = 6;
+const TYPE_DATE: u8 = 8;
+const TYPE_TIME: u8 = 9;
+const TYPE_TIMESTAMP: u8 = 10;
+const TYPE_LIST: u8 = 12;
+const TYPE_STRUCT: u8 = 13;
+
+/// The name of the child field of a list
+const LIST_ITEM_NAME: &str = "item";
+
+/// The metadata of a `RecordBatch` message
+pub(super) struct RecordBatchMetadata {
+    /// The number of rows of the batch
+    pub length: i64,
+    /// The length and null count of each array, in a depth-first pre-order
+    pub nodes: Vec<(i64, i64)>,
+    /// The offset and length of each buffer within the message body
+    pub buffers: Vec<(i64, i64)>,
+}
+
+/// Encodes a `Message` flatbuffer, whose header is the table `header` of type
+/// `header_type`
+pub(super) fn message_to_fb(
+    header_type: u8,
+    header: Table,
+    body_length: usize,
+) -> Vec<u8> {
+    super::flatbuf::finish(
+        &Table::new()
+            .with(0, Value::Short(METADATA_VERSION))
+            .with(1, Value::Byte(header_type))
+            .with(2, Value::Table(header))
+            .with(3, Value::Long(body_length as i64)),
+    )
+}
+
+/// Decodes a `Message` flatbuffer, returning its header type, header and body length
+pub(super) fn fb_to_message(buf: &[u8]) -> Result<(u8, TableRef<'_>, i64)> {
+    let message = TableRef::root(buf)?;
+    let header = message
+        .get_table(2)?
+        .ok_or_else(|| invalid_metadata("message without header"))?;
+    Ok((message.get_byte(1, 0)?, header, message.get_long(3, 0)?))
+}
+
+pub(super) fn record_batch_to_fb(metadata: RecordBatchMetadata) -> Table {
+    Table::new()
+        .with(0, Value::Long(metadata.length))
+        .with(1, Value::LongPairs(metadata.nodes))
+        .with(2, Value::LongPairs(metadata.buffers))
+}
+
+pub(super) fn fb_to_record_batch(batch: TableRef) -> Result<RecordBatchMetadata> {
+    Ok(RecordBatchMetadata {
+        length: batch.get_long(0, 0)?,
+        nodes: batch.get_long_pairs(1)?,
+        buffers: batch.get_long_pairs(2)?,
+    })
+}
+
+/// Converts `schema` to a `Schema` table, failing if it contains unsupported types
+pub(super) fn schema_to_fb(schema: &Schema) -> Result<Table> {
+    let fields = schema
+        .fields()
+        .iter()
+        .map(field_to_fb)
+        .collect::<Result<_>>()?;
+    // sort the metadata by key, so that equal schemas are encoded the same way
+    let mut metadata: Vec<_> = schema.metadata().iter().collect();
+    metadata.sort();
+    let custom_metadata = metadata
+        .into_iter()
+        .map(|(key, value)| {
+            Table::new()
+                .with(0, Value::String(key.clone()))
+                .with(1, Value::String(value.clone()))
+        })
+        .collect();
+    Ok(Table::new()
+        .with(1, Value::Tables(fields))
+        .with(2, Value::Tables(custom_metadata)))
+}
+
+pub(super) fn fb_to_schema(schema: TableRef) -> Result<Schema> {
+    if schema.get_short(0, 0)? != 0 {
+        return Err(ArrowError::InvalidArgumentError(
+            "Big-endian IPC streams are not supported".to_string(),
+        ));
+    }
+    let fields = schema
+        .get_tables(1)?
+        .into_iter()
+        .map(fb_to_field)
+        .collect::<Result<_>>()?;
+    let mut metadata = HashMap::new();
+    for key_value in schema.get_tables(2)? {
+        metadata.insert(
+            key_value.get_str(0)?.unwrap_or("").to_string(),
+            key_value.get_str(1)?.unwrap_or("").to_string(),
+        );
+    }
+    Ok(Schema::new_with_metadata(fields, metadata))
+}
+
+fn field_to_fb(field: &Field) -> Result<Table> {
+    let mut children = vec![];
+    let (type_type, type_table) = match field.data_type() {
+        DataType::Boolean => (TYPE_BOOL, Table::new()),
+        DataType::Int8 => (TYPE_INT, int_to_fb(8, true)),
+        DataType::Int16 => (TYPE_INT, int_to_fb(16, true)),
+        DataType::Int32 => (TYPE_INT, int_to_fb(32, true)),
+        DataType::Int64 => (TYPE_INT, int_to_fb(64, true)),
+        DataType::UInt8 => (TYPE_INT, int_to_fb(8, false)),
+        DataType::UInt16 => (TYPE_INT, int_to_fb(16, false)),
+        DataType::UInt32 => (TYPE_INT, int_to_fb(32, false)),
+        DataType::UInt64 => (TYPE_INT, int_to_fb(64, false)),
+        DataType::Float32 => (TYPE_FLOATING_POINT, precision_to_fb(1)),
+        DataType::Float64 => (TYPE_FLOATING_POINT, precision_to_fb(2)),
+        DataType::Date32(DateUnit::Day) => (TYPE_DATE, unit_to_fb(0)),
+        DataType::Date64(DateUnit::Millisecond) => (TYPE_DATE, unit_to_fb(1)),
+        DataType::Time32(unit @ TimeUnit::Second)
+        | DataType::Time32(unit @ TimeUnit::Millisecond) => (
+            TYPE_TIME,
+            unit_to_fb(time_unit_to_fb(unit)).with(1, Value::Int(32)),
+        ),
+        DataType::Time64(unit @ TimeUnit::Microsecond)
+        | DataType::Time64(unit @ TimeUnit::Nanosecond) => (
+            TYPE_TIME,
+            unit_to_fb(time_unit_to_fb(unit)).with(1, Value::Int(64)),
+        ),
+        DataType::Timestamp(unit, timezone) => {
+            let mut table = unit_to_fb(time_unit_to_fb(unit));
+            if let Some(timezone) = timezone {
+                table = table.with(1, Value::String(timezone.to_string()));
+            }
+            (TYPE_TIMESTAMP, table)
+        }
+        DataType::Utf8 => (TYPE_UTF8, Table::new()),
+        DataType::List(value_type) => {
+            let item = Field::new(LIST_ITEM_NAME, *value_type.clone(), true);
+            children.push(field_to_fb(&item)?);
+            (TYPE_LIST, Table::new())
+        }
+        DataType::Struct(fields) => {
+            for field in fields {
+                children.push(field_to_fb(field)?);
+            }
+            (TYPE_STRUCT, Table::new())
+        }
+        other => {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "IPC doesn't support data type {:?} yet",
+                other
+            )))
+        }
+    };
+    Ok(Table::new()
+        .with(0, Value::String(field.name().clone()))
+        .with(1, Value::Bool(field.is_nullable()))
+        .with(2, Value::Byte(type_type))
+        .with(3, Value::Table(type_table))
+        .with(5, Value::Tables(children)))
+}
+
+fn int_to_fb(bit_width: i32, is_signed: bool) -> Table {
+    Table::new()
+        .with(0, Value::Int(bit_width))
+        .with(1, Value::Bool(is_signed))
+}
+
+fn precision_to_fb(precision: i16) -> Table {
+    Table::new().with(0, Value::Short(precision))
+}
+
+fn unit_to_fb(unit: i16) -> Table {
+    Table::new().with(0, Value::Short(unit))
+}
+
+fn time_unit_to_fb(unit: &TimeUnit) -> i16 {
+    match unit {
+        TimeUnit::Second => 0,
+        TimeUnit::Millisecond => 1,
+        TimeUnit::Microsecond => 2,
+        TimeUnit::Nanosecond => 3,
+    }
+}
+
+fn fb_to_field(field: TableRef) -> Result<Field> {
+    let name = field.get_str(0)?.unwrap_or("");
+    let type_table = field
+        .get_table(3)?
+        .ok_or_else(|| invalid_metadata("field without type"))?;
+    let children = field.get_tables(5)?;
+    let data_type = match field.get_byte(2, 0)? {
+        TYPE_BOOL => DataType::Boolean,
+        TYPE_INT => match (type_table.get_int(0, 0)?, type_table.get_bool(1, false)?) {
+            (8, true) => DataType::Int8,
+            (16, true) => DataType::Int16,
+            (32, true) => DataType::Int32,
+            (64, true) => DataType::Int64,
+            (8, false) => DataType::UInt8,
+            (16, false) => DataType::UInt16,
+            (32, false) => DataType::UInt32,
+            (64, false) => DataType::UInt64,
+            _ => return Err(invalid_metadata("invalid integer bit width")),
+        },
+        TYPE_FLOATING_POINT => match type_table.get_short(0, 0)? {
+            1 => DataType::Float32,
+            2 => DataType::Float64,
+            _ => return Err(unsupported("half-precision floating point")),
+        },
+        TYPE_DATE => match type_table.get_short(0, 1)? {
+            0 => DataType::Date32(DateUnit::Day),
+            _ => DataType::Date64(DateUnit::Millisecond),
+        },
+        TYPE_TIME => {
+            let unit = fb_to_time_unit(type_table.get_short(0, 1)?)?;
+            match type_table.get_int(1, 32)? {
+                32 => DataType::Time32(unit),
+                _ => DataType::Time64(unit),
+            }
+        }
+        TYPE_TIMESTAMP => DataType::Timestamp(
+            fb_to_time_unit(type_table.get_short(0, 0)?)?,
+            type_table.get_str(1)?.map(|tz| Arc::new(tz.to_string())),
+        ),
+        // `DataType::Utf8` arrays are binary arrays
+        TYPE_UTF8 | TYPE_BINARY => DataType::Utf8,
+        TYPE_LIST => match children.as_slice() {
+            [item] => DataType::List(Box::new(fb_to_field(*item)?.data_type().clone())),
+            _ => return Err(invalid_metadata("list field without a single child")),
+        },
+        TYPE_STRUCT => DataType::Struct(
+            children
+                .into_iter()
+                .map(fb_to_field)
+                .collect::<Result<_>>()?,
+        ),
+        other => return Err(unsupported(&format!("type {}", other))),
+    };
+    Ok(Field::new(name, data_type, field.get_bool(1, false)?))
+}
+
+fn fb_to_time_unit(unit: i16) -> Result<TimeUnit> {
+    match unit {
+        0 => Ok(TimeUnit::Second),
+        1 => Ok(TimeUnit::Millisecond),
+        2 => Ok(TimeUnit::Microsecond),
+        3 => Ok(TimeUnit::Nanosecond),
+        _ => Err(invalid_metadata("invalid time unit")),
+    }
+}
+
+fn invalid_metadata(message: &str) -> ArrowError {
+    ArrowError::ParseError(format!("Invalid IPC metadata: {}", message))
+}
+
+fn unsupported(what: &str) -> ArrowError {
+    ArrowError::InvalidArgumentError(format!("IPC doesn't support {} yet", what))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schema_round_trip() {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int16, false),
+            Field::new("b", DataType::UInt64, true),
+            Field::new("c", DataType::Float64, true),
+            Field::new("d", DataType::Boolean, true),
+            Field::new("e", DataType::Utf8, false),
+            Field::new("f", DataType::List(Box::new(DataType::Int32)), true),
+            Field::new(
+                "g",
+                DataType::Struct(vec![
+                    Field::new("x", DataType::Date32(DateUnit::Day), true),
+                    Field::new("y", DataType::Time64(TimeUnit::Nanosecond), false),
+                ]),
+                true,
+            ),
+            Field::new(
+                "h",
+                DataType::Timestamp(
+                    TimeUnit::Microsecond,
+                    Some(Arc::new("Europe/Paris".to_string())),
+                ),
+                true,
+            ),
+        ]);
+        let buf = super::super::flatbuf::finish(&schema_to_fb(&schema).unwrap());
+        let decoded = fb_to_schema(TableRef::root(&buf).unwrap()).unwrap();
+        assert_eq!(schema, decoded);
+    }
+
+    #[test]
+    fn test_schema_metadata_round_trip() {
+        let mut metadata = HashMap::new();
+        metadata.insert("origin".to_string(), "sensors".to_string());
+        metadata.insert("version".to_string(), "".to_string());
+        let schema = Schema::new_with_metadata(
+            vec![Field::new("a", DataType::Int32, false)],
+            metadata,
+        );
+        let buf = super::super::flatbuf::finish(&schema_to_fb(&schema).unwrap());
+        let decoded = fb_to_schema(TableRef::root(&buf).unwrap()).unwrap();
+        assert_eq!(schema.metadata(), decoded.metadata());
+        assert_eq!(schema, decoded);
+    }
+
+    #[test]
+    fn test_unsupported_type() {
+        let schema = Schema::new(vec![Field::new("a", DataType::Float16, false)]);
+        assert!(schema_to_fb(&schema).is_err());
+    }
+}
@@ -0,0 +1,403 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! A minimal FlatBuffers encoder and decoder, supporting the subset of the format used
+//! by the `Schema` and `Message` tables of the IPC metadata.
+//!
+//! Tables are encoded front to back: each table is preceded by its vtable and followed
+//! by the objects it references, so that all offsets point forward as the format
+//! requires. Scalars are aligned to their size relative to the start of the buffer.
+
+use std::convert::TryInto;
+
+use crate::error::{ArrowError, Result};
+use crate::util::bit_util;
+
+/// The value of a table field
+pub(super) enum Value {
+    Bool(bool),
+    Byte(u8),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    String(String),
+    Table(Table),
+    Tables(Vec<Table>),
+    /// A vector of structs made of two `long` fields, like `FieldNode` and `Buffer`
+    LongPairs(Vec<(i64, i64)>),
+}
+
+impl Value {
+    /// Returns the number of bytes the value takes within its table
+    fn inline_size(&self) -> usize {
+        match self {
+            Value::Bool(_) | Value::Byte(_) => 1,
+            Value::Short(_) => 2,
+            Value::Long(_) => 8,
+            _ => 4,
+        }
+    }
+}
+
+/// A table to encode, as a list of fields identified by their slot in the schema
+#[derive(Default)]
+pub(super) struct Table {
+    fields: Vec<(u16, Value)>,
+}
+
+impl Table {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the field at `slot` to `value`
+    pub fn with(mut self, slot: u16, value: Value) -> Self {
+        self.fields.push((slot, value));
+        self
+    }
+}
+
+/// Encodes `root` as a flatbuffer
+pub(super) fn finish(root: &Table) -> Vec<u8> {
+    let mut buf = vec![0; 4];
+    let pos = write_table(&mut buf, root);
+    patch_offset(&mut buf, 0, pos);
+    buf
+}
+
+fn pad_to(buf: &mut Vec<u8>, alignment: usize) {
+    let len = bit_util::ceil(buf.len(), alignment) * alignment;
+    buf.resize(len, 0);
+}
+
+/// Writes the offset from position `at` to position `target` at position `at`
+fn patch_offset(buf: &mut [u8], at: usize, target: usize) {
+    let offset = (target - at) as u32;
+    buf[at..at + 4].copy_from_slice(&offset.to_le_bytes());
+}
+
+fn write_table(buf: &mut Vec<u8>, table: &Table) -> usize {
+    // lay out the fields after the vtable offset, largest first to limit padding
+    let mut fields: Vec<&(u16, Value)> = table.fields.iter().collect();
+    fields.sort_by_key(|(_, value)| std::cmp::Reverse(value.inline_size()));
+    let mut positions = Vec::with_capacity(fields.len());
+    let mut table_size = 4;
+    for (_, value) in &fields {
+        let size = value.inline_size();
+        table_size = bit_util::ceil(table_size, size) * size;
+        positions.push(table_size);
+        table_size += size;
+    }
+
+    let num_slots = fields.iter().map(|(slot, _)| *slot as usize + 1).max();
+    let mut vtable = vec![0u16; 2 + num_slots.unwrap_or(0)];
+    vtable[0] = (vtable.len() * 2) as u16;
+    vtable[1] = table_size as u16;
+    for ((slot, _), position) in fields.iter().zip(&positions) {
+        vtable[2 + *slot as usize] = *position as u16;
+    }
+    pad_to(buf, 2);
+    let vtable_pos = buf.len();
+    for entry in vtable {
+        buf.extend_from_slice(&entry.to_le_bytes());
+    }
+
+    // tables are aligned to the largest scalar, i.e. a `long`
+    pad_to(buf, 8);
+    let table_pos = buf.len();
+    buf.resize(table_pos + table_size, 0);
+    let vtable_offset = (table_pos - vtable_pos) as i32;
+    buf[table_pos..table_pos + 4].copy_from_slice(&vtable_offset.to_le_bytes());
+    let mut children = vec![];
+    for ((_, value), position) in fields.iter().zip(&positions) {
+        let pos = table_pos + position;
+        match value {
+            Value::Bool(v) => buf[pos] = *v as u8,
+            Value::Byte(v) => buf[pos] = *v,
+            Value::Short(v) => buf[pos..pos + 2].copy_from_slice(&v.to_le_bytes()),
+            Value::Int(v) => buf[pos..pos + 4].copy_from_slice(&v.to_le_bytes()),
+            Value::Long(v) => buf[pos..pos + 8].copy_from_slice(&v.to_le_bytes()),
+            _ => children.push((pos, value)),
+        }
+    }
+    for (pos, value) in children {
+        let child_pos = write_child(buf, value);
+        patch_offset(buf, pos, child_pos);
+    }
+    table_pos
+}
+
+/// Writes an object referenced by a table, returning its position
+fn write_child(buf: &mut Vec<u8>, value: &Value) -> usize {
+    pad_to(buf, 4);
+    let pos = buf.len();
+    match value {
+        Value::String(s) => {
+            buf.extend_from_slice(&(s.len() as u32).to_le_bytes());
+            buf.extend_from_slice(s.as_bytes());
+            buf.push(0);
+        }
+        Value::Table(table) => return write_table(buf, table),
+        Value::Tables(tables) => {
+            buf.extend_from_slice(&(tables.len() as u32).to_le_bytes());
+            buf.resize(pos + 4 + 4 * tables.len(), 0);
+            for (i, table) in tables.iter().enumerate() {
+                let table_pos = write_table(buf, table);
+                patch_offset(buf, pos + 4 + 4 * i, table_pos);
+            }
+        }
+        Value::LongPairs(pairs) => {
+            // the elements follow the length and must be aligned to 8 bytes
+            if pos & 7 == 0 {
+                buf.extend_from_slice(&[0; 4]);
+            }
+            let pos = buf.len();
+            buf.extend_from_slice(&(pairs.len() as u32).to_le_bytes());
+            for (a, b) in pairs {
+                buf.extend_from_slice(&a.to_le_bytes());
+                buf.extend_from_slice(&b.to_le_bytes());
+            }
+            return pos;
+        }
+        _ => unreachable!("scalars are written inline"),
+    }
+    pos
+}
+
+fn invalid(message: &str) -> ArrowError {
+    ArrowError::ParseError(format!("Invalid flatbuffer: {}", message))
+}
+
+fn read_bytes(buf: &[u8], pos: usize, len: usize) -> Result<&[u8]> {
+    pos.checked_add(len)
+        .and_then(|end| buf.get(pos..end))
+        .ok_or_else(|| invalid("position out of bounds"))
+}
+
+fn read_u16(buf: &[u8], pos: usize) -> Result<u16> {
+    Ok(u16::from_le_bytes(
+        read_bytes(buf, pos, 2)?.try_into().unwrap(),
+    ))
+}
+
+fn read_u32(buf: &[u8], pos: usize) -> Result<u32> {
+    Ok(u32::from_le_bytes(
+        read_bytes(buf, pos, 4)?.try_into().unwrap(),
+    ))
+}
+
+fn read_i64(buf: &[u8], pos: usize) -> Result<i64> {
+    Ok(i64::from_le_bytes(
+        read_bytes(buf, pos, 8)?.try_into().unwrap(),
+    ))
+}
+
+/// Follows the offset stored at `pos`
+fn deref(buf: &[u8], pos: usize) -> Result<usize> {
+    Ok(pos + read_u32(buf, pos)? as usize)
+}
+
+/// A table of an encoded flatbuffer
+#[derive(Clone, Copy)]
+pub(super) struct TableRef<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> TableRef<'a> {
+    /// Returns the root table of the flatbuffer `buf`
+    pub fn root(buf: &'a [u8]) -> Result<Self> {
+        Ok(Self {
+            buf,
+            pos: deref(buf, 0)?,
+        })
+    }
+
+    /// Returns the position of the field at `slot`, or `None` if it isn't set
+    fn field_pos(&self, slot: u16) -> Result<Option<usize>> {
+        let vtable_offset = i64::from(read_u32(self.buf, self.pos)? as i32);
+        let vtable = self.pos as i64 - vtable_offset;
+        if vtable < 0 {
+            return Err(invalid("vtable out of bounds"));
+        }
+        let vtable = vtable as usize;
+        let entry = 4 + 2 * slot as usize;
+        if entry + 2 > read_u16(self.buf, vtable)? as usize {
+            return Ok(None);
+        }
+        match read_u16(self.buf, vtable + entry)? {
+            0 => Ok(None),
+            offset => Ok(Some(self.pos + offset as usize)),
+        }
+    }
+
+    fn get_scalar(&self, slot: u16, len: usize) -> Result<Option<&'a [u8]>> {
+        match self.field_pos(slot)? {
+            Some(pos) => Ok(Some(read_bytes(self.buf, pos, len)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn get_bool(&self, slot: u16, default: bool) -> Result<bool> {
+        Ok(self.get_scalar(slot, 1)?.map_or(default, |b| b[0] != 0))
+    }
+
+    pub fn get_byte(&self, slot: u16, default: u8) -> Result<u8> {
+        Ok(self.get_scalar(slot, 1)?.map_or(default, |b| b[0]))
+    }
+
+    pub fn get_short(&self, slot: u16, default: i16) -> Result<i16> {
+        Ok(self
+            .get_scalar(slot, 2)?
+            .map_or(default, |b| i16::from_le_bytes(b.try_into().unwrap())))
+    }
+
+    pub fn get_int(&self, slot: u16, default: i32) -> Result<i32> {
+        Ok(self
+            .get_scalar(slot, 4)?
+            .map_or(default, |b| i32::from_le_bytes(b.try_into().unwrap())))
+    }
+
+    pub fn get_long(&self, slot: u16, default: i64) -> Result<i64> {
+        Ok(self
+            .get_scalar(slot, 8)?
+            .map_or(default, |b| i64::from_le_bytes(b.try_into().unwrap())))
+    }
+
+    /// Returns the position of the object referenced by the field at `slot`
+    fn get_child(&self, slot: u16) -> Result<Option<usize>> {
+        match self.field_pos(slot)? {
+            Some(pos) => Ok(Some(deref(self.buf, pos)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn get_str(&self, slot: u16) -> Result<Option<&'a str>> {
+        match self.get_child(slot)? {
+            Some(pos) => {
+                let len = read_u32(self.buf, pos)? as usize;
+                let bytes = read_bytes(self.buf, pos + 4, len)?;
+                std::str::from_utf8(bytes)
+                    .map(Some)
+                    .map_err(|_| invalid("string is not valid UTF-8"))
+            }
+            None => Ok(None),
+        }
+    }
+
+    pub fn get_table(&self, slot: u16) -> Result<Option<TableRef<'a>>> {
+        Ok(self.get_child(slot)?.map(|pos| Self { buf: self.buf, pos }))
+    }
+
+    /// Returns the tables of the vector at `slot`, which are empty if it isn't set
+    pub fn get_tables(&self, slot: u16) -> Result<Vec<TableRef<'a>>> {
+        match self.get_child(slot)? {
+            Some(pos) => {
+                let len = read_u32(self.buf, pos)? as usize;
+                (0..len)
+                    .map(|i| {
+                        Ok(Self {
+                            buf: self.buf,
+                            pos: deref(self.buf, pos + 4 + 4 * i)?,
+                        })
+                    })
+                    .collect()
+            }
+            None => Ok(vec![]),
+        }
+    }
+
+    /// Returns the elements of the vector of `long` pairs at `slot`, which are empty if
+    /// it isn't set
+    pub fn get_long_pairs(&self, slot: u16) -> Result<Vec<(i64, i64)>> {
+        match self.get_child(slot)? {
+            Some(pos) => {
+                let len = read_u32(self.buf, pos)? as usize;
+                (0..len)
+                    .map(|i| {
+                        let element = pos + 4 + 16 * i;
+                        Ok((
+                            read_i64(self.buf, element)?,
+                            read_i64(self.buf, element + 8)?,
+                        ))
+                    })
+                    .collect()
+            }
+            None => Ok(vec![]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let child = |name: &str| Table::new().with(0, Value::String(name.to_string()));
+        let buf = finish(
+            &Table::new()
+                .with(0, Value::Short(3))
+                .with(2, Value::Bool(true))
+                .with(3, Value::Long(-42))
+                .with(4, Value::Table(child("nested")))
+                .with(5, Value::Tables(vec![child("a"), child("bc")]))
+                .with(6, Value::LongPairs(vec![(1, 2), (3, -4)]))
+                .with(7, Value::Byte(9)),
+        );
+        let root = TableRef::root(&buf).unwrap();
+        assert_eq!(3, root.get_short(0, 0).unwrap());
+        assert_eq!(7, root.get_int(1, 7).unwrap());
+        assert!(root.get_bool(2, false).unwrap());
+        assert_eq!(-42, root.get_long(3, 0).unwrap());
+        let nested = root.get_table(4).unwrap().unwrap();
+        assert_eq!(Some("nested"), nested.get_str(0).unwrap());
+        let names: Vec<_> = root
+            .get_tables(5)
+            .unwrap()
+            .iter()
+            .map(|t| t.get_str(0).unwrap().unwrap())
+            .collect();
+        assert_eq!(vec!["a", "bc"], names);
+        assert_eq!(vec![(1, 2), (3, -4)], root.get_long_pairs(6).unwrap());
+        assert_eq!(9, root.get_byte(7, 0).unwrap());
+        assert!(root.get_table(8).unwrap().is_none());
+        assert!(root.get_tables(9).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_alignment() {
+        let buf = finish(
+            &Table::new()
+                .with(0, Value::Byte(1))
+                .with(1, Value::Long(2))
+                .with(2, Value::LongPairs(vec![(3, 4)])),
+        );
+        let root = TableRef::root(&buf).unwrap();
+        assert_eq!(0, root.field_pos(1).unwrap().unwrap() % 8);
+        assert_eq!(0, (root.get_child(2).unwrap().unwrap() + 4) % 8);
+    }
+
+    #[test]
+    fn test_out_of_bounds() {
+        let mut buf = finish(&Table::new().with(0, Value::String("abc".to_string())));
+        buf.truncate(buf.len() - 3);
+        let root = TableRef::root(&buf).unwrap();
+        assert!(root.get_str(0).is_err());
+        assert!(TableRef::root(&[1, 0]).is_err());
+    }
+}
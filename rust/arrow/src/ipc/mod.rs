@@ -0,0 +1,42 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Transfer data between the Arrow memory format and the Arrow IPC stream format, the
+//! format used to exchange record batches with other Arrow implementations.
+//!
+//! A stream is a sequence of encapsulated messages: a `Schema` message followed by
+//! `RecordBatch` messages, and an optional end-of-stream marker. Each message is made
+//! of its length as an `int32`, its metadata as a flatbuffer padded to 8 bytes, and a
+//! body holding the buffers of the record batch.
+
+use crate::util::bit_util;
+
+mod convert;
+mod flatbuf;
+pub mod reader;
+pub mod writer;
+
+pub use self::reader::StreamReader;
+pub use self::writer::StreamWriter;
+
+/// The alignment of messages and of the buffers within their body
+const ALIGNMENT: usize = 8;
+
+/// Returns `len` rounded up to a multiple of `ALIGNMENT`
+fn pad_len(len: usize) -> usize {
+    bit_util::ceil(len, ALIGNMENT) * ALIGNMENT
+}
@@ -0,0 +1,593 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Reads record batches in the Arrow IPC stream format.
+
+use std::io::{ErrorKind, Read};
+use std::mem;
+use std::sync::Arc;
+
+use super::convert::{self, RecordBatchMetadata};
+use crate::array::{make_array, ArrayRef};
+use crate::array_data::{ArrayData, ArrayDataRef};
+use crate::buffer::Buffer;
+use crate::datatypes::{DataType, Schema};
+use crate::error::{ArrowError, Result};
+use crate::record_batch::RecordBatch;
+use crate::util::bit_util;
+
+/// Reads record batches from a `Read` in the IPC stream format, starting with its
+/// `Schema` message
+pub struct StreamReader<R: Read> {
+    reader: R,
+    schema: Arc<Schema>,
+    finished: bool,
+}
+
+impl<R: Read> StreamReader<R> {
+    /// Creates a reader over `reader`, reading the schema message of the stream
+    pub fn try_new(mut reader: R) -> Result<Self> {
+        let (metadata, _) = read_message(&mut reader)?
+            .ok_or_else(|| invalid_stream("stream doesn't start with a schema"))?;
+        let (header_type, header, _) = convert::fb_to_message(&metadata)?;
+        if header_type != convert::MESSAGE_SCHEMA {
+            return Err(invalid_stream("stream doesn't start with a schema"));
+        }
+        let schema = convert::fb_to_schema(header)?;
+        Ok(Self {
+            reader,
+            schema: Arc::new(schema),
+            finished: false,
+        })
+    }
+
+    /// Returns the schema of the stream
+    pub fn schema(&self) -> Arc<Schema> {
+        self.schema.clone()
+    }
+
+    /// Reads the next record batch, or returns `None` at the end of the stream
+    pub fn next(&mut self) -> Result<Option<RecordBatch>> {
+        if self.finished {
+            return Ok(None);
+        }
+        let (metadata, body) = match read_message(&mut self.reader)? {
+            Some(message) => message,
+            None => {
+                self.finished = true;
+                return Ok(None);
+            }
+        };
+        let (header_type, header, _) = convert::fb_to_message(&metadata)?;
+        if header_type != convert::MESSAGE_RECORD_BATCH {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "IPC doesn't support messages of type {} yet",
+                header_type
+            )));
+        }
+        let batch = convert::fb_to_record_batch(header)?;
+        let mut decoder = BodyReader {
+            metadata: &batch,
+            body: &body,
+            next_node: 0,
+            next_buffer: 0,
+        };
+        let columns = self
+            .schema
+            .fields()
+            .iter()
+            .map(|field| Ok(make_array(decoder.read_array(field.data_type())?)))
+            .collect::<Result<Vec<ArrayRef>>>()?;
+        if columns.iter().any(|c| c.len() as i64 != batch.length) {
+            return Err(invalid_stream(
+                "array length doesn't match the batch length",
+            ));
+        }
+        RecordBatch::try_new(self.schema.clone(), columns).map(Some)
+    }
+}
+
+/// The marker preceding the length of a message since version 0.15 of the format
+const CONTINUATION_MARKER: i32 = -1;
+
+/// Reads a message, returning its metadata and body, or `None` at the end of the stream
+fn read_message<R: Read>(reader: &mut R) -> Result<Option<(Vec<u8>, Vec<u8>)>> {
+    let mut prefix = [0; 4];
+    match reader.read_exact(&mut prefix) {
+        Ok(()) => {}
+        Err(ref e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e.into()),
+    }
+    let mut metadata_len = i32::from_le_bytes(prefix);
+    if metadata_len == CONTINUATION_MARKER {
+        reader.read_exact(&mut prefix)?;
+        metadata_len = i32::from_le_bytes(prefix);
+    }
+    if metadata_len == 0 {
+        return Ok(None);
+    }
+    if metadata_len < 0 {
+        return Err(invalid_stream("negative message length"));
+    }
+    let metadata = read_exact_len(reader, metadata_len as u64)?;
+    let (_, _, body_len) = convert::fb_to_message(&metadata)?;
+    if body_len < 0 {
+        return Err(invalid_stream("negative body length"));
+    }
+    let body = read_exact_len(reader, body_len as u64)?;
+    Ok(Some((metadata, body)))
+}
+
+/// Reads exactly `len` bytes from `reader`.
+///
+/// The lengths come from the stream itself, so rather than allocating them upfront the
+/// buffer only grows with the bytes actually read, and a truncated stream is an error.
+fn read_exact_len<R: Read>(reader: &mut R, len: u64) -> Result<Vec<u8>> {
+    let mut buf = vec![];
+    reader.take(len).read_to_end(&mut buf)?;
+    if buf.len() as u64 != len {
+        return Err(invalid_stream("unexpected end of stream"));
+    }
+    Ok(buf)
+}
+
+/// Reads the arrays of a record batch from its field nodes and buffers
+struct BodyReader<'a> {
+    metadata: &'a RecordBatchMetadata,
+    body: &'a [u8],
+    next_node: usize,
+    next_buffer: usize,
+}
+
+impl<'a> BodyReader<'a> {
+    fn next_node(&mut self) -> Result<(usize, usize)> {
+        let (len, null_count) = *self
+            .metadata
+            .nodes
+            .get(self.next_node)
+            .ok_or_else(|| invalid_stream("missing field node"))?;
+        self.next_node += 1;
+        if len < 0 || null_count < 0 || null_count > len {
+            return Err(invalid_stream("invalid field node"));
+        }
+        Ok((len as usize, null_count as usize))
+    }
+
+    fn next_buffer(&mut self) -> Result<Buffer> {
+        let (offset, len) = *self
+            .metadata
+            .buffers
+            .get(self.next_buffer)
+            .ok_or_else(|| invalid_stream("missing buffer"))?;
+        self.next_buffer += 1;
+        let end = match offset.checked_add(len) {
+            Some(end)
+                if offset >= 0 && len >= 0 && end as u64 <= self.body.len() as u64 =>
+            {
+                end as usize
+            }
+            _ => return Err(invalid_stream("buffer out of bounds")),
+        };
+        Ok(Buffer::from(&self.body[offset as usize..end]))
+    }
+
+    /// Reads the next buffer, checking that it holds at least `min_len` bytes
+    fn next_buffer_of(&mut self, min_len: Option<usize>) -> Result<Buffer> {
+        let buffer = self.next_buffer()?;
+        match min_len {
+            Some(min_len) if buffer.len() >= min_len => Ok(buffer),
+            _ => Err(invalid_stream("buffer too short for its array")),
+        }
+    }
+
+    /// Reads the next offsets buffer of an array of `len` elements, checking that it
+    /// holds `len + 1` non-negative and non-decreasing offsets, and returns it along with
+    /// its last offset
+    fn next_offsets(&mut self, len: usize) -> Result<(Buffer, usize)> {
+        let min_len = len
+            .checked_add(1)
+            .and_then(|n| n.checked_mul(mem::size_of::<i32>()));
+        let buffer = self.next_buffer_of(min_len)?;
+        let offsets = &buffer.typed_data::<i32>()[..=len];
+        if offsets[0] < 0 || offsets.windows(2).any(|w| w[0] > w[1]) {
+            return Err(invalid_stream("invalid offsets"));
+        }
+        let last = offsets[len] as usize;
+        Ok((buffer, last))
+    }
+
+    fn read_array(&mut self, data_type: &DataType) -> Result<ArrayDataRef> {
+        let (len, null_count) = self.next_node()?;
+        let validity = self.next_buffer()?;
+        let mut builder = ArrayData::builder(data_type.clone())
+            .len(len)
+            .null_count(null_count);
+        if null_count > 0 {
+            if validity.len() < bit_util::ceil(len, 8) {
+                return Err(invalid_stream("validity buffer too short for its array"));
+            }
+            builder = builder.null_bit_buffer(validity);
+        }
+        builder = match data_type {
+            DataType::Boolean => {
+                builder.add_buffer(self.next_buffer_of(Some(bit_util::ceil(len, 8)))?)
+            }
+            DataType::Utf8 => {
+                let (offsets, last) = self.next_offsets(len)?;
+                let values = self.next_buffer_of(Some(last))?;
+                builder.add_buffer(offsets).add_buffer(values)
+            }
+            DataType::List(value_type) => {
+                let (offsets, last) = self.next_offsets(len)?;
+                let values = self.read_array(value_type)?;
+                if offsets.typed_data::<i32>()[0] != 0 || values.len() < last {
+                    return Err(invalid_stream("list offsets out of bounds of values"));
+                }
+                builder
+                    .add_buffer(offsets)
+                    .add_child_data(values.slice(0, last))
+            }
+            DataType::Struct(fields) => {
+                let mut children = Vec::with_capacity(fields.len());
+                for field in fields {
+                    let child = self.read_array(field.data_type())?;
+                    if child.len() < len {
+                        return Err(invalid_stream(
+                            "struct child shorter than its parent",
+                        ));
+                    }
+                    children.push(child.slice(0, len));
+                }
+                builder.child_data(children)
+            }
+            other => {
+                let min_len = other.primitive_width().and_then(|w| w.checked_mul(len));
+                builder.add_buffer(self.next_buffer_of(min_len)?)
+            }
+        };
+        let data = builder.build();
+        data.validate().map_err(|_| {
+            invalid_stream("null count doesn't match the validity buffer")
+        })?;
+        Ok(data)
+    }
+}
+
+fn invalid_stream(message: &str) -> ArrowError {
+    ArrowError::ParseError(format!("Invalid IPC stream: {}", message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::array::*;
+    use crate::builder::{BinaryBuilder, Int32Builder, ListBuilder};
+    use crate::datatypes::*;
+    use crate::ipc::StreamWriter;
+    use std::fs::File;
+
+    fn round_trip(schema: Schema, batches: &[Vec<ArrayRef>]) {
+        let schema = Arc::new(schema);
+        let batches: Vec<RecordBatch> = batches
+            .iter()
+            .map(|columns| RecordBatch::try_new(schema.clone(), columns.clone()).unwrap())
+            .collect();
+        let mut stream = vec![];
+        {
+            let mut writer = StreamWriter::try_new(&mut stream, &schema).unwrap();
+            for batch in &batches {
+                writer.write(batch).unwrap();
+            }
+            writer.finish().unwrap();
+        }
+
+        let mut reader = StreamReader::try_new(stream.as_slice()).unwrap();
+        assert_eq!(schema, reader.schema());
+        for batch in &batches {
+            let read = reader.next().unwrap().unwrap();
+            assert_eq!(batch.num_rows(), read.num_rows());
+            for (expected, actual) in batch.columns().iter().zip(read.columns()) {
+                assert!(expected.equals(actual.as_ref()));
+            }
+        }
+        assert!(reader.next().unwrap().is_none());
+        assert!(reader.next().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_round_trip_primitive_and_boolean() {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Float64, false),
+            Field::new("c", DataType::Boolean, true),
+            Field::new("d", DataType::UInt8, false),
+        ]);
+        let batch = |offset: i32| -> Vec<ArrayRef> {
+            vec![
+                Arc::new(Int32Array::from(vec![Some(offset), None, Some(offset + 2)])),
+                Arc::new(Float64Array::from(vec![1.5, -2.25, 1e10])),
+                Arc::new(BooleanArray::from(vec![Some(true), Some(false), None])),
+                Arc::new(UInt8Array::from(vec![0, 255, 7])),
+            ]
+        };
+        round_trip(schema, &[batch(0), batch(10)]);
+    }
+
+    #[test]
+    fn test_round_trip_utf8_and_list() {
+        let schema = Schema::new(vec![
+            Field::new("s", DataType::Utf8, true),
+            Field::new("l", DataType::List(Box::new(DataType::Int32)), true),
+        ]);
+        let mut strings = BinaryBuilder::new(3);
+        strings.append_string("hello").unwrap();
+        strings.append_null().unwrap();
+        strings.append_string("arrow ipc").unwrap();
+        let mut lists = ListBuilder::new(Int32Builder::new(5));
+        lists.values().append_slice(&[1, 2]).unwrap();
+        lists.append(true).unwrap();
+        lists.append(false).unwrap();
+        lists.values().append_null().unwrap();
+        lists.values().append_value(3).unwrap();
+        lists.append(true).unwrap();
+        round_trip(
+            schema,
+            &[vec![Arc::new(strings.finish()), Arc::new(lists.finish())]],
+        );
+    }
+
+    #[test]
+    fn test_round_trip_struct_and_empty() {
+        let fields = vec![
+            Field::new("x", DataType::Int64, false),
+            Field::new("y", DataType::Utf8, false),
+        ];
+        let schema = Schema::new(vec![Field::new(
+            "s",
+            DataType::Struct(fields.clone()),
+            false,
+        )]);
+        let batch = |values: Vec<i64>, names: Vec<&str>| -> Vec<ArrayRef> {
+            vec![Arc::new(StructArray::from(vec![
+                (
+                    fields[0].clone(),
+                    Arc::new(Int64Array::from(values)) as ArrayRef,
+                ),
+                (
+                    fields[1].clone(),
+                    Arc::new(BinaryArray::from(names)) as ArrayRef,
+                ),
+            ]))]
+        };
+        round_trip(
+            schema,
+            &[batch(vec![1, 2], vec!["a", "b"]), batch(vec![], vec![])],
+        );
+    }
+
+    #[test]
+    fn test_round_trip_sliced() {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Boolean, true),
+            Field::new("c", DataType::Utf8, true),
+        ]);
+        let slice = |array: ArrayRef| -> ArrayRef {
+            let data = array.data();
+            make_array(
+                ArrayData::builder(data.data_type().clone())
+                    .len(3)
+                    .offset(2)
+                    .null_bit_buffer(data.null_bitmap().as_ref().unwrap().bits.clone())
+                    .buffers(data.buffers().to_vec())
+                    .build(),
+            )
+        };
+        let columns = vec![
+            slice(Arc::new(Int32Array::from(vec![
+                Some(1),
+                None,
+                Some(3),
+                None,
+                Some(5),
+            ]))),
+            slice(Arc::new(BooleanArray::from(vec![
+                Some(true),
+                None,
+                Some(false),
+                Some(true),
+                None,
+            ]))),
+            slice(Arc::new(BinaryArray::from(vec![
+                Some("a"),
+                None,
+                Some("bc"),
+                Some("def"),
+                None,
+            ]))),
+        ];
+        round_trip(schema, &[columns]);
+    }
+
+    #[test]
+    fn test_round_trip_sliced_struct() {
+        let fields = vec![
+            Field::new("x", DataType::Int32, true),
+            Field::new("y", DataType::Utf8, false),
+        ];
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "s",
+            DataType::Struct(fields.clone()),
+            false,
+        )]));
+        let array: ArrayRef = Arc::new(StructArray::from(vec![
+            (
+                fields[0].clone(),
+                Arc::new(Int32Array::from(vec![Some(1), None, Some(3), Some(4)]))
+                    as ArrayRef,
+            ),
+            (
+                fields[1].clone(),
+                Arc::new(BinaryArray::from(vec!["a", "b", "c", "d"])) as ArrayRef,
+            ),
+        ]));
+        let batch = RecordBatch::try_new(schema.clone(), vec![array]).unwrap();
+        let sliced = batch.slice(1, 2);
+        round_trip(
+            Schema::new(schema.fields().clone()),
+            &[sliced.columns().to_vec()],
+        );
+
+        let expected = StructArray::from(vec![
+            (
+                fields[0].clone(),
+                Arc::new(Int32Array::from(vec![None, Some(3)])) as ArrayRef,
+            ),
+            (
+                fields[1].clone(),
+                Arc::new(BinaryArray::from(vec!["b", "c"])) as ArrayRef,
+            ),
+        ]);
+        assert!(sliced.column(0).equals(&expected));
+    }
+
+    #[test]
+    fn test_read_stream_of_other_implementation() {
+        // written by the `StreamWriter` of the arrow-rs 54.3.1 crates with default
+        // options, i.e. with continuation markers and version 5 metadata
+        let file = File::open("test/data/arrow_rs_54.stream").unwrap();
+        let mut reader = StreamReader::try_new(file).unwrap();
+        let struct_fields = vec![
+            Field::new("x", DataType::Int16, false),
+            Field::new("y", DataType::Utf8, true),
+        ];
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Float64, false),
+            Field::new("c", DataType::Boolean, true),
+            Field::new("d", DataType::Utf8, true),
+            Field::new("e", DataType::List(Box::new(DataType::Int32)), true),
+            Field::new("f", DataType::Struct(struct_fields), true),
+            Field::new("g", DataType::Date32(DateUnit::Day), true),
+            Field::new(
+                "h",
+                DataType::Timestamp(
+                    TimeUnit::Millisecond,
+                    Some(Arc::new("UTC".to_string())),
+                ),
+                false,
+            ),
+        ]);
+        assert_eq!(Arc::new(schema), reader.schema());
+
+        for base in &[0, 10] {
+            let batch = reader.next().unwrap().unwrap();
+            assert_eq!(3, batch.num_rows());
+            let expected: Vec<ArrayRef> = vec![
+                Arc::new(Int32Array::from(vec![Some(*base), None, Some(base + 2)])),
+                Arc::new(Float64Array::from(vec![1.5, -2.25, 1e10])),
+                Arc::new(BooleanArray::from(vec![Some(true), Some(false), None])),
+                Arc::new(BinaryArray::from(vec![Some("arrow"), None, Some("")])),
+            ];
+            for (i, column) in expected.iter().enumerate() {
+                assert!(column.equals(batch.column(i).as_ref()), "column {}", i);
+            }
+
+            let lists = as_list_array(batch.column(4));
+            assert_eq!(1, lists.null_count());
+            assert!(lists.is_null(1));
+            assert_eq!(&[0, 2, 2, 3], lists.value_offsets());
+            let values = lists.values();
+            let values = as_primitive_array::<Int32Type>(&values);
+            assert_eq!(&[*base, base + 1], values.value_slice(0, 2));
+            assert!(values.is_null(2));
+
+            let structs = as_struct_array(batch.column(5));
+            assert!(structs.is_null(2));
+            let xs = as_primitive_array::<Int16Type>(structs.column(0));
+            assert_eq!(&[1, 2, 3], xs.value_slice(0, 3));
+            let ys = as_string_array(structs.column(1));
+            assert_eq!(
+                vec![Some("p"), None, Some("q")],
+                ys.string_iter().collect::<Vec<_>>()
+            );
+
+            let dates = batch.column(6);
+            assert_eq!("2019-04-14", dates.value_to_string(0));
+            assert!(dates.is_null(1));
+            assert_eq!("1969-12-31", dates.value_to_string(2));
+            let timestamps =
+                as_primitive_array::<TimestampMillisecondType>(batch.column(7));
+            assert_eq!(&[0, 1_500_000_000_000, -1], timestamps.value_slice(0, 3));
+        }
+        assert!(reader.next().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_read_corrupt_stream() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("s", DataType::Utf8, true),
+            Field::new("l", DataType::List(Box::new(DataType::Int32)), true),
+        ]));
+        let mut lists = ListBuilder::new(Int32Builder::new(5));
+        lists.values().append_slice(&[1, 2]).unwrap();
+        lists.append(true).unwrap();
+        lists.append(false).unwrap();
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(BinaryArray::from(vec![Some("hello"), None])),
+            Arc::new(lists.finish()),
+        ];
+        let batch = RecordBatch::try_new(schema.clone(), columns).unwrap();
+        let mut stream = vec![];
+        {
+            let mut writer = StreamWriter::try_new(&mut stream, &schema).unwrap();
+            writer.write(&batch).unwrap();
+            writer.finish().unwrap();
+        }
+
+        let read_all = |stream: &[u8]| -> Result<()> {
+            let mut reader = StreamReader::try_new(stream)?;
+            while let Some(batch) = reader.next()? {
+                for column in batch.columns() {
+                    for i in 0..column.len() {
+                        column.value_to_string(i);
+                    }
+                }
+            }
+            Ok(())
+        };
+        assert!(read_all(&stream).is_ok());
+        // truncated streams and corrupted bytes are errors, never out of bounds reads
+        for len in 0..stream.len() {
+            let _ = read_all(&stream[..len]);
+        }
+        for i in 0..stream.len() {
+            for byte in &[0x00, 0x7f, 0x80, 0xff] {
+                let mut corrupted = stream.clone();
+                corrupted[i] = *byte;
+                let _ = read_all(&corrupted);
+            }
+        }
+    }
+
+    #[test]
+    fn test_read_invalid_stream() {
+        assert!(StreamReader::try_new(&[][..]).is_err());
+        assert!(StreamReader::try_new(&[8, 0, 0, 0, 1, 2][..]).is_err());
+    }
+}
@@ -0,0 +1,284 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Writes record batches in the Arrow IPC stream format.
+//!
+//! # Example
+//!
+//! ```
+//! use std::sync::Arc;
+//!
+//! use arrow::array::Int32Array;
+//! use arrow::datatypes::{DataType, Field, Schema};
+//! use arrow::ipc::StreamWriter;
+//! use arrow::record_batch::RecordBatch;
+//!
+//! let schema = Schema::new(vec![Field::new("a", DataType::Int32, false)]);
+//! let batch = RecordBatch::try_new(
+//!     Arc::new(schema.clone()),
+//!     vec![Arc::new(Int32Array::from(vec![1, 2, 3]))],
+//! )
+//! .unwrap();
+//!
+//! let mut stream = vec![];
+//! let mut writer = StreamWriter::try_new(&mut stream, &schema).unwrap();
+//! writer.write(&batch).unwrap();
+//! writer.finish().unwrap();
+//! ```
+
+use std::io::Write;
+
+use super::convert::{self, RecordBatchMetadata};
+use super::pad_len;
+use crate::array_data::ArrayData;
+use crate::datatypes::{DataType, Schema, ToByteSlice};
+use crate::error::{ArrowError, Result};
+use crate::record_batch::RecordBatch;
+use crate::util::bit_util;
+
+/// Writes a `Schema` message and then one `RecordBatch` message per batch to a `Write`
+pub struct StreamWriter<W: Write> {
+    writer: W,
+    schema: Schema,
+    finished: bool,
+}
+
+impl<W: Write> StreamWriter<W> {
+    /// Creates a writer for batches of `schema`, and writes the schema message
+    pub fn try_new(mut writer: W, schema: &Schema) -> Result<Self> {
+        let header = convert::schema_to_fb(schema)?;
+        let metadata = convert::message_to_fb(convert::MESSAGE_SCHEMA, header, 0);
+        write_message(&mut writer, &metadata, &[])?;
+        Ok(Self {
+            writer,
+            schema: schema.clone(),
+            finished: false,
+        })
+    }
+
+    /// Writes `batch`, whose schema must be the schema of the stream
+    pub fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+        if self.finished {
+            return Err(ArrowError::InvalidArgumentError(
+                "Cannot write a record batch to a finished stream".to_string(),
+            ));
+        }
+        if batch.schema().as_ref() != &self.schema {
+            return Err(ArrowError::InvalidArgumentError(
+                "Record batch schema doesn't match the schema of the stream".to_string(),
+            ));
+        }
+        let mut body = BodyWriter::default();
+        for column in batch.columns() {
+            body.write_array(column.data_ref(), 0, column.len())?;
+        }
+        let header = convert::record_batch_to_fb(RecordBatchMetadata {
+            length: batch.num_rows() as i64,
+            nodes: body.nodes,
+            buffers: body.buffers,
+        });
+        let metadata = convert::message_to_fb(
+            convert::MESSAGE_RECORD_BATCH,
+            header,
+            body.data.len(),
+        );
+        write_message(&mut self.writer, &metadata, &body.data)
+    }
+
+    /// Writes the end-of-stream marker and flushes the stream. No batches can be
+    /// written afterwards.
+    pub fn finish(&mut self) -> Result<()> {
+        if !self.finished {
+            self.writer.write_all(&0i32.to_le_bytes())?;
+            self.finished = true;
+        }
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Writes a message made of its metadata length, its `metadata` padded so that the body
+/// is aligned, and its `body`
+fn write_message<W: Write>(writer: &mut W, metadata: &[u8], body: &[u8]) -> Result<()> {
+    let prefix_len = 4;
+    let metadata_len = pad_len(prefix_len + metadata.len()) - prefix_len;
+    writer.write_all(&(metadata_len as i32).to_le_bytes())?;
+    writer.write_all(metadata)?;
+    writer.write_all(&vec![0; metadata_len - metadata.len()])?;
+    writer.write_all(body)?;
+    Ok(())
+}
+
+/// Accumulates the field nodes and buffers of the arrays of a record batch
+#[derive(Default)]
+struct BodyWriter {
+    nodes: Vec<(i64, i64)>,
+    buffers: Vec<(i64, i64)>,
+    data: Vec<u8>,
+}
+
+impl BodyWriter {
+    fn push_buffer(&mut self, bytes: &[u8]) {
+        self.buffers
+            .push((self.data.len() as i64, bytes.len() as i64));
+        self.data.extend_from_slice(bytes);
+        self.data.resize(pad_len(self.data.len()), 0);
+    }
+
+    /// Writes the `len` elements of `data` starting at `start`, copying the buffers of
+    /// sliced arrays so that all offsets start at zero.
+    fn write_array(&mut self, data: &ArrayData, start: usize, len: usize) -> Result<()> {
+        let offset = data.offset() + start;
+        match data.null_bitmap() {
+            Some(bitmap) => {
+                let validity = copy_bits(bitmap.bits.data(), offset, len);
+                let null_count = len - bit_util::count_set_bits(&validity, 0, len);
+                self.nodes.push((len as i64, null_count as i64));
+                if null_count > 0 {
+                    self.push_buffer(&validity);
+                } else {
+                    self.push_buffer(&[]);
+                }
+            }
+            None => {
+                self.nodes.push((len as i64, 0));
+                self.push_buffer(&[]);
+            }
+        }
+
+        match data.data_type() {
+            DataType::Boolean => {
+                self.push_buffer(&copy_bits(data.buffers()[0].data(), offset, len));
+            }
+            DataType::Utf8 => {
+                let (first, last) = self.write_offsets(data, offset, len);
+                self.push_buffer(&data.buffers()[1].data()[first..last]);
+            }
+            DataType::List(_) => {
+                let (first, last) = self.write_offsets(data, offset, len);
+                self.write_array(&data.child_data()[0], first, last - first)?;
+            }
+            DataType::Struct(_) => {
+                for child in data.child_data() {
+                    self.write_array(child, offset, len)?;
+                }
+            }
+            data_type => {
+                let width = data_type.primitive_width().ok_or_else(|| {
+                    ArrowError::InvalidArgumentError(format!(
+                        "IPC doesn't support data type {:?} yet",
+                        data_type
+                    ))
+                })?;
+                let values = data.buffers()[0].data();
+                self.push_buffer(&values[offset * width..(offset + len) * width]);
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the `len + 1` offsets starting at `offset`, rebased to start at zero, and
+    /// returns the range of values they span.
+    fn write_offsets(
+        &mut self,
+        data: &ArrayData,
+        offset: usize,
+        len: usize,
+    ) -> (usize, usize) {
+        let offsets = &data.buffers()[0].typed_data::<i32>()[offset..=offset + len];
+        let first = offsets[0];
+        let rebased: Vec<i32> = offsets.iter().map(|o| o - first).collect();
+        self.push_buffer(rebased.to_byte_slice());
+        (first as usize, offsets[len] as usize)
+    }
+}
+
+/// Returns the `len` bits of `data` starting at bit `offset`
+fn copy_bits(data: &[u8], offset: usize, len: usize) -> Vec<u8> {
+    let mut bytes: Vec<u8> = bit_util::get_bit_chunks(data, offset, len)
+        .iter()
+        .flat_map(|word| word.to_le_bytes().to_vec())
+        .collect();
+    bytes.truncate(bit_util::ceil(len, 8));
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    use crate::array::Int32Array;
+    use crate::datatypes::Field;
+
+    #[test]
+    fn test_message_alignment() {
+        let schema = Schema::new(vec![Field::new("a", DataType::Int32, true)]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema.clone()),
+            vec![Arc::new(Int32Array::from(vec![Some(1), None, Some(3)]))],
+        )
+        .unwrap();
+        let mut stream = vec![];
+        let mut writer = StreamWriter::try_new(&mut stream, &schema).unwrap();
+        writer.write(&batch).unwrap();
+        writer.finish().unwrap();
+
+        // schema message, then record batch message with a padded validity buffer and
+        // padded values buffer
+        let schema_len = i32::from_le_bytes([stream[0], stream[1], stream[2], stream[3]]);
+        assert_eq!(0, (4 + schema_len) % 8);
+        let batch_pos = 4 + schema_len as usize;
+        let batch_len = i32::from_le_bytes([
+            stream[batch_pos],
+            stream[batch_pos + 1],
+            stream[batch_pos + 2],
+            stream[batch_pos + 3],
+        ]);
+        assert_eq!(0, (4 + batch_len) % 8);
+        let body_pos = batch_pos + 4 + batch_len as usize;
+        assert_eq!(body_pos + 8 + 16 + 4, stream.len());
+        assert_eq!(0b101, stream[body_pos]);
+        assert_eq!(&[0, 0, 0, 0], &stream[stream.len() - 4..]);
+    }
+
+    #[test]
+    fn test_write_mismatched_schema() {
+        let schema = Schema::new(vec![Field::new("a", DataType::Int32, false)]);
+        let other = Schema::new(vec![Field::new("b", DataType::Int32, false)]);
+        let batch = RecordBatch::try_new(
+            Arc::new(other),
+            vec![Arc::new(Int32Array::from(vec![1]))],
+        )
+        .unwrap();
+        let mut stream = vec![];
+        let mut writer = StreamWriter::try_new(&mut stream, &schema).unwrap();
+        assert!(writer.write(&batch).is_err());
+        writer.finish().unwrap();
+        assert!(writer.write(&batch).is_err());
+    }
+
+    #[test]
+    fn test_copy_bits() {
+        assert_eq!(
+            vec![0b1011_0110, 0b1],
+            copy_bits(&[0b1101_1000, 0b110], 2, 9)
+        );
+        assert_eq!(Vec::<u8>::new(), copy_bits(&[], 0, 0));
+    }
+}
@@ -51,6 +51,7 @@ use std::sync::Arc;
 use serde_json::Value;
 
 use crate::array::*;
+use crate::array_data::ArrayData;
 use crate::builder::*;
 use crate::datatypes::*;
 use crate::error::{ArrowError, Result};
@@ -345,18 +346,25 @@ impl<R: Read> Reader<R> {
         }
     }
 
-    /// Read the next batch of records
+    /// Read the next batch of records, or `None` if there are no more records
     pub fn next(&mut self) -> Result<Option<RecordBatch>> {
         let mut rows: Vec<Value> = Vec::with_capacity(self.batch_size);
         let mut line = String::new();
-        for _ in 0..self.batch_size {
-            self.reader.read_line(&mut line)?;
-            if !line.is_empty() {
-                rows.push(serde_json::from_str(&line).expect("Not valid JSON"));
-                line = String::new();
-            } else {
+        while rows.len() < self.batch_size {
+            line.clear();
+            if self.reader.read_line(&mut line)? == 0 {
                 break;
             }
+            // skip blank lines
+            if line.trim().is_empty() {
+                continue;
+            }
+            let row = serde_json::from_str(&line)
+                .map_err(|e| ArrowError::JsonError(format!("Not valid JSON: {}", e)))?;
+            rows.push(row);
+        }
+        if rows.is_empty() {
+            return Ok(None);
         }
 
         let rows = &rows[..];
@@ -372,105 +380,7 @@ impl<R: Read> Reader<R> {
                 }
                 projection.contains(field.name())
             })
-            .map(|field| {
-                match field.data_type().clone() {
-                    DataType::Boolean => self.build_boolean_array(rows, field.name()),
-                    DataType::Float64 => {
-                        self.build_primitive_array::<Float64Type>(rows, field.name())
-                    }
-                    DataType::Float32 => {
-                        self.build_primitive_array::<Float32Type>(rows, field.name())
-                    }
-                    DataType::Int64 => self.build_primitive_array::<Int64Type>(rows, field.name()),
-                    DataType::Int32 => self.build_primitive_array::<Int32Type>(rows, field.name()),
-                    DataType::Int16 => self.build_primitive_array::<Int16Type>(rows, field.name()),
-                    DataType::Int8 => self.build_primitive_array::<Int8Type>(rows, field.name()),
-                    DataType::UInt64 => {
-                        self.build_primitive_array::<UInt64Type>(rows, field.name())
-                    }
-                    DataType::UInt32 => {
-                        self.build_primitive_array::<UInt32Type>(rows, field.name())
-                    }
-                    DataType::UInt16 => {
-                        self.build_primitive_array::<UInt16Type>(rows, field.name())
-                    }
-                    DataType::UInt8 => self.build_primitive_array::<UInt8Type>(rows, field.name()),
-                    DataType::Utf8 => {
-                        let mut builder = BinaryBuilder::new(rows.len());
-                        for row_index in 0..rows.len() {
-                            match rows[row_index].get(field.name()) {
-                                Some(value) => {
-                                    match value.as_str() {
-                                        Some(v) => builder.append_string(v)?,
-                                        // TODO: value might exist as something else, coerce so we don't lose it
-                                        None => builder.append(false)?,
-                                    }
-                                }
-                                None => builder.append(false)?,
-                            }
-                        }
-                        Ok(Arc::new(builder.finish()) as ArrayRef)
-                    }
-                    DataType::List(ref t) => match **t {
-                        DataType::Int8 => self.build_list_array::<Int8Type>(rows, field.name()),
-                        DataType::Int16 => self.build_list_array::<Int16Type>(rows, field.name()),
-                        DataType::Int32 => self.build_list_array::<Int32Type>(rows, field.name()),
-                        DataType::Int64 => self.build_list_array::<Int64Type>(rows, field.name()),
-                        DataType::UInt8 => self.build_list_array::<UInt8Type>(rows, field.name()),
-                        DataType::UInt16 => self.build_list_array::<UInt16Type>(rows, field.name()),
-                        DataType::UInt32 => self.build_list_array::<UInt32Type>(rows, field.name()),
-                        DataType::UInt64 => self.build_list_array::<UInt64Type>(rows, field.name()),
-                        DataType::Float32 => self.build_list_array::<Float32Type>(rows, field.name()),
-                        DataType::Float64 => self.build_list_array::<Float64Type>(rows, field.name()),
-                        DataType::Boolean => self.build_boolean_list_array(rows, field.name()),
-                        DataType::Utf8 => {
-                            let values_builder = BinaryBuilder::new(rows.len() * 5);
-                            let mut builder = ListBuilder::new(values_builder);
-                            for row_index in 0..rows.len() {
-                                match rows[row_index].get(field.name()) {
-                                    Some(value) => {
-                                        // value can be an array or a scalar
-                                        let vals: Vec<Option<String>> = if let Value::String(v) = value {
-                                            vec![Some(v.to_string())]
-                                        } else if let Value::Array(n) = value {
-                                            n.iter().map(|v: &Value| {
-                                                if v.is_string() {
-                                                    Some(v.as_str().unwrap().to_string())
-                                                } else if v.is_array() || v.is_object() {
-                                                    // implicitly drop nested values
-                                                    // TODO support deep-nesting
-                                                    None
-                                                } else {
-                                                    Some(v.to_string())
-                                                }
-                                            }).collect()
-                                        } else if let Value::Null = value {
-                                            vec![None]
-                                        } else {
-                                            if !value.is_object() {
-                                                vec![Some(value.to_string())]
-                                            } else {
-                                                return Err(ArrowError::JsonError("1Only scalars are currently supported in JSON arrays".to_string()))
-                                            }
-                                        };
-                                        for i in 0..vals.len() {
-                                            match &vals[i] {
-                                                Some(v) => builder.values().append_string(&v)?,
-                                                None => builder.values().append_null()?,
-                                            };
-                                        }
-                                    }
-                                    None => {}
-                                }
-                                builder.append(true)?
-                            }
-                            Ok(Arc::new(builder.finish()) as ArrayRef)
-                        }
-                        _ => return Err(ArrowError::JsonError("Data type is currently not supported in a list".to_string())),
-                    },
-                    _ => return Err(ArrowError::JsonError("struct types are not yet supported".to_string())),
-                }
-            })
+            .map(|field| self.build_array(rows, field))
             .collect();
 
         let projected_fields: Vec<Field> = if projection.is_empty() {
@@ -495,6 +405,177 @@ impl<R: Read> Reader<R> {
         }
     }
 
+    /// Builds the array of the values of `field` in `rows`. Rows without the field, or
+    /// with a value of another type, have a null value.
+    fn build_array(&self, rows: &[Value], field: &Field) -> Result<ArrayRef> {
+        match field.data_type().clone() {
+            DataType::Boolean => self.build_boolean_array(rows, field.name()),
+            DataType::Float64 => {
+                self.build_primitive_array::<Float64Type>(rows, field.name())
+            }
+            DataType::Float32 => {
+                self.build_primitive_array::<Float32Type>(rows, field.name())
+            }
+            DataType::Int64 => {
+                self.build_primitive_array::<Int64Type>(rows, field.name())
+            }
+            DataType::Int32 => {
+                self.build_primitive_array::<Int32Type>(rows, field.name())
+            }
+            DataType::Int16 => {
+                self.build_primitive_array::<Int16Type>(rows, field.name())
+            }
+            DataType::Int8 => self.build_primitive_array::<Int8Type>(rows, field.name()),
+            DataType::UInt64 => {
+                self.build_primitive_array::<UInt64Type>(rows, field.name())
+            }
+            DataType::UInt32 => {
+                self.build_primitive_array::<UInt32Type>(rows, field.name())
+            }
+            DataType::UInt16 => {
+                self.build_primitive_array::<UInt16Type>(rows, field.name())
+            }
+            DataType::UInt8 => {
+                self.build_primitive_array::<UInt8Type>(rows, field.name())
+            }
+            DataType::Utf8 => {
+                let mut builder = BinaryBuilder::new(rows.len());
+                for row_index in 0..rows.len() {
+                    match rows[row_index].get(field.name()) {
+                        Some(value) => {
+                            match value.as_str() {
+                                Some(v) => builder.append_string(v)?,
+                                // TODO: value might exist as something else, coerce so we
+                                // don't lose it
+                                None => builder.append(false)?,
+                            }
+                        }
+                        None => builder.append(false)?,
+                    }
+                }
+                Ok(Arc::new(builder.finish()) as ArrayRef)
+            }
+            DataType::List(ref t) => match **t {
+                DataType::Int8 => self.build_list_array::<Int8Type>(rows, field.name()),
+                DataType::Int16 => self.build_list_array::<Int16Type>(rows, field.name()),
+                DataType::Int32 => self.build_list_array::<Int32Type>(rows, field.name()),
+                DataType::Int64 => self.build_list_array::<Int64Type>(rows, field.name()),
+                DataType::UInt8 => self.build_list_array::<UInt8Type>(rows, field.name()),
+                DataType::UInt16 => {
+                    self.build_list_array::<UInt16Type>(rows, field.name())
+                }
+                DataType::UInt32 => {
+                    self.build_list_array::<UInt32Type>(rows, field.name())
+                }
+                DataType::UInt64 => {
+                    self.build_list_array::<UInt64Type>(rows, field.name())
+                }
+                DataType::Float32 => {
+                    self.build_list_array::<Float32Type>(rows, field.name())
+                }
+                DataType::Float64 => {
+                    self.build_list_array::<Float64Type>(rows, field.name())
+                }
+                DataType::Boolean => self.build_boolean_list_array(rows, field.name()),
+                DataType::Utf8 => {
+                    let values_builder = BinaryBuilder::new(rows.len() * 5);
+                    let mut builder = ListBuilder::new(values_builder);
+                    for row_index in 0..rows.len() {
+                        match rows[row_index].get(field.name()) {
+                            Some(value) => {
+                                // value can be an array or a scalar
+                                let vals: Vec<Option<String>> = if let Value::String(v) =
+                                    value
+                                {
+                                    vec![Some(v.to_string())]
+                                } else if let Value::Array(n) = value {
+                                    n.iter()
+                                        .map(|v: &Value| {
+                                            if v.is_string() {
+                                                Some(v.as_str().unwrap().to_string())
+                                            } else if v.is_array() || v.is_object() {
+                                                // implicitly drop nested values
+                                                // TODO support deep-nesting
+                                                None
+                                            } else {
+                                                Some(v.to_string())
+                                            }
+                                        })
+                                        .collect()
+                                } else if let Value::Null = value {
+                                    vec![None]
+                                } else {
+                                    if !value.is_object() {
+                                        vec![Some(value.to_string())]
+                                    } else {
+                                        return Err(ArrowError::JsonError("1Only scalars are currently supported in JSON arrays".to_string()));
+                                    }
+                                };
+                                for i in 0..vals.len() {
+                                    match &vals[i] {
+                                        Some(v) => builder.values().append_string(&v)?,
+                                        None => builder.values().append_null()?,
+                                    };
+                                }
+                            }
+                            None => {}
+                        }
+                        builder.append(true)?
+                    }
+                    Ok(Arc::new(builder.finish()) as ArrayRef)
+                }
+                _ => {
+                    return Err(ArrowError::JsonError(
+                        "Data type is currently not supported in a list".to_string(),
+                    ))
+                }
+            },
+            DataType::Struct(ref fields) => {
+                self.build_struct_array(rows, field.name(), fields)
+            }
+            _ => {
+                return Err(ArrowError::JsonError(format!(
+                    "Data type {:?} is currently not supported",
+                    field.data_type()
+                )))
+            }
+        }
+    }
+
+    /// Builds a struct array from the nested objects of `rows` at `col_name`. Rows where
+    /// the value is missing or isn't an object have a null value.
+    fn build_struct_array(
+        &self,
+        rows: &[Value],
+        col_name: &str,
+        fields: &[Field],
+    ) -> Result<ArrayRef> {
+        let mut null_buffer = BooleanBufferBuilder::new(rows.len());
+        let nested_rows: Vec<Value> = rows
+            .iter()
+            .map(|row| match row.get(col_name) {
+                Some(value @ Value::Object(_)) => {
+                    null_buffer.append(true)?;
+                    Ok(value.clone())
+                }
+                _ => {
+                    null_buffer.append(false)?;
+                    Ok(Value::Null)
+                }
+            })
+            .collect::<Result<_>>()?;
+        let child_data = fields
+            .iter()
+            .map(|f| Ok(self.build_array(&nested_rows, f)?.data()))
+            .collect::<Result<Vec<_>>>()?;
+        let data = ArrayData::builder(DataType::Struct(fields.to_vec()))
+            .len(rows.len())
+            .null_bit_buffer(null_buffer.finish())
+            .child_data(child_data)
+            .build();
+        Ok(Arc::new(StructArray::from(data)))
+    }
+
     fn build_boolean_array(&self, rows: &[Value], col_name: &str) -> Result<ArrayRef> {
         let mut builder = BooleanBuilder::new(rows.len());
         for row_index in 0..rows.len() {
@@ -1084,4 +1165,63 @@ mod tests {
         assert_eq!("array", &String::from_utf8(dd.value(5).to_vec()).unwrap());
         assert_eq!("2.4", &String::from_utf8(dd.value(6).to_vec()).unwrap());
     }
+
+    #[test]
+    fn test_json_nested_struct() {
+        let data = r#"{"id": 1, "address": {"city": "Paris", "zip": 75001}, "extra": true}
+
+{"id": 2, "address": {"city": "Oslo"}}
+{"id": 3}
+{"address": {"zip": 1234.0, "unknown": [1, 2]}}
+"#;
+        let address_type = DataType::Struct(vec![
+            Field::new("city", DataType::Utf8, true),
+            Field::new("zip", DataType::Int32, true),
+        ]);
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int64, true),
+            Field::new("address", address_type.clone(), true),
+        ]);
+        let mut reader = Reader::new(
+            BufReader::new(std::io::Cursor::new(data)),
+            Arc::new(schema),
+            3,
+            None,
+        );
+
+        let batch = reader.next().unwrap().unwrap();
+        assert_eq!(3, batch.num_rows());
+        assert_eq!(&address_type, batch.column(1).data_type());
+        let address = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<StructArray>()
+            .unwrap();
+        assert_eq!("{city: Paris, zip: 75001}", address.value_to_string(0));
+        assert_eq!("{city: Oslo, zip: null}", address.value_to_string(1));
+        assert!(address.is_null(2));
+        assert_eq!(1, address.null_count());
+
+        let batch = reader.next().unwrap().unwrap();
+        assert_eq!(1, batch.num_rows());
+        assert!(batch.column(0).is_null(0));
+        assert_eq!(
+            "{city: null, zip: 1234}",
+            batch.column(1).value_to_string(0)
+        );
+
+        assert!(reader.next().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_json_invalid_line() {
+        let schema = Schema::new(vec![Field::new("a", DataType::Int64, true)]);
+        let mut reader = Reader::new(
+            BufReader::new(std::io::Cursor::new("{\"a\": 1}\n{\"a\": \n")),
+            Arc::new(schema),
+            10,
+            None,
+        );
+        assert!(reader.next().is_err());
+    }
 }
@@ -27,6 +27,7 @@
 
 pub mod array;
 pub mod array_data;
+pub mod array_reader;
 pub mod bitmap;
 pub mod buffer;
 pub mod builder;
@@ -34,6 +35,7 @@ pub mod compute;
 pub mod csv;
 pub mod datatypes;
 pub mod error;
+pub mod ipc;
 pub mod json;
 pub mod memory;
 pub mod record_batch;
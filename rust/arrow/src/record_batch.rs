@@ -56,12 +56,15 @@ impl RecordBatch {
             ));
         }
         // check that all columns have the same row count, and match the schema
-        let len = columns[0].data().len();
+        let len = columns[0].len();
         for i in 0..columns.len() {
             if columns[i].len() != len {
-                return Err(ArrowError::InvalidArgumentError(
-                    "all columns in a record batch must have the same length".to_string(),
-                ));
+                return Err(ArrowError::InvalidArgumentError(format!(
+                    "all columns in a record batch must have the same length, expected {} but found {} at column index {}",
+                    len,
+                    columns[i].len(),
+                    i
+                )));
             }
             if columns[i].data_type() != schema.field(i).data_type() {
                 return Err(ArrowError::InvalidArgumentError(format!(
@@ -86,13 +89,66 @@ impl RecordBatch {
 
     /// Number of rows in each column
     pub fn num_rows(&self) -> usize {
-        self.columns[0].data().len()
+        self.columns[0].len()
     }
 
     /// Get a reference to a column's array by index
     pub fn column(&self, i: usize) -> &ArrayRef {
         &self.columns[i]
     }
+
+    /// Get a reference to all columns in the record batch
+    pub fn columns(&self) -> &[ArrayRef] {
+        &self.columns[..]
+    }
+
+    /// Returns a new record batch with the `length` rows of this batch starting at row
+    /// `offset`, and the same schema.
+    ///
+    /// The columns are zero-copy slices of the columns of this batch.
+    ///
+    /// Panics if `offset + length` is greater than the number of rows.
+    pub fn slice(&self, offset: usize, length: usize) -> RecordBatch {
+        assert!(
+            offset + length <= self.num_rows(),
+            "the slice {}..{} is out of bounds of a record batch of {} rows",
+            offset,
+            offset + length,
+            self.num_rows()
+        );
+        let columns = self
+            .columns
+            .iter()
+            .map(|column| make_array(column.data().slice(offset, length)))
+            .collect();
+        RecordBatch {
+            schema: self.schema.clone(),
+            columns,
+        }
+    }
+
+    /// Returns a new record batch with only the columns at `indices`, in that order,
+    /// and the corresponding fields of the schema.
+    ///
+    /// The columns are shared with this batch rather than copied. Returns an error if an
+    /// index is out of bounds, or if `indices` is empty.
+    pub fn project(&self, indices: &[usize]) -> Result<RecordBatch> {
+        let mut fields = Vec::with_capacity(indices.len());
+        let mut columns = Vec::with_capacity(indices.len());
+        for &i in indices {
+            if i >= self.columns.len() {
+                return Err(ArrowError::InvalidArgumentError(format!(
+                    "project index {} out of bounds, the record batch has {} columns",
+                    i,
+                    self.columns.len()
+                )));
+            }
+            fields.push(self.schema.field(i).clone());
+            columns.push(self.columns[i].clone());
+        }
+        let schema = Schema::new_with_metadata(fields, self.schema.metadata().clone());
+        RecordBatch::try_new(Arc::new(schema), columns)
+    }
 }
 
 unsafe impl Send for RecordBatch {}
@@ -104,6 +160,7 @@ mod tests {
 
     use crate::array_data::*;
     use crate::buffer::*;
+    use crate::builder::{Int32Builder, ListBuilder};
 
     #[test]
     fn create_record_batch() {
@@ -150,6 +207,43 @@ mod tests {
         assert!(!batch.is_ok());
     }
 
+    #[test]
+    fn create_record_batch_length_mismatch() {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Int32, false),
+        ]);
+
+        let a = Int32Array::from(vec![1, 2, 3, 4, 5]);
+        let b = Int32Array::from(vec![1, 2, 3]);
+
+        let batch =
+            RecordBatch::try_new(Arc::new(schema), vec![Arc::new(a), Arc::new(b)]);
+        assert_eq!(
+            ArrowError::InvalidArgumentError(
+                "all columns in a record batch must have the same length, expected 5 but found 3 at column index 1"
+                    .to_string()
+            ),
+            batch.err().unwrap()
+        );
+    }
+
+    #[test]
+    fn record_batch_columns() {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Boolean, true),
+        ]);
+        let a: ArrayRef = Arc::new(Int32Array::from(vec![1, 2]));
+        let b: ArrayRef = Arc::new(BooleanArray::from(vec![Some(true), None]));
+
+        let batch =
+            RecordBatch::try_new(Arc::new(schema), vec![a.clone(), b.clone()]).unwrap();
+        assert_eq!(2, batch.columns().len());
+        assert!(Arc::ptr_eq(&a, &batch.columns()[0]));
+        assert!(Arc::ptr_eq(&b, &batch.columns()[1]));
+    }
+
     #[test]
     fn create_record_batch_record_mismatch() {
         let schema = Schema::new(vec![Field::new("a", DataType::Int32, false)]);
@@ -161,4 +255,142 @@ mod tests {
             RecordBatch::try_new(Arc::new(schema), vec![Arc::new(a), Arc::new(b)]);
         assert!(!batch.is_ok());
     }
+
+    #[test]
+    fn project_record_batch() {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Boolean, true),
+            Field::new("c", DataType::Utf8, false),
+        ]);
+        let a: ArrayRef = Arc::new(Int32Array::from(vec![1, 2]));
+        let b: ArrayRef = Arc::new(BooleanArray::from(vec![Some(true), None]));
+        let c: ArrayRef = Arc::new(BinaryArray::from(vec!["x", "y"]));
+        let batch = RecordBatch::try_new(Arc::new(schema), vec![a.clone(), b, c.clone()])
+            .unwrap();
+
+        let projected = batch.project(&[2, 0]).unwrap();
+        assert_eq!(2, projected.num_columns());
+        assert_eq!(2, projected.num_rows());
+        assert_eq!("c", projected.schema().field(0).name());
+        assert_eq!("a", projected.schema().field(1).name());
+        assert!(Arc::ptr_eq(&c, projected.column(0)));
+        assert!(Arc::ptr_eq(&a, projected.column(1)));
+
+        assert_eq!(
+            ArrowError::InvalidArgumentError(
+                "project index 3 out of bounds, the record batch has 3 columns"
+                    .to_string()
+            ),
+            batch.project(&[0, 3]).err().unwrap()
+        );
+        assert!(batch.project(&[]).is_err());
+    }
+
+    #[test]
+    fn slice_record_batch() {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Utf8, false),
+        ]);
+        let a: ArrayRef =
+            Arc::new(Int32Array::from(vec![Some(1), None, Some(3), Some(4)]));
+        let b: ArrayRef = Arc::new(BinaryArray::from(vec!["w", "x", "y", "z"]));
+        let batch = RecordBatch::try_new(Arc::new(schema), vec![a, b]).unwrap();
+
+        let sliced = batch.slice(1, 2);
+        assert!(Arc::ptr_eq(batch.schema(), sliced.schema()));
+        assert_eq!(2, sliced.num_rows());
+        let a = as_primitive_array::<Int32Type>(sliced.column(0));
+        assert_eq!(1, a.null_count());
+        assert!(a.is_null(0));
+        assert_eq!(3, a.value(1));
+        let b = as_string_array(sliced.column(1));
+        assert_eq!("x", b.get_string(0));
+        assert_eq!("y", b.get_string(1));
+        // the buffers are shared with the original batch
+        assert_eq!(
+            batch.column(1).data().buffers()[1].raw_data(),
+            sliced.column(1).data().buffers()[1].raw_data()
+        );
+
+        assert_eq!(0, batch.slice(4, 0).num_rows());
+    }
+
+    #[test]
+    fn slice_record_batch_with_struct_column() {
+        let struct_array = StructArray::from(vec![
+            (
+                Field::new("a", DataType::Int32, false),
+                Arc::new(Int32Array::from(vec![1, 2, 3, 4])) as ArrayRef,
+            ),
+            (
+                Field::new("b", DataType::Utf8, false),
+                Arc::new(BinaryArray::from(vec!["w", "x", "y", "z"])) as ArrayRef,
+            ),
+        ]);
+        let schema = Schema::new(vec![Field::new(
+            "s",
+            struct_array.data_type().clone(),
+            false,
+        )]);
+        let batch =
+            RecordBatch::try_new(Arc::new(schema), vec![Arc::new(struct_array)]).unwrap();
+
+        let sliced = batch.slice(2, 2);
+        assert_eq!(2, sliced.num_rows());
+        let column = sliced.column(0);
+        assert_eq!(2, column.len());
+        assert_eq!("{a: 3, b: y}", column.value_to_string(0));
+        assert_eq!("{a: 4, b: z}", column.value_to_string(1));
+        let expected = StructArray::from(vec![
+            (
+                Field::new("a", DataType::Int32, false),
+                Arc::new(Int32Array::from(vec![3, 4])) as ArrayRef,
+            ),
+            (
+                Field::new("b", DataType::Utf8, false),
+                Arc::new(BinaryArray::from(vec!["y", "z"])) as ArrayRef,
+            ),
+        ]);
+        assert!(column.equals(&expected));
+        let a = as_struct_array(column).column(0);
+        assert_eq!(3, as_primitive_array::<Int32Type>(a).value(0));
+    }
+
+    #[test]
+    fn slice_record_batch_with_list_column() {
+        let mut builder = ListBuilder::new(Int32Builder::new(6));
+        builder.values().append_slice(&[1, 2]).unwrap();
+        builder.append(true).unwrap();
+        builder.append(false).unwrap();
+        builder.values().append_slice(&[3, 4, 5]).unwrap();
+        builder.append(true).unwrap();
+        builder.values().append_value(6).unwrap();
+        builder.append(true).unwrap();
+        let list_array = builder.finish();
+        let schema =
+            Schema::new(vec![Field::new("l", list_array.data_type().clone(), true)]);
+        let batch =
+            RecordBatch::try_new(Arc::new(schema), vec![Arc::new(list_array)]).unwrap();
+
+        let sliced = batch.slice(1, 2);
+        let column = sliced.column(0);
+        assert_eq!(2, column.len());
+        assert_eq!(1, column.null_count());
+        assert_eq!("null", column.value_to_string(0));
+        assert_eq!("[3, 4, 5]", column.value_to_string(1));
+        assert_eq!(&[2, 2, 5], as_list_array(column).value_offsets());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "the slice 3..5 is out of bounds of a record batch of 4 rows"
+    )]
+    fn slice_record_batch_out_of_bounds() {
+        let schema = Schema::new(vec![Field::new("a", DataType::Int32, false)]);
+        let a: ArrayRef = Arc::new(Int32Array::from(vec![1, 2, 3, 4]));
+        let batch = RecordBatch::try_new(Arc::new(schema), vec![a]).unwrap();
+        batch.slice(3, 2);
+    }
 }
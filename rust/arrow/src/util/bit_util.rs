@@ -21,19 +21,6 @@ use packed_simd::u8x64;
 
 static BIT_MASK: [u8; 8] = [1, 2, 4, 8, 16, 32, 64, 128];
 
-static POPCOUNT_TABLE: [u8; 256] = [
-    0, 1, 1, 2, 1, 2, 2, 3, 1, 2, 2, 3, 2, 3, 3, 4, 1, 2, 2, 3, 2, 3, 3, 4, 2, 3, 3, 4,
-    3, 4, 4, 5, 1, 2, 2, 3, 2, 3, 3, 4, 2, 3, 3, 4, 3, 4, 4, 5, 2, 3, 3, 4, 3, 4, 4, 5,
-    3, 4, 4, 5, 4, 5, 5, 6, 1, 2, 2, 3, 2, 3, 3, 4, 2, 3, 3, 4, 3, 4, 4, 5, 2, 3, 3, 4,
-    3, 4, 4, 5, 3, 4, 4, 5, 4, 5, 5, 6, 2, 3, 3, 4, 3, 4, 4, 5, 3, 4, 4, 5, 4, 5, 5, 6,
-    3, 4, 4, 5, 4, 5, 5, 6, 4, 5, 5, 6, 5, 6, 6, 7, 1, 2, 2, 3, 2, 3, 3, 4, 2, 3, 3, 4,
-    3, 4, 4, 5, 2, 3, 3, 4, 3, 4, 4, 5, 3, 4, 4, 5, 4, 5, 5, 6, 2, 3, 3, 4, 3, 4, 4, 5,
-    3, 4, 4, 5, 4, 5, 5, 6, 3, 4, 4, 5, 4, 5, 5, 6, 4, 5, 5, 6, 5, 6, 6, 7, 2, 3, 3, 4,
-    3, 4, 4, 5, 3, 4, 4, 5, 4, 5, 5, 6, 3, 4, 4, 5, 4, 5, 5, 6, 4, 5, 5, 6, 5, 6, 6, 7,
-    3, 4, 4, 5, 4, 5, 5, 6, 4, 5, 5, 6, 5, 6, 6, 7, 4, 5, 5, 6, 5, 6, 6, 7, 5, 6, 6, 7,
-    6, 7, 7, 8,
-];
-
 /// Returns the nearest number that is `>=` than `num` and is a multiple of 64
 #[inline]
 pub fn round_upto_multiple_of_64(num: usize) -> usize {
@@ -77,12 +64,80 @@ pub unsafe fn set_bit_raw(data: *mut u8, i: usize) {
     *data.offset((i >> 3) as isize) |= BIT_MASK[i & 7]
 }
 
-/// Returns the number of 1-bits in `data`
-#[inline]
-pub fn count_set_bits(data: &[u8]) -> usize {
-    let mut count: usize = 0;
-    for u in data {
-        count += POPCOUNT_TABLE[*u as usize] as usize;
+/// Sets the `len` bits of `data` starting at bit `offset`.
+///
+/// The bytes within the range are set at once, and the bits of the first and last bytes
+/// are set with masks.
+pub fn set_bits(data: &mut [u8], offset: usize, len: usize) {
+    fill_bits(data, offset, len, true)
+}
+
+/// Unsets the `len` bits of `data` starting at bit `offset`, like `set_bits`.
+pub fn unset_bits(data: &mut [u8], offset: usize, len: usize) {
+    fill_bits(data, offset, len, false)
+}
+
+fn fill_bits(data: &mut [u8], offset: usize, len: usize, value: bool) {
+    if len == 0 {
+        return;
+    }
+    let end = offset + len;
+    let (first, last) = (offset >> 3, (end - 1) >> 3);
+    // the bits of the first and last bytes that are within the range
+    let head_mask = 0xFFu8 << (offset & 7);
+    let tail_mask = 0xFFu8 >> (7 - ((end - 1) & 7));
+    let fill = |byte: &mut u8, mask: u8| {
+        if value {
+            *byte |= mask
+        } else {
+            *byte &= !mask
+        }
+    };
+    if first == last {
+        fill(&mut data[first], head_mask & tail_mask);
+        return;
+    }
+    fill(&mut data[first], head_mask);
+    for byte in &mut data[first + 1..last] {
+        *byte = if value { 0xFF } else { 0 };
+    }
+    fill(&mut data[last], tail_mask);
+}
+
+/// Returns the number of 1-bits among the `len` bits of `data` starting at bit `offset`.
+///
+/// The bytes are counted 8 at a time as 64-bit words, and the bits of the first and last
+/// bytes that are outside of the range are masked out.
+pub fn count_set_bits(data: &[u8], offset: usize, len: usize) -> usize {
+    debug_assert!(offset + len <= (data.len() << 3));
+    if len == 0 {
+        return 0;
+    }
+    let end = offset + len;
+    let bytes = &data[offset >> 3..ceil(end, 8)];
+
+    let chunks = bytes.chunks_exact(8);
+    let remainder = chunks.remainder();
+    let mut count: usize = chunks
+        .map(|chunk| {
+            let mut word = [0u8; 8];
+            word.copy_from_slice(chunk);
+            u64::from_le_bytes(word).count_ones() as usize
+        })
+        .sum();
+    count += remainder
+        .iter()
+        .map(|b| b.count_ones() as usize)
+        .sum::<usize>();
+
+    // remove the bits before `offset` and from `end` on
+    let head_bits = offset & 7;
+    if head_bits != 0 {
+        count -= (bytes[0] & ((1 << head_bits) - 1)).count_ones() as usize;
+    }
+    let tail_bits = end & 7;
+    if tail_bits != 0 {
+        count -= (bytes[bytes.len() - 1] & !((1 << tail_bits) - 1)).count_ones() as usize;
     }
     count
 }
@@ -91,22 +146,34 @@ pub fn count_set_bits(data: &[u8]) -> usize {
 #[inline]
 pub fn count_set_bits_offset(data: &[u8], offset: usize) -> usize {
     debug_assert!(offset <= (data.len() << 3));
+    count_set_bits(data, offset, (data.len() << 3) - offset)
+}
 
-    let start_byte_pos = offset >> 3;
-    let start_bit_pos = offset & 7;
-
-    if start_bit_pos == 0 {
-        count_set_bits(&data[start_byte_pos..])
-    } else {
-        let mut result = 0;
-        result += count_set_bits(&data[start_byte_pos + 1..]);
-        for i in start_bit_pos..8 {
-            if get_bit(&data[start_byte_pos..start_byte_pos + 1], i) {
-                result += 1;
-            }
+/// Returns the `len` bits of `data` starting at bit `offset` as 64-bit words, where the
+/// bit at position `offset + i` is bit `i % 64` of word `i / 64`.
+///
+/// This allows processing bitmaps a word at a time regardless of the alignment of
+/// `offset`. The bits after `len` in the last word are zero.
+pub fn get_bit_chunks(data: &[u8], offset: usize, len: usize) -> Vec<u64> {
+    let start = offset >> 3;
+    let shift = offset & 7;
+    let mut words = Vec::with_capacity(ceil(len, 64));
+    for i in 0..ceil(len, 64) {
+        // a word at an unaligned offset spans up to 9 bytes
+        let first = start + i * 8;
+        let last = (first + 9).min(data.len());
+        let mut bytes = [0u8; 16];
+        if first < last {
+            bytes[..last - first].copy_from_slice(&data[first..last]);
         }
-        result
+        words.push((u128::from_le_bytes(bytes) >> shift) as u64);
     }
+    if len % 64 != 0 {
+        if let Some(word) = words.last_mut() {
+            *word &= (1u64 << (len % 64)) - 1;
+        }
+    }
+    words
 }
 
 /// Returns the ceil of `value`/`divisor`
@@ -254,12 +321,85 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_set_unset_bits() {
+        let mut data = [0u8; 4];
+        set_bits(&mut data, 2, 3);
+        assert_eq!([0b0001_1100, 0, 0, 0], data);
+        set_bits(&mut data, 6, 20);
+        assert_eq!([0b1101_1100, 0xFF, 0xFF, 0b0000_0011], data);
+        unset_bits(&mut data, 3, 14);
+        assert_eq!([0b0000_0100, 0, 0b1111_1110, 0b0000_0011], data);
+        unset_bits(&mut data, 8, 8);
+        assert_eq!([0b0000_0100, 0, 0b1111_1110, 0b0000_0011], data);
+        set_bits(&mut data, 32, 0);
+        unset_bits(&mut data, 0, 0);
+        assert_eq!([0b0000_0100, 0, 0b1111_1110, 0b0000_0011], data);
+        set_bits(&mut data, 0, 32);
+        assert_eq!([0xFF; 4], data);
+        unset_bits(&mut data, 31, 1);
+        assert_eq!([0xFF, 0xFF, 0xFF, 0b0111_1111], data);
+
+        // compare with setting and getting each bit
+        for offset in 0..20 {
+            for len in 0..(40 - offset) {
+                let mut data = [0b1010_1010u8; 5];
+                set_bits(&mut data, offset, len);
+                for i in 0..40 {
+                    let expected = (i >= offset && i < offset + len) || i % 2 == 1;
+                    assert_eq!(expected, get_bit(&data, i), "failed at {}", i);
+                }
+                unset_bits(&mut data, offset, len);
+                for i in 0..40 {
+                    let expected = !(i >= offset && i < offset + len) && i % 2 == 1;
+                    assert_eq!(expected, get_bit(&data, i), "failed at {}", i);
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_count_bits_slice() {
-        assert_eq!(0, count_set_bits(&[0b00000000]));
-        assert_eq!(8, count_set_bits(&[0b11111111]));
-        assert_eq!(3, count_set_bits(&[0b00001101]));
-        assert_eq!(6, count_set_bits(&[0b01001001, 0b01010010]));
+        assert_eq!(0, count_set_bits(&[0b00000000], 0, 8));
+        assert_eq!(8, count_set_bits(&[0b11111111], 0, 8));
+        assert_eq!(3, count_set_bits(&[0b00001101], 0, 8));
+        assert_eq!(6, count_set_bits(&[0b01001001, 0b01010010], 0, 16));
+    }
+
+    #[test]
+    fn test_count_bits_range() {
+        let data = [0b01001001, 0b01010010];
+        assert_eq!(0, count_set_bits(&data, 0, 0));
+        assert_eq!(0, count_set_bits(&data, 16, 0));
+        // within a single byte
+        assert_eq!(1, count_set_bits(&data, 1, 5));
+        assert_eq!(2, count_set_bits(&data, 3, 4));
+        assert_eq!(1, count_set_bits(&data, 6, 1));
+        // across bytes
+        assert_eq!(3, count_set_bits(&data, 3, 7));
+        assert_eq!(5, count_set_bits(&data, 1, 15));
+        assert_eq!(3, count_set_bits(&data, 9, 6));
+
+        // across whole words
+        let mut data = vec![0u8; 40];
+        let set: Vec<usize> = (0..320).filter(|i| i % 3 == 0 || i % 7 == 0).collect();
+        for i in &set {
+            set_bit(&mut data, *i);
+        }
+        for (offset, len) in &[(0, 320), (5, 300), (63, 130), (64, 64), (13, 1), (100, 0)]
+        {
+            let expected = set
+                .iter()
+                .filter(|i| **i >= *offset && **i < offset + len)
+                .count();
+            assert_eq!(
+                expected,
+                count_set_bits(&data, *offset, *len),
+                "failed for {}..{}",
+                offset,
+                offset + len
+            );
+        }
     }
 
     #[test]
@@ -274,6 +414,19 @@ mod tests {
         assert_eq!(0, count_set_bits_offset(&[0b11111111, 0b11111111], 16));
     }
 
+    #[test]
+    fn test_get_bit_chunks() {
+        let data: Vec<u8> = (0..20).map(|i| (i * 37 + 11) as u8).collect();
+        for &(offset, len) in &[(0, 160), (3, 100), (13, 64), (7, 1), (64, 65), (5, 0)] {
+            let words = get_bit_chunks(&data, offset, len);
+            assert_eq!(ceil(len, 64), words.len());
+            for i in 0..words.len() * 64 {
+                let expected = i < len && get_bit(&data, offset + i);
+                assert_eq!(expected, words[i / 64] & (1 << (i % 64)) != 0);
+            }
+        }
+    }
+
     #[test]
     fn test_ceil() {
         assert_eq!(ceil(0, 1), 0);
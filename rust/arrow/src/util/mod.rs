@@ -16,4 +16,5 @@
 // under the License.
 
 pub mod bit_util;
+pub mod pretty;
 pub mod test_util;
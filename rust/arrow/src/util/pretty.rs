@@ -0,0 +1,216 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Utilities to render record batches and struct arrays as ASCII tables, e.g.
+//!
+//! ```text
+//! +---+-------+
+//! | a | b     |
+//! +---+-------+
+//! | 1 | hello |
+//! |   | world |
+//! +---+-------+
+//! ```
+
+use crate::array::{Array, ArrayRef, StructArray};
+use crate::datatypes::DataType;
+use crate::record_batch::RecordBatch;
+
+/// Maximum number of characters of a cell, longer values are truncated
+const MAX_CELL_WIDTH: usize = 40;
+
+/// Renders `batch` as a table, with one column per field of its schema and one row per
+/// record. Null values are rendered as empty cells.
+pub fn print_batch(batch: &RecordBatch) -> String {
+    let headers = batch
+        .schema()
+        .fields()
+        .iter()
+        .map(|f| f.name().clone())
+        .collect();
+    format_table(headers, batch.columns(), batch.num_rows(), |_| false)
+}
+
+/// Renders `array` as a table, with one column per field of the struct and one row per
+/// element. Null values, and every cell of a null element, are rendered as empty cells.
+pub fn print_struct_array(array: &StructArray) -> String {
+    let fields = match array.data_type() {
+        DataType::Struct(fields) => fields,
+        _ => unreachable!("StructArray must have a Struct data type"),
+    };
+    let headers = fields.iter().map(|f| f.name().clone()).collect();
+    let columns: Vec<ArrayRef> =
+        (0..fields.len()).map(|i| array.column(i).clone()).collect();
+    format_table(headers, &columns, array.len(), |row| array.is_null(row))
+}
+
+/// Renders the first `num_rows` rows of `columns`, leaving every cell of the rows for
+/// which `is_null_row` returns true empty.
+fn format_table<F>(
+    headers: Vec<String>,
+    columns: &[ArrayRef],
+    num_rows: usize,
+    is_null_row: F,
+) -> String
+where
+    F: Fn(usize) -> bool,
+{
+    let rows: Vec<Vec<String>> = (0..num_rows)
+        .map(|row| {
+            if is_null_row(row) {
+                vec![String::new(); columns.len()]
+            } else {
+                columns.iter().map(|c| format_cell(c, row)).collect()
+            }
+        })
+        .collect();
+
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.chars().count()).collect();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let separator = format_separator(&widths);
+    let mut table = separator.clone();
+    table.push_str(&format_row(&headers, &widths));
+    table.push_str(&separator);
+    for row in &rows {
+        table.push_str(&format_row(row, &widths));
+    }
+    table.push_str(&separator);
+    table
+}
+
+/// Formats the element at index `row` of `column`, truncating long values.
+fn format_cell(column: &ArrayRef, row: usize) -> String {
+    if column.is_null(row) {
+        return String::new();
+    }
+    let value = column.value_to_string(row);
+    if value.chars().count() > MAX_CELL_WIDTH {
+        let mut truncated: String = value.chars().take(MAX_CELL_WIDTH - 3).collect();
+        truncated.push_str("...");
+        truncated
+    } else {
+        value
+    }
+}
+
+fn format_separator(widths: &[usize]) -> String {
+    let mut line = String::from("+");
+    for width in widths {
+        line.push_str(&"-".repeat(width + 2));
+        line.push('+');
+    }
+    line.push('\n');
+    line
+}
+
+fn format_row(cells: &[String], widths: &[usize]) -> String {
+    let mut line = String::from("|");
+    for (cell, width) in cells.iter().zip(widths) {
+        let padding = width - cell.chars().count();
+        line.push(' ');
+        line.push_str(cell);
+        line.push_str(&" ".repeat(padding + 1));
+        line.push('|');
+    }
+    line.push('\n');
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    use crate::array::{Float64Array, Int32Array};
+    use crate::array_data::ArrayData;
+    use crate::buffer::Buffer;
+    use crate::builder::BinaryBuilder;
+    use crate::datatypes::{Field, Schema};
+
+    #[test]
+    fn test_print_batch() {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("score", DataType::Float64, true),
+        ]);
+        let mut names = BinaryBuilder::new(3);
+        names.append_string("a").unwrap();
+        names.append_null().unwrap();
+        names
+            .append_string("a rather long name that doesn't fit in a cell")
+            .unwrap();
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 3])),
+                Arc::new(names.finish()),
+                Arc::new(Float64Array::from(vec![Some(1.5), Some(-2.0), None])),
+            ],
+        )
+        .unwrap();
+
+        let expected = vec![
+            "+----+------------------------------------------+-------+",
+            "| id | name                                     | score |",
+            "+----+------------------------------------------+-------+",
+            "| 1  | a                                        | 1.5   |",
+            "| 2  |                                          | -2.0  |",
+            "| 3  | a rather long name that doesn't fit i... |       |",
+            "+----+------------------------------------------+-------+",
+            "",
+        ];
+        assert_eq!(expected.join("\n"), print_batch(&batch));
+    }
+
+    #[test]
+    fn test_print_struct_array() {
+        let array = StructArray::from(vec![(
+            Field::new("x", DataType::Int32, false),
+            Arc::new(Int32Array::from(vec![10, 200])) as ArrayRef,
+        )]);
+        let expected = vec![
+            "+-----+", "| x   |", "+-----+", "| 10  |", "| 200 |", "+-----+", "",
+        ];
+        assert_eq!(expected.join("\n"), print_struct_array(&array));
+    }
+
+    #[test]
+    fn test_print_struct_array_with_null_element() {
+        let values = Int32Array::from(vec![10, 200, 3]);
+        let data = ArrayData::builder(DataType::Struct(vec![Field::new(
+            "x",
+            DataType::Int32,
+            false,
+        )]))
+        .len(3)
+        .null_bit_buffer(Buffer::from([0b0000_0101]))
+        .add_child_data(values.data())
+        .build();
+        let array = StructArray::from(data);
+        let expected = vec![
+            "+----+", "| x  |", "+----+", "| 10 |", "|    |", "| 3  |", "+----+", "",
+        ];
+        assert_eq!(expected.join("\n"), print_struct_array(&array));
+    }
+}
@@ -63,7 +63,7 @@ impl Relation for FilterRelation {
                         let filtered_columns: Result<Vec<ArrayRef>> = (0..batch
                             .num_columns())
                             .map(|i| {
-                                match filter(batch.column(i).as_ref(), &filter_bools) {
+                                match filter(batch.column(i), &filter_bools) {
                                     Ok(result) => Ok(result),
                                     Err(error) => Err(ExecutionError::from(error)),
                                 }
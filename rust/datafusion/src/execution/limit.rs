@@ -26,7 +26,7 @@ use arrow::compute::array_ops::limit;
 use arrow::datatypes::Schema;
 use arrow::record_batch::RecordBatch;
 
-use super::error::{ExecutionError, Result};
+use super::error::Result;
 use super::relation::Relation;
 
 pub struct LimitRelation {
@@ -58,15 +58,12 @@ impl Relation for LimitRelation {
                 }
 
                 if batch.num_rows() >= capacity {
-                    let limited_columns: Result<Vec<ArrayRef>> = (0..batch.num_columns())
-                        .map(|i| match limit(batch.column(i), capacity) {
-                            Ok(result) => Ok(result),
-                            Err(error) => Err(ExecutionError::from(error)),
-                        })
+                    let limited_columns: Vec<ArrayRef> = (0..batch.num_columns())
+                        .map(|i| limit(batch.column(i), capacity))
                         .collect();
 
                     let limited_batch: RecordBatch =
-                        RecordBatch::try_new(self.schema.clone(), limited_columns?)?;
+                        RecordBatch::try_new(self.schema.clone(), limited_columns)?;
                     self.num_consumed_rows += capacity;
 
                     Ok(Some(limited_batch))